@@ -0,0 +1,101 @@
+use std::hint::black_box;
+
+use blockchain::{encoding, Address, Chain, ChainTransactions, Hash, Transaction};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+/// Build a chain with two funded wallets.
+fn funded_chain() -> (Chain, Address, Address) {
+    let mut chain = Chain::new(1.0, 100.0, 0.1);
+    let from = chain.create_wallet("sender@mail.com");
+    let to = chain.create_wallet("receiver@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = f64::MAX;
+
+    (chain, from, to)
+}
+
+/// Build a map of distinct transactions.
+fn transactions(count: usize) -> ChainTransactions {
+    let (from, to) = (Address::random(), Address::random());
+
+    (0..count)
+        .map(|i| Transaction::new(from.clone(), to.clone(), 0.1, i as f64 + 1.0))
+        .map(|tx| (tx.hash, tx))
+        .collect()
+}
+
+fn add_transaction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("add_transaction");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("single", |b| {
+        b.iter_batched_ref(
+            funded_chain,
+            |(chain, from, to)| chain.add_transaction(from, to, black_box(1.0)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn mining(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mining");
+    group.sample_size(10);
+
+    for difficulty in [1.0, 2.0, 3.0] {
+        group.bench_with_input(
+            BenchmarkId::new("generate_new_block", difficulty),
+            &difficulty,
+            |b, &difficulty| {
+                b.iter_batched_ref(
+                    || Chain::new(difficulty, 100.0, 0.1),
+                    |chain| chain.generate_new_block(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn merkle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merkle");
+
+    for count in [100, 1_000, 10_000] {
+        let transactions = transactions(count);
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::new("get_merkle", count),
+            &transactions,
+            |b, transactions| b.iter(|| Chain::get_merkle(black_box(transactions))),
+        );
+    }
+
+    group.finish();
+}
+
+fn serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialization");
+    let transaction = transactions(1).into_values().next().unwrap();
+
+    group.bench_function("hash_transaction", |b| {
+        b.iter(|| Chain::hash(black_box(&transaction)))
+    });
+    group.bench_function("encode_transaction", |b| {
+        b.iter(|| encoding::to_bytes(black_box(&transaction)))
+    });
+    group.bench_function("json_transaction", |b| {
+        b.iter(|| serde_json::to_vec(black_box(&transaction)))
+    });
+    group.bench_function("digest_1kib", |b| {
+        let bytes = vec![0u8; 1024];
+        b.iter(|| Hash::digest(black_box(&bytes)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, add_transaction, mining, merkle, serialization);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    #[cfg(feature = "grpc")]
+    {
+        // Use the vendored compiler so that no system-wide protoc is required
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("protoc is not available");
+        std::env::set_var("PROTOC", protoc);
+
+        tonic_build::compile_protos("proto/blockchain.proto").expect("Failed to compile protos");
+    }
+}
@@ -6,7 +6,7 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use blockchain::Chain;
+use blockchain::{Address, Chain, Hash};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -28,10 +28,10 @@ pub struct CreateWalletInput {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AddTransactionInput {
     /// The sender address.
-    pub from: String,
+    pub from: Address,
 
     /// The receiver address.
-    pub to: String,
+    pub to: Address,
 
     /// The transaction amount.
     pub amount: f64,
@@ -41,14 +41,14 @@ pub struct AddTransactionInput {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetWalletBalanceInput {
     /// The wallet address.
-    pub address: String,
+    pub address: Address,
 }
 
 /// Get a list of transactions of a wallet.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetWalletTransactionInput {
     /// The wallet address.
-    pub address: String,
+    pub address: Address,
 
     /// The page number.
     pub page: usize,
@@ -171,7 +171,7 @@ pub async fn get_transactions(
 /// The transaction.
 pub async fn get_transaction(
     State(state): State<AppState>,
-    Path(hash): Path<String>,
+    Path(hash): Path<Hash>,
 ) -> impl IntoResponse {
     let chain = state.chain.lock().unwrap();
     let transaction = chain.get_transaction(&hash);
@@ -201,7 +201,7 @@ pub async fn add_transaction(
 ) -> impl IntoResponse {
     let mut chain = state.chain.lock().unwrap();
 
-    let result = chain.add_transaction(body.from, body.to, body.amount);
+    let result = chain.add_transaction(&body.from, &body.to, body.amount);
 
     (StatusCode::OK, Json(json!({ "data": result })))
 }
@@ -1,4 +1,4 @@
-use blockchain::Chain;
+use blockchain::{Address, Chain};
 use cliclack::spinner;
 
 /// The main function.
@@ -89,7 +89,7 @@ fn main() -> std::io::Result<()> {
                 }
             }
             "get_wallet_balance" => {
-                let address: String = cliclack::input("Address")
+                let address: Address = cliclack::input("Address")
                     .validate(|input: &String| {
                         if input.is_empty() {
                             Err("Please enter an address")
@@ -107,7 +107,7 @@ fn main() -> std::io::Result<()> {
                 }
             }
             "get_wallet_transactions" => {
-                let address: String = cliclack::input("Address")
+                let address: Address = cliclack::input("Address")
                     .validate(|input: &String| {
                         if input.is_empty() {
                             Err("Please enter an address")
@@ -125,7 +125,7 @@ fn main() -> std::io::Result<()> {
                 }
             }
             "add_transaction" => {
-                let sender: String = cliclack::input("Sender")
+                let sender: Address = cliclack::input("Sender")
                     .validate(|input: &String| {
                         if input.is_empty() {
                             Err("Please enter a sender")
@@ -135,7 +135,7 @@ fn main() -> std::io::Result<()> {
                     })
                     .interact()?;
 
-                let receiver: String = cliclack::input("Receiver")
+                let receiver: Address = cliclack::input("Receiver")
                     .validate(|input: &String| {
                         if input.is_empty() {
                             Err("Please enter a receiver")
@@ -158,11 +158,7 @@ fn main() -> std::io::Result<()> {
                 let confirm = cliclack::confirm("Confirm adding a transaction").interact()?;
 
                 if confirm {
-                    let res = chain.add_transaction(
-                        sender.trim().to_string(),
-                        receiver.trim().to_string(),
-                        amount,
-                    );
+                    let res = chain.add_transaction(&sender, &receiver, amount);
 
                     match res {
                         Ok(()) => println!("✅ Transaction was added successfully"),
@@ -181,7 +177,10 @@ fn main() -> std::io::Result<()> {
                     })
                     .interact()?;
 
-                let res = chain.get_transaction(&hash);
+                let res = hash
+                    .trim()
+                    .parse()
+                    .and_then(|hash| chain.get_transaction(&hash));
 
                 match res {
                     Ok(trx) => println!("📦 {:?}", trx),
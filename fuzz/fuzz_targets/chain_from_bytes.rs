@@ -0,0 +1,8 @@
+#![no_main]
+
+use blockchain::Chain;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Chain::from_bytes_untrusted(data);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use blockchain::Transaction;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Transaction::decode_untrusted(data);
+});
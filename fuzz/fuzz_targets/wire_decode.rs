@@ -0,0 +1,8 @@
+#![no_main]
+
+use blockchain::WireMessage;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = WireMessage::decode(data);
+});
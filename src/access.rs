@@ -0,0 +1,407 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Block, BlockchainError, Chain, Hash};
+
+/// Administrative operation gated by an admin key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Permission {
+    /// Update the fee, the block reward and the difficulty.
+    UpdateParameters,
+
+    /// Deny, allow and lift the denial of addresses, freezing or unfreezing their transfers.
+    Freeze,
+
+    /// Remove or replace blocks at the tip of the chain.
+    Rollback,
+}
+
+/// Authority delegated by a wallet API key.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KeyScope {
+    /// Read the chain, without sending from the wallet.
+    ReadOnly,
+
+    /// Additionally send from the wallet, up to a total amount over the life of the key.
+    Send {
+        /// Maximum total amount sent with the key.
+        limit: f64,
+    },
+}
+
+/// API key delegating access to a wallet, e.g. to an application acting for its owner.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WalletKey {
+    /// Address of the wallet.
+    pub wallet: Address,
+
+    /// Authority delegated by the key.
+    pub scope: KeyScope,
+
+    /// Total amount sent with the key.
+    #[serde(default)]
+    pub spent: f64,
+}
+
+impl WalletKey {
+    /// Check whether the key may send an amount from the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount`: The amount to send.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key may send and the amount keeps the total within its limit.
+    pub fn can_send(&self, amount: f64) -> bool {
+        match self.scope {
+            KeyScope::ReadOnly => false,
+            KeyScope::Send { limit } => self.spent + amount <= limit,
+        }
+    }
+}
+
+/// Admin keys and the permissions they grant, and wallet API keys.
+///
+/// Only the hashes of the keys are stored, so a persisted chain does not leak them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccessControl {
+    /// Permissions granted by each key, by hash of the key.
+    #[serde(default)]
+    keys: BTreeMap<Hash, BTreeSet<Permission>>,
+
+    /// Wallet API keys, by hash of the key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    wallet_keys: BTreeMap<Hash, WalletKey>,
+}
+
+impl AccessControl {
+    /// Grant permissions to a key, in addition to the ones it already has.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The admin key.
+    /// - `permissions`: The permissions granted.
+    pub fn grant(&mut self, key: &str, permissions: &[Permission]) {
+        self.keys
+            .entry(Hash::digest(key.as_bytes()))
+            .or_default()
+            .extend(permissions);
+    }
+
+    /// Revoke a key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The admin key.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was known, `false` otherwise.
+    pub fn revoke(&mut self, key: &str) -> bool {
+        self.keys.remove(&Hash::digest(key.as_bytes())).is_some()
+    }
+
+    /// Get the permissions granted by a key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The admin key.
+    ///
+    /// # Returns
+    ///
+    /// The permissions, or `Unauthenticated` if the key is unknown.
+    pub fn permissions(&self, key: &str) -> Result<&BTreeSet<Permission>, BlockchainError> {
+        self.keys
+            .get(&Hash::digest(key.as_bytes()))
+            .ok_or(BlockchainError::Unauthenticated)
+    }
+
+    /// Register a wallet API key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The wallet API key.
+    /// - `wallet`: The address of the wallet.
+    /// - `scope`: The authority delegated by the key.
+    pub fn issue_wallet_key(&mut self, key: &str, wallet: &Address, scope: KeyScope) {
+        self.wallet_keys.insert(
+            Hash::digest(key.as_bytes()),
+            WalletKey {
+                wallet: wallet.to_owned(),
+                scope,
+                spent: 0.0,
+            },
+        );
+    }
+
+    /// Revoke a wallet API key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The wallet API key.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was known, `false` otherwise.
+    pub fn revoke_wallet_key(&mut self, key: &str) -> bool {
+        self.wallet_keys
+            .remove(&Hash::digest(key.as_bytes()))
+            .is_some()
+    }
+
+    /// Get a wallet API key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The wallet API key.
+    ///
+    /// # Returns
+    ///
+    /// The wallet and scope of the key, or `Unauthenticated` if the key is unknown.
+    pub fn wallet_key(&self, key: &str) -> Result<&WalletKey, BlockchainError> {
+        self.wallet_keys
+            .get(&Hash::digest(key.as_bytes()))
+            .ok_or(BlockchainError::Unauthenticated)
+    }
+
+    /// Count an amount sent with a wallet API key against its limit.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The wallet API key.
+    /// - `amount`: The amount sent.
+    pub(crate) fn record_spend(&mut self, key: &str, amount: f64) {
+        if let Some(wallet_key) = self.wallet_keys.get_mut(&Hash::digest(key.as_bytes())) {
+            wallet_key.spent += amount;
+        }
+    }
+}
+
+/// Administrative operations on a chain, each checked against the permissions of an admin key.
+///
+/// Obtained with `Chain::admin`, so an application exposing the chain to remote callers can hand
+/// them scoped keys instead of the chain itself.
+#[derive(Debug)]
+pub struct Admin<'a> {
+    /// The administered chain.
+    chain: &'a mut Chain,
+
+    /// Permissions granted by the key.
+    permissions: BTreeSet<Permission>,
+}
+
+impl<'a> Admin<'a> {
+    /// Create the administrative operations of a key.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The administered chain.
+    /// - `permissions`: The permissions granted by the key.
+    ///
+    /// # Returns
+    ///
+    /// The administrative operations.
+    pub(crate) fn new(chain: &'a mut Chain, permissions: BTreeSet<Permission>) -> Self {
+        Admin { chain, permissions }
+    }
+
+    /// Check whether the key grants a permission.
+    ///
+    /// # Arguments
+    ///
+    /// - `permission`: The permission.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the permission is granted, `false` otherwise.
+    pub fn can(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+
+    /// Update the transaction fee, with `Permission::UpdateParameters`.
+    ///
+    /// # Arguments
+    ///
+    /// - `fee`: The new transaction fee.
+    ///
+    /// # Returns
+    ///
+    /// `Unauthorized` if the permission is not granted.
+    pub fn update_fee(&mut self, fee: f64) -> Result<(), BlockchainError> {
+        self.require(Permission::UpdateParameters)?;
+        self.chain.update_fee(fee);
+
+        Ok(())
+    }
+
+    /// Update the block reward, with `Permission::UpdateParameters`.
+    ///
+    /// # Arguments
+    ///
+    /// - `reward`: The new block reward.
+    ///
+    /// # Returns
+    ///
+    /// `Unauthorized` if the permission is not granted.
+    pub fn update_reward(&mut self, reward: f64) -> Result<(), BlockchainError> {
+        self.require(Permission::UpdateParameters)?;
+        self.chain.update_reward(reward);
+
+        Ok(())
+    }
+
+    /// Update the mining difficulty, with `Permission::UpdateParameters`.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The new mining difficulty.
+    ///
+    /// # Returns
+    ///
+    /// `Unauthorized` if the permission is not granted.
+    pub fn update_difficulty(&mut self, difficulty: f64) -> Result<(), BlockchainError> {
+        self.require(Permission::UpdateParameters)?;
+        self.chain.update_difficulty(difficulty);
+
+        Ok(())
+    }
+
+    /// Deny an address, freezing its transfers, with `Permission::Freeze`.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to deny.
+    /// - `jurisdiction`: The jurisdiction the address is denied under, if any.
+    ///
+    /// # Returns
+    ///
+    /// `Unauthorized` if the permission is not granted.
+    pub fn deny_address(
+        &mut self,
+        address: &Address,
+        jurisdiction: Option<&str>,
+    ) -> Result<(), BlockchainError> {
+        self.require(Permission::Freeze)?;
+        self.chain.deny_address(address, jurisdiction);
+
+        Ok(())
+    }
+
+    /// Lift the denial of an address, with `Permission::Freeze`.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The denied address.
+    ///
+    /// # Returns
+    ///
+    /// Whether the address was denied, or `Unauthorized` if the permission is not granted.
+    pub fn lift_denial(&mut self, address: &Address) -> Result<bool, BlockchainError> {
+        self.require(Permission::Freeze)?;
+
+        Ok(self.chain.lift_denial(address))
+    }
+
+    /// Replace the allow list, with `Permission::Freeze`.
+    ///
+    /// # Arguments
+    ///
+    /// - `allowed`: The allowed addresses, or `None` to disable the allow list.
+    ///
+    /// # Returns
+    ///
+    /// `Unauthorized` if the permission is not granted.
+    pub fn set_allowlist(&mut self, allowed: Option<Vec<Address>>) -> Result<(), BlockchainError> {
+        self.require(Permission::Freeze)?;
+        self.chain.set_allowlist(allowed);
+
+        Ok(())
+    }
+
+    /// Remove the blocks above a height, with `Permission::Rollback`.
+    ///
+    /// # Arguments
+    ///
+    /// - `height`: The number of blocks to keep.
+    ///
+    /// # Returns
+    ///
+    /// The removed blocks, or `Unauthorized` if the permission is not granted.
+    pub fn truncate(&mut self, height: usize) -> Result<Vec<Block>, BlockchainError> {
+        self.require(Permission::Rollback)?;
+
+        Ok(self.chain.truncate(height))
+    }
+
+    /// Replace the tip of the chain with a competing branch, with `Permission::Rollback`.
+    ///
+    /// # Arguments
+    ///
+    /// - `blocks`: The blocks of the branch, from the lowest height up.
+    ///
+    /// # Returns
+    ///
+    /// The replaced blocks, `Unauthorized` if the permission is not granted, or the error of
+    /// `Chain::reorganize`.
+    pub fn reorganize(&mut self, blocks: Vec<Block>) -> Result<Vec<Block>, BlockchainError> {
+        self.require(Permission::Rollback)?;
+
+        self.chain.reorganize(blocks)
+    }
+
+    /// Require a permission.
+    fn require(&self, permission: Permission) -> Result<(), BlockchainError> {
+        match self.can(permission) {
+            true => Ok(()),
+            false => Err(BlockchainError::Unauthorized),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permissions() {
+        let mut access = AccessControl::default();
+        access.grant("key", &[Permission::Freeze]);
+        access.grant("key", &[Permission::Rollback]);
+
+        assert_eq!(
+            access.permissions("key").unwrap(),
+            &BTreeSet::from([Permission::Freeze, Permission::Rollback])
+        );
+        assert_eq!(
+            access.permissions("other"),
+            Err(BlockchainError::Unauthenticated)
+        );
+        assert!(access.revoke("key"));
+        assert!(!access.revoke("key"));
+    }
+
+    #[test]
+    fn test_wallet_keys() {
+        let mut access = AccessControl::default();
+        let wallet = Address::random();
+        access.issue_wallet_key("app", &wallet, KeyScope::Send { limit: 10.0 });
+        access.issue_wallet_key("viewer", &wallet, KeyScope::ReadOnly);
+
+        access.record_spend("app", 6.0);
+        assert!(access.wallet_key("app").unwrap().can_send(4.0));
+        assert!(!access.wallet_key("app").unwrap().can_send(4.5));
+        assert!(!access.wallet_key("viewer").unwrap().can_send(0.1));
+        assert_eq!(access.wallet_key("viewer").unwrap().wallet, wallet);
+        assert!(access.revoke_wallet_key("viewer"));
+        assert_eq!(
+            access.wallet_key("viewer"),
+            Err(BlockchainError::Unauthenticated)
+        );
+    }
+}
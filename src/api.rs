@@ -0,0 +1,788 @@
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, Request, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use futures::{stream, Stream};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::{
+    auth::{self, Auth, Role, API_KEY_HEADER},
+    Address, Block, BlockchainError, Chain, ChainEvent, Hash, SubscriptionFilter, Transaction,
+    Wallet,
+};
+
+/// Default number of items per page.
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Maximum number of items per page.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Chain shared between the request handlers.
+pub type ApiState = Arc<Mutex<Chain>>;
+
+/// Error returned by the API, mapped from `BlockchainError` to an HTTP status.
+#[derive(Debug, PartialEq)]
+pub struct ApiError(pub BlockchainError);
+
+impl ApiError {
+    /// Get the HTTP status code of the error.
+    ///
+    /// # Returns
+    ///
+    /// `404` for missing resources, `401` for missing credentials, `403` for unauthorized
+    /// operations, `429` for rate limited clients, `503` for network failures and `400` for
+    /// any other rejected request.
+    pub fn status(&self) -> StatusCode {
+        match self.0 {
+            BlockchainError::TransactionNotFound { .. }
+            | BlockchainError::WalletNotFound { .. }
+            | BlockchainError::BlockNotFound
+            | BlockchainError::TokenNotFound
+            | BlockchainError::NftNotFound
+            | BlockchainError::ContractNotFound
+            | BlockchainError::ProposalNotFound
+            | BlockchainError::FeedNotFound
+            | BlockchainError::ChainNotFound { .. } => StatusCode::NOT_FOUND,
+            BlockchainError::Unauthenticated => StatusCode::UNAUTHORIZED,
+            BlockchainError::Unauthorized | BlockchainError::ScriptFailed => StatusCode::FORBIDDEN,
+            BlockchainError::RateLimited | BlockchainError::FaucetCooldown { .. } => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            BlockchainError::NetworkError => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<BlockchainError> for ApiError {
+    fn from(error: BlockchainError) -> Self {
+        ApiError(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({ "message": self.0.to_string(), "error": self.0 });
+
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+/// Pagination query parameters.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Pagination {
+    /// Page number, starting at 1.
+    #[serde(default = "first_page")]
+    pub page: usize,
+
+    /// Number of items per page, capped at `MAX_PAGE_SIZE`.
+    #[serde(default = "default_page_size")]
+    pub size: usize,
+}
+
+impl Pagination {
+    /// Get the clamped page number and page size.
+    ///
+    /// # Returns
+    ///
+    /// The page number, at least 1, and the page size, between 1 and `MAX_PAGE_SIZE`.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.page.max(1), self.size.clamp(1, MAX_PAGE_SIZE))
+    }
+}
+
+/// Page of items with the total number of items.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Page<T> {
+    /// Items of the page.
+    pub items: Vec<T>,
+
+    /// Page number.
+    pub page: usize,
+
+    /// Number of items per page.
+    pub size: usize,
+
+    /// Total number of items.
+    pub total: usize,
+}
+
+/// Query parameters of `GET /events`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Kind of events, `blocks`, `transactions`, `lifecycle` or `watched`; every event if not given.
+    pub kind: Option<String>,
+
+    /// Watched address; takes precedence over the kind.
+    pub address: Option<Address>,
+}
+
+impl EventsQuery {
+    /// Get the subscription filter of the query.
+    ///
+    /// # Returns
+    ///
+    /// The filter, or an `InvalidData` error if the kind is unknown.
+    pub fn filter(&self) -> Result<SubscriptionFilter, BlockchainError> {
+        if let Some(address) = &self.address {
+            return Ok(SubscriptionFilter::Address(address.to_owned()));
+        }
+
+        match self.kind.as_deref() {
+            None | Some("all") => Ok(SubscriptionFilter::All),
+            Some("blocks") => Ok(SubscriptionFilter::Blocks),
+            Some("transactions") => Ok(SubscriptionFilter::Transactions),
+            Some("lifecycle") => Ok(SubscriptionFilter::Lifecycle),
+            Some("watched") => Ok(SubscriptionFilter::Watched),
+            Some(_) => Err(BlockchainError::InvalidData),
+        }
+    }
+}
+
+/// Request body of `POST /wallets`.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateWalletInput {
+    /// Email of the wallet owner.
+    pub email: String,
+}
+
+/// Request body of `POST /transactions`.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AddTransactionInput {
+    /// Sender address.
+    pub from: Address,
+
+    /// Receiver address.
+    pub to: Address,
+
+    /// Transaction amount.
+    pub amount: f64,
+}
+
+/// Response payload of `GET /stats`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Stats {
+    /// Number of blocks, including the genesis block.
+    pub height: usize,
+
+    /// Hash of the last block.
+    pub last_hash: Hash,
+
+    /// Current difficulty level of the network.
+    pub difficulty: f64,
+
+    /// Block reward.
+    pub reward: f64,
+
+    /// Transaction fee.
+    pub fee: f64,
+
+    /// Number of transactions.
+    pub transactions: usize,
+
+    /// Number of wallets.
+    pub wallets: usize,
+
+    /// Total size in bytes of the blocks in their canonical binary encoding.
+    pub size: usize,
+}
+
+/// Build the REST API router over a shared chain.
+///
+/// Routes:
+///
+/// - `POST /wallets`: create a wallet, rate limited per email and API key with `Chain::set_rate_limits`.
+/// - `GET /wallets/:address`: get a wallet.
+/// - `GET /wallets/:address/transactions`: list the transactions of a wallet.
+/// - `GET /transactions`, `POST /transactions`: list or add transactions.
+/// - `GET /transactions/:hash`: get a transaction.
+/// - `GET /blocks`, `POST /blocks`: list or mine blocks.
+/// - `GET /blocks/:height`: get a block.
+/// - `GET /stats`: get chain statistics.
+/// - `GET /events`: server-sent events of new blocks and transactions and of chain lifecycle
+///   changes, filtered by `kind` or `address`, for consumers that cannot use other subscription
+///   transports.
+/// - `GET /openapi.json`: get the OpenAPI document of these routes, see `openapi`.
+///
+/// Successful responses wrap their payload in `{ "data": ... }`, errors are returned as
+/// `{ "message": ..., "error": ... }` with a status code mapped from the `BlockchainError`.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+///
+/// # Returns
+///
+/// A new router.
+pub fn router(chain: ApiState) -> Router {
+    Router::new()
+        .route("/wallets", post(create_wallet))
+        .route("/wallets/:address", get(get_wallet))
+        .route(
+            "/wallets/:address/transactions",
+            get(get_wallet_transactions),
+        )
+        .route("/transactions", get(get_transactions).post(add_transaction))
+        .route("/transactions/:hash", get(get_transaction))
+        .route("/blocks", get(get_blocks).post(generate_block))
+        .route("/blocks/:height", get(get_block))
+        .route("/stats", get(get_stats))
+        .route("/events", get(get_events))
+        .route("/openapi.json", get(get_openapi))
+        .with_state(chain)
+}
+
+/// Build the REST API router over a shared chain, requiring an API key on every request.
+///
+/// Requests are rate limited per API key. Creating wallets and mining blocks require the
+/// `Admin` role.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+/// - `auth`: The API keys and rate limits.
+///
+/// # Returns
+///
+/// A new router.
+pub fn router_with_auth(chain: ApiState, auth: Auth) -> Router {
+    router(chain).layer(middleware::from_fn_with_state(Arc::new(auth), authenticate))
+}
+
+/// Check whether a route requires the `Admin` role.
+fn requires_admin(method: &Method, path: &str) -> bool {
+    *method == Method::POST && matches!(path, "/wallets" | "/blocks")
+}
+
+/// Authenticate, rate limit and authorize a request.
+async fn authenticate<B>(
+    State(auth): State<Arc<Auth>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, ApiError> {
+    let role = auth.authorize(request.headers())?;
+
+    if role < Role::Admin && requires_admin(request.method(), request.uri().path()) {
+        return Err(ApiError(BlockchainError::Unauthorized));
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Wrap a payload in a successful response.
+fn data<T: Serialize>(status: StatusCode, payload: T) -> Response {
+    (status, Json(json!({ "data": payload }))).into_response()
+}
+
+/// Lock the shared chain, recovering from a poisoned lock.
+fn lock(state: &ApiState) -> MutexGuard<'_, Chain> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Paginate a list of items.
+fn paginate<T: Clone>(items: &[T], pagination: Pagination) -> Page<T> {
+    let (page, size) = pagination.bounds();
+
+    Page {
+        items: items
+            .iter()
+            .skip((page - 1).saturating_mul(size))
+            .take(size)
+            .cloned()
+            .collect(),
+        page,
+        size,
+        total: items.len(),
+    }
+}
+
+/// Create a wallet.
+async fn create_wallet(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(body): Json<CreateWalletInput>,
+) -> Result<Response, ApiError> {
+    let client = auth::api_key(&headers).unwrap_or_default();
+    let address = lock(&state).create_wallet_from(&body.email, client)?;
+
+    Ok(data(StatusCode::CREATED, address))
+}
+
+/// Get a wallet.
+async fn get_wallet(
+    State(state): State<ApiState>,
+    Path(address): Path<Address>,
+) -> Result<Response, ApiError> {
+    let chain = lock(&state);
+    let wallet = chain
+        .wallets
+        .get(&address)
+        .ok_or_else(|| BlockchainError::WalletNotFound {
+            address: address.to_owned(),
+        })?;
+
+    Ok(data(StatusCode::OK, wallet))
+}
+
+/// List the transactions of a wallet, most recent first.
+async fn get_wallet_transactions(
+    State(state): State<ApiState>,
+    Path(address): Path<Address>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Response, ApiError> {
+    let chain = lock(&state);
+    let wallet = chain
+        .wallets
+        .get(&address)
+        .ok_or_else(|| BlockchainError::WalletNotFound {
+            address: address.to_owned(),
+        })?;
+
+    let transactions: Vec<_> = wallet
+        .transaction_hashes
+        .iter()
+        .rev()
+        .filter_map(|hash| chain.transactions.get(hash))
+        .collect();
+
+    Ok(data(StatusCode::OK, paginate(&transactions, pagination)))
+}
+
+/// List all transactions, most recent first.
+async fn get_transactions(
+    State(state): State<ApiState>,
+    Query(pagination): Query<Pagination>,
+) -> Response {
+    let chain = lock(&state);
+
+    let mut transactions: Vec<_> = chain.transactions.values().collect();
+    transactions.sort_by_key(|tx| (std::cmp::Reverse(tx.timestamp), tx.hash));
+
+    data(StatusCode::OK, paginate(&transactions, pagination))
+}
+
+/// Add a transaction.
+async fn add_transaction(
+    State(state): State<ApiState>,
+    Json(body): Json<AddTransactionInput>,
+) -> Result<Response, ApiError> {
+    let mut chain = lock(&state);
+    chain.add_transaction(&body.from, &body.to, body.amount)?;
+
+    // The new transaction is the last one recorded for the sender
+    let transaction = chain
+        .wallets
+        .get(&body.from)
+        .and_then(|wallet| wallet.transaction_hashes.last())
+        .and_then(|hash| chain.transactions.get(hash));
+
+    Ok(data(StatusCode::CREATED, transaction))
+}
+
+/// Get a transaction.
+async fn get_transaction(
+    State(state): State<ApiState>,
+    Path(hash): Path<Hash>,
+) -> Result<Response, ApiError> {
+    let chain = lock(&state);
+
+    Ok(data(StatusCode::OK, chain.get_transaction(&hash)?))
+}
+
+/// List the blocks, most recent first.
+async fn get_blocks(
+    State(state): State<ApiState>,
+    Query(pagination): Query<Pagination>,
+) -> Response {
+    let chain = lock(&state);
+    let blocks: Vec<&Block> = chain.chain.iter().rev().collect();
+
+    data(StatusCode::OK, paginate(&blocks, pagination))
+}
+
+/// Mine a new block.
+async fn generate_block(State(state): State<ApiState>) -> Response {
+    let mut chain = lock(&state);
+    chain.generate_new_block();
+
+    data(StatusCode::CREATED, chain.chain.last())
+}
+
+/// Get a block by height.
+async fn get_block(
+    State(state): State<ApiState>,
+    Path(height): Path<usize>,
+) -> Result<Response, ApiError> {
+    let chain = lock(&state);
+    let block = chain
+        .chain
+        .get(height)
+        .ok_or(BlockchainError::BlockNotFound)?;
+
+    Ok(data(StatusCode::OK, block))
+}
+
+/// Get chain statistics.
+async fn get_stats(State(state): State<ApiState>) -> Response {
+    let chain = lock(&state);
+
+    let stats = Stats {
+        height: chain.chain.len(),
+        last_hash: chain.get_last_hash(),
+        difficulty: chain.difficulty,
+        reward: chain.reward,
+        fee: chain.fee,
+        transactions: chain.transactions.len(),
+        wallets: chain.wallets.len(),
+        size: chain.chain.iter().map(|block| block.size).sum(),
+    };
+
+    data(StatusCode::OK, stats)
+}
+
+/// Stream chain events as server-sent events.
+///
+/// Events are named `block` or `transaction` and carry the JSON-encoded item as data.
+async fn get_events(
+    State(state): State<ApiState>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let subscription = lock(&state).subscribe(query.filter()?);
+    let chain = Arc::downgrade(&state);
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    // Forward the events until the client disconnects or the chain is dropped
+    thread::spawn(move || loop {
+        let closed = match subscription.next_timeout(Duration::from_secs(1)) {
+            Some(event) => sender.send(event).is_err(),
+            None => sender.is_closed() || chain.strong_count() == 0,
+        };
+
+        if closed {
+            break;
+        }
+    });
+
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        let event = match receiver.recv().await? {
+            ChainEvent::Block(block) => Event::default().event("block").json_data(block),
+            ChainEvent::Transaction(tx) => Event::default().event("transaction").json_data(tx),
+            ChainEvent::WalletCreated(address) => {
+                Event::default().event("wallet").json_data(address)
+            }
+            ChainEvent::ParameterChanged(change) => {
+                Event::default().event("parameter").json_data(change)
+            }
+            ChainEvent::Reorg { height, removed } => Event::default()
+                .event("reorg")
+                .json_data(json!({ "height": height, "removed": removed })),
+            ChainEvent::BalanceChanged {
+                address,
+                previous,
+                balance,
+            } => Event::default().event("balance").json_data(json!({
+                "address": address,
+                "previous": previous,
+                "balance": balance,
+            })),
+            ChainEvent::WatchedBlock {
+                address,
+                height,
+                hash,
+                transactions,
+            } => Event::default().event("watched_block").json_data(json!({
+                "address": address,
+                "height": height,
+                "hash": hash,
+                "transactions": transactions,
+            })),
+        };
+
+        Some((event.unwrap_or_default(), receiver))
+    });
+
+    Ok(Sse::new(stream::StreamExt::map(events, Ok)).keep_alive(KeepAlive::default()))
+}
+
+/// Get the OpenAPI document.
+async fn get_openapi() -> Json<Value> {
+    Json(openapi())
+}
+
+/// Build the OpenAPI 3.0 document of the routes of `router`.
+///
+/// Payload schemas are generated from the Rust types with `schemars`, and the document is
+/// versioned with the crate version, so it matches the code it is served by.
+///
+/// # Returns
+///
+/// The OpenAPI document.
+pub fn openapi() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+
+    let address = schema::<Address>(&mut gen);
+    let wallet = schema::<Wallet>(&mut gen);
+    let transaction = schema::<Transaction>(&mut gen);
+    let transactions = schema::<Page<Transaction>>(&mut gen);
+    let block = schema::<Block>(&mut gen);
+    let blocks = schema::<Page<Block>>(&mut gen);
+    let stats = schema::<Stats>(&mut gen);
+    let create_wallet = schema::<CreateWalletInput>(&mut gen);
+    let add_transaction = schema::<AddTransactionInput>(&mut gen);
+    let error = schema::<BlockchainError>(&mut gen);
+
+    let pagination = json!([
+        query(
+            "page",
+            "Page number, starting at 1.",
+            json!({ "type": "integer", "default": 1 })
+        ),
+        query(
+            "size",
+            "Number of items per page.",
+            json!({ "type": "integer", "default": DEFAULT_PAGE_SIZE, "maximum": MAX_PAGE_SIZE }),
+        ),
+    ]);
+    let address_path = path("address", "Address of the wallet.", address.clone());
+    let hash_path = path("hash", "Hash of the transaction.", schema::<Hash>(&mut gen));
+    let height_path = path(
+        "height",
+        "Height of the block.",
+        json!({ "type": "integer", "minimum": 0 }),
+    );
+    let events = json!([
+        query(
+            "kind",
+            "Kind of events; every event if not given.",
+            json!({ "type": "string", "enum": ["all", "blocks", "transactions", "lifecycle", "watched"] }),
+        ),
+        query(
+            "address",
+            "Watched address; takes precedence over the kind.",
+            address.clone(),
+        ),
+    ]);
+
+    let mut schemas = serde_json::to_value(gen.take_definitions()).unwrap_or_default();
+    schemas["ApiError"] = json!({
+        "type": "object",
+        "required": ["message", "error"],
+        "properties": { "message": { "type": "string" }, "error": error },
+    });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "blockchain",
+            "description": env!("CARGO_PKG_DESCRIPTION"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {
+            "/wallets": {
+                "post": {
+                    "summary": "Create a wallet.",
+                    "requestBody": body(create_wallet),
+                    "responses": { "201": ok("Address of the new wallet.", address) },
+                },
+            },
+            "/wallets/{address}": {
+                "get": {
+                    "summary": "Get a wallet.",
+                    "parameters": [address_path],
+                    "responses": {
+                        "200": ok("The wallet.", wallet),
+                        "404": failed("Wallet not found."),
+                    },
+                },
+            },
+            "/wallets/{address}/transactions": {
+                "get": {
+                    "summary": "List the transactions of a wallet, most recent first.",
+                    "parameters": [address_path, pagination[0], pagination[1]],
+                    "responses": {
+                        "200": ok("Page of transactions.", transactions.clone()),
+                        "404": failed("Wallet not found."),
+                    },
+                },
+            },
+            "/transactions": {
+                "get": {
+                    "summary": "List all transactions, most recent first.",
+                    "parameters": pagination,
+                    "responses": { "200": ok("Page of transactions.", transactions) },
+                },
+                "post": {
+                    "summary": "Add a transaction.",
+                    "requestBody": body(add_transaction),
+                    "responses": {
+                        "201": ok("The new transaction.", transaction.clone()),
+                        "400": failed("Transaction rejected."),
+                        "404": failed("Wallet not found."),
+                    },
+                },
+            },
+            "/transactions/{hash}": {
+                "get": {
+                    "summary": "Get a transaction.",
+                    "parameters": [hash_path],
+                    "responses": {
+                        "200": ok("The transaction.", transaction),
+                        "404": failed("Transaction not found."),
+                    },
+                },
+            },
+            "/blocks": {
+                "get": {
+                    "summary": "List the blocks, most recent first.",
+                    "parameters": pagination,
+                    "responses": { "200": ok("Page of blocks.", blocks) },
+                },
+                "post": {
+                    "summary": "Mine a new block.",
+                    "responses": { "201": ok("The new block.", block.clone()) },
+                },
+            },
+            "/blocks/{height}": {
+                "get": {
+                    "summary": "Get a block by height.",
+                    "parameters": [height_path],
+                    "responses": {
+                        "200": ok("The block.", block),
+                        "404": failed("Block not found."),
+                    },
+                },
+            },
+            "/stats": {
+                "get": {
+                    "summary": "Get chain statistics.",
+                    "responses": { "200": ok("Chain statistics.", stats) },
+                },
+            },
+            "/events": {
+                "get": {
+                    "summary": "Stream chain events as server-sent events.",
+                    "description": "Events are named `block`, `transaction`, `wallet`, \
+                        `parameter` or `reorg` and carry the JSON-encoded item as data.",
+                    "parameters": events,
+                    "responses": {
+                        "200": {
+                            "description": "Stream of events.",
+                            "content": { "text/event-stream": { "schema": { "type": "string" } } },
+                        },
+                        "400": failed("Unknown kind of events."),
+                    },
+                },
+            },
+            "/openapi.json": {
+                "get": {
+                    "summary": "Get this document.",
+                    "responses": {
+                        "200": {
+                            "description": "The OpenAPI document.",
+                            "content": { "application/json": { "schema": { "type": "object" } } },
+                        },
+                    },
+                },
+            },
+        },
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": API_KEY_HEADER,
+                    "description": "Required by `router_with_auth`.",
+                },
+            },
+        },
+    })
+}
+
+/// Get the schema of a type, or a reference to it in the generator definitions.
+fn schema<T: JsonSchema>(gen: &mut SchemaGenerator) -> Value {
+    serde_json::to_value(gen.subschema_for::<T>()).unwrap_or_default()
+}
+
+/// Describe a path parameter.
+fn path(name: &str, description: &str, schema: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": schema,
+    })
+}
+
+/// Describe an optional query parameter.
+fn query(name: &str, description: &str, schema: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": schema,
+    })
+}
+
+/// Describe a JSON request body.
+fn body(schema: Value) -> Value {
+    json!({ "required": true, "content": { "application/json": { "schema": schema } } })
+}
+
+/// Describe a successful response wrapping its payload in `{ "data": ... }`.
+fn ok(description: &str, schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": {
+                    "type": "object",
+                    "required": ["data"],
+                    "properties": { "data": schema },
+                },
+            },
+        },
+    })
+}
+
+/// Describe an error response.
+fn failed(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": { "schema": { "$ref": "#/components/schemas/ApiError" } },
+        },
+    })
+}
+
+/// First page number, used when the page is not given.
+fn first_page() -> usize {
+    1
+}
+
+/// Default page size, used when the size is not given.
+fn default_page_size() -> usize {
+    DEFAULT_PAGE_SIZE
+}
@@ -0,0 +1,205 @@
+use std::{ops::Range, sync::Arc};
+
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection::vec,
+    sample::select,
+    strategy::{BoxedStrategy, Strategy},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use uuid::Uuid;
+
+use crate::{
+    Address, BlockchainError, Chain, Environment, Hash, ManualClock, Transaction, Wallet,
+    NATIVE_ASSET,
+};
+
+/// Time of the genesis block of the generated chains, in seconds since the Unix epoch.
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+
+/// Assets of the generated transactions.
+const ASSETS: &[&str] = &[NATIVE_ASSET, "GOLD", "SILVER"];
+
+impl Arbitrary for Hash {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[u8; 32]>()
+            .prop_map(|bytes| Hash::digest(&bytes))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Address {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<u64>()
+            .prop_map(|seed| Address::from_rng(&mut StdRng::seed_from_u64(seed)))
+            .boxed()
+    }
+}
+
+impl Arbitrary for Transaction {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u128>(),
+            GENESIS_TIME..GENESIS_TIME + 365 * 24 * 60 * 60,
+            any::<Address>(),
+            any::<Address>(),
+            0.0..1.0,
+            0.0..1_000_000.0,
+            select(ASSETS),
+        )
+            .prop_map(|(id, timestamp, from, to, fee, amount, asset)| {
+                Transaction::from_parts(
+                    Uuid::from_u128(id),
+                    timestamp,
+                    from,
+                    to,
+                    fee,
+                    amount,
+                    asset,
+                )
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Wallet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u128>(),
+            any::<u16>(),
+            any::<Address>(),
+            0.0..1_000_000.0,
+        )
+            .prop_map(|(id, user, address, balance)| {
+                let mut wallet = Wallet::new(&format!("user{user}@mail.com"), &address);
+                wallet.id = Uuid::from_u128(id);
+                wallet.balance = balance;
+
+                wallet
+            })
+            .boxed()
+    }
+}
+
+/// Native transfer between two wallets of a chain, chosen by index.
+#[derive(Clone, Debug)]
+pub struct Transfer {
+    /// Index of the sender's wallet, modulo the number of wallets.
+    pub from: usize,
+
+    /// Index of the receiver's wallet, modulo the number of wallets.
+    pub to: usize,
+
+    /// Amount of the transfer.
+    pub amount: f64,
+}
+
+impl Transfer {
+    /// Apply the transfer to a chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain.
+    /// - `wallets`: The addresses the indexes of the transfer refer to.
+    ///
+    /// # Returns
+    ///
+    /// The result of `Chain::add_transaction`, e.g. `InsufficientFunds` if the sender cannot afford
+    /// the transfer.
+    pub fn apply(&self, chain: &mut Chain, wallets: &[Address]) -> Result<(), BlockchainError> {
+        let from = &wallets[self.from % wallets.len()];
+        let to = &wallets[self.to % wallets.len()];
+
+        chain.add_transaction(from, to, self.amount)
+    }
+}
+
+impl Arbitrary for Transfer {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<usize>(), any::<usize>(), 0.0..100.0)
+            .prop_map(|(from, to, amount)| Transfer { from, to, amount })
+            .boxed()
+    }
+}
+
+/// Generate chains with funded wallets, transfers between them and mined blocks.
+///
+/// Each chain is built with a seeded `Environment` and a `ManualClock` starting at `GENESIS_TIME`,
+/// so a failing case is reproduced exactly from its seed. Transfers rejected by the chain, e.g.
+/// for insufficient funds, are skipped.
+///
+/// # Arguments
+///
+/// - `wallets`: The range of the number of wallets, which must not be empty.
+/// - `transfers`: The range of the number of attempted transfers.
+/// - `blocks`: The range of the number of blocks mined after the genesis block.
+///
+/// # Returns
+///
+/// A strategy generating valid chains.
+pub fn chains(
+    wallets: Range<usize>,
+    transfers: Range<usize>,
+    blocks: Range<usize>,
+) -> impl Strategy<Value = Chain> {
+    (
+        any::<u64>(),
+        vec(0.0..1_000.0, wallets),
+        vec(any::<Transfer>(), transfers),
+        blocks,
+    )
+        .prop_map(|(seed, balances, transfers, blocks)| {
+            let clock = Arc::new(ManualClock::new(GENESIS_TIME));
+            let environment = Environment::seeded(seed, clock.clone());
+            let mut chain = Chain::with_environment(1.0, 100.0, 0.1, environment);
+
+            let wallets: Vec<Address> = balances
+                .iter()
+                .enumerate()
+                .map(|(i, balance)| {
+                    let address = chain.create_wallet(&format!("wallet{i}@mail.com"));
+                    if let Some(wallet) = chain.wallets.get_mut(&address) {
+                        wallet.balance = *balance;
+                    }
+
+                    address
+                })
+                .collect();
+
+            for transfer in &transfers {
+                clock.advance(1);
+                let _ = transfer.apply(&mut chain, &wallets);
+            }
+
+            for _ in 0..blocks {
+                clock.advance(60);
+                chain.generate_new_block();
+            }
+
+            chain
+        })
+}
+
+impl Arbitrary for Chain {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        chains(1..8, 0..32, 0..4).boxed()
+    }
+}
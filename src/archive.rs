@@ -0,0 +1,330 @@
+use std::io::{Read, Write};
+
+use serde_json::Value;
+
+use crate::{wire::checksum, Block, BlockchainError, STATE_VERSION};
+
+/// Magic bytes opening every block archive.
+pub const ARCHIVE_MAGIC: [u8; 4] = *b"BCRA";
+
+/// Version of the archive container, files of a newer version are rejected.
+pub const ARCHIVE_VERSION: u16 = 1;
+
+/// Size in bytes of the archive header: magic, version, flags and state version.
+pub const ARCHIVE_HEADER_SIZE: usize = 16;
+
+/// Size in bytes of a record header: length and checksum.
+pub const RECORD_HEADER_SIZE: usize = 8;
+
+/// Maximum size in bytes of a record payload.
+pub const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+/// Flag set when some archived blocks have their bodies pruned.
+pub const FLAG_PRUNED: u16 = 0x0001;
+
+/// Flags understood by this version, files with any other flag are rejected.
+const KNOWN_FLAGS: u16 = FLAG_PRUNED;
+
+/// Header of a block archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchiveHeader {
+    /// Version of the archive container.
+    pub version: u16,
+
+    /// Optional features used by the archive, e.g. `FLAG_PRUNED`.
+    pub flags: u16,
+
+    /// Version of the persisted block format of the records, see `STATE_VERSION`.
+    pub state_version: u64,
+}
+
+impl ArchiveHeader {
+    /// Parse and validate an archive header.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: The header bytes.
+    ///
+    /// # Returns
+    ///
+    /// The header, `CorruptArchive` if the magic bytes, the version or a flag is unknown, or
+    /// `UnsupportedStateVersion` if the blocks were written by a newer version of the crate.
+    pub fn parse(bytes: &[u8; ARCHIVE_HEADER_SIZE]) -> Result<Self, BlockchainError> {
+        let version = u16::from_be_bytes([bytes[4], bytes[5]]);
+        let flags = u16::from_be_bytes([bytes[6], bytes[7]]);
+        let state_version = u64::from_be_bytes(bytes[8..].try_into().unwrap_or_default());
+
+        if bytes[..4] != ARCHIVE_MAGIC || version == 0 || version > ARCHIVE_VERSION {
+            return Err(BlockchainError::CorruptArchive { offset: 0 });
+        }
+
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(BlockchainError::CorruptArchive { offset: 6 });
+        }
+
+        if state_version > STATE_VERSION {
+            return Err(BlockchainError::UnsupportedStateVersion {
+                version: state_version,
+            });
+        }
+
+        Ok(ArchiveHeader {
+            version,
+            flags,
+            state_version,
+        })
+    }
+
+    /// Serialize the header.
+    ///
+    /// # Returns
+    ///
+    /// The header bytes.
+    pub fn to_bytes(&self) -> [u8; ARCHIVE_HEADER_SIZE] {
+        let mut bytes = [0u8; ARCHIVE_HEADER_SIZE];
+        bytes[..4].copy_from_slice(&ARCHIVE_MAGIC);
+        bytes[4..6].copy_from_slice(&self.version.to_be_bytes());
+        bytes[6..8].copy_from_slice(&self.flags.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.state_version.to_be_bytes());
+
+        bytes
+    }
+}
+
+/// Writer of a block archive: a header followed by one checksummed record per block.
+#[derive(Debug)]
+pub struct ArchiveWriter<W: Write> {
+    /// Destination of the archive.
+    writer: W,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Start an archive by writing its header.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer`: The destination of the archive.
+    /// - `flags`: The optional features used by the archive.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or `StorageError` if the header cannot be written.
+    pub fn new(mut writer: W, flags: u16) -> Result<Self, BlockchainError> {
+        let header = ArchiveHeader {
+            version: ARCHIVE_VERSION,
+            flags,
+            state_version: STATE_VERSION,
+        };
+        writer
+            .write_all(&header.to_bytes())
+            .map_err(|_| BlockchainError::StorageError)?;
+
+        Ok(ArchiveWriter { writer })
+    }
+
+    /// Append a block.
+    ///
+    /// # Arguments
+    ///
+    /// - `block`: The block.
+    ///
+    /// # Returns
+    ///
+    /// `StorageError` if the block cannot be serialized, is too large or cannot be written.
+    pub fn write_block(&mut self, block: &Block) -> Result<(), BlockchainError> {
+        let payload = serde_json::to_vec(block).map_err(|_| BlockchainError::StorageError)?;
+        if payload.len() > MAX_RECORD_SIZE {
+            return Err(BlockchainError::StorageError);
+        }
+
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        header[..4].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+        header[4..].copy_from_slice(&checksum(&payload));
+
+        self.writer
+            .write_all(&header)
+            .and_then(|_| self.writer.write_all(&payload))
+            .map_err(|_| BlockchainError::StorageError)
+    }
+
+    /// Flush the archive.
+    ///
+    /// # Returns
+    ///
+    /// The destination of the archive, or `StorageError` if it cannot be flushed.
+    pub fn finish(mut self) -> Result<W, BlockchainError> {
+        self.writer
+            .flush()
+            .map_err(|_| BlockchainError::StorageError)?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Reader of a block archive, yielding its blocks in order.
+///
+/// Records written with an older block format are upgraded as they are read.
+#[derive(Debug)]
+pub struct ArchiveReader<R: Read> {
+    /// Source of the archive.
+    reader: R,
+
+    /// Header of the archive.
+    header: ArchiveHeader,
+
+    /// Number of records read.
+    records: usize,
+
+    /// Offset of the next record.
+    offset: u64,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    /// Open an archive by reading and validating its header.
+    ///
+    /// # Arguments
+    ///
+    /// - `reader`: The source of the archive.
+    ///
+    /// # Returns
+    ///
+    /// The reader, or the error of `ArchiveHeader::parse`.
+    pub fn new(mut reader: R) -> Result<Self, BlockchainError> {
+        let mut bytes = [0u8; ARCHIVE_HEADER_SIZE];
+        reader
+            .read_exact(&mut bytes)
+            .map_err(|_| BlockchainError::CorruptArchive { offset: 0 })?;
+
+        Ok(ArchiveReader {
+            reader,
+            header: ArchiveHeader::parse(&bytes)?,
+            records: 0,
+            offset: ARCHIVE_HEADER_SIZE as u64,
+        })
+    }
+
+    /// Get the header of the archive.
+    ///
+    /// # Returns
+    ///
+    /// The archive header.
+    pub fn header(&self) -> &ArchiveHeader {
+        &self.header
+    }
+
+    /// Read the next record.
+    ///
+    /// # Returns
+    ///
+    /// The block, `None` at the end of the archive, or `CorruptArchive` with the offset of the
+    /// truncated, corrupted or unparsable record.
+    fn read_block(&mut self) -> Result<Option<Block>, BlockchainError> {
+        let corrupt = BlockchainError::CorruptArchive {
+            offset: self.offset,
+        };
+
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        match self.reader.read(&mut header[..1]) {
+            Ok(0) => return Ok(None),
+            Ok(_) => self.reader.read_exact(&mut header[1..]).map_err(|_| {
+                BlockchainError::CorruptArchive {
+                    offset: self.offset,
+                }
+            })?,
+            Err(_) => return Err(BlockchainError::StorageError),
+        }
+
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        if length > MAX_RECORD_SIZE {
+            return Err(corrupt);
+        }
+
+        let mut payload = vec![0u8; length];
+        if self.reader.read_exact(&mut payload).is_err() || checksum(&payload) != header[4..] {
+            return Err(corrupt);
+        }
+
+        let mut value: Value =
+            serde_json::from_slice(&payload).map_err(|_| BlockchainError::CorruptArchive {
+                offset: self.offset,
+            })?;
+
+        // Blocks persisted before version 1 have no height, set as in the chain migration
+        if self.header.state_version == 0 {
+            if let Some(block) = value.as_object_mut() {
+                block.insert("height".to_string(), self.records.into());
+            }
+        }
+
+        let block = serde_json::from_value(value).map_err(|_| corrupt)?;
+        self.records += 1;
+        self.offset += (RECORD_HEADER_SIZE + length) as u64;
+
+        Ok(Some(block))
+    }
+}
+
+impl<R: Read> Iterator for ArchiveReader<R> {
+    type Item = Result<Block, BlockchainError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_block().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_write_read() {
+        let block = Block::new(crate::Hash::zero(), 1.0);
+        let mut writer = ArchiveWriter::new(vec![], FLAG_PRUNED).unwrap();
+        writer.write_block(&block).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        assert_eq!(&bytes[..4], b"BCRA");
+
+        let mut reader = ArchiveReader::new(Cursor::new(&bytes)).unwrap();
+        assert_eq!(reader.header().flags, FLAG_PRUNED);
+        assert_eq!(reader.next().unwrap().unwrap().hash, block.hash);
+        assert!(reader.next().is_none());
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let mut reader = ArchiveReader::new(Cursor::new(&corrupted)).unwrap();
+        assert_eq!(
+            reader.next().unwrap().unwrap_err(),
+            BlockchainError::CorruptArchive {
+                offset: ARCHIVE_HEADER_SIZE as u64
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let header = ArchiveHeader {
+            version: ARCHIVE_VERSION,
+            flags: 0x8000,
+            state_version: STATE_VERSION,
+        };
+        assert_eq!(
+            ArchiveHeader::parse(&header.to_bytes()),
+            Err(BlockchainError::CorruptArchive { offset: 6 })
+        );
+
+        let newer = ArchiveHeader {
+            flags: 0,
+            state_version: STATE_VERSION + 1,
+            ..header
+        };
+        assert_eq!(
+            ArchiveHeader::parse(&newer.to_bytes()),
+            Err(BlockchainError::UnsupportedStateVersion {
+                version: STATE_VERSION + 1
+            })
+        );
+    }
+}
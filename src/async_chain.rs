@@ -0,0 +1,273 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tokio::task;
+
+use crate::{migration, Address, BlockchainError, Chain, Hash, Subscription, SubscriptionFilter};
+
+/// Async facade over a shared chain.
+///
+/// Operations that may take a long time, such as proof-of-work, full-chain validation and
+/// persistence, run on the blocking thread pool of the tokio runtime, so awaiting them does not
+/// block the async workers. The underlying chain stays available to synchronous code through
+/// `shared`.
+#[derive(Clone, Debug)]
+pub struct AsyncChain {
+    /// Chain shared with the application.
+    chain: Arc<Mutex<Chain>>,
+}
+
+impl AsyncChain {
+    /// Wrap a chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain.
+    ///
+    /// # Returns
+    ///
+    /// A new facade owning the chain.
+    pub fn new(chain: Chain) -> Self {
+        AsyncChain::from_shared(Arc::new(Mutex::new(chain)))
+    }
+
+    /// Wrap a chain already shared with the application.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The shared chain.
+    ///
+    /// # Returns
+    ///
+    /// A new facade over the shared chain.
+    pub fn from_shared(chain: Arc<Mutex<Chain>>) -> Self {
+        AsyncChain { chain }
+    }
+
+    /// Get the underlying shared chain, e.g. to build an `api::router`.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain.
+    pub fn shared(&self) -> Arc<Mutex<Chain>> {
+        self.chain.clone()
+    }
+
+    /// Run a closure reading the chain on the blocking thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// - `f`: The closure.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure.
+    pub async fn read<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&Chain) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let chain = self.chain.clone();
+
+        blocking(move || f(&lock(&chain))).await
+    }
+
+    /// Run a closure updating the chain on the blocking thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// - `f`: The closure.
+    ///
+    /// # Returns
+    ///
+    /// The result of the closure.
+    pub async fn write<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Chain) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let chain = self.chain.clone();
+
+        blocking(move || f(&mut lock(&chain))).await
+    }
+
+    /// Create a new wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `email`: The email of the wallet owner.
+    ///
+    /// # Returns
+    ///
+    /// The address of the new wallet.
+    pub async fn create_wallet(&self, email: &str) -> Address {
+        let email = email.to_string();
+
+        self.write(move |chain| chain.create_wallet(&email)).await
+    }
+
+    /// Get the balance of a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// The balance, or `None` if the wallet does not exist.
+    pub async fn get_wallet_balance(&self, address: &Address) -> Option<f64> {
+        let address = address.clone();
+
+        self.read(move |chain| chain.get_wallet_balance(&address))
+            .await
+    }
+
+    /// Add a new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// An error if the transaction is invalid.
+    pub async fn add_transaction(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+    ) -> Result<(), BlockchainError> {
+        let (from, to) = (from.clone(), to.clone());
+
+        self.write(move |chain| chain.add_transaction(&from, &to, amount))
+            .await
+    }
+
+    /// Mine a new block and append it to the chain.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the new block.
+    pub async fn generate_new_block(&self) -> Hash {
+        self.write(|chain| {
+            chain.generate_new_block();
+            chain.get_last_hash()
+        })
+        .await
+    }
+
+    /// Get the hash of the last block.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the last block.
+    pub async fn get_last_hash(&self) -> Hash {
+        self.read(|chain| chain.get_last_hash()).await
+    }
+
+    /// Validate the whole chain.
+    ///
+    /// # Returns
+    ///
+    /// An error describing the first invalid block, if any.
+    pub async fn validate_chain(&self) -> Result<(), BlockchainError> {
+        self.read(|chain| chain.validate_chain()).await
+    }
+
+    /// Subscribe to the events of the chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `filter`: The events to receive.
+    ///
+    /// # Returns
+    ///
+    /// A new subscription.
+    pub async fn subscribe(&self, filter: SubscriptionFilter) -> Subscription {
+        self.write(move |chain| chain.subscribe(filter)).await
+    }
+
+    /// Save the chain to a JSON file tagged with `STATE_VERSION`.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: The path of the file, created or replaced.
+    ///
+    /// # Returns
+    ///
+    /// `StorageError` if the chain cannot be serialized or written.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(path = %path.as_ref().display()), err)
+    )]
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), BlockchainError> {
+        let path = path.as_ref().to_path_buf();
+
+        self.read(move |chain| {
+            let bytes = migration::encode(chain)?;
+
+            fs::write(path, bytes).map_err(|_| BlockchainError::StorageError)
+        })
+        .await
+    }
+
+    /// Load a chain saved with `save`, possibly by an older version of the crate, migrate it to
+    /// the current format, verify its cached block hashes and audit log, and validate it.
+    ///
+    /// # Arguments
+    ///
+    /// - `path`: The path of the file.
+    ///
+    /// # Returns
+    ///
+    /// A new facade over the loaded chain, `StorageError` if the file cannot be read or parsed,
+    /// `UnsupportedStateVersion` if it was saved by a newer version of the crate, or the
+    /// validation error if the chain is invalid.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(path = %path.as_ref().display()), err)
+    )]
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self, BlockchainError> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+
+        let chain = blocking(move || {
+            let bytes = fs::read(path).map_err(|_| BlockchainError::StorageError)?;
+            let mut chain = migration::decode(&bytes)?;
+
+            chain.verify_block_hashes()?;
+            chain.audit_log().verify()?;
+            chain.revalidate().map(|_| chain)
+        })
+        .await?;
+
+        Ok(AsyncChain::new(chain))
+    }
+}
+
+/// Run a closure on the blocking thread pool, in the current span, propagating its panics.
+async fn blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    #[cfg(feature = "tracing")]
+    let f = {
+        let span = tracing::Span::current();
+        move || span.in_scope(f)
+    };
+
+    match task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+/// Lock the shared chain, recovering from a poisoned lock.
+fn lock(chain: &Arc<Mutex<Chain>>) -> MutexGuard<'_, Chain> {
+    chain.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,230 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError, Chain, Hash, ProposalChange};
+
+/// Administrative mutation of a chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AuditAction {
+    /// Chain parameter updated, directly or by a governance proposal.
+    ParameterChanged {
+        /// The new value of the parameter.
+        change: ProposalChange,
+
+        /// The value of the parameter before the update.
+        previous: f64,
+    },
+
+    /// Blocks removed from the tip of the chain.
+    Rollback {
+        /// Height of the new tip, i.e. the number of remaining blocks.
+        height: usize,
+
+        /// Hashes of the removed blocks, from the lowest height up.
+        removed: Vec<Hash>,
+    },
+
+    /// Address added to the deny list.
+    AddressDenied {
+        /// The denied address.
+        address: Address,
+
+        /// Jurisdiction the address is denied under.
+        jurisdiction: Option<String>,
+    },
+
+    /// Address removed from the deny list.
+    DenialLifted {
+        /// The address.
+        address: Address,
+    },
+
+    /// Allow list enabled, replaced or disabled.
+    AllowlistChanged {
+        /// The allowed addresses, or `None` if the allow list was disabled.
+        allowed: Option<Vec<Address>>,
+    },
+
+    /// Transfer refused because a party is denied or not allowed.
+    TransferRefused {
+        /// The sender's address.
+        from: Address,
+
+        /// The receiver's address.
+        to: Address,
+
+        /// The party that is not permitted.
+        address: Address,
+    },
+}
+
+/// Entry of an audit log, linked to the previous entry by its hash.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AuditEntry {
+    /// Position of the entry in the log, starting at zero.
+    pub sequence: u64,
+
+    /// Time of the mutation.
+    pub timestamp: i64,
+
+    /// The recorded mutation.
+    pub action: AuditAction,
+
+    /// Hash of the previous entry, or the zero hash for the first entry.
+    pub previous: Hash,
+
+    /// Hash of the entry.
+    pub hash: Hash,
+}
+
+impl AuditEntry {
+    /// Calculate the hash of the entry.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the sequence, timestamp, action and previous hash.
+    pub fn compute_hash(&self) -> Hash {
+        Chain::hash(&(self.sequence, self.timestamp, &self.action, self.previous))
+    }
+}
+
+/// Append-only, hash-chained log of administrative mutations.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AuditLog(Vec<AuditEntry>);
+
+impl AuditLog {
+    /// Append a mutation to the log.
+    ///
+    /// # Arguments
+    ///
+    /// - `action`: The mutation.
+    ///
+    /// # Returns
+    ///
+    /// The new entry.
+    pub fn append(&mut self, action: AuditAction) -> &AuditEntry {
+        self.append_at(action, Utc::now().timestamp())
+    }
+
+    /// Append a mutation to the log with the given timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// - `action`: The mutation.
+    /// - `timestamp`: The time of the mutation, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// The new entry.
+    pub fn append_at(&mut self, action: AuditAction, timestamp: i64) -> &AuditEntry {
+        let (sequence, previous) = match self.0.last() {
+            Some(last) => (last.sequence + 1, last.hash),
+            None => (0, Hash::zero()),
+        };
+
+        let mut entry = AuditEntry {
+            sequence,
+            timestamp,
+            action,
+            previous,
+            hash: Hash::zero(),
+        };
+        entry.hash = entry.compute_hash();
+        self.0.push(entry);
+
+        &self.0[self.0.len() - 1]
+    }
+
+    /// Get the entries of the log.
+    ///
+    /// # Returns
+    ///
+    /// The entries, oldest first.
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.0
+    }
+
+    /// Get the hash of the last entry, e.g. to anchor the log externally.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the last entry, or `None` if the log is empty.
+    pub fn head(&self) -> Option<Hash> {
+        self.0.last().map(|entry| entry.hash)
+    }
+
+    /// Verify the sequence numbers, hashes and links of every entry.
+    ///
+    /// # Returns
+    ///
+    /// `InvalidAuditLog` if an entry was altered, removed or reordered.
+    pub fn verify(&self) -> Result<(), BlockchainError> {
+        let mut previous = Hash::zero();
+
+        for (sequence, entry) in self.0.iter().enumerate() {
+            if entry.sequence != sequence as u64
+                || entry.previous != previous
+                || entry.hash != entry.compute_hash()
+            {
+                return Err(BlockchainError::InvalidAuditLog);
+            }
+
+            previous = entry.hash;
+        }
+
+        Ok(())
+    }
+
+    /// Get the number of entries.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether the log is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no mutation was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let mut log = AuditLog::default();
+        log.append(AuditAction::ParameterChanged {
+            change: ProposalChange::Fee(0.2),
+            previous: 0.1,
+        });
+        log.append(AuditAction::Rollback {
+            height: 1,
+            removed: vec![],
+        });
+
+        assert!(log.verify().is_ok());
+        assert_eq!(log.entries()[1].previous, log.entries()[0].hash);
+
+        let mut tampered = log.clone();
+        tampered.0[0].action = AuditAction::ParameterChanged {
+            change: ProposalChange::Fee(0.0),
+            previous: 0.1,
+        };
+        assert_eq!(tampered.verify(), Err(BlockchainError::InvalidAuditLog));
+
+        let mut truncated = log.clone();
+        truncated.0.remove(0);
+        assert_eq!(truncated.verify(), Err(BlockchainError::InvalidAuditLog));
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::http::{header, HeaderMap};
+
+use crate::BlockchainError;
+
+/// Header carrying the API key, as an alternative to `Authorization: Bearer <key>`.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Default number of requests a client may send per window.
+pub const DEFAULT_RATE_LIMIT: u32 = 60;
+
+/// Default length of a rate limiting window, in seconds.
+pub const DEFAULT_RATE_WINDOW: u64 = 60;
+
+/// Role granted to an API key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    /// Read the chain and submit transactions.
+    User,
+
+    /// Additionally create wallets, mine blocks and update chain parameters.
+    Admin,
+}
+
+/// Configuration of the authentication and rate limiting of the RPC and REST layers.
+#[derive(Clone, Debug)]
+pub struct AuthConfig {
+    /// API keys and the roles they grant.
+    pub keys: HashMap<String, Role>,
+
+    /// Number of requests a client may send per window.
+    pub rate_limit: u32,
+
+    /// Length of a rate limiting window.
+    pub rate_window: Duration,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            keys: HashMap::new(),
+            rate_limit: DEFAULT_RATE_LIMIT,
+            rate_window: Duration::from_secs(DEFAULT_RATE_WINDOW),
+        }
+    }
+}
+
+/// Authentication and per-client rate limiting, shared between requests.
+#[derive(Debug)]
+pub struct Auth {
+    /// Configuration of the keys and limits.
+    config: AuthConfig,
+
+    /// Start of the current window and number of requests in it, by API key.
+    usage: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl Auth {
+    /// Create a new authenticator.
+    ///
+    /// # Arguments
+    ///
+    /// - `config`: The keys and limits.
+    ///
+    /// # Returns
+    ///
+    /// A new authenticator.
+    pub fn new(config: AuthConfig) -> Self {
+        Auth {
+            config,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Authenticate a request and count it against the rate limit of its client.
+    ///
+    /// The API key is read from the `x-api-key` header or from an `Authorization: Bearer` header.
+    ///
+    /// # Arguments
+    ///
+    /// - `headers`: The request headers.
+    ///
+    /// # Returns
+    ///
+    /// The role of the client, `Unauthenticated` if the key is missing or unknown,
+    /// or `RateLimited` if the client exceeded its limit.
+    pub fn authorize(&self, headers: &HeaderMap) -> Result<Role, BlockchainError> {
+        let key = api_key(headers).ok_or(BlockchainError::Unauthenticated)?;
+        let role = self.authenticate(key)?;
+
+        self.check_rate(key)?;
+
+        Ok(role)
+    }
+
+    /// Get the role of an API key.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The API key.
+    ///
+    /// # Returns
+    ///
+    /// The role, or `Unauthenticated` if the key is unknown.
+    pub fn authenticate(&self, key: &str) -> Result<Role, BlockchainError> {
+        self.config
+            .keys
+            .get(key)
+            .copied()
+            .ok_or(BlockchainError::Unauthenticated)
+    }
+
+    /// Count a request against the rate limit of a client.
+    ///
+    /// # Arguments
+    ///
+    /// - `key`: The API key identifying the client.
+    ///
+    /// # Returns
+    ///
+    /// `RateLimited` if the client already sent the maximum number of requests in the current window.
+    pub fn check_rate(&self, key: &str) -> Result<(), BlockchainError> {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+
+        let (start, count) = usage.entry(key.to_string()).or_insert((now, 0));
+
+        // Start a new window once the current one has elapsed
+        if now.duration_since(*start) >= self.config.rate_window {
+            *start = now;
+            *count = 0;
+        }
+
+        if *count >= self.config.rate_limit {
+            return Err(BlockchainError::RateLimited);
+        }
+
+        *count += 1;
+
+        Ok(())
+    }
+}
+
+/// Read the API key of a request.
+pub(crate) fn api_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(key) = headers.get(API_KEY_HEADER) {
+        return key.to_str().ok();
+    }
+
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(rate_limit: u32) -> Auth {
+        Auth::new(AuthConfig {
+            keys: HashMap::from([
+                ("user".to_string(), Role::User),
+                ("admin".to_string(), Role::Admin),
+            ]),
+            rate_limit,
+            ..AuthConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_authorize() {
+        let auth = auth(10);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            auth.authorize(&headers),
+            Err(BlockchainError::Unauthenticated)
+        );
+
+        headers.insert(API_KEY_HEADER, "user".parse().unwrap());
+        assert_eq!(auth.authorize(&headers), Ok(Role::User));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer admin".parse().unwrap());
+        assert_eq!(auth.authorize(&headers), Ok(Role::Admin));
+
+        headers.insert(header::AUTHORIZATION, "Bearer unknown".parse().unwrap());
+        assert_eq!(
+            auth.authorize(&headers),
+            Err(BlockchainError::Unauthenticated)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_per_client() {
+        let auth = auth(2);
+
+        assert!(auth.check_rate("user").is_ok());
+        assert!(auth.check_rate("user").is_ok());
+        assert_eq!(auth.check_rate("user"), Err(BlockchainError::RateLimited));
+        assert!(auth.check_rate("admin").is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let auth = Auth::new(AuthConfig {
+            rate_limit: 1,
+            rate_window: Duration::ZERO,
+            ..AuthConfig::default()
+        });
+
+        assert!(auth.check_rate("user").is_ok());
+        assert!(auth.check_rate("user").is_ok());
+    }
+}
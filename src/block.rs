@@ -3,35 +3,96 @@ use std::collections::HashMap;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::{Chain, ChainTransactions};
+pub use crate::core::header::BlockHeader;
+use crate::{
+    encoding, merkle, AccountState, Address, BlockchainError, Bloom, Chain, ChainTransactions,
+    Hash, MerkleProof, Transaction, TransactionKind,
+};
 
-/// Identifier of a particular block on an entire blockchain.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct BlockHeader {
-    /// Timestamp at which a block was mined.
-    pub timestamp: i64,
+/// Maximum number of seconds a block timestamp may be ahead of the local clock.
+pub const MAX_FUTURE_BLOCK_TIME: i64 = 2 * 60 * 60;
 
-    /// Integer to achieve the network's difficulty.
-    pub nonce: u32,
+/// Number of recent blocks used to calculate the median time past.
+pub const MEDIAN_TIME_SPAN: usize = 11;
 
-    /// Hash of a previous block.
-    pub previous_hash: String,
+/// Limits on the body of a block, enforced when blocks are assembled and validated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BlockLimits {
+    /// Maximum number of transactions in a block, reward included, or `None` for no limit.
+    #[serde(default)]
+    pub max_transactions: Option<usize>,
 
-    /// Merkel root hash.
-    pub merkle: String,
+    /// Maximum size in bytes of the transactions of a block, in their canonical binary encoding,
+    /// or `None` for no limit.
+    #[serde(default)]
+    pub max_size: Option<usize>,
+}
 
-    /// Current difficulty level of the network.
-    pub difficulty: f64,
+impl BlockLimits {
+    /// Check whether a block body with the given number of transactions and size is allowed.
+    ///
+    /// # Arguments
+    ///
+    /// - `transactions`: The number of transactions.
+    /// - `size`: The size in bytes of the transactions.
+    ///
+    /// # Returns
+    ///
+    /// `true` if neither limit is exceeded, `false` otherwise.
+    pub fn allows(&self, transactions: usize, size: usize) -> bool {
+        self.max_transactions.is_none_or(|max| transactions <= max)
+            && self.max_size.is_none_or(|max| size <= max)
+    }
+
+    /// Check a block against the limits.
+    ///
+    /// # Arguments
+    ///
+    /// - `block`: The block to check.
+    ///
+    /// # Returns
+    ///
+    /// `BlockLimitExceeded` if the block has too many transactions or is too large.
+    pub fn check(&self, block: &Block) -> Result<(), BlockchainError> {
+        match self.allows(block.transactions.len(), block.body_size()) {
+            true => Ok(()),
+            false => Err(BlockchainError::BlockLimitExceeded),
+        }
+    }
 }
 
 /// Data storage in a blockchain.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Block {
     /// Information about the block and the miner.
     pub header: BlockHeader,
 
+    /// Position of the block in the chain.
+    #[serde(default)]
+    pub height: usize,
+
+    /// Hash of the block header, computed at mining time.
+    #[serde(default)]
+    pub hash: Hash,
+
     /// List of transactions.
     pub transactions: ChainTransactions,
+
+    /// Whether the block body is not available locally.
+    #[serde(default)]
+    pub pruned: bool,
+
+    /// Size in bytes of the canonical binary encoding of the header and the transactions,
+    /// recorded when the block is mined or its body restored, or zero if the body was never
+    /// available locally.
+    #[serde(default)]
+    pub size: usize,
+
+    /// Snapshot of the wallet state committed in the header state root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state: Vec<AccountState>,
 }
 
 impl Block {
@@ -45,66 +106,468 @@ impl Block {
     /// # Returns
     ///
     /// A new block with the given previous hash and difficulty.
-    pub fn new(previous_hash: String, difficulty: f64) -> Self {
+    pub fn new(previous_hash: Hash, difficulty: f64) -> Self {
         // Create a new block header
         let header = BlockHeader {
             nonce: 0,
             difficulty,
             previous_hash,
-            merkle: String::new(),
+            merkle: Hash::zero(),
+            state_root: Hash::zero(),
+            contract_root: Hash::zero(),
+            bloom: Bloom::default(),
             timestamp: Utc::now().timestamp(),
         };
 
         Block {
             header,
+            height: 0,
+            hash: Hash::zero(),
             transactions: HashMap::default(),
+            pruned: false,
+            size: 0,
+            state: vec![],
         }
     }
 
+    /// Create a block that only holds a header, with the body to be fetched later.
+    ///
+    /// # Arguments
+    ///
+    /// - `header`: The header of the block.
+    /// - `height`: The position of the block in the chain.
+    ///
+    /// # Returns
+    ///
+    /// A new pruned block with the given header and no transactions.
+    pub fn from_header(header: BlockHeader, height: usize) -> Self {
+        Block {
+            hash: Chain::hash(&header),
+            header,
+            height,
+            transactions: HashMap::default(),
+            pruned: true,
+            size: 0,
+            state: vec![],
+        }
+    }
+
+    /// Remove the block body, keeping only the header.
+    pub fn prune(&mut self) {
+        self.transactions = HashMap::default();
+        self.pruned = true;
+    }
+
+    /// Restore the block body after verifying it against the header.
+    ///
+    /// # Arguments
+    ///
+    /// - `transactions`: The transactions of the block.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the transactions match the header Merkle root and bloom filter and were
+    /// restored, `false` otherwise.
+    pub fn restore(&mut self, transactions: ChainTransactions) -> bool {
+        let body: Vec<_> = transactions.values().collect();
+
+        if Chain::get_merkle(&transactions) != self.header.merkle
+            || !self.header.matches_bloom(&body)
+        {
+            return false;
+        }
+
+        self.transactions = transactions;
+        self.pruned = false;
+        self.refresh_size();
+
+        true
+    }
+
+    /// Check whether the block may involve an address, using the bloom filter of its header.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to look for.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no transaction of the block sends or receives to the address, `true` if one
+    /// may.
+    pub fn may_involve(&self, address: &Address) -> bool {
+        self.header.bloom.contains(address)
+    }
+
+    /// Calculate the fees paid by the transactions of the block.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the fees of every transaction but the reward and the treasury share.
+    pub fn fees(&self) -> f64 {
+        self.transactions
+            .values()
+            .filter(|tx| !tx.kind.is_block_production())
+            .map(|tx| tx.fee)
+            .sum()
+    }
+
+    /// Get the share of the fees of the block routed to the treasury.
+    ///
+    /// # Returns
+    ///
+    /// The amount of the treasury transaction, or zero if the block has none.
+    pub fn treasury_fees(&self) -> f64 {
+        self.transactions
+            .values()
+            .filter(|tx| tx.kind == TransactionKind::Treasury)
+            .map(|tx| tx.amount)
+            .sum()
+    }
+
+    /// Get the share of the fees of the block destroyed.
+    ///
+    /// # Returns
+    ///
+    /// The amount of the fee burn transaction, or zero if the block has none.
+    pub fn burned_fees(&self) -> f64 {
+        self.transactions
+            .values()
+            .filter(|tx| tx.kind == TransactionKind::FeeBurn)
+            .map(|tx| tx.amount)
+            .sum()
+    }
+
+    /// Get the share of the fees of the block left to the miner.
+    ///
+    /// # Returns
+    ///
+    /// The fees of the block minus the treasury share and the burned share.
+    pub fn miner_fees(&self) -> f64 {
+        self.fees() - self.treasury_fees() - self.burned_fees()
+    }
+
+    /// Calculate the size of the block body.
+    ///
+    /// # Returns
+    ///
+    /// The size in bytes of the transactions in their canonical binary encoding.
+    pub fn body_size(&self) -> usize {
+        self.transactions
+            .values()
+            .map(Block::transaction_size)
+            .sum()
+    }
+
+    /// Calculate the size of a transaction in a block body.
+    ///
+    /// # Arguments
+    ///
+    /// - `transaction`: The transaction.
+    ///
+    /// # Returns
+    ///
+    /// The size in bytes of the canonical binary encoding of the transaction.
+    pub fn transaction_size(transaction: &Transaction) -> usize {
+        transaction.encoded_size()
+    }
+
+    /// Calculate the size of the block.
+    ///
+    /// # Returns
+    ///
+    /// The size in bytes of the header and the transactions in their canonical binary encoding.
+    pub fn encoded_size(&self) -> usize {
+        encoding::to_bytes(&self.header).map_or(0, |bytes| bytes.len()) + self.body_size()
+    }
+
+    /// Record the size of the block.
+    pub fn refresh_size(&mut self) {
+        self.size = self.encoded_size();
+    }
+
+    /// Mine the block, cache the hash of its header and record its size.
+    pub fn mine(&mut self) {
+        Block::proof_of_work(&mut self.header);
+
+        self.refresh_hash();
+        self.refresh_size();
+    }
+
+    /// Recompute the cached hash after the header changed.
+    pub fn refresh_hash(&mut self) {
+        self.hash = self.header.hash();
+    }
+
+    /// Check whether the cached hash matches the header.
+    ///
+    /// # Returns
+    /// `true` if the cached hash is the hash of the header, `false` otherwise.
+    pub fn has_valid_hash(&self) -> bool {
+        self.hash == self.header.hash()
+    }
+
+    /// Calculate the expected number of hashes needed to mine the block, to compare branches by
+    /// their total work rather than their length.
+    ///
+    /// # Returns
+    /// Two to the power of the difficulty, the number of leading zero bits of the hash.
+    pub fn work(&self) -> f64 {
+        self.header.difficulty.exp2()
+    }
+
     /// Perform the proof-of-work process to mine a block.
     ///
     /// # Arguments
     /// - `header`: A mutable reference to the block header to be mined.
     pub fn proof_of_work(header: &mut BlockHeader) {
-        loop {
-            let hash = Chain::hash(header);
-            let slice = &hash[..header.difficulty as usize];
-
-            match slice.parse::<u32>() {
-                Ok(val) => {
-                    if val != 0 {
-                        header.nonce += 1;
-                    } else {
-                        break;
-                    }
-                }
-                Err(_) => {
-                    header.nonce += 1;
-
-                    continue;
-                }
-            };
+        while !Block::validate_proof_of_work(header, header.difficulty) {
+            header.nonce += 1;
+        }
+    }
+
+    /// Check whether a block header satisfies the proof-of-work difficulty expected of it.
+    ///
+    /// # Arguments
+    /// - `header`: The block header to check.
+    /// - `difficulty`: The difficulty expected at the height of the block, see
+    ///   `Chain::expected_difficulty`.
+    ///
+    /// # Returns
+    /// `true` if the header declares the expected difficulty and its hash starts with the
+    /// required number of zeros, `false` otherwise.
+    pub fn validate_proof_of_work(header: &BlockHeader, difficulty: f64) -> bool {
+        header.meets_difficulty(difficulty)
+    }
+
+    /// Validate the block on its own, independently of the rest of the chain.
+    ///
+    /// The cached hash must match the header, the header must satisfy the difficulty it declares
+    /// and, if the body is available, every transaction must match its hash, the transactions
+    /// must match the Merkle root of the header and block production transactions must be valid,
+    /// see `validate_block_production`. The difficulty expected at the height of the block is
+    /// checked by the chain, see `Chain::add_header`.
+    ///
+    /// # Returns
+    /// An error if the header, a transaction or the body is invalid.
+    pub fn validate(&self) -> Result<(), BlockchainError> {
+        self.header.verify(&self.hash, self.header.difficulty)?;
+
+        if self.pruned {
+            return Ok(());
+        }
+
+        if self.transactions.iter().any(|(hash, tx)| *hash != tx.hash) {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        self.validate_block_production()?;
+
+        let transactions: Vec<_> = self.transactions.values().collect();
+        self.header.verify_transactions(&transactions)
+    }
+
+    /// Validate the transactions only created by block production.
+    ///
+    /// They must be sent by the root, and a block holds at most one reward, one treasury share
+    /// and one burned share of the fees.
+    ///
+    /// # Returns
+    /// `InvalidTransaction` if a block production transaction is forged or repeated.
+    pub fn validate_block_production(&self) -> Result<(), BlockchainError> {
+        let mut kinds = Vec::new();
+
+        for tx in self.transactions.values() {
+            if !tx.kind.is_block_production() {
+                continue;
+            }
+
+            if !tx.from.is_root() || kinds.contains(&tx.kind) {
+                return Err(BlockchainError::InvalidTransaction);
+            }
+
+            kinds.push(tx.kind);
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the median of the given block timestamps.
+    ///
+    /// # Arguments
+    /// - `timestamps`: Timestamps of the most recent blocks.
+    ///
+    /// # Returns
+    /// The median timestamp, or `None` if there are no timestamps.
+    pub fn median_time_past(timestamps: &[i64]) -> Option<i64> {
+        let mut sorted = timestamps.to_vec();
+        sorted.sort_unstable();
+
+        sorted.get(sorted.len() / 2).copied()
+    }
+
+    /// Check whether a block timestamp is acceptable.
+    ///
+    /// # Arguments
+    /// - `timestamp`: The block timestamp to check.
+    /// - `median_time_past`: The median timestamp of the most recent blocks.
+    ///
+    /// # Returns
+    /// `true` if the timestamp is not too far in the future and is greater than the median time past.
+    pub fn validate_timestamp(timestamp: i64, median_time_past: Option<i64>) -> bool {
+        Block::validate_timestamp_at(timestamp, median_time_past, Utc::now().timestamp())
+    }
+
+    /// Check whether a block timestamp is acceptable at a given time.
+    ///
+    /// # Arguments
+    /// - `timestamp`: The block timestamp to check.
+    /// - `median_time_past`: The median timestamp of the most recent blocks.
+    /// - `now`: The current time, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    /// `true` if the timestamp is not too far ahead of `now` and is greater than the median time past.
+    pub fn validate_timestamp_at(timestamp: i64, median_time_past: Option<i64>, now: i64) -> bool {
+        if timestamp > now + MAX_FUTURE_BLOCK_TIME {
+            return false;
+        }
+
+        match median_time_past {
+            Some(median) => timestamp > median,
+            None => true,
         }
     }
+
+    /// Build a Merkle inclusion proof for one of the block transactions.
+    ///
+    /// # Arguments
+    /// - `hash`: The hash of the transaction to prove.
+    ///
+    /// # Returns
+    /// The Merkle proof, or `None` if the transaction is not part of the block.
+    pub fn merkle_proof(&self, hash: &Hash) -> Option<MerkleProof> {
+        let transactions = merkle::sorted_transactions(&self.transactions);
+        let index = transactions.iter().position(|tx| tx.hash == *hash)?;
+        let leaves = merkle::leaves(&transactions, Chain::hash);
+
+        merkle::proof(leaves, index)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Address;
 
     #[test]
     fn test_proof_of_work() {
-        let mut block = Block::new("0".to_string(), 1.0);
+        let mut block = Block::new(Hash::zero(), 1.0);
         Block::proof_of_work(&mut block.header);
 
         assert_eq!(block.header.difficulty, 1.0);
-        assert!(!block.header.previous_hash.is_empty());
+        assert!(block.header.previous_hash.is_zero());
+    }
+
+    #[test]
+    fn test_validate_proof_of_work() {
+        let mut block = Block::new(Hash::zero(), 1.0);
+        Block::proof_of_work(&mut block.header);
+
+        assert!(Block::validate_proof_of_work(&block.header, 1.0));
+        assert!(!Block::validate_proof_of_work(&block.header, 0.0));
+    }
+
+    #[test]
+    fn test_work() {
+        assert_eq!(Block::new(Hash::zero(), 0.0).work(), 1.0);
+        assert_eq!(Block::new(Hash::zero(), 3.0).work(), 8.0);
+    }
+
+    #[test]
+    fn test_mine() {
+        let mut block = Block::new(Hash::zero(), 1.0);
+        block.mine();
+
+        assert_eq!(block.hash, Chain::hash(&block.header));
+        assert!(Block::validate_proof_of_work(&block.header, 1.0));
+    }
+
+    #[test]
+    fn test_median_time_past() {
+        assert_eq!(Block::median_time_past(&[]), None);
+        assert_eq!(Block::median_time_past(&[5, 1, 3]), Some(3));
+        assert_eq!(Block::median_time_past(&[4, 1, 3, 2]), Some(3));
+    }
+
+    #[test]
+    fn test_validate_timestamp() {
+        let now = Utc::now().timestamp();
+
+        assert!(Block::validate_timestamp(now, None));
+        assert!(Block::validate_timestamp(now, Some(now - 1)));
+        assert!(!Block::validate_timestamp(now, Some(now)));
+        assert!(!Block::validate_timestamp(
+            now + MAX_FUTURE_BLOCK_TIME + 60,
+            None
+        ));
     }
 
     #[test]
     fn test_new_block() {
-        let block = Block::new("0".to_string(), 3.0);
+        let block = Block::new(Hash::zero(), 3.0);
 
         assert_eq!(block.transactions.len(), 0);
+        assert!(!block.pruned);
+    }
+
+    #[test]
+    fn test_block_limits() {
+        let mut block = Block::new(Hash::zero(), 1.0);
+        let transaction = Transaction::new(Address::root(), Address::random(), 0.0, 1.0);
+        let size = Block::transaction_size(&transaction);
+        block.transactions.insert(transaction.hash, transaction);
+
+        assert_eq!(block.body_size(), size);
+        assert!(BlockLimits::default().check(&block).is_ok());
+
+        let limits = BlockLimits {
+            max_transactions: Some(1),
+            max_size: Some(size - 1),
+        };
+        assert!(limits.allows(1, size - 1));
+        assert!(!limits.allows(2, size - 1));
+        assert_eq!(
+            limits.check(&block),
+            Err(BlockchainError::BlockLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn test_validate_block_production() {
+        let mut block = Block::new(Hash::zero(), 1.0);
+        let reward = Transaction::new(Address::root(), Address::random(), 0.0, 1.0)
+            .with_kind(TransactionKind::Reward);
+        block.transactions.insert(reward.hash, reward);
+
+        assert!(block.validate_block_production().is_ok());
+
+        let forged = Transaction::new(Address::random(), Address::random(), 0.0, 1.0)
+            .with_kind(TransactionKind::Reward);
+        block.transactions.insert(forged.hash, forged);
+
+        assert_eq!(
+            block.validate_block_production(),
+            Err(BlockchainError::InvalidTransaction)
+        );
+    }
+
+    #[test]
+    fn test_from_header() {
+        let block = Block::new(Hash::zero(), 1.0);
+        let header = Block::from_header(block.header, 3);
+
+        assert!(header.pruned);
+        assert_eq!(header.height, 3);
+        assert_eq!(header.hash, Chain::hash(&header.header));
+        assert!(header.transactions.is_empty());
     }
 }
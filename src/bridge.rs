@@ -0,0 +1,320 @@
+use std::{
+    str,
+    sync::{Mutex, MutexGuard},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Address, BlockchainError, Chain, ChainRegistry, Hash, MerkleProof, SharedChain, TokenConfig,
+    Transaction, TransactionKind, NATIVE_ASSET,
+};
+
+/// Proof that a bridge transaction was mined on one of the chains of a bridge.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BridgeProof {
+    /// Name of the chain the transaction was mined on.
+    pub chain: String,
+
+    /// Height of the block holding the transaction.
+    pub height: usize,
+
+    /// The lock or burn transaction.
+    pub transaction: Transaction,
+
+    /// Merkle inclusion proof of the transaction in the block.
+    pub merkle: MerkleProof,
+}
+
+impl BridgeProof {
+    /// Get the address credited on the other chain, carried as the data of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The recipient, or `InvalidBridgeProof` if the data is not an address.
+    pub fn recipient(&self) -> Result<Address, BlockchainError> {
+        str::from_utf8(&self.transaction.data)
+            .ok()
+            .and_then(|address| Address::new(address).ok())
+            .ok_or(BlockchainError::InvalidBridgeProof)
+    }
+}
+
+/// Lock-and-mint bridge between two chains of a registry.
+///
+/// Native coins locked in an escrow wallet of the source chain are minted as a wrapped token on
+/// the destination chain; burning the wrapped token releases the coins. Funds only move on the
+/// other chain against a `BridgeProof` that the lock or burn was mined, and each proof is only
+/// claimed once. Claims are recorded in the state of the chain paying them out, so they persist
+/// with it and hold across bridges reopened over the same chains.
+#[derive(Debug)]
+pub struct Bridge {
+    /// Name of the chain of the native coins.
+    source: String,
+
+    /// Name of the chain of the wrapped token.
+    destination: String,
+
+    /// Chain of the native coins.
+    source_chain: SharedChain,
+
+    /// Chain of the wrapped token.
+    destination_chain: SharedChain,
+
+    /// Wallet of the source chain holding the locked coins.
+    escrow: Address,
+
+    /// Wallet of the destination chain issuing the wrapped token.
+    issuer: Address,
+
+    /// Symbol of the wrapped token.
+    asset: String,
+}
+
+impl Bridge {
+    /// Open a bridge between two chains of a registry, creating the escrow wallet on the source
+    /// chain and issuing the wrapped token on the destination chain, or reusing them if the
+    /// bridge was opened before, e.g. before a restart.
+    ///
+    /// # Arguments
+    ///
+    /// - `registry`: The registry of the chains.
+    /// - `source`: The name of the chain of the native coins.
+    /// - `destination`: The name of the chain of the wrapped token.
+    /// - `asset`: The symbol of the wrapped token.
+    ///
+    /// # Returns
+    ///
+    /// The bridge, `InvalidConfiguration` if both chains are the same, `ChainNotFound`, or the
+    /// error of issuing the token, e.g. if the symbol is taken by another issuer.
+    pub fn new(
+        registry: &ChainRegistry,
+        source: &str,
+        destination: &str,
+        asset: &str,
+    ) -> Result<Self, BlockchainError> {
+        if source == destination {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
+
+        let (source_chain, destination_chain) = (registry.get(source)?, registry.get(destination)?);
+
+        let escrow = lock(&source_chain).bridge_wallet(destination);
+        let issuer = {
+            let mut chain = lock(&destination_chain);
+            let issuer = chain.bridge_wallet(source);
+            let issued = chain
+                .get_token(asset)
+                .is_ok_and(|token| token.issuer == issuer);
+
+            if !issued {
+                chain.issue_token(TokenConfig {
+                    symbol: asset.to_string(),
+                    name: format!("Wrapped {source}"),
+                    issuer: issuer.to_owned(),
+                    initial_supply: 0.0,
+                    max_supply: None,
+                })?;
+            }
+
+            issuer
+        };
+
+        Ok(Bridge {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            source_chain,
+            destination_chain,
+            escrow,
+            issuer,
+            asset: asset.to_string(),
+        })
+    }
+
+    /// Get the escrow wallet holding the locked coins on the source chain.
+    ///
+    /// # Returns
+    ///
+    /// The address of the escrow wallet.
+    pub fn escrow(&self) -> &Address {
+        &self.escrow
+    }
+
+    /// Get the symbol of the wrapped token on the destination chain.
+    ///
+    /// # Returns
+    ///
+    /// The token symbol.
+    pub fn asset(&self) -> &str {
+        &self.asset
+    }
+
+    /// Lock native coins on the source chain for the wrapped token, see `Chain::bridge_lock`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender's address on the source chain.
+    /// - `amount`: The amount to lock.
+    /// - `recipient`: The address credited on the destination chain.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the lock transaction, to be proven with `prove` once mined.
+    pub fn lock(
+        &self,
+        from: &Address,
+        amount: f64,
+        recipient: &Address,
+    ) -> Result<Hash, BlockchainError> {
+        lock(&self.source_chain).bridge_lock(from, &self.escrow, amount, recipient)
+    }
+
+    /// Burn the wrapped token on the destination chain to release the locked coins, see
+    /// `Chain::bridge_burn`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The holder's address on the destination chain.
+    /// - `amount`: The amount to burn.
+    /// - `recipient`: The address credited on the source chain.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the burn transaction, to be proven with `prove` once mined.
+    pub fn burn(
+        &self,
+        from: &Address,
+        amount: f64,
+        recipient: &Address,
+    ) -> Result<Hash, BlockchainError> {
+        lock(&self.destination_chain).bridge_burn(from, &self.asset, amount, recipient)
+    }
+
+    /// Build the proof of a mined lock or burn transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The name of the chain of the transaction.
+    /// - `hash`: The hash of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The proof, `ChainNotFound` if the chain is not part of the bridge, or
+    /// `TransactionNotFound` if the transaction is not mined yet.
+    pub fn prove(&self, chain: &str, hash: &Hash) -> Result<BridgeProof, BlockchainError> {
+        let shared = self.chain(chain)?;
+        let guard = lock(shared);
+        let (height, merkle) = guard.get_merkle_proof(hash)?;
+        let transaction = guard.chain[height]
+            .transactions
+            .get(hash)
+            .cloned()
+            .ok_or(BlockchainError::TransactionNotFound { hash: *hash })?;
+
+        Ok(BridgeProof {
+            chain: chain.to_string(),
+            height,
+            transaction,
+            merkle,
+        })
+    }
+
+    /// Mint the wrapped token on the destination chain for coins locked on the source chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `proof`: The proof of the lock transaction.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the mint transaction, `InvalidBridgeProof`, `ProofAlreadyClaimed`, or the
+    /// error of minting, e.g. `WalletNotFound` if the recipient has no wallet.
+    pub fn mint(&self, proof: &BridgeProof) -> Result<Hash, BlockchainError> {
+        self.verify(proof, &self.source, |tx| {
+            tx.to == self.escrow && tx.asset == NATIVE_ASSET
+        })?;
+
+        let (recipient, transaction) = (proof.recipient()?, &proof.transaction);
+        lock(&self.destination_chain).bridge_mint(
+            &self.issuer,
+            &self.asset,
+            &recipient,
+            transaction.amount,
+            &transaction.hash,
+        )
+    }
+
+    /// Release the locked coins on the source chain for the wrapped token burned on the
+    /// destination chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `proof`: The proof of the burn transaction.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the release transaction, `InvalidBridgeProof`, `ProofAlreadyClaimed`, or the
+    /// error of `Chain::bridge_release`.
+    pub fn release(&self, proof: &BridgeProof) -> Result<Hash, BlockchainError> {
+        self.verify(proof, &self.destination, |tx| {
+            tx.to.is_burn() && tx.asset == self.asset
+        })?;
+
+        let (recipient, transaction) = (proof.recipient()?, &proof.transaction);
+        lock(&self.source_chain).bridge_release(
+            &self.escrow,
+            &recipient,
+            transaction.amount,
+            &transaction.hash,
+        )
+    }
+
+    /// Check that a proof holds a bridge transaction of a chain, mined in the block at its
+    /// height.
+    fn verify<F>(
+        &self,
+        proof: &BridgeProof,
+        chain: &str,
+        expected: F,
+    ) -> Result<(), BlockchainError>
+    where
+        F: Fn(&Transaction) -> bool,
+    {
+        let transaction = &proof.transaction;
+
+        if proof.chain != chain
+            || transaction.kind != TransactionKind::Bridge
+            || transaction.hash != transaction.compute_hash()
+            || !expected(transaction)
+        {
+            return Err(BlockchainError::InvalidBridgeProof);
+        }
+
+        let guard = lock(self.chain(chain)?);
+        let mined = guard.chain.get(proof.height).is_some_and(|block| {
+            proof.merkle.leaf == Chain::hash(transaction)
+                && proof.merkle.verify(&block.header.merkle)
+        });
+
+        match mined {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidBridgeProof),
+        }
+    }
+
+    /// Get one of the chains of the bridge by name.
+    fn chain(&self, name: &str) -> Result<&SharedChain, BlockchainError> {
+        match name {
+            _ if name == self.source => Ok(&self.source_chain),
+            _ if name == self.destination => Ok(&self.destination_chain),
+            _ => Err(BlockchainError::ChainNotFound {
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Lock a shared chain, recovering from a poisoned lock.
+fn lock(chain: &Mutex<Chain>) -> MutexGuard<'_, Chain> {
+    chain.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -1,21 +1,68 @@
-use std::{collections::HashMap, fmt::Write, hash::BuildHasherDefault, iter};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::BuildHasherDefault,
+    io::{BufRead, Read, Write},
+    iter, mem, slice,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
 
 use derive_builder::Builder;
 use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use twox_hash::XxHash64;
+use uuid::Uuid;
 
-use crate::{Block, BlockchainError, Transaction, Wallet};
+use crate::{
+    archive, consensus::Assembler, contract, core::header, memory, migration, settlement, state,
+    vanity, AccessControl, AccountProof, Address, AddressScheme, Admin, ArchiveReader,
+    ArchiveWriter, AuditAction, AuditLog, Block, BlockAssembler, BlockHeader, BlockLimits,
+    BlockchainError, Bloom, ChainCapacity, ChainEvent, Compliance, ComponentStatus, ConsensusRules,
+    Contract, DeadLetter, DeadLetterQueue, DeniedAddress, DifficultyTarget, EmissionSchedule,
+    Environment, Faucet, GenesisConfig, Hash, HashVersion, Health, Hooks, Invoice, InvoiceStatus,
+    KeyScope, Kyc, KycProvider, KycTier, LazyFork, MemoryUsage, Mempool, MerkleProof, Nft,
+    NftMetadata, Oracle, OracleFeed, OracleReport, Permission, Proposal, ProposalChange,
+    ProposalStatus, ProtocolFeature, ProtocolUpgrade, QueuedTransaction, RateLimiter, RateProvider,
+    Rates, ReplayFailure, ReplayFormat, ReplayReport, Script, ScriptContext, Settlement,
+    SettlementPeriod, StateSnapshot, StateTrie, Subscribers, Subscription, SubscriptionFilter,
+    Token, TokenConfig, Transaction, TransactionHook, TransactionKind, TransactionOutput,
+    TransactionPriority, TransactionRequest, TransactionSimulation, Treasury, VanityAddress,
+    Wallet, WalletKey, Watchlist, DATA_FEE_PER_BYTE, MAX_DATA_SIZE, MEDIAN_TIME_SPAN, NATIVE_ASSET,
+};
+
+/// Number of most recent blocks listed one by one in a block locator.
+pub const LOCATOR_DENSE_SPAN: usize = 10;
 
 /// A map of transactions.
-pub type ChainTransactions = HashMap<String, Transaction, BuildHasherDefault<XxHash64>>;
+pub type ChainTransactions = HashMap<Hash, Transaction, BuildHasherDefault<XxHash64>>;
 
 /// A map of wallets.
-pub type ChainWallets = HashMap<String, Wallet, BuildHasherDefault<XxHash64>>;
+pub type ChainWallets = HashMap<Address, Wallet, BuildHasherDefault<XxHash64>>;
+
+/// A map of registered tokens.
+pub type ChainTokens = HashMap<String, Token, BuildHasherDefault<XxHash64>>;
+
+/// A map of non-fungible tokens.
+pub type ChainNfts = HashMap<Uuid, Nft, BuildHasherDefault<XxHash64>>;
+
+/// A map of governance proposals.
+pub type ChainProposals = HashMap<Uuid, Proposal, BuildHasherDefault<XxHash64>>;
+
+/// A map of oracle data feeds.
+pub type ChainOracleFeeds = HashMap<String, OracleFeed, BuildHasherDefault<XxHash64>>;
+
+/// A map of invoices.
+pub type ChainInvoices = HashMap<Uuid, Invoice, BuildHasherDefault<XxHash64>>;
+
+/// A map of deployed smart contracts.
+pub type ChainContracts = HashMap<Address, Contract, BuildHasherDefault<XxHash64>>;
 
 /// Blockchain.
 #[derive(Clone, Debug, Default, Builder, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Chain {
     /// Chain of blocks.
     pub chain: Vec<Block>,
@@ -27,7 +74,12 @@ pub struct Chain {
     pub difficulty: f64,
 
     /// Blockchain genesis address.
-    pub address: String,
+    pub address: Address,
+
+    /// Recipient of the block rewards of `generate_new_block`, or the genesis address if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    beneficiary: Option<Address>,
 
     /// Block reward.
     pub reward: f64,
@@ -37,6 +89,131 @@ pub struct Chain {
 
     /// Map to associate wallets with their corresponding addresses and balances.
     pub wallets: ChainWallets,
+
+    /// Registry of tokens tracked in addition to the native coin.
+    #[serde(default)]
+    #[builder(default)]
+    pub tokens: ChainTokens,
+
+    /// Registry of non-fungible tokens.
+    #[serde(default)]
+    #[builder(default)]
+    pub nfts: ChainNfts,
+
+    /// Registry of deployed smart contracts.
+    #[serde(default)]
+    #[builder(default)]
+    pub contracts: ChainContracts,
+
+    /// Governance proposals to change chain parameters.
+    #[serde(default)]
+    #[builder(default)]
+    pub proposals: ChainProposals,
+
+    /// External data feeds reported by allowed oracles.
+    #[serde(default)]
+    #[builder(default)]
+    pub oracle_feeds: ChainOracleFeeds,
+
+    /// Payment requests to wallets, by identifier.
+    #[serde(default)]
+    #[builder(default)]
+    pub invoices: ChainInvoices,
+
+    /// Transactions waiting to be included in a block, oldest first.
+    #[serde(default)]
+    #[builder(default)]
+    pub pending: Vec<QueuedTransaction>,
+
+    /// Allow and deny lists screening the parties of transfers.
+    #[serde(default)]
+    #[builder(default)]
+    pub compliance: Compliance,
+
+    /// Rules applied when assembling and validating blocks.
+    #[serde(default)]
+    #[builder(default)]
+    pub consensus: ConsensusRules,
+
+    /// Faucet funding test wallets, or `None` if it is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub faucet: Option<Faucet>,
+
+    /// Append-only log of administrative mutations.
+    #[serde(default)]
+    #[builder(default)]
+    audit_log: AuditLog,
+
+    /// Rejected transfer attempts, recorded once enabled with `enable_dead_letters`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    dead_letters: Option<DeadLetterQueue>,
+
+    /// Hashes of the bridge transactions of other chains already claimed on this chain.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    #[builder(default)]
+    bridge_claims: HashSet<Hash>,
+
+    /// Escrow and issuer wallets of the bridges of this chain, by the name of the other chain.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[builder(default)]
+    bridge_wallets: HashMap<String, Address>,
+
+    /// Admin keys gating administrative operations.
+    #[serde(default)]
+    #[builder(default)]
+    access: AccessControl,
+
+    /// Addresses watched for balance changes, with their last reported balances.
+    #[serde(default)]
+    #[builder(default)]
+    watchlist: Watchlist,
+
+    /// Subscriptions notified of new blocks and transactions.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    subscribers: Subscribers,
+
+    /// Hooks run around each transfer.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    hooks: Hooks,
+
+    /// Custom block assembler replacing the transaction priority, if set.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    assembler: Assembler,
+
+    /// Identity verification of new wallets.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    kyc: Kyc,
+
+    /// Exchange rates converting amounts for display.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    rates: Rates,
+
+    /// Rate limits of wallet creation, by email and by client, if enabled.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    wallet_limiter: Option<RateLimiter>,
+
+    /// Rate limits of faucet claims, by client, if enabled.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    faucet_limiter: Option<RateLimiter>,
+
+    /// Outcome of the last validation of the whole chain, reported by `health`.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    validation: ComponentStatus,
+
+    /// Sources of randomness and time.
+    #[serde(skip)]
+    #[builder(setter(skip))]
+    environment: Environment,
 }
 
 impl Chain {
@@ -50,377 +227,4853 @@ impl Chain {
     /// # Returns
     /// New `Chain` instance with the given parameters and a genesis block.
     pub fn new(difficulty: f64, reward: f64, fee: f64) -> Self {
-        let mut chain = Chain {
-            fee,
-            reward,
-            difficulty,
-            chain: vec![],
-            wallets: HashMap::default(),
-            transactions: HashMap::default(),
-            address: Chain::generate_address(42),
-        };
-
-        chain.generate_new_block();
-
-        chain
+        Chain::with_environment(difficulty, reward, fee, Environment::default())
     }
 
-    /// Get a list of current transactions in the blockchain.
+    /// Initialize a new blockchain generating its addresses, identifiers and timestamps from an
+    /// environment, e.g. a seeded one for reproducible tests and simulations.
     ///
     /// # Arguments
-    /// - `page`: The page number.
-    /// - `size`: The number of transactions per page.
+    /// - `difficulty`: The initial mining difficulty level of the network.
+    /// - `reward`: The initial block reward for miners.
+    /// - `fee`: The transaction fee.
+    /// - `environment`: The sources of randomness and time.
     ///
     /// # Returns
-    /// A list of transactions for the specified page.
-    pub fn get_transactions(&self, page: usize, size: usize) -> ChainTransactions {
-        // Calculate the total number of pages
-        let total_transactions = self.transactions.len();
-        let total_pages = total_transactions.div_ceil(size);
-
-        // Return an empty vector if the page is greater than the total number of pages
-        if page > total_pages {
-            return HashMap::default();
-        }
-
-        // Calculate the start and end indices for the transactions of the current page
-        let start = page.saturating_sub(1) * size;
-        let end = start + size;
+    /// New `Chain` instance with the given parameters and a genesis block.
+    pub fn with_environment(
+        difficulty: f64,
+        reward: f64,
+        fee: f64,
+        environment: Environment,
+    ) -> Self {
+        let mut chain = Chain::empty(difficulty, reward, fee, environment);
+        chain.generate_new_block();
 
-        // Get the transactions for the current page
-        self.transactions
-            .iter()
-            .skip(start)
-            .take(end.min(total_transactions))
-            .map(|(k, v)| (k.to_owned(), v.to_owned()))
-            .collect()
+        chain
     }
 
-    /// Get a transaction by its identifier.
-    ///
-    /// # Arguments
-    /// - `hash`: The hash of the transaction to retrieve.
+    /// Initialize a new blockchain from a genesis configuration.
     ///
-    /// # Returns
-    /// An option containing a reference to the transaction if found, or `None` if not found.
-    pub fn get_transaction(&self, hash: &str) -> Result<&Transaction, BlockchainError> {
-        match self.transactions.get(hash) {
-            Some(transaction) => Ok(transaction),
-            None => Err(BlockchainError::TransactionNotFound),
-        }
-    }
-
-    /// Add a new transaction to the blockchain.
+    /// The genesis block depends only on the configuration, so independent nodes initialized
+    /// with the same configuration produce byte-identical genesis blocks and can sync with each
+    /// other. Later blocks use the default environment.
     ///
     /// # Arguments
-    /// - `from`: The sender's address.
-    /// - `to`: The receiver's address.
-    /// - `amount`: The amount of the transaction.
+    /// - `config`: The genesis configuration of the network.
     ///
     /// # Returns
-    /// `true` if the transaction is successfully added to the current transactions.
-    pub fn add_transaction(
-        &mut self,
-        from: String,
-        to: String,
-        amount: f64,
-    ) -> Result<(), BlockchainError> {
-        let total = amount * self.fee;
-
-        // Validate the transaction and create a new transaction if it is valid
-        let transaction = match self.validate_transaction(&from, &to, total) {
-            true => Transaction::new(from.to_owned(), to.to_owned(), self.fee, total),
-            false => return Err(BlockchainError::InvalidTransaction),
-        };
-
-        // Update sender's balance
-        match self.wallets.get_mut(&from) {
-            Some(wallet) => {
-                // Determine the wallet balance is sufficient for the transaction. If not, return false.
-                if wallet.balance < total {
-                    return Err(BlockchainError::InsufficientFunds);
-                }
-
-                wallet.balance -= total;
+    /// New `Chain` instance with the configured parameters and genesis block.
+    pub fn from_genesis(config: &GenesisConfig) -> Self {
+        let mut chain = Chain::empty(
+            config.difficulty,
+            config.reward,
+            config.fee,
+            Environment::default(),
+        );
+        chain.address = config.address.to_owned();
 
-                // Add the transaction to the sender's transaction history
-                wallet.transaction_hashes.push(transaction.hash.to_owned());
-            }
-            None => return Err(BlockchainError::WalletNotFound),
-        };
+        let mut block = Block::new(Hash::zero(), config.difficulty);
+        block.header.timestamp = config.timestamp;
 
-        // Update receiver's balance
-        match self.wallets.get_mut(&to) {
-            Some(wallet) => {
-                wallet.balance += amount;
+        let transaction = Transaction::from_parts(
+            Uuid::nil(),
+            config.timestamp,
+            Address::root(),
+            config.address.to_owned(),
+            config.fee,
+            chain.block_reward(0),
+            NATIVE_ASSET,
+        )
+        .with_kind(TransactionKind::Reward)
+        .with_size();
+        block.transactions.insert(transaction.hash, transaction);
 
-                // Add the transaction to the receiver's transaction history
-                wallet.transaction_hashes.push(transaction.hash.to_owned());
-            }
-            None => return Err(BlockchainError::WalletNotFound),
-        };
+        block.header.merkle = Chain::get_merkle(&block.transactions);
+        block.header.bloom = Bloom::of_transactions(block.transactions.values());
+        block.header.state_root = state::accounts_root(&block.state);
+        block.header.contract_root = chain.contract_root();
+        block.mine();
 
-        // Add the transaction to the current transactions
-        self.transactions
-            .insert(transaction.hash.to_owned(), transaction);
+        chain.chain.push(block);
 
-        Ok(())
+        chain
     }
 
-    /// Validate a transaction.
+    /// Bootstrap a chain from a state snapshot, e.g. downloaded from a peer, instead of
+    /// replaying every block.
+    ///
+    /// The blocks up to the checkpoint are kept as headers, the wallets are restored from the
+    /// account states without their history, and the following blocks can then be synchronized
+    /// as usual, e.g. with `import_block` or `import_archive`.
     ///
     /// # Arguments
-    /// - `from`: The sender's address.
-    /// - `to`: The receiver's address.
-    /// - `amount`: The amount of the transaction.
+    /// - `snapshot`: The state snapshot.
+    /// - `trusted`: The hash of the checkpoint block, obtained from a trusted source.
     ///
     /// # Returns
-    /// `true` if the transaction is valid, `false` otherwise.
-    pub fn validate_transaction(&self, from: &str, to: &str, amount: f64) -> bool {
-        // Validate if the sender is not the root
-        if from == "Root" {
-            return false;
-        }
+    /// The chain at the checkpoint height, or the error of `StateSnapshot::verify`.
+    pub fn from_snapshot(snapshot: StateSnapshot, trusted: &Hash) -> Result<Self, BlockchainError> {
+        snapshot.verify(trusted)?;
 
-        // Validate that sender and receiver addresses are different
-        if from == to {
-            return false;
-        }
+        let StateSnapshot {
+            headers,
+            accounts,
+            difficulty,
+            reward,
+            fee,
+        } = snapshot;
+        let mut chain = Chain::empty(difficulty, reward, fee, Environment::default());
 
-        // Validate if the amount is non-negative
-        if amount <= 0.0 {
-            return false;
-        }
+        chain.chain = headers
+            .into_iter()
+            .enumerate()
+            .map(|(height, header)| Block::from_header(header, height))
+            .collect();
 
-        // Validate if sender and receiver addresses are valid
-        let sender = match self.wallets.get(from) {
-            Some(wallet) => wallet,
-            None => return false,
-        };
+        for account in &accounts {
+            let mut wallet = Wallet::new("", &account.address);
+            wallet.balance = account.balance;
+            wallet.balances = account
+                .balances
+                .iter()
+                .map(|(asset, amount)| (asset.to_owned(), *amount))
+                .collect();
 
-        // Validate if the receiver address is valid
-        if !self.wallets.contains_key(to) {
-            return false;
+            chain.wallets.insert(account.address.to_owned(), wallet);
         }
 
-        // Validate if sender can send the amount of the transaction
-        if sender.balance < amount {
-            return false;
+        if let Some(checkpoint) = chain.chain.last_mut() {
+            checkpoint.state = accounts;
         }
 
-        true
+        Ok(chain)
     }
 
-    /// Create a new wallet with a unique email and an initial balance.
+    /// Take a state snapshot at a checkpoint block, e.g. to let new nodes fast-sync.
     ///
     /// # Arguments
-    /// - `email`: The unique user email.
+    /// - `height`: The height of the checkpoint block.
     ///
     /// # Returns
-    /// The newly created wallet address.
-    pub fn create_wallet(&mut self, email: &str) -> String {
-        let address = Chain::generate_address(42);
-        let wallet = Wallet::new(email, &address);
+    /// The snapshot, `BlockNotFound` if there is no block at the height, or `InvalidSnapshot` if
+    /// the block does not hold the state committed in its header.
+    pub fn snapshot(&self, height: usize) -> Result<StateSnapshot, BlockchainError> {
+        let block = self
+            .chain
+            .get(height)
+            .ok_or(BlockchainError::BlockNotFound)?;
 
-        self.wallets.insert(address.to_string(), wallet);
+        if state::accounts_root(&block.state) != block.header.state_root {
+            return Err(BlockchainError::InvalidSnapshot);
+        }
 
-        address
+        Ok(StateSnapshot {
+            headers: self.get_headers(0, height + 1),
+            accounts: block.state.clone(),
+            difficulty: self.difficulty,
+            reward: self.reward,
+            fee: self.fee,
+        })
     }
 
-    /// Get a wallet's balance based on its address.
+    /// Create a chain without any block.
     ///
     /// # Arguments
-    /// - `address`: The unique wallet address.
+    /// - `difficulty`: The mining difficulty level of the network.
+    /// - `reward`: The block reward for miners.
+    /// - `fee`: The transaction fee.
+    /// - `environment`: The sources of randomness and time.
     ///
     /// # Returns
-    /// The wallet balance.
-    pub fn get_wallet_balance(&self, address: &str) -> Option<f64> {
-        self.wallets.get(address).map(|wallet| wallet.balance)
+    /// A new `Chain` instance without blocks, wallets or transactions.
+    fn empty(difficulty: f64, reward: f64, fee: f64, mut environment: Environment) -> Self {
+        Chain {
+            fee,
+            reward,
+            difficulty,
+            chain: vec![],
+            wallets: HashMap::default(),
+            transactions: HashMap::default(),
+            address: environment.address(),
+            beneficiary: None,
+            tokens: HashMap::default(),
+            nfts: HashMap::default(),
+            contracts: HashMap::default(),
+            proposals: HashMap::default(),
+            oracle_feeds: HashMap::default(),
+            invoices: HashMap::default(),
+            pending: vec![],
+            compliance: Compliance::default(),
+            consensus: ConsensusRules::default(),
+            faucet: None,
+            audit_log: AuditLog::default(),
+            dead_letters: None,
+            bridge_claims: HashSet::new(),
+            bridge_wallets: HashMap::default(),
+            access: AccessControl::default(),
+            watchlist: Watchlist::default(),
+            subscribers: Subscribers::default(),
+            hooks: Hooks::default(),
+            assembler: Assembler::default(),
+            kyc: Kyc::default(),
+            rates: Rates::default(),
+            wallet_limiter: None,
+            faucet_limiter: None,
+            validation: ComponentStatus::Unknown,
+            environment,
+        }
     }
 
-    /// Get a wallet's transaction history based on its address.
+    /// Initialize a new blockchain with preallocated collections.
     ///
     /// # Arguments
-    /// - `address`: The unique wallet address.
-    /// - `page`: The page number.
-    /// - `size`: The number of transactions per page.
+    /// - `difficulty`: The initial mining difficulty level of the network.
+    /// - `reward`: The initial block reward for miners.
+    /// - `fee`: The transaction fee.
+    /// - `capacity`: The expected number of wallets, transactions and blocks.
     ///
     /// # Returns
-    /// The wallet transaction history for the specified page.
-    pub fn get_wallet_transactions(
-        &self,
-        address: &str,
-        page: usize,
-        size: usize,
-    ) -> Option<Vec<Transaction>> {
-        match self.wallets.get(address) {
-            // Get the transaction history of the wallet
-            Some(wallet) => {
-                let mut result = vec![];
-
-                // Calculate the total number of pages
-                let total_pages = self.transactions.len().div_ceil(size);
-
-                // Return an empty vector if the page is greater than the total number of pages
-                if page > total_pages {
-                    return Some(result);
-                }
-
-                // Calculate the start and end indices for the transactions of the current page
-                let start = page.saturating_sub(1) * size;
-                let end = start + size;
-                let hashes = &wallet.transaction_hashes;
-
-                for tx in hashes[start..end.min(hashes.len())].iter() {
-                    match self.get_transaction(tx) {
-                        Ok(transaction) => result.push(transaction.to_owned()),
-                        Err(_) => continue,
-                    }
-                }
+    /// New `Chain` instance with the given parameters and a genesis block.
+    pub fn with_capacity(difficulty: f64, reward: f64, fee: f64, capacity: ChainCapacity) -> Self {
+        let mut chain = Chain::new(difficulty, reward, fee);
+        chain.reserve(capacity);
 
-                Some(result)
-            }
-            // Return None if the wallet is not found
-            None => None,
-        }
+        chain
     }
 
-    /// Get the hash of the last block in the blockchain.
+    /// Reserve capacity for additional wallets, transactions and blocks.
     ///
-    /// # Returns
-    /// The hash of the last block in the blockchain as a string.
-    pub fn get_last_hash(&self) -> String {
-        let block = match self.chain.last() {
-            Some(block) => block,
-            None => return String::from_utf8(vec![48; 64]).unwrap(),
-        };
+    /// # Arguments
+    /// - `capacity`: The number of additional items to make room for.
+    pub fn reserve(&mut self, capacity: ChainCapacity) {
+        self.wallets.reserve(capacity.wallets);
+        self.transactions.reserve(capacity.transactions);
+        self.chain.reserve(capacity.blocks);
+    }
 
-        Chain::hash(&block.header)
+    /// Release the unused capacity of the chain collections.
+    pub fn shrink_to_fit(&mut self) {
+        self.wallets.shrink_to_fit();
+        self.transactions.shrink_to_fit();
+        self.chain.shrink_to_fit();
     }
 
-    /// Update the mining difficulty of the blockchain.
+    /// Replace the sources of randomness and time, e.g. after loading a chain, which always
+    /// starts with the default environment.
     ///
     /// # Arguments
-    /// - `difficulty`: The new mining difficulty level.
-    pub fn update_difficulty(&mut self, difficulty: f64) {
-        self.difficulty = difficulty;
+    /// - `environment`: The new environment.
+    pub fn set_environment(&mut self, environment: Environment) {
+        self.environment = environment;
     }
 
-    /// Update the block reward.
+    /// Set the limits on the number of transactions and the size of new blocks.
+    ///
+    /// Transactions that do not fit in a block stay pending for the next one.
     ///
     /// # Arguments
-    /// - `reward`: The new block reward value.
-    pub fn update_reward(&mut self, reward: f64) {
-        self.reward = reward;
+    /// - `limits`: The new block limits.
+    pub fn set_block_limits(&mut self, limits: BlockLimits) {
+        self.consensus.block_limits = limits;
     }
 
-    /// Update the transaction fee.
+    /// Set the emission schedule of the block reward, before any block is mined after genesis.
     ///
     /// # Arguments
-    /// - `fee`: The new transaction fee value.
-    pub fn update_fee(&mut self, fee: f64) {
-        self.fee = fee;
-    }
-
-    /// Generate a new block and append it to the blockchain.
+    /// - `schedule`: The emission schedule, applied to the current block reward as the base.
     ///
     /// # Returns
-    /// `true` if a new block is successfully generated and added to the blockchain.
-    pub fn generate_new_block(&mut self) -> bool {
-        // Create a new block
-        let mut block = Block::new(self.get_last_hash(), self.difficulty);
+    /// `InvalidConfiguration` if the schedule is invalid or blocks were mined after genesis.
+    pub fn set_emission_schedule(
+        &mut self,
+        schedule: EmissionSchedule,
+    ) -> Result<(), BlockchainError> {
+        schedule.validate()?;
 
-        // Create a reward transaction
-        let transaction = Transaction::new(
-            "Root".to_string(),
-            self.address.to_string(),
-            self.fee,
-            self.reward,
-        );
+        if self.chain.len() > 1 {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
 
-        // Add the reward transaction to the block
-        block
-            .transactions
-            .insert(transaction.hash.to_owned(), transaction);
+        self.consensus.emission = schedule;
+
+        Ok(())
+    }
 
-        // Update the block count and the Merkle root hash
-        block.header.merkle = Chain::get_merkle(block.transactions.clone());
+    /// Route a share of the fees of every new block to a treasury wallet, before any block is
+    /// mined after genesis.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the treasury wallet.
+    /// - `share`: The share of the fees routed to the treasury, from 0 to 1.
+    ///
+    /// # Returns
+    /// `WalletNotFound` if the wallet does not exist, or `InvalidConfiguration` if the share is
+    /// outside `[0, 1]`, exceeds the share left by the fee burn, or blocks were mined after
+    /// genesis.
+    pub fn set_treasury(&mut self, address: &Address, share: f64) -> Result<(), BlockchainError> {
+        if !(0.0..=1.0 - self.consensus.fee_burn).contains(&share) || self.chain.len() > 1 {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
 
-        // Perform the proof-of-work process
-        Block::proof_of_work(&mut block.header);
+        if !self.wallets.contains_key(address) {
+            return Err(BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            });
+        }
 
-        // Add the block to the blockchain
-        self.chain.push(block);
+        self.consensus.treasury = Some(Treasury {
+            address: address.to_owned(),
+            share,
+        });
 
-        true
+        Ok(())
     }
 
-    /// Calculate the Merkle root hash for a list of transactions.
+    /// Destroy a share of the fees of every new block, before any block is mined after genesis.
+    ///
+    /// The burned share is credited to the unspendable `Address::burn()` wallet, so it is
+    /// reported by `burned` and leaves the circulating supply; the miner keeps the share left by
+    /// the treasury and the burn.
     ///
     /// # Arguments
-    /// - `transactions`: A vector of transactions for which the Merkle root hash is calculated.
+    /// - `share`: The share of the fees destroyed, from 0 to 1.
     ///
     /// # Returns
-    /// The Merkle root hash as a string.
-    pub fn get_merkle(transactions: ChainTransactions) -> String {
-        let mut merkle = vec![];
+    /// `InvalidConfiguration` if the share is outside `[0, 1]`, exceeds the share left by the
+    /// treasury, or blocks were mined after genesis.
+    pub fn set_fee_burn(&mut self, share: f64) -> Result<(), BlockchainError> {
+        let treasury = self
+            .consensus
+            .treasury
+            .as_ref()
+            .map_or(0.0, |treasury| treasury.share);
 
-        for transaction in transactions.values() {
-            let hash = Chain::hash(transaction);
-            merkle.push(hash);
+        if !(0.0..=1.0 - treasury).contains(&share) || self.chain.len() > 1 {
+            return Err(BlockchainError::InvalidConfiguration);
         }
 
-        if merkle.len() % 2 == 1 {
-            let last = merkle.last().cloned().unwrap();
-            merkle.push(last);
+        self.consensus.fee_burn = share;
+
+        Ok(())
+    }
+
+    /// Set the fee charged for each byte of the canonical binary encoding of a transfer, in
+    /// addition to the transaction fee.
+    ///
+    /// # Arguments
+    /// - `fee`: The fee per byte, zero to disable the size-based component.
+    ///
+    /// # Returns
+    /// `InvalidConfiguration` if the fee is negative or not finite.
+    pub fn set_fee_per_byte(&mut self, fee: f64) -> Result<(), BlockchainError> {
+        if !fee.is_finite() || fee < 0.0 {
+            return Err(BlockchainError::InvalidConfiguration);
         }
 
-        while merkle.len() > 1 {
-            let mut h1 = merkle.remove(0);
-            let h2 = merkle.remove(0);
+        self.consensus.fee_per_byte = fee;
 
-            h1.push_str(&h2);
+        Ok(())
+    }
 
-            let nh = Chain::hash(&h1);
-            merkle.push(nh);
-        }
+    /// Calculate the size-based component of the fee of a transaction.
+    ///
+    /// # Arguments
+    /// - `size`: The size in bytes of the canonical binary encoding of the transaction.
+    ///
+    /// # Returns
+    /// The size times the fee per byte set with `set_fee_per_byte`.
+    pub fn size_fee(&self, size: usize) -> f64 {
+        size as f64 * self.consensus.fee_per_byte
+    }
 
-        merkle.pop().unwrap()
+    /// Allow or forbid the genesis and treasury addresses to take part in user transactions,
+    /// forbidden by default so that block rewards and routed fees only move through block
+    /// production.
+    ///
+    /// # Arguments
+    /// - `allowed`: Whether the reserved addresses may send and receive user transactions.
+    pub fn set_reserved_transfers(&mut self, allowed: bool) {
+        self.consensus.reserved_transfers = allowed;
     }
 
-    /// Calculate the SHA-256 hash of a serializable item.
+    /// Set the identifier of the chain, committed by the transaction hashes from
+    /// `HashVersion::V2` so that they cannot be replayed on another chain.
     ///
     /// # Arguments
-    /// - `item`: A serializable item to be hashed.
+    /// - `chain_id`: The identifier of the chain.
     ///
     /// # Returns
-    /// The SHA-256 hash of the item as a string.
-    pub fn hash<T: serde::Serialize>(item: &T) -> String {
-        let input = serde_json::to_string(&item).unwrap();
-        let mut hasher = Sha256::new();
-        hasher.update(input.as_bytes());
-        let res = hasher.finalize();
-        let vec_res = res.to_vec();
+    /// `InvalidConfiguration` if blocks were mined after genesis.
+    pub fn set_chain_id(&mut self, chain_id: &str) -> Result<(), BlockchainError> {
+        if self.chain.len() > 1 {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
 
-        let mut result = String::new();
+        self.consensus.chain_id = chain_id.to_string();
 
-        for b in vec_res.as_slice() {
-            write!(&mut result, "{:x}", b).expect("Unable to write");
+        Ok(())
+    }
+
+    /// Schedule the activation of a protocol feature from a future block, e.g. a new fee model
+    /// or hash scheme.
+    ///
+    /// Blocks below the height keep being validated and applied under the previous rules.
+    ///
+    /// # Arguments
+    /// - `feature`: The protocol feature.
+    /// - `height`: The height of the first block following the new rule.
+    ///
+    /// # Returns
+    /// `InvalidConfiguration` if the height is not above the current blocks or the feature is
+    /// already scheduled.
+    pub fn schedule_upgrade(
+        &mut self,
+        feature: ProtocolFeature,
+        height: usize,
+    ) -> Result<(), BlockchainError> {
+        if height < self.chain.len()
+            || self
+                .consensus
+                .upgrades
+                .iter()
+                .any(|upgrade| upgrade.feature == feature)
+        {
+            return Err(BlockchainError::InvalidConfiguration);
         }
 
-        result
+        self.consensus
+            .upgrades
+            .push(ProtocolUpgrade { feature, height });
+
+        Ok(())
     }
 
-    /// Generates a random alphanumeric string of a specified length.
+    /// Check whether a protocol feature is active at a height.
     ///
     /// # Arguments
-    /// - `length`: The length of the generated string.
+    /// - `feature`: The protocol feature.
+    /// - `height`: The height of the block.
     ///
     /// # Returns
-    /// A `String` containing the generated alphanumeric string.
-    pub fn generate_address(length: usize) -> String {
-        let mut rng = rand::thread_rng();
+    /// `true` if the feature is scheduled at or below the height.
+    pub fn is_upgrade_active(&self, feature: ProtocolFeature, height: usize) -> bool {
+        self.consensus.is_active(feature, height)
+    }
 
+    /// Schedule the activation of a transaction hash version, e.g. to start committing the
+    /// chain identifier and fee, from a future block.
+    ///
+    /// Transactions keep the version they were created with, and a block may only hold
+    /// transactions of versions active at its height.
+    ///
+    /// # Arguments
+    /// - `version`: The version of the hash preimage of new transactions.
+    /// - `height`: The height of the first block whose new transactions use the version.
+    ///
+    /// # Returns
+    /// `InvalidConfiguration` if the version is the original one, or the error of
+    /// `schedule_upgrade`.
+    pub fn activate_hash_version(
+        &mut self,
+        version: HashVersion,
+        height: usize,
+    ) -> Result<(), BlockchainError> {
+        match version {
+            HashVersion::V1 => Err(BlockchainError::InvalidConfiguration),
+            HashVersion::V2 => self.schedule_upgrade(ProtocolFeature::HashV2, height),
+        }
+    }
+
+    /// Get the transaction hash version active at a height.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    /// The version of the hash preimage of the transactions created for the block.
+    pub fn hash_version(&self, height: usize) -> HashVersion {
+        self.consensus.hash_version(height)
+    }
+
+    /// Check that the transactions of a block use hash versions active at its height, committing
+    /// the identifier of the chain.
+    ///
+    /// # Arguments
+    /// - `block`: The block.
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    /// `InvalidTransaction` if a transaction uses a later version or belongs to another chain.
+    fn check_hash_versions(&self, block: &Block, height: usize) -> Result<(), BlockchainError> {
+        let active = self.consensus.hash_version(height);
+
+        match block.transactions.values().any(|tx| {
+            tx.hash_version > active
+                || (!tx.hash_version.is_v1() && tx.chain_id != self.consensus.chain_id)
+        }) {
+            true => Err(BlockchainError::InvalidTransaction),
+            false => Ok(()),
+        }
+    }
+
+    /// Calculate the reward of the block at a height under the emission schedule.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    /// The block reward.
+    pub fn block_reward(&self, height: usize) -> f64 {
+        self.consensus.emission.reward(self.reward, height)
+    }
+
+    /// Project the total block rewards paid up to a height, e.g. for planning.
+    ///
+    /// The projection assumes the base reward is not changed by governance in the meantime.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the last block.
+    ///
+    /// # Returns
+    /// The sum of the rewards from the genesis block to `height` inclusive.
+    pub fn projected_supply(&self, height: usize) -> f64 {
+        self.consensus.emission.supply(self.reward, height)
+    }
+
+    /// Set the order in which pending transactions are included in new blocks.
+    ///
+    /// # Arguments
+    /// - `priority`: The new transaction priority.
+    pub fn set_transaction_priority(&mut self, priority: TransactionPriority) {
+        self.consensus.priority = priority;
+    }
+
+    /// Set the target block time held by retargeting the difficulty as blocks are mined.
+    ///
+    /// Every `interval` blocks, the difficulty is adjusted by the ratio between the target and
+    /// the block time observed over the last `interval` blocks.
+    ///
+    /// # Arguments
+    /// - `target`: The difficulty target, or `None` to keep the difficulty as it is.
+    ///
+    /// # Returns
+    /// `InvalidConfiguration` if the target is invalid.
+    pub fn set_difficulty_target(
+        &mut self,
+        target: Option<DifficultyTarget>,
+    ) -> Result<(), BlockchainError> {
+        if let Some(target) = &target {
+            target.validate()?;
+        }
+
+        self.consensus.difficulty_target = target;
+
+        Ok(())
+    }
+
+    /// Calculate the average time between the most recent blocks.
+    ///
+    /// # Arguments
+    /// - `blocks`: The number of most recent block intervals to average.
+    ///
+    /// # Returns
+    /// The average number of seconds between two blocks, or `None` if the chain has fewer than
+    /// `blocks + 1` blocks or `blocks` is zero.
+    pub fn observed_block_time(&self, blocks: usize) -> Option<f64> {
+        let last = self.chain.last()?;
+        let first = self
+            .chain
+            .len()
+            .checked_sub(blocks + 1)
+            .map(|i| &self.chain[i])?;
+
+        (blocks > 0)
+            .then(|| (last.header.timestamp - first.header.timestamp) as f64 / blocks as f64)
+    }
+
+    /// Set a custom policy selecting the pending transactions included in new blocks.
+    ///
+    /// The assembler replaces the transaction priority until it is cleared; it is not persisted.
+    ///
+    /// # Arguments
+    /// - `assembler`: The block assembler.
+    pub fn set_block_assembler(&mut self, assembler: impl BlockAssembler + 'static) {
+        self.assembler = Assembler(Some(Arc::new(assembler)));
+    }
+
+    /// Remove the custom block assembler, going back to the transaction priority.
+    pub fn clear_block_assembler(&mut self) {
+        self.assembler = Assembler::default();
+    }
+
+    /// Decode a persisted chain from untrusted bytes, e.g. a file received from a peer.
+    ///
+    /// The bytes are decoded and migrated as with `migration::decode`, then the chain must be
+    /// internally consistent: block heights match their position, transactions and wallets are
+    /// stored under their own hash and address, and the chain passes `validate_chain`. This never
+    /// panics on malformed input, which makes it a fuzzing entry point for the persisted format.
+    ///
+    /// # Arguments
+    /// - `bytes`: The persisted chain.
+    ///
+    /// # Returns
+    /// The chain, `StorageError` if it cannot be decoded or is inconsistent, or the error
+    /// reported by `validate_chain`.
+    pub fn from_bytes_untrusted(bytes: &[u8]) -> Result<Self, BlockchainError> {
+        let mut chain = migration::decode(bytes)?;
+
+        let consistent = chain
+            .chain
+            .iter()
+            .enumerate()
+            .all(|(height, block)| block.height == height)
+            && chain
+                .transactions
+                .iter()
+                .all(|(hash, tx)| *hash == tx.hash && tx.hash == tx.compute_hash())
+            && chain
+                .wallets
+                .iter()
+                .all(|(address, wallet)| *address == wallet.address);
+
+        if !consistent {
+            return Err(BlockchainError::StorageError);
+        }
+
+        chain.revalidate()?;
+
+        Ok(chain)
+    }
+
+    /// Re-apply an exported stream of native transfers, e.g. onto a fresh chain to reconstruct a
+    /// ledger or migrate it between incompatible versions.
+    ///
+    /// Wallets are created for unknown addresses, and records sent by `Address::root()` credit
+    /// their receiver, e.g. for the initial allocations. Every other record is validated and
+    /// applied as with `add_transaction`, and a rejected record does not stop the replay.
+    ///
+    /// # Arguments
+    /// - `reader`: The exported stream.
+    /// - `format`: The encoding of the stream.
+    ///
+    /// # Returns
+    /// The number of applied records with the rejected ones, or `StorageError` if the stream
+    /// cannot be read.
+    pub fn replay<R: BufRead>(
+        &mut self,
+        reader: R,
+        format: ReplayFormat,
+    ) -> Result<ReplayReport, BlockchainError> {
+        let mut report = ReplayReport::default();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|_| BlockchainError::StorageError)?;
+
+            let result = match format.parse(&line) {
+                Ok(Some(request)) => self.replay_request(request, &mut report),
+                Ok(None) => continue,
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok(()) => report.applied += 1,
+                Err(error) => report.failures.push(ReplayFailure {
+                    line: index + 1,
+                    error,
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Apply a record of a replayed stream.
+    fn replay_request(
+        &mut self,
+        TransactionRequest { from, to, amount }: TransactionRequest,
+        report: &mut ReplayReport,
+    ) -> Result<(), BlockchainError> {
+        for address in [&from, &to] {
+            if !address.is_root() && !self.wallets.contains_key(address) {
+                let mut wallet = Wallet::new("", address);
+                wallet.id = self.environment.uuid();
+
+                self.wallets.insert(address.to_owned(), wallet);
+                report.wallets_created.push(address.to_owned());
+            }
+        }
+
+        if !from.is_root() {
+            return self.add_transaction(&from, &to, amount);
+        }
+
+        // Credit an allocation
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        if to.is_root() {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        let transaction = self
+            .new_transaction(&from, &to, 0.0, amount, NATIVE_ASSET)
+            .with_kind(TransactionKind::Mint);
+        let hash = transaction.hash;
+
+        if self.transactions.contains_key(&hash) {
+            return Err(BlockchainError::DuplicateTransaction { hash });
+        }
+
+        if let Some(wallet) = self.wallets.get_mut(&to) {
+            wallet.balance += amount;
+            wallet.transaction_hashes.push(hash);
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(())
+    }
+
+    /// Approximate the memory footprint of the chain.
+    ///
+    /// # Returns
+    /// The approximate number of bytes used by the blocks, transactions and wallets.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let blocks = self.chain.capacity() * mem::size_of::<Block>()
+            + self
+                .chain
+                .iter()
+                .map(memory::block_heap_size)
+                .sum::<usize>();
+
+        let transactions = memory::map_size::<Hash, Transaction>(self.transactions.capacity())
+            + self
+                .transactions
+                .values()
+                .map(memory::transaction_heap_size)
+                .sum::<usize>();
+
+        let wallets = memory::map_size::<Address, Wallet>(self.wallets.capacity())
+            + self
+                .wallets
+                .iter()
+                .map(|(address, wallet)| address.as_str().len() + memory::wallet_heap_size(wallet))
+                .sum::<usize>();
+
+        MemoryUsage {
+            blocks,
+            transactions,
+            wallets,
+            total: blocks + transactions + wallets,
+        }
+    }
+
+    /// Get a list of current transactions in the blockchain.
+    ///
+    /// # Arguments
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// References to the transactions of the specified page.
+    pub fn get_transactions(&self, page: usize, size: usize) -> Vec<&Transaction> {
+        self.iter_transactions(page, size).collect()
+    }
+
+    /// Iterate over a page of current transactions without allocating.
+    ///
+    /// # Arguments
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// An iterator over the transactions of the specified page.
+    pub fn iter_transactions(
+        &self,
+        page: usize,
+        size: usize,
+    ) -> impl Iterator<Item = &Transaction> + '_ {
+        self.transactions
+            .values()
+            .skip(page.saturating_sub(1) * size)
+            .take(size)
+    }
+
+    /// Get an owned copy of a page of current transactions.
+    ///
+    /// # Arguments
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// The transactions of the specified page, by hash.
+    pub fn get_transactions_owned(&self, page: usize, size: usize) -> ChainTransactions {
+        self.iter_transactions(page, size)
+            .map(|tx| (tx.hash, tx.to_owned()))
+            .collect()
+    }
+
+    /// Get a transaction by its identifier.
+    ///
+    /// # Arguments
+    /// - `hash`: The hash of the transaction to retrieve.
+    ///
+    /// # Returns
+    /// An option containing a reference to the transaction if found, or `None` if not found.
+    pub fn get_transaction(&self, hash: &Hash) -> Result<&Transaction, BlockchainError> {
+        match self.transactions.get(hash) {
+            Some(transaction) => Ok(transaction),
+            None => Err(BlockchainError::TransactionNotFound { hash: *hash }),
+        }
+    }
+
+    /// Add a new transaction to the blockchain.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    ///
+    /// # Returns
+    /// `true` if the transaction is successfully added to the current transactions.
+    pub fn add_transaction(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+    ) -> Result<(), BlockchainError> {
+        self.add_authorized_transaction(from, to, amount, slice::from_ref(from))
+    }
+
+    /// Add a new transaction authorized by a set of signers.
+    ///
+    /// The signers are checked against the spend condition of the sender wallet, if any.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    /// - `signers`: The addresses that authorized the transaction.
+    ///
+    /// # Returns
+    /// An error if the spend condition is not satisfied or the transaction is invalid.
+    pub fn add_authorized_transaction(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+        signers: &[Address],
+    ) -> Result<(), BlockchainError> {
+        self.transfer(from, to, amount, signers).map(|_| ())
+    }
+
+    /// Add many transfers in one pass.
+    ///
+    /// Each transfer is validated and applied in order as with `add_transaction`, so a transfer
+    /// can spend funds received earlier in the batch. A failed transfer does not stop the batch.
+    ///
+    /// # Arguments
+    /// - `requests`: The transfers to apply.
+    ///
+    /// # Returns
+    /// The hash of the transaction created for each transfer, or the reason it was rejected.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(count = requests.len()))
+    )]
+    pub fn add_transactions(
+        &mut self,
+        requests: Vec<TransactionRequest>,
+    ) -> Vec<Result<Hash, BlockchainError>> {
+        self.transactions.reserve(requests.len());
+
+        requests
+            .into_iter()
+            .map(|TransactionRequest { from, to, amount }| {
+                self.transfer(&from, &to, amount, slice::from_ref(&from))
+            })
+            .collect()
+    }
+
+    /// Distribute native coins from one wallet to many in a single multi-output transaction,
+    /// e.g. for a token launch or rewards.
+    ///
+    /// Every credit and the total funds are validated before any balance changes, so either all
+    /// recipients are credited or none. The source is debited as for a transfer to each recipient
+    /// under the fee model of the next block, see `transfer_debit`.
+    ///
+    /// # Arguments
+    /// - `source`: The sender's address.
+    /// - `recipients`: The receiver's address and amount of each credit.
+    ///
+    /// # Returns
+    /// The hash of the transaction, `InvalidTransaction` if there are no recipients, the source
+    /// is the root or a recipient is repeated or the source, `InvalidAmount`, `WalletNotFound`,
+    /// `AddressDenied`, `KycRequired` or `InsufficientFunds`.
+    pub fn airdrop(
+        &mut self,
+        source: &Address,
+        recipients: &[(Address, f64)],
+    ) -> Result<Hash, BlockchainError> {
+        self.authorize(source, slice::from_ref(source))?;
+
+        if recipients.is_empty() || source.is_root() {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        let mut seen = HashSet::with_capacity(recipients.len());
+        for (to, amount) in recipients {
+            if !amount.is_finite() || *amount <= 0.0 {
+                return Err(BlockchainError::InvalidAmount { amount: *amount });
+            }
+
+            if to == source || !seen.insert(to) {
+                return Err(BlockchainError::InvalidTransaction);
+            }
+
+            if !self.wallets.contains_key(to) {
+                return Err(BlockchainError::WalletNotFound {
+                    address: to.to_owned(),
+                });
+            }
+
+            self.screen(source, to)?;
+            self.check_reserved(source, to)?;
+            self.check_kyc(source, to, *amount)?;
+        }
+
+        let total: f64 = recipients.iter().map(|(_, amount)| amount).sum();
+        let required: f64 = recipients
+            .iter()
+            .map(|(_, amount)| self.transfer_debit(*amount))
+            .sum();
+        let fee = match self
+            .consensus
+            .is_active(ProtocolFeature::FlatFee, self.chain.len())
+        {
+            true => self.fee * recipients.len() as f64,
+            false => self.fee,
+        };
+
+        match self.wallets.get(source) {
+            Some(wallet) if wallet.balance < required => {
+                return Err(BlockchainError::InsufficientFunds {
+                    address: source.to_owned(),
+                    required,
+                    available: wallet.balance,
+                });
+            }
+            Some(_) => {}
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: source.to_owned(),
+                })
+            }
+        }
+
+        let outputs = recipients
+            .iter()
+            .map(|(to, amount)| TransactionOutput {
+                to: to.to_owned(),
+                amount: *amount,
+            })
+            .collect();
+        let transaction = self
+            .new_transaction(source, &Address::root(), fee, total, NATIVE_ASSET)
+            .with_kind(TransactionKind::Airdrop)
+            .with_outputs(outputs);
+        let hash = transaction.hash;
+
+        if self.transactions.contains_key(&hash) {
+            return Err(BlockchainError::DuplicateTransaction { hash });
+        }
+
+        // Apply the debit and every credit, all validated above
+        self.charge_fee(source, required, hash)?;
+        for (to, amount) in recipients {
+            if let Some(wallet) = self.wallets.get_mut(to) {
+                wallet.balance += amount;
+                wallet.transaction_hashes.push(hash);
+            }
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Validate and apply a native transfer authorized by a set of signers.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "add_transaction",
+            level = "debug",
+            skip_all,
+            fields(from = %from, to = %to, amount, hash = tracing::field::Empty),
+            err(level = "debug"),
+        )
+    )]
+    fn transfer(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+        signers: &[Address],
+    ) -> Result<Hash, BlockchainError> {
+        let result = self.transfer_with_data(from, to, amount, signers, vec![]);
+
+        if let (Err(error), Some(dead_letters)) = (&result, &mut self.dead_letters) {
+            dead_letters.push(DeadLetter {
+                from: from.to_owned(),
+                to: to.to_owned(),
+                amount,
+                reason: error.to_string(),
+                timestamp: self.environment.now(),
+            });
+        }
+
+        result
+    }
+
+    /// Apply a transfer carrying a data payload, e.g. the reference of an invoice.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    /// - `signers`: The addresses that authorized the transaction.
+    /// - `data`: The data payload, or an empty one.
+    ///
+    /// # Returns
+    /// The hash of the new transaction, or an error if the transfer is invalid.
+    fn transfer_with_data(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+        signers: &[Address],
+        data: Vec<u8>,
+    ) -> Result<Hash, BlockchainError> {
+        if !self.hooks.is_empty() {
+            let request = TransactionRequest::new(from, to, amount);
+            self.hooks.before_validate(self, &request)?;
+        }
+
+        self.authorize(from, signers)?;
+        self.screen(from, to)?;
+        self.check_kyc(from, to, amount)?;
+
+        let total = self.transfer_debit(amount);
+
+        // Validate the transaction and create a new transaction if it is valid
+        self.check_transaction(from, to, total)?;
+        let flat_fee = self
+            .consensus
+            .is_active(ProtocolFeature::FlatFee, self.chain.len());
+        let recorded = match flat_fee {
+            true => amount,
+            false => total,
+        };
+        let mut transaction = self.new_transaction(from, to, self.fee, recorded, NATIVE_ASSET);
+        if !data.is_empty() {
+            transaction = transaction.with_data(data);
+        }
+
+        // Charge the size-based fee component, the size of the encoding not depending on the
+        // amounts
+        let size_fee = self.size_fee(transaction.encoded_size());
+        let total = total + size_fee;
+        if size_fee > 0.0 {
+            transaction.fee += size_fee;
+            if !flat_fee {
+                transaction.amount = total;
+            }
+            transaction.hash = transaction.compute_hash();
+        }
+
+        // Reject a transaction identical to an existing one, e.g. the same transfer in the same second
+        if self.transactions.contains_key(&transaction.hash) {
+            return Err(BlockchainError::DuplicateTransaction {
+                hash: transaction.hash,
+            });
+        }
+
+        // Update sender's balance
+        match self.wallets.get_mut(from) {
+            Some(wallet) => {
+                // Determine the wallet balance is sufficient for the transaction. If not, return false.
+                if wallet.balance < total {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: from.to_owned(),
+                        required: total,
+                        available: wallet.balance,
+                    });
+                }
+
+                wallet.balance -= total;
+
+                // Add the transaction to the sender's transaction history
+                wallet.transaction_hashes.push(transaction.hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: from.to_owned(),
+                })
+            }
+        };
+
+        // Update receiver's balance
+        match self.wallets.get_mut(to) {
+            Some(wallet) => {
+                wallet.balance += amount;
+
+                // Add the transaction to the receiver's transaction history
+                wallet.transaction_hashes.push(transaction.hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: to.to_owned(),
+                })
+            }
+        };
+
+        // Add the transaction to the current transactions
+        let hash = transaction.hash;
+        self.record_transaction(transaction);
+
+        if let Some(transaction) = self.transactions.get(&hash) {
+            self.hooks.after_apply(self, transaction);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("hash", tracing::field::display(hash));
+
+        Ok(hash)
+    }
+
+    /// Calculate the amount debited from the sender of a transfer under the fee model of the
+    /// next block.
+    ///
+    /// # Arguments
+    /// - `amount`: The amount received.
+    ///
+    /// # Returns
+    /// The amount plus the fee once `ProtocolFeature::FlatFee` is active, the amount times the
+    /// fee before.
+    pub fn transfer_debit(&self, amount: f64) -> f64 {
+        match self
+            .consensus
+            .is_active(ProtocolFeature::FlatFee, self.chain.len())
+        {
+            true => amount + self.fee,
+            false => amount * self.fee,
+        }
+    }
+
+    /// Calculate the largest amount a wallet can transfer, the fee included.
+    ///
+    /// The amount is rounded down so that the debit of the transfer never exceeds the balance.
+    ///
+    /// # Arguments
+    /// - `address`: The sender's address.
+    ///
+    /// # Returns
+    /// The amount sweeping the whole balance, or `None` if the wallet does not exist, has nothing
+    /// to spend, or transfers carry no fee.
+    pub fn sweep_amount(&self, address: &Address) -> Option<f64> {
+        let balance = self.get_wallet_balance(address)?;
+        if !(self.fee > 0.0 && balance > 0.0) {
+            return None;
+        }
+
+        // The transfer debits the amount with the fee, which may round above the balance
+        let mut amount = match self
+            .consensus
+            .is_active(ProtocolFeature::FlatFee, self.chain.len())
+        {
+            true => balance - self.fee,
+            false => balance / self.fee,
+        };
+        while amount > 0.0 && self.transfer_debit(amount) > balance {
+            amount = f64::from_bits(amount.to_bits() - 1);
+        }
+
+        (amount > 0.0 && amount.is_finite()).then_some(amount)
+    }
+
+    /// Build the transfers sweeping the spendable balance of wallets to a target address, e.g.
+    /// a cold storage wallet.
+    ///
+    /// # Arguments
+    /// - `from`: The addresses of the swept wallets.
+    /// - `to`: The target address.
+    ///
+    /// # Returns
+    /// One transfer per wallet with a spendable balance, skipping the target itself.
+    pub fn build_sweep(&self, from: &[Address], to: &Address) -> Vec<TransactionRequest> {
+        from.iter()
+            .filter(|address| *address != to)
+            .filter_map(|address| {
+                self.sweep_amount(address)
+                    .map(|amount| TransactionRequest::new(address, to, amount))
+            })
+            .collect()
+    }
+
+    /// Sweep the spendable balance of wallets to a target address.
+    ///
+    /// The transfers of `build_sweep` are applied as with `add_transactions`.
+    ///
+    /// # Arguments
+    /// - `from`: The addresses of the swept wallets.
+    /// - `to`: The target address.
+    ///
+    /// # Returns
+    /// The hash of the transaction sweeping each wallet with a spendable balance, or the reason
+    /// it was rejected.
+    pub fn sweep(&mut self, from: &[Address], to: &Address) -> Vec<Result<Hash, BlockchainError>> {
+        let requests = self.build_sweep(from, to);
+
+        self.add_transactions(requests)
+    }
+
+    /// Apply the oldest transfers waiting in a mempool.
+    ///
+    /// Each transfer is removed from the mempool and applied as with `add_transaction`.
+    ///
+    /// # Arguments
+    /// - `mempool`: The mempool to drain.
+    /// - `limit`: The maximum number of transfers to apply.
+    ///
+    /// # Returns
+    /// The hash of each drained transfer with the outcome of applying it, oldest first.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(pending = mempool.len(), limit))
+    )]
+    pub fn apply_pending(
+        &mut self,
+        mempool: &Mempool,
+        limit: usize,
+    ) -> Vec<(Hash, Result<(), BlockchainError>)> {
+        mempool
+            .drain(limit)
+            .into_iter()
+            .map(|pending| {
+                let TransactionRequest { from, to, amount } = &pending.request;
+
+                (pending.hash, self.add_transaction(from, to, *amount))
+            })
+            .collect()
+    }
+
+    /// Validate a transaction.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    ///
+    /// # Returns
+    /// `true` if the transaction is valid, `false` otherwise.
+    pub fn validate_transaction(&self, from: &Address, to: &Address, amount: f64) -> bool {
+        self.check_transaction(from, to, amount).is_ok()
+    }
+
+    /// Validate a transaction, reporting why it is invalid.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    ///
+    /// # Returns
+    /// `InvalidTransaction` if the sender is the root or the receiver, `AddressDenied`,
+    /// `ReservedAddress`, `KycRequired`, `InvalidAmount`, `WalletNotFound` or
+    /// `InsufficientFunds`.
+    pub fn check_transaction(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+    ) -> Result<(), BlockchainError> {
+        // Validate if the sender is not the root
+        if from.is_root() {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        // Validate that sender and receiver addresses are different
+        if from == to {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        // Validate that neither party is denied by the compliance lists
+        if let Some(address) = self.compliance.screen(from, to) {
+            return Err(BlockchainError::AddressDenied {
+                address: address.to_owned(),
+            });
+        }
+
+        // Validate that neither party is the genesis or treasury address
+        self.check_reserved(from, to)?;
+
+        // Validate that restricted wallets do not send or exceed their cap
+        self.check_kyc(from, to, amount)?;
+
+        // Validate if the amount is positive
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        // Validate if sender and receiver addresses are valid
+        let sender = match self.wallets.get(from) {
+            Some(wallet) => wallet,
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: from.to_owned(),
+                })
+            }
+        };
+
+        // Validate if the receiver address is valid
+        if !self.wallets.contains_key(to) {
+            return Err(BlockchainError::WalletNotFound {
+                address: to.to_owned(),
+            });
+        }
+
+        // Validate if sender can send the amount of the transaction
+        if sender.balance < amount {
+            return Err(BlockchainError::InsufficientFunds {
+                address: from.to_owned(),
+                required: amount,
+                available: sender.balance,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Set or clear the spend condition of a wallet.
+    ///
+    /// A wallet that already has a spend condition can only change it when the condition is
+    /// satisfied by the signers.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `script`: The script outgoing transactions must satisfy, or `None` to remove it.
+    /// - `signers`: The addresses that authorized the change.
+    ///
+    /// # Returns
+    /// An error if the wallet does not exist or the current spend condition is not satisfied.
+    pub fn set_spend_condition(
+        &mut self,
+        address: &Address,
+        script: Option<Script>,
+        signers: &[Address],
+    ) -> Result<(), BlockchainError> {
+        if !self.wallets.contains_key(address) {
+            return Err(BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            });
+        }
+
+        self.authorize(address, signers)?;
+
+        if let Some(wallet) = self.wallets.get_mut(address) {
+            wallet.spend_condition = script;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the spend condition of a wallet.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the spending wallet.
+    /// - `signers`: The addresses that authorized the spend.
+    ///
+    /// # Returns
+    /// An error if the wallet has a spend condition that is not satisfied.
+    fn authorize(&self, address: &Address, signers: &[Address]) -> Result<(), BlockchainError> {
+        // Burned funds can never be spent
+        if address.is_burn() {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        let script = match self.wallets.get(address) {
+            Some(Wallet {
+                spend_condition: Some(script),
+                ..
+            }) => script,
+            _ => return Ok(()),
+        };
+
+        let context = ScriptContext {
+            signers: signers.to_vec(),
+            timestamp: self.environment.now(),
+            height: self.chain.len(),
+        };
+
+        script.evaluate(&context)
+    }
+
+    /// Screen the parties of a transfer against the compliance lists.
+    ///
+    /// A refused transfer is recorded in the audit log.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    ///
+    /// # Returns
+    /// `AddressDenied` if a party is denied or not allowed.
+    fn screen(&mut self, from: &Address, to: &Address) -> Result<(), BlockchainError> {
+        let address = match self.compliance.screen(from, to) {
+            Some(address) => address.to_owned(),
+            None => return Ok(()),
+        };
+
+        self.audit_log.append_at(
+            AuditAction::TransferRefused {
+                from: from.to_owned(),
+                to: to.to_owned(),
+                address: address.to_owned(),
+            },
+            self.environment.now(),
+        );
+
+        Err(BlockchainError::AddressDenied { address })
+    }
+
+    /// Reject the genesis and treasury addresses as parties of a user transaction, unless
+    /// reserved transfers are allowed.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    ///
+    /// # Returns
+    /// `ReservedAddress` if a party is reserved.
+    fn check_reserved(&self, from: &Address, to: &Address) -> Result<(), BlockchainError> {
+        if self.consensus.reserved_transfers {
+            return Ok(());
+        }
+
+        let treasury = self
+            .consensus
+            .treasury
+            .as_ref()
+            .map(|treasury| &treasury.address);
+
+        match [from, to]
+            .into_iter()
+            .find(|address| **address == self.address || Some(*address) == treasury)
+        {
+            Some(address) => Err(BlockchainError::ReservedAddress {
+                address: address.to_owned(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Deny an address, rejecting the transfers it takes part in.
+    ///
+    /// # Arguments
+    /// - `address`: The address to deny.
+    /// - `jurisdiction`: The jurisdiction the address is denied under, if any.
+    pub fn deny_address(&mut self, address: &Address, jurisdiction: Option<&str>) {
+        let timestamp = self.environment.now();
+        let jurisdiction = jurisdiction.map(str::to_string);
+
+        self.compliance.denied.insert(
+            address.to_owned(),
+            DeniedAddress {
+                jurisdiction: jurisdiction.to_owned(),
+                timestamp,
+            },
+        );
+        self.audit_log.append_at(
+            AuditAction::AddressDenied {
+                address: address.to_owned(),
+                jurisdiction,
+            },
+            timestamp,
+        );
+    }
+
+    /// Remove an address from the deny list.
+    ///
+    /// # Arguments
+    /// - `address`: The denied address.
+    ///
+    /// # Returns
+    /// `true` if the address was denied, `false` otherwise.
+    pub fn lift_denial(&mut self, address: &Address) -> bool {
+        if self.compliance.denied.remove(address).is_none() {
+            return false;
+        }
+
+        self.audit_log.append_at(
+            AuditAction::DenialLifted {
+                address: address.to_owned(),
+            },
+            self.environment.now(),
+        );
+
+        true
+    }
+
+    /// Enable, replace or disable the allow list.
+    ///
+    /// # Arguments
+    /// - `allowed`: The only addresses allowed to take part in transfers, or `None` to allow
+    ///   every address that is not denied.
+    pub fn set_allowlist(&mut self, allowed: Option<Vec<Address>>) {
+        self.compliance.allowed = allowed.clone().map(|allowed| allowed.into_iter().collect());
+        self.audit_log.append_at(
+            AuditAction::AllowlistChanged { allowed },
+            self.environment.now(),
+        );
+    }
+
+    /// Check that a transfer respects the restrictions of unverified wallets.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The native amount received.
+    ///
+    /// # Returns
+    /// `KycRequired` if the sender is restricted or the receiver would exceed its cap.
+    fn check_kyc(&self, from: &Address, to: &Address, amount: f64) -> Result<(), BlockchainError> {
+        if let Some(Wallet {
+            kyc: KycTier::Restricted,
+            ..
+        }) = self.wallets.get(from)
+        {
+            return Err(BlockchainError::KycRequired {
+                address: from.to_owned(),
+            });
+        }
+
+        match self.wallets.get(to) {
+            Some(wallet) if !self.kyc.can_receive(wallet, amount) => {
+                Err(BlockchainError::KycRequired {
+                    address: to.to_owned(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Set the scheme generating the addresses of new wallets and contracts.
+    ///
+    /// Existing addresses are unaffected. The scheme is part of the environment, which is not
+    /// persisted, and must be set again after loading.
+    ///
+    /// # Arguments
+    /// - `scheme`: The address scheme.
+    pub fn set_address_scheme(&mut self, scheme: impl AddressScheme + 'static) {
+        self.environment = mem::take(&mut self.environment).with_address_scheme(Arc::new(scheme));
+    }
+
+    /// Search, on every available core, for a public key whose `PublicKeyScheme` address starts
+    /// with a prefix, e.g. for demo or branded addresses.
+    ///
+    /// # Arguments
+    /// - `prefix`: The case-sensitive prefix of the address.
+    /// - `timeout`: The maximum duration of the search.
+    ///
+    /// # Returns
+    /// The address and its public key, `InvalidAddress` if the prefix is not alphanumeric or
+    /// too long, or `VanityNotFound` if the timeout elapsed first.
+    pub fn generate_vanity_address(
+        prefix: &str,
+        timeout: Duration,
+    ) -> Result<VanityAddress, BlockchainError> {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+
+        vanity::search(prefix, timeout, threads)
+    }
+
+    /// Rate limit wallet creation and faucet claims made with `create_wallet_from` and
+    /// `faucet_from`, e.g. by a public-facing deployment.
+    ///
+    /// The limits are not persisted and must be set again after loading.
+    ///
+    /// # Arguments
+    /// - `wallets`: The limiter of wallet creations, keyed by email and by client, or `None`.
+    /// - `faucet`: The limiter of faucet claims, keyed by client, or `None`.
+    pub fn set_rate_limits(&mut self, wallets: Option<RateLimiter>, faucet: Option<RateLimiter>) {
+        self.wallet_limiter = wallets;
+        self.faucet_limiter = faucet;
+    }
+
+    /// Register the exchange rate provider used by `convert`.
+    ///
+    /// The provider is not persisted and must be registered again after loading.
+    ///
+    /// # Arguments
+    /// - `provider`: The exchange rate provider, replacing any previous one.
+    pub fn set_rate_provider(&mut self, provider: impl RateProvider + 'static) {
+        self.rates = Rates::new(Arc::new(provider));
+    }
+
+    /// Convert an amount of native coins to a currency, e.g. to display fiat values.
+    ///
+    /// # Arguments
+    /// - `amount`: The amount of native coins.
+    /// - `currency`: The target currency, e.g. `USD`.
+    ///
+    /// # Returns
+    /// The value of the amount in the currency, or `RateUnavailable` if no provider is registered
+    /// or it does not know the rate.
+    pub fn convert(&self, amount: f64, currency: &str) -> Result<f64, BlockchainError> {
+        self.rates.convert(amount, NATIVE_ASSET, currency)
+    }
+
+    /// Convert an amount of an asset to a currency.
+    ///
+    /// # Arguments
+    /// - `amount`: The amount of the asset.
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `currency`: The target currency.
+    ///
+    /// # Returns
+    /// The value of the amount in the currency, or `RateUnavailable` if no provider is registered
+    /// or it does not know the rate.
+    pub fn convert_asset(
+        &self,
+        amount: f64,
+        asset: &str,
+        currency: &str,
+    ) -> Result<f64, BlockchainError> {
+        self.rates.convert(amount, asset, currency)
+    }
+
+    /// Register an identity verification provider called for each new wallet.
+    ///
+    /// New wallets are restricted to receiving up to `restricted_cap` until the provider marks
+    /// them verified. The provider is not persisted and must be registered again after loading.
+    ///
+    /// # Arguments
+    /// - `provider`: The verification provider.
+    /// - `restricted_cap`: The maximum native balance of a restricted wallet.
+    pub fn set_kyc_provider(&mut self, provider: impl KycProvider + 'static, restricted_cap: f64) {
+        self.kyc = Kyc::new(Arc::new(provider), restricted_cap);
+    }
+
+    /// Set the verification tier of a wallet, e.g. once the provider verified it.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `tier`: The new tier.
+    ///
+    /// # Returns
+    /// An error if the wallet does not exist.
+    pub fn set_kyc_tier(
+        &mut self,
+        address: &Address,
+        tier: KycTier,
+    ) -> Result<(), BlockchainError> {
+        match self.wallets.get_mut(address) {
+            Some(wallet) => {
+                wallet.kyc = tier;
+
+                Ok(())
+            }
+            None => Err(BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            }),
+        }
+    }
+
+    /// Create a new wallet with a unique email and an initial balance.
+    ///
+    /// # Arguments
+    /// - `email`: The unique user email.
+    ///
+    /// # Returns
+    /// The newly created wallet address.
+    pub fn create_wallet(&mut self, email: &str) -> Address {
+        let address = self.environment.address();
+        let mut wallet = Wallet::new(email, &address);
+        wallet.id = self.environment.uuid();
+        wallet.kyc = self.kyc.request_verification(&wallet);
+
+        self.wallets.insert(address.to_owned(), wallet);
+        self.notify(ChainEvent::WalletCreated(address.to_owned()));
+
+        address
+    }
+
+    /// Create a new wallet on behalf of a client, subject to the wallet creation rate limit.
+    ///
+    /// # Arguments
+    /// - `email`: The unique user email.
+    /// - `client`: The token identifying the client, e.g. its IP address or API key.
+    ///
+    /// # Returns
+    /// The newly created wallet address, or `RateLimited` if the email or the client created
+    /// too many wallets recently.
+    pub fn create_wallet_from(
+        &mut self,
+        email: &str,
+        client: &str,
+    ) -> Result<Address, BlockchainError> {
+        let now = self.environment.now();
+        if let Some(limiter) = &mut self.wallet_limiter {
+            let (email, client) = (format!("email:{email}"), format!("client:{client}"));
+            limiter.check(&[&email, &client], now)?;
+        }
+
+        Ok(self.create_wallet(email))
+    }
+
+    /// Enable the faucet with a new funded wallet, e.g. on a development chain.
+    ///
+    /// The faucet wallet is verified and replaces the wallet of a previously enabled faucet.
+    ///
+    /// # Arguments
+    /// - `balance`: The initial balance of the faucet wallet.
+    /// - `cooldown`: The minimum number of seconds between two claims of the same address.
+    /// - `max_amount`: The maximum amount of a single claim.
+    ///
+    /// # Returns
+    /// The address of the faucet wallet.
+    pub fn enable_faucet(&mut self, balance: f64, cooldown: i64, max_amount: f64) -> Address {
+        let address = self.create_wallet("faucet");
+
+        if let Some(wallet) = self.wallets.get_mut(&address) {
+            wallet.balance = balance;
+            wallet.kyc = KycTier::Verified;
+        }
+
+        self.faucet = Some(Faucet::new(address.to_owned(), cooldown, max_amount));
+
+        address
+    }
+
+    /// Send funds from the faucet to a wallet.
+    ///
+    /// The transfer is validated and applied as with `add_transaction`, with the faucet wallet
+    /// paying the fee.
+    ///
+    /// # Arguments
+    /// - `address`: The receiver's address.
+    /// - `amount`: The amount to send, up to the maximum of a single claim.
+    ///
+    /// # Returns
+    /// The hash of the transaction, `InvalidConfiguration` if the faucet is disabled,
+    /// `InvalidAmount` if the amount exceeds the maximum, `FaucetCooldown` if the address claimed
+    /// funds too recently, or the error of the transfer.
+    pub fn faucet(&mut self, address: &Address, amount: f64) -> Result<Hash, BlockchainError> {
+        let Some(faucet) = &self.faucet else {
+            return Err(BlockchainError::InvalidConfiguration);
+        };
+
+        if amount > faucet.max_amount {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        let now = self.environment.now();
+        if let Some(available_at) = faucet.next_claim(address).filter(|&at| now < at) {
+            return Err(BlockchainError::FaucetCooldown {
+                address: address.to_owned(),
+                available_at,
+            });
+        }
+
+        let from = faucet.address.to_owned();
+        let hash = self.transfer(&from, address, amount, slice::from_ref(&from))?;
+
+        if let Some(faucet) = &mut self.faucet {
+            faucet.claims.insert(address.to_owned(), now);
+        }
+
+        Ok(hash)
+    }
+
+    /// Send funds from the faucet on behalf of a client, subject to the faucet rate limit.
+    ///
+    /// # Arguments
+    /// - `address`: The receiver's address.
+    /// - `amount`: The amount to send, up to the maximum of a single claim.
+    /// - `client`: The token identifying the client, e.g. its IP address or API key.
+    ///
+    /// # Returns
+    /// The hash of the transaction, `RateLimited` if the client claimed too often, or the error
+    /// of `faucet`.
+    pub fn faucet_from(
+        &mut self,
+        address: &Address,
+        amount: f64,
+        client: &str,
+    ) -> Result<Hash, BlockchainError> {
+        let now = self.environment.now();
+        if let Some(limiter) = &mut self.faucet_limiter {
+            limiter.check(&[client], now)?;
+        }
+
+        self.faucet(address, amount)
+    }
+
+    /// Get a wallet's balance based on its address.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    ///
+    /// # Returns
+    /// The wallet balance.
+    pub fn get_wallet_balance(&self, address: &Address) -> Option<f64> {
+        self.wallets.get(address).map(|wallet| wallet.balance)
+    }
+
+    /// Get a wallet's settled balance, confirmed by the state snapshot of the last block.
+    ///
+    /// Transfers are applied as they are added, so the settled balance leaves out the pending
+    /// transactions that the current balance already includes.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    ///
+    /// # Returns
+    /// The settled balance, zero if the wallet was created after the last block, or `None` if the
+    /// wallet is not found.
+    pub fn settled_balance(&self, address: &Address) -> Option<f64> {
+        self.wallets.get(address)?;
+
+        let state = self.chain.last().map_or(&[][..], |block| &block.state[..]);
+        let balance = state
+            .binary_search_by(|account| account.address.cmp(address))
+            .map_or(0.0, |index| state[index].balance);
+
+        Some(balance)
+    }
+
+    /// Get a wallet's available balance: the settled balance minus the pending outgoing amounts
+    /// plus the pending incoming amounts.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    ///
+    /// # Returns
+    /// The available balance, or `None` if the wallet is not found.
+    pub fn available_balance(&self, address: &Address) -> Option<f64> {
+        self.get_wallet_balance(address)
+    }
+
+    /// Get a wallet's transaction history based on its address.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// References to the wallet transactions of the specified page, or `None` if the wallet is not found.
+    pub fn get_wallet_transactions(
+        &self,
+        address: &Address,
+        page: usize,
+        size: usize,
+    ) -> Option<Vec<&Transaction>> {
+        self.iter_wallet_transactions(address, page, size)
+            .map(Iterator::collect)
+    }
+
+    /// Iterate over a page of a wallet's transaction history without allocating.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// An iterator over the wallet transactions of the specified page, or `None` if the wallet is not found.
+    pub fn iter_wallet_transactions(
+        &self,
+        address: &Address,
+        page: usize,
+        size: usize,
+    ) -> Option<impl Iterator<Item = &Transaction> + '_> {
+        let wallet = self.wallets.get(address)?;
+
+        Some(
+            wallet
+                .transaction_hashes
+                .iter()
+                .skip(page.saturating_sub(1) * size)
+                .take(size)
+                .filter_map(|hash| self.transactions.get(hash)),
+        )
+    }
+
+    /// Stream a wallet's transaction history as JSON Lines, e.g. to export the activity of a
+    /// very active wallet.
+    ///
+    /// Each transaction is written as soon as it is serialized, so the history is never
+    /// materialized in memory.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `writer`: The destination of the export, e.g. a buffered file.
+    ///
+    /// # Returns
+    /// The number of written lines, `WalletNotFound`, or `StorageError` if a line cannot be
+    /// written.
+    pub fn export_wallet_activity<W: Write>(
+        &self,
+        address: &Address,
+        mut writer: W,
+    ) -> Result<usize, BlockchainError> {
+        let wallet = self
+            .wallets
+            .get(address)
+            .ok_or_else(|| BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            })?;
+
+        let mut lines = 0;
+        for transaction in wallet
+            .transaction_hashes
+            .iter()
+            .filter_map(|hash| self.transactions.get(hash))
+        {
+            serde_json::to_writer(&mut writer, transaction)
+                .map_err(|_| BlockchainError::StorageError)?;
+            writer
+                .write_all(b"\n")
+                .map_err(|_| BlockchainError::StorageError)?;
+
+            lines += 1;
+        }
+
+        writer.flush().map_err(|_| BlockchainError::StorageError)?;
+
+        Ok(lines)
+    }
+
+    /// Rebuild the mined transaction history of an address from the blocks, skipping the blocks
+    /// whose bloom filter excludes the address.
+    ///
+    /// # Arguments
+    /// - `address`: The address to look for.
+    ///
+    /// # Returns
+    /// The transactions of the available block bodies sent or received by the address, in block
+    /// order and by timestamp within a block.
+    pub fn find_address_transactions(&self, address: &Address) -> Vec<&Transaction> {
+        self.chain
+            .iter()
+            .filter(|block| block.may_involve(address))
+            .flat_map(|block| {
+                let mut transactions: Vec<&Transaction> = block
+                    .transactions
+                    .values()
+                    .filter(|tx| tx.involves(address))
+                    .collect();
+                transactions.sort_by_key(|tx| (tx.timestamp, tx.hash));
+
+                transactions
+            })
+            .collect()
+    }
+
+    /// Get an owned copy of a page of a wallet's transaction history.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `page`: The page number.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    /// The wallet transactions of the specified page, or `None` if the wallet is not found.
+    pub fn get_wallet_transactions_owned(
+        &self,
+        address: &Address,
+        page: usize,
+        size: usize,
+    ) -> Option<Vec<Transaction>> {
+        self.iter_wallet_transactions(address, page, size)
+            .map(|transactions| transactions.cloned().collect())
+    }
+
+    /// Register a new token issued by an existing wallet.
+    ///
+    /// # Arguments
+    /// - `token`: The token to register.
+    ///
+    /// # Returns
+    /// An error if the issuer wallet does not exist or the symbol is already taken.
+    pub fn register_token(&mut self, token: Token) -> Result<(), BlockchainError> {
+        if !Token::validate_symbol(&token.symbol) {
+            return Err(BlockchainError::InvalidToken);
+        }
+
+        if !self.wallets.contains_key(&token.issuer) {
+            return Err(BlockchainError::WalletNotFound {
+                address: token.issuer.to_owned(),
+            });
+        }
+
+        if self.tokens.contains_key(&token.symbol) {
+            return Err(BlockchainError::TokenAlreadyExists);
+        }
+
+        self.tokens.insert(token.symbol.to_owned(), token);
+
+        Ok(())
+    }
+
+    /// Get a registered token by its symbol.
+    ///
+    /// # Arguments
+    /// - `symbol`: The token symbol.
+    ///
+    /// # Returns
+    /// A reference to the token, or an error if it is not registered.
+    pub fn get_token(&self, symbol: &str) -> Result<&Token, BlockchainError> {
+        self.tokens
+            .get(symbol)
+            .ok_or(BlockchainError::TokenNotFound)
+    }
+
+    /// Get a wallet's balance of a specific asset.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `asset`: The asset identifier, or `NATIVE_ASSET` for the native coin.
+    ///
+    /// # Returns
+    /// The wallet balance of the asset.
+    pub fn get_asset_balance(&self, address: &Address, asset: &str) -> Option<f64> {
+        self.wallets
+            .get(address)
+            .map(|wallet| wallet.balance_of(asset))
+    }
+
+    /// Transfer a registered token between two wallets.
+    ///
+    /// The transaction fee is charged in the native coin from the sender.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `asset`: The asset identifier, or `NATIVE_ASSET` for the native coin.
+    /// - `amount`: The amount of the asset to transfer.
+    ///
+    /// # Returns
+    /// An error if the token is unknown, the transfer is invalid, or the sender lacks funds.
+    pub fn add_asset_transaction(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        asset: &str,
+        amount: f64,
+    ) -> Result<(), BlockchainError> {
+        if asset == NATIVE_ASSET {
+            return self.add_transaction(from, to, amount);
+        }
+
+        self.get_token(asset)?;
+        self.authorize(from, slice::from_ref(from))?;
+        self.screen(from, to)?;
+        self.check_reserved(from, to)?;
+        self.check_kyc(from, to, 0.0)?;
+
+        if from.is_root() || from == to {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        if !self.wallets.contains_key(to) {
+            return Err(BlockchainError::WalletNotFound {
+                address: to.to_owned(),
+            });
+        }
+
+        let transaction = self.new_transaction(from, to, self.fee, amount, asset);
+
+        // Update sender's balances
+        match self.wallets.get_mut(from) {
+            Some(wallet) => {
+                if wallet.balance_of(asset) < amount {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: from.to_owned(),
+                        required: amount,
+                        available: wallet.balance_of(asset),
+                    });
+                }
+
+                if wallet.balance < self.fee {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: from.to_owned(),
+                        required: self.fee,
+                        available: wallet.balance,
+                    });
+                }
+
+                wallet.balance -= self.fee;
+                *wallet.balances.entry(asset.to_string()).or_default() -= amount;
+                wallet.transaction_hashes.push(transaction.hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: from.to_owned(),
+                })
+            }
+        };
+
+        // Update receiver's balance
+        if let Some(wallet) = self.wallets.get_mut(to) {
+            *wallet.balances.entry(asset.to_string()).or_default() += amount;
+            wallet.transaction_hashes.push(transaction.hash);
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(())
+    }
+
+    /// Register a new token and credit its initial supply to the issuer.
+    ///
+    /// # Arguments
+    /// - `config`: The configuration of the token.
+    ///
+    /// # Returns
+    /// The token that was issued, or an error if it cannot be registered or minted.
+    pub fn issue_token(&mut self, config: TokenConfig) -> Result<&Token, BlockchainError> {
+        let mut token = Token::new(
+            &config.symbol,
+            &config.name,
+            &config.issuer,
+            config.max_supply,
+        )?;
+        token.timestamp = self.environment.now();
+
+        if config.initial_supply < 0.0
+            || config
+                .max_supply
+                .is_some_and(|max| config.initial_supply > max)
+        {
+            return Err(BlockchainError::SupplyCapExceeded);
+        }
+
+        self.register_token(token)?;
+
+        if config.initial_supply > 0.0 {
+            self.mint(
+                &config.issuer,
+                &config.symbol,
+                &config.issuer,
+                config.initial_supply,
+            )?;
+        }
+
+        self.get_token(&config.symbol)
+    }
+
+    /// Mint new supply of a token, recorded as a mint transaction.
+    ///
+    /// # Arguments
+    /// - `issuer`: The address of the token issuer.
+    /// - `asset`: The token symbol.
+    /// - `to`: The address receiving the new supply.
+    /// - `amount`: The amount to mint.
+    ///
+    /// # Returns
+    /// The hash of the mint transaction, or an error if the caller is not the issuer or the cap is exceeded.
+    pub fn mint(
+        &mut self,
+        issuer: &Address,
+        asset: &str,
+        to: &Address,
+        amount: f64,
+    ) -> Result<Hash, BlockchainError> {
+        let token = self.get_token(asset)?;
+
+        if token.issuer != *issuer {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        if token
+            .max_supply
+            .is_some_and(|max| token.supply + amount > max)
+        {
+            return Err(BlockchainError::SupplyCapExceeded);
+        }
+
+        let transaction = self
+            .new_transaction(issuer, to, 0.0, amount, asset)
+            .with_kind(TransactionKind::Mint);
+        let hash = transaction.hash;
+
+        // Credit the receiver
+        match self.wallets.get_mut(to) {
+            Some(wallet) => {
+                *wallet.balances.entry(asset.to_string()).or_default() += amount;
+                wallet.transaction_hashes.push(hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: to.to_owned(),
+                })
+            }
+        };
+
+        if let Some(token) = self.tokens.get_mut(asset) {
+            token.supply += amount;
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Destroy native coins or token supply held by a wallet, recorded as a burn transaction.
+    ///
+    /// The funds are credited to the unspendable `Address::burn()` wallet, so the total burned
+    /// can be proven with `prove_balance`, and leave the circulating supply.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the wallet holding the funds.
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `amount`: The amount to burn.
+    ///
+    /// # Returns
+    /// The hash of the burn transaction, or an error if the wallet lacks funds.
+    pub fn burn(
+        &mut self,
+        address: &Address,
+        asset: &str,
+        amount: f64,
+    ) -> Result<Hash, BlockchainError> {
+        if asset != NATIVE_ASSET {
+            self.get_token(asset)?;
+        }
+
+        self.authorize(address, slice::from_ref(address))?;
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        let burn = Address::burn();
+        let transaction = self
+            .new_transaction(address, &burn, 0.0, amount, asset)
+            .with_kind(TransactionKind::Burn);
+        let hash = transaction.hash;
+
+        // Debit the holder
+        match self.wallets.get_mut(address) {
+            Some(wallet) => {
+                if wallet.balance_of(asset) < amount {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: address.to_owned(),
+                        required: amount,
+                        available: wallet.balance_of(asset),
+                    });
+                }
+
+                *wallet.balance_of_mut(asset) -= amount;
+                wallet.transaction_hashes.push(hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: address.to_owned(),
+                })
+            }
+        };
+
+        self.credit_burn(asset, amount, hash);
+
+        if let Some(token) = self.tokens.get_mut(asset) {
+            token.supply -= amount;
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Lock native coins in the escrow wallet of a bridge, to be minted as a wrapped token on
+    /// another chain.
+    ///
+    /// The sender pays the amount and the transaction fee, recorded in a `Bridge` transaction
+    /// carrying the recipient on the other chain as data.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `escrow`: The address of the escrow wallet of the bridge.
+    /// - `amount`: The amount to lock.
+    /// - `recipient`: The address credited on the other chain.
+    ///
+    /// # Returns
+    /// The hash of the lock transaction, `AddressDenied`, `ReservedAddress`, `KycRequired`,
+    /// `InvalidAmount`, `WalletNotFound` or `InsufficientFunds`.
+    pub fn bridge_lock(
+        &mut self,
+        from: &Address,
+        escrow: &Address,
+        amount: f64,
+        recipient: &Address,
+    ) -> Result<Hash, BlockchainError> {
+        self.authorize(from, slice::from_ref(from))?;
+        self.screen(from, escrow)?;
+        self.check_reserved(from, escrow)?;
+        self.check_kyc(from, escrow, amount)?;
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        if !self.wallets.contains_key(escrow) {
+            return Err(BlockchainError::WalletNotFound {
+                address: escrow.to_owned(),
+            });
+        }
+
+        let transaction = self
+            .new_transaction(from, escrow, self.fee, amount, NATIVE_ASSET)
+            .with_kind(TransactionKind::Bridge)
+            .with_data(recipient.as_str().as_bytes().to_vec());
+        let hash = transaction.hash;
+
+        self.charge_fee(from, amount + self.fee, hash)?;
+        if let Some(wallet) = self.wallets.get_mut(escrow) {
+            wallet.balance += amount;
+            wallet.transaction_hashes.push(hash);
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Burn a wrapped token to release the coins locked for it on another chain.
+    ///
+    /// The burn is recorded in a `Bridge` transaction to the burn address carrying the recipient
+    /// on the other chain as data.
+    ///
+    /// # Arguments
+    /// - `from`: The address of the wallet holding the wrapped token.
+    /// - `asset`: The symbol of the wrapped token.
+    /// - `amount`: The amount to burn.
+    /// - `recipient`: The address credited on the other chain.
+    ///
+    /// # Returns
+    /// The hash of the burn transaction, `TokenNotFound`, `AddressDenied`, `ReservedAddress`,
+    /// `KycRequired`, `InvalidAmount`, `WalletNotFound` or `InsufficientFunds`.
+    pub fn bridge_burn(
+        &mut self,
+        from: &Address,
+        asset: &str,
+        amount: f64,
+        recipient: &Address,
+    ) -> Result<Hash, BlockchainError> {
+        self.get_token(asset)?;
+        self.authorize(from, slice::from_ref(from))?;
+
+        let burn = Address::burn();
+        self.screen(from, &burn)?;
+        self.check_reserved(from, &burn)?;
+        self.check_kyc(from, &burn, 0.0)?;
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        let transaction = self
+            .new_transaction(from, &burn, 0.0, amount, asset)
+            .with_kind(TransactionKind::Bridge)
+            .with_data(recipient.as_str().as_bytes().to_vec());
+        let hash = transaction.hash;
+
+        match self.wallets.get_mut(from) {
+            Some(wallet) if wallet.balance_of(asset) < amount => {
+                return Err(BlockchainError::InsufficientFunds {
+                    address: from.to_owned(),
+                    required: amount,
+                    available: wallet.balance_of(asset),
+                });
+            }
+            Some(wallet) => {
+                *wallet.balance_of_mut(asset) -= amount;
+                wallet.transaction_hashes.push(hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: from.to_owned(),
+                })
+            }
+        }
+
+        self.credit_burn(asset, amount, hash);
+        if let Some(token) = self.tokens.get_mut(asset) {
+            token.supply -= amount;
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Mint a wrapped token for native coins locked on another chain, claiming the lock.
+    ///
+    /// Only reachable through `Bridge::mint`, once the lock is proven.
+    ///
+    /// # Arguments
+    /// - `issuer`: The address of the issuer of the wrapped token.
+    /// - `asset`: The symbol of the wrapped token.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount to mint.
+    /// - `lock`: The hash of the lock transaction on the other chain.
+    ///
+    /// # Returns
+    /// The hash of the mint transaction, `ProofAlreadyClaimed` if the lock was already claimed,
+    /// or the error of `mint`.
+    pub(crate) fn bridge_mint(
+        &mut self,
+        issuer: &Address,
+        asset: &str,
+        to: &Address,
+        amount: f64,
+        lock: &Hash,
+    ) -> Result<Hash, BlockchainError> {
+        self.check_bridge_claim(lock)?;
+
+        let hash = self.mint(issuer, asset, to, amount)?;
+        self.bridge_claims.insert(*lock);
+
+        Ok(hash)
+    }
+
+    /// Release native coins from the escrow wallet of a bridge, for a wrapped token burned on
+    /// another chain, claiming the burn.
+    ///
+    /// The release is recorded in a `Bridge` transaction without fee carrying the hash of the
+    /// burn as data. Only reachable through `Bridge::release`, once the burn is proven.
+    ///
+    /// # Arguments
+    /// - `escrow`: The address of the escrow wallet of the bridge.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount to release.
+    /// - `burn`: The hash of the burn transaction on the other chain.
+    ///
+    /// # Returns
+    /// The hash of the release transaction, `ProofAlreadyClaimed` if the burn was already
+    /// claimed, `InvalidAmount`, `WalletNotFound` or `InsufficientFunds` if the escrow holds
+    /// less than the amount.
+    pub(crate) fn bridge_release(
+        &mut self,
+        escrow: &Address,
+        to: &Address,
+        amount: f64,
+        burn: &Hash,
+    ) -> Result<Hash, BlockchainError> {
+        self.check_bridge_claim(burn)?;
+        self.authorize(escrow, slice::from_ref(escrow))?;
+        self.screen(escrow, to)?;
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        if !self.wallets.contains_key(to) {
+            return Err(BlockchainError::WalletNotFound {
+                address: to.to_owned(),
+            });
+        }
+
+        let transaction = self
+            .new_transaction(escrow, to, 0.0, amount, NATIVE_ASSET)
+            .with_kind(TransactionKind::Bridge)
+            .with_data(burn.to_string().into_bytes());
+        let hash = transaction.hash;
+
+        self.charge_fee(escrow, amount, hash)?;
+        if let Some(wallet) = self.wallets.get_mut(to) {
+            wallet.balance += amount;
+            wallet.transaction_hashes.push(hash);
+        }
+
+        self.bridge_claims.insert(*burn);
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Get the wallet of the bridge to another chain, creating it on the first opening.
+    ///
+    /// The address is stored in the state of the chain, so the bridge finds the same wallet when
+    /// reopened, and no other wallet can stand in for it.
+    ///
+    /// # Arguments
+    /// - `chain`: The name of the other chain of the bridge.
+    ///
+    /// # Returns
+    /// The address of the escrow or issuer wallet of the bridge.
+    pub(crate) fn bridge_wallet(&mut self, chain: &str) -> Address {
+        if let Some(address) = self.bridge_wallets.get(chain) {
+            return address.to_owned();
+        }
+
+        let address = self.create_wallet(&format!("bridge@{chain}"));
+        self.bridge_wallets
+            .insert(chain.to_string(), address.to_owned());
+
+        address
+    }
+
+    /// Check whether a bridge transaction of another chain was already claimed on this chain.
+    ///
+    /// # Arguments
+    /// - `hash`: The hash of the lock or burn transaction.
+    ///
+    /// # Returns
+    /// `true` if the coins or tokens of the transaction were already minted or released.
+    pub fn is_bridge_claimed(&self, hash: &Hash) -> bool {
+        self.bridge_claims.contains(hash)
+    }
+
+    /// Reject a bridge transaction already claimed on this chain.
+    fn check_bridge_claim(&self, hash: &Hash) -> Result<(), BlockchainError> {
+        match self.is_bridge_claimed(hash) {
+            true => Err(BlockchainError::ProofAlreadyClaimed { hash: *hash }),
+            false => Ok(()),
+        }
+    }
+
+    /// Credit the burn wallet, created on the first burn.
+    ///
+    /// # Arguments
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `amount`: The amount destroyed.
+    /// - `hash`: The hash of the burn transaction.
+    fn credit_burn(&mut self, asset: &str, amount: f64, hash: Hash) {
+        let burn = Address::burn();
+        let id = self.environment.uuid();
+        let wallet = self.wallets.entry(burn.to_owned()).or_insert_with(|| {
+            let mut wallet = Wallet::new("burn", &burn);
+            wallet.id = id;
+
+            wallet
+        });
+
+        *wallet.balance_of_mut(asset) += amount;
+        wallet.transaction_hashes.push(hash);
+    }
+
+    /// Calculate the native coins held by wallets, excluding burned funds.
+    ///
+    /// # Returns
+    /// The sum of the native balances of every wallet but the burn wallet.
+    pub fn circulating_supply(&self) -> f64 {
+        self.wallets
+            .iter()
+            .filter(|(address, _)| !address.is_burn())
+            .map(|(_, wallet)| wallet.balance)
+            .sum()
+    }
+
+    /// Get the total amount of an asset ever burned.
+    ///
+    /// # Arguments
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    ///
+    /// # Returns
+    /// The balance of the asset held by the burn wallet.
+    pub fn burned(&self, asset: &str) -> f64 {
+        self.wallets
+            .get(&Address::burn())
+            .map_or(0.0, |wallet| wallet.balance_of(asset))
+    }
+
+    /// Summarize the mined native coin movements by period, e.g. for back-office
+    /// reconciliation.
+    ///
+    /// # Arguments
+    /// - `period`: The grouping of the blocks, by day or by number of blocks.
+    ///
+    /// # Returns
+    /// One settlement per period with blocks, from the genesis block up, or
+    /// `InvalidConfiguration` for periods of zero blocks.
+    pub fn settlement_report(
+        &self,
+        period: SettlementPeriod,
+    ) -> Result<Vec<Settlement>, BlockchainError> {
+        settlement::settle(&self.chain, period)
+    }
+
+    /// Mint a new non-fungible token owned by its creator.
+    ///
+    /// # Arguments
+    /// - `creator`: The address of the minting wallet.
+    /// - `metadata`: The metadata describing the token.
+    ///
+    /// # Returns
+    /// The identifier of the new token, or an error if the creator wallet does not exist.
+    pub fn mint_nft(
+        &mut self,
+        creator: &Address,
+        metadata: NftMetadata,
+    ) -> Result<Uuid, BlockchainError> {
+        let mut nft = Nft::new(creator, metadata);
+        nft.id = self.environment.uuid();
+        nft.timestamp = self.environment.now();
+
+        let transaction = self
+            .new_transaction(&Address::root(), creator, 0.0, 1.0, &nft.id.to_string())
+            .with_kind(TransactionKind::NftMint);
+
+        match self.wallets.get_mut(creator) {
+            Some(wallet) => wallet.transaction_hashes.push(transaction.hash),
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: creator.to_owned(),
+                })
+            }
+        };
+
+        let id = nft.id;
+        nft.history.push(transaction.hash);
+
+        self.nfts.insert(id, nft);
+        self.record_transaction(transaction);
+
+        Ok(id)
+    }
+
+    /// Transfer a non-fungible token to another wallet.
+    ///
+    /// The transaction fee is charged in the native coin from the sender.
+    ///
+    /// # Arguments
+    /// - `from`: The address of the current owner.
+    /// - `to`: The address of the new owner.
+    /// - `id`: The identifier of the token.
+    ///
+    /// # Returns
+    /// The hash of the transfer transaction, or an error if the sender does not own the token.
+    pub fn transfer_nft(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        id: &Uuid,
+    ) -> Result<Hash, BlockchainError> {
+        let nft = self.get_nft(id)?;
+
+        if nft.owner != *from {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        self.authorize(from, slice::from_ref(from))?;
+        self.screen(from, to)?;
+        self.check_reserved(from, to)?;
+        self.check_kyc(from, to, 0.0)?;
+
+        if from == to {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        if !self.wallets.contains_key(to) {
+            return Err(BlockchainError::WalletNotFound {
+                address: to.to_owned(),
+            });
+        }
+
+        let transaction = self
+            .new_transaction(from, to, self.fee, 1.0, &id.to_string())
+            .with_kind(TransactionKind::NftTransfer);
+        let hash = transaction.hash;
+
+        // Charge the fee from the sender
+        match self.wallets.get_mut(from) {
+            Some(wallet) => {
+                if wallet.balance < self.fee {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: from.to_owned(),
+                        required: self.fee,
+                        available: wallet.balance,
+                    });
+                }
+
+                wallet.balance -= self.fee;
+                wallet.transaction_hashes.push(hash);
+            }
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: from.to_owned(),
+                })
+            }
+        };
+
+        if let Some(wallet) = self.wallets.get_mut(to) {
+            wallet.transaction_hashes.push(hash);
+        }
+
+        if let Some(nft) = self.nfts.get_mut(id) {
+            nft.owner = to.to_owned();
+            nft.history.push(hash);
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Get a non-fungible token by its identifier.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier of the token.
+    ///
+    /// # Returns
+    /// A reference to the token, or an error if it does not exist.
+    pub fn get_nft(&self, id: &Uuid) -> Result<&Nft, BlockchainError> {
+        self.nfts.get(id).ok_or(BlockchainError::NftNotFound)
+    }
+
+    /// Get the non-fungible tokens owned by a wallet.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    ///
+    /// # Returns
+    /// The tokens currently owned by the wallet.
+    pub fn get_nfts(&self, address: &Address) -> Vec<&Nft> {
+        self.nfts
+            .values()
+            .filter(|nft| nft.owner == *address)
+            .collect()
+    }
+
+    /// Get the provenance history of a non-fungible token.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier of the token.
+    ///
+    /// # Returns
+    /// The mint and transfer transactions of the token, oldest first.
+    pub fn get_nft_history(&self, id: &Uuid) -> Result<Vec<&Transaction>, BlockchainError> {
+        let nft = self.get_nft(id)?;
+
+        Ok(nft
+            .history
+            .iter()
+            .filter_map(|hash| self.transactions.get(hash))
+            .collect())
+    }
+
+    /// Deploy a WebAssembly smart contract.
+    ///
+    /// The deploy transaction carries the code hash as data, and the contract address is derived
+    /// from the owner and the transaction hash, see `Contract::derive_address`. The transaction
+    /// fee is charged in the native coin from the owner.
+    ///
+    /// # Arguments
+    /// - `owner`: The address of the deploying wallet.
+    /// - `code`: The WebAssembly module of the contract.
+    ///
+    /// # Returns
+    /// The address of the new contract, or an error if the module is invalid.
+    #[cfg(feature = "contracts")]
+    pub fn deploy_contract(
+        &mut self,
+        owner: &Address,
+        code: Vec<u8>,
+    ) -> Result<Address, BlockchainError> {
+        self.authorize(owner, slice::from_ref(owner))?;
+        contract::runtime::validate(&code)?;
+
+        let transaction = self
+            .new_transaction(owner, &Address::root(), self.fee, 0.0, NATIVE_ASSET)
+            .with_kind(TransactionKind::ContractDeploy)
+            .with_data(Hash::digest(&code).as_bytes().to_vec());
+
+        let contract = Contract::new(owner, code, &transaction.hash, transaction.timestamp);
+        let address = contract.address.to_owned();
+
+        self.charge_fee(owner, self.fee, transaction.hash)?;
+
+        self.contracts.insert(address.to_owned(), contract);
+        self.record_transaction(transaction);
+
+        Ok(address)
+    }
+
+    /// Call a function exported by a smart contract.
+    ///
+    /// The function runs with the given amount of gas and may read and write the
+    /// contract store. Changes to the store are only kept if the call succeeds.
+    /// The call transaction carries the function name as data.
+    /// The transaction fee is charged in the native coin from the caller.
+    ///
+    /// # Arguments
+    /// - `caller`: The address of the calling wallet.
+    /// - `address`: The address of the contract.
+    /// - `function`: The name of the exported function.
+    /// - `args`: The arguments passed to the function.
+    /// - `gas_limit`: The maximum amount of gas the call may consume.
+    ///
+    /// # Returns
+    /// The result of the call, `InvalidData` if the function name is longer than
+    /// `MAX_DATA_SIZE`, or an error if the call fails or runs out of gas.
+    #[cfg(feature = "contracts")]
+    pub fn call_contract(
+        &mut self,
+        caller: &Address,
+        address: &Address,
+        function: &str,
+        args: &[i64],
+        gas_limit: u64,
+    ) -> Result<contract::ContractCall, BlockchainError> {
+        self.authorize(caller, slice::from_ref(caller))?;
+
+        if function.len() > MAX_DATA_SIZE {
+            return Err(BlockchainError::InvalidData);
+        }
+
+        let contract = self.get_contract(address)?;
+
+        match self.wallets.get(caller) {
+            Some(wallet) if wallet.balance < self.fee => {
+                return Err(BlockchainError::InsufficientFunds {
+                    address: caller.to_owned(),
+                    required: self.fee,
+                    available: wallet.balance,
+                })
+            }
+            Some(_) => {}
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: caller.to_owned(),
+                })
+            }
+        };
+
+        let (storage, result, gas_used) = contract::runtime::execute(
+            &contract.code,
+            contract.storage.clone(),
+            function,
+            args,
+            gas_limit,
+        )?;
+
+        let transaction = self
+            .new_transaction(caller, address, self.fee, 0.0, NATIVE_ASSET)
+            .with_kind(TransactionKind::ContractCall)
+            .with_data(function.as_bytes().to_vec());
+        let hash = transaction.hash;
+
+        self.charge_fee(caller, self.fee, hash)?;
+
+        if let Some(contract) = self.contracts.get_mut(address) {
+            contract.storage = storage;
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(contract::ContractCall {
+            hash,
+            result,
+            gas_used,
+        })
+    }
+
+    /// Get a smart contract by its address.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the contract.
+    ///
+    /// # Returns
+    /// A reference to the contract, or an error if it does not exist.
+    pub fn get_contract(&self, address: &Address) -> Result<&Contract, BlockchainError> {
+        self.contracts
+            .get(address)
+            .ok_or(BlockchainError::ContractNotFound)
+    }
+
+    /// Calculate the Merkle root of the current contract state.
+    ///
+    /// # Returns
+    /// The contract root, or the zero hash if there are no contracts.
+    pub fn contract_root(&self) -> Hash {
+        contract::root(&self.contracts)
+    }
+
+    /// Anchor a data payload on the chain.
+    ///
+    /// The payload is recorded in a zero-value transaction. In addition to the transaction fee,
+    /// a fee is charged for each byte of the payload.
+    ///
+    /// # Arguments
+    /// - `from`: The address of the paying wallet.
+    /// - `data`: The data payload, e.g. the hash of a document.
+    ///
+    /// # Returns
+    /// The hash of the anchor transaction, or an error if the payload is empty or too large.
+    pub fn add_anchor(&mut self, from: &Address, data: Vec<u8>) -> Result<Hash, BlockchainError> {
+        self.authorize(from, slice::from_ref(from))?;
+
+        if data.is_empty() || data.len() > MAX_DATA_SIZE {
+            return Err(BlockchainError::InvalidData);
+        }
+
+        let fee = self.fee + data.len() as f64 * DATA_FEE_PER_BYTE;
+
+        let transaction = self
+            .new_transaction(from, &Address::root(), fee, 0.0, NATIVE_ASSET)
+            .with_kind(TransactionKind::Anchor)
+            .with_data(data);
+        let hash = transaction.hash;
+
+        self.charge_fee(from, fee, hash)?;
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Find anchor transactions whose data payload starts with the given hex prefix.
+    ///
+    /// # Arguments
+    /// - `hash_prefix`: The hex-encoded prefix of the anchored data, e.g. of a document hash.
+    ///
+    /// # Returns
+    /// The matching anchor transactions, oldest first.
+    pub fn find_anchors(&self, hash_prefix: &str) -> Vec<&Transaction> {
+        let prefix = hash_prefix.to_lowercase();
+
+        let mut anchors: Vec<&Transaction> = self
+            .transactions
+            .values()
+            .filter(|tx| tx.kind == TransactionKind::Anchor && tx.data_hex().starts_with(&prefix))
+            .collect();
+        anchors.sort_by_key(|tx| tx.timestamp);
+
+        anchors
+    }
+
+    /// Charge a fee in the native coin from a wallet.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the paying wallet.
+    /// - `fee`: The fee to charge.
+    /// - `hash`: The hash of the transaction the fee is paid for.
+    ///
+    /// # Returns
+    /// An error if the wallet does not exist or cannot pay the fee.
+    fn charge_fee(
+        &mut self,
+        address: &Address,
+        fee: f64,
+        hash: Hash,
+    ) -> Result<(), BlockchainError> {
+        match self.wallets.get_mut(address) {
+            Some(wallet) => {
+                if wallet.balance < fee {
+                    return Err(BlockchainError::InsufficientFunds {
+                        address: address.to_owned(),
+                        required: fee,
+                        available: wallet.balance,
+                    });
+                }
+
+                wallet.balance -= fee;
+                wallet.transaction_hashes.push(hash);
+
+                Ok(())
+            }
+            None => Err(BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            }),
+        }
+    }
+
+    /// Submit a proposal to change a chain parameter.
+    ///
+    /// # Arguments
+    /// - `proposer`: The address of the submitting wallet, which must hold a balance.
+    /// - `change`: The requested parameter change.
+    /// - `activation_height`: The height of the block at which the change takes effect.
+    ///
+    /// # Returns
+    /// The identifier of the proposal, or an error if the proposal is invalid.
+    pub fn submit_proposal(
+        &mut self,
+        proposer: &Address,
+        change: ProposalChange,
+        activation_height: usize,
+    ) -> Result<Uuid, BlockchainError> {
+        self.authorize(proposer, slice::from_ref(proposer))?;
+
+        match self.wallets.get(proposer) {
+            Some(wallet) if wallet.balance <= 0.0 => return Err(BlockchainError::Unauthorized),
+            Some(_) => {}
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: proposer.to_owned(),
+                })
+            }
+        };
+
+        // The change must be valid and take effect at a future block
+        if !change.is_valid() || activation_height < self.chain.len() {
+            return Err(BlockchainError::InvalidProposal);
+        }
+
+        let mut proposal = Proposal::new(proposer, change, activation_height);
+        proposal.id = self.environment.uuid();
+        proposal.timestamp = self.environment.now();
+        let id = proposal.id;
+
+        self.proposals.insert(id, proposal);
+
+        Ok(id)
+    }
+
+    /// Vote on an open proposal.
+    ///
+    /// Votes are weighted by the balance of the voter when the proposal is tallied.
+    /// Voting again replaces the previous vote.
+    ///
+    /// # Arguments
+    /// - `voter`: The address of the voting wallet, which must hold a balance.
+    /// - `id`: The identifier of the proposal.
+    /// - `approve`: Whether the voter approves the proposal.
+    ///
+    /// # Returns
+    /// An error if the proposal does not exist or is no longer open.
+    pub fn vote(
+        &mut self,
+        voter: &Address,
+        id: &Uuid,
+        approve: bool,
+    ) -> Result<(), BlockchainError> {
+        self.authorize(voter, slice::from_ref(voter))?;
+
+        match self.wallets.get(voter) {
+            Some(wallet) if wallet.balance <= 0.0 => return Err(BlockchainError::Unauthorized),
+            Some(_) => {}
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: voter.to_owned(),
+                })
+            }
+        };
+
+        let proposal = self
+            .proposals
+            .get_mut(id)
+            .ok_or(BlockchainError::ProposalNotFound)?;
+
+        if proposal.status != ProposalStatus::Open {
+            return Err(BlockchainError::ProposalClosed);
+        }
+
+        proposal.votes.insert(voter.to_owned(), approve);
+
+        Ok(())
+    }
+
+    /// Get a governance proposal by its identifier.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier of the proposal.
+    ///
+    /// # Returns
+    /// A reference to the proposal, or an error if it does not exist.
+    pub fn get_proposal(&self, id: &Uuid) -> Result<&Proposal, BlockchainError> {
+        self.proposals
+            .get(id)
+            .ok_or(BlockchainError::ProposalNotFound)
+    }
+
+    /// Tally the open proposals due at a block height and apply the ones that passed.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block being generated.
+    fn apply_proposals(&mut self, height: usize) {
+        let mut due: Vec<&Proposal> = self
+            .proposals
+            .values()
+            .filter(|p| p.status == ProposalStatus::Open && p.activation_height <= height)
+            .collect();
+
+        // Apply proposals in a deterministic order, so later ones win on conflicts
+        due.sort_by_key(|p| (p.activation_height, p.timestamp, p.id));
+
+        let results: Vec<(Uuid, ProposalChange, bool)> = due
+            .into_iter()
+            .map(|p| (p.id, p.change, p.tally(&self.wallets)))
+            .collect();
+
+        for (id, change, passed) in results {
+            if passed {
+                match change {
+                    ProposalChange::Fee(fee) => self.update_fee(fee),
+                    ProposalChange::Reward(reward) => self.update_reward(reward),
+                    ProposalChange::Difficulty(difficulty) => self.update_difficulty(difficulty),
+                }
+            }
+
+            if let Some(proposal) = self.proposals.get_mut(&id) {
+                proposal.status = match passed {
+                    true => ProposalStatus::Passed,
+                    false => ProposalStatus::Rejected,
+                };
+            }
+        }
+    }
+
+    /// Allow an oracle to report a data feed, creating the feed if needed.
+    ///
+    /// # Arguments
+    /// - `feed`: The name of the feed.
+    /// - `oracle`: The address of the oracle wallet.
+    ///
+    /// # Returns
+    /// An error if the feed name is empty or the oracle wallet does not exist.
+    pub fn allow_oracle(&mut self, feed: &str, oracle: &Address) -> Result<(), BlockchainError> {
+        if feed.is_empty() {
+            return Err(BlockchainError::InvalidData);
+        }
+
+        if !self.wallets.contains_key(oracle) {
+            return Err(BlockchainError::WalletNotFound {
+                address: oracle.to_owned(),
+            });
+        }
+
+        self.oracle_feeds
+            .entry(feed.to_string())
+            .or_default()
+            .oracles
+            .insert(oracle.to_owned());
+
+        Ok(())
+    }
+
+    /// Revoke the permission of an oracle to report a data feed.
+    ///
+    /// # Arguments
+    /// - `feed`: The name of the feed.
+    /// - `oracle`: The address of the oracle wallet.
+    ///
+    /// # Returns
+    /// An error if the feed does not exist.
+    pub fn revoke_oracle(&mut self, feed: &str, oracle: &Address) -> Result<(), BlockchainError> {
+        let feed = self
+            .oracle_feeds
+            .get_mut(feed)
+            .ok_or(BlockchainError::FeedNotFound)?;
+
+        feed.oracles.remove(oracle);
+
+        Ok(())
+    }
+
+    /// Record a value reported by an allowed oracle.
+    ///
+    /// The report is recorded as an oracle transaction carrying the value as its amount.
+    ///
+    /// # Arguments
+    /// - `oracle`: The address of the reporting oracle.
+    /// - `feed`: The name of the feed.
+    /// - `value`: The reported value.
+    /// - `signers`: The addresses that authorized the report.
+    ///
+    /// # Returns
+    /// The hash of the oracle transaction, or an error if the oracle is not allowed to report the feed.
+    pub fn submit_oracle_report(
+        &mut self,
+        oracle: &Address,
+        feed: &str,
+        value: f64,
+        signers: &[Address],
+    ) -> Result<Hash, BlockchainError> {
+        self.authorize(oracle, signers)?;
+
+        // Reports must be signed by the oracle itself unless it has a spend condition
+        let has_condition = self
+            .wallets
+            .get(oracle)
+            .is_some_and(|wallet| wallet.spend_condition.is_some());
+
+        if !has_condition && !signers.contains(oracle) {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        let allowed = self
+            .oracle_feeds
+            .get(feed)
+            .ok_or(BlockchainError::FeedNotFound)?
+            .oracles
+            .contains(oracle);
+
+        if !allowed {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        if !value.is_finite() {
+            return Err(BlockchainError::InvalidData);
+        }
+
+        let transaction = self
+            .new_transaction(oracle, &Address::root(), 0.0, value, feed)
+            .with_kind(TransactionKind::Oracle);
+        let hash = transaction.hash;
+
+        match self.wallets.get_mut(oracle) {
+            Some(wallet) => wallet.transaction_hashes.push(hash),
+            None => {
+                return Err(BlockchainError::WalletNotFound {
+                    address: oracle.to_owned(),
+                })
+            }
+        };
+
+        if let Some(feed) = self.oracle_feeds.get_mut(feed) {
+            feed.latest = Some(OracleReport {
+                feed: transaction.asset.to_owned(),
+                value,
+                oracle: oracle.to_owned(),
+                hash,
+                timestamp: transaction.timestamp,
+            });
+        }
+
+        self.record_transaction(transaction);
+
+        Ok(hash)
+    }
+
+    /// Fetch a value from an oracle and record it on the chain.
+    ///
+    /// # Arguments
+    /// - `oracle`: The oracle to fetch the value from.
+    /// - `feed`: The name of the feed.
+    ///
+    /// # Returns
+    /// The hash of the oracle transaction, or an error if the oracle does not provide the feed.
+    pub fn pull_oracle(
+        &mut self,
+        oracle: &impl Oracle,
+        feed: &str,
+    ) -> Result<Hash, BlockchainError> {
+        let value = oracle.fetch(feed).ok_or(BlockchainError::InvalidData)?;
+        let address = oracle.address();
+
+        self.submit_oracle_report(address, feed, value, slice::from_ref(address))
+    }
+
+    /// Get the most recent report of a data feed.
+    ///
+    /// # Arguments
+    /// - `feed`: The name of the feed.
+    ///
+    /// # Returns
+    /// The latest report, or an error if the feed does not exist or has not been reported yet.
+    pub fn get_oracle_value(&self, feed: &str) -> Result<&OracleReport, BlockchainError> {
+        self.oracle_feeds
+            .get(feed)
+            .and_then(|feed| feed.latest.as_ref())
+            .ok_or(BlockchainError::FeedNotFound)
+    }
+
+    /// Create an invoice requesting a payment to a wallet.
+    ///
+    /// # Arguments
+    /// - `to`: The address of the receiving wallet.
+    /// - `amount`: The amount to pay.
+    /// - `expiry`: The number of seconds the invoice can be paid for.
+    ///
+    /// # Returns
+    /// The identifier of the invoice, referenced by its payment, or an error if the wallet does
+    /// not exist or the amount or expiry is not positive.
+    pub fn create_invoice(
+        &mut self,
+        to: &Address,
+        amount: f64,
+        expiry: i64,
+    ) -> Result<Uuid, BlockchainError> {
+        if !self.wallets.contains_key(to) {
+            return Err(BlockchainError::WalletNotFound {
+                address: to.to_owned(),
+            });
+        }
+
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(BlockchainError::InvalidAmount { amount });
+        }
+
+        if expiry <= 0 {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
+
+        let id = self.environment.uuid();
+        let invoice = Invoice::new(id, to.to_owned(), amount, self.environment.now(), expiry);
+        self.invoices.insert(id, invoice);
+
+        Ok(id)
+    }
+
+    /// Pay an invoice.
+    ///
+    /// The payment is a transfer of the invoice amount carrying the invoice identifier as its data
+    /// payload, and is matched with the invoice, which becomes paid.
+    ///
+    /// # Arguments
+    /// - `from`: The payer's address.
+    /// - `id`: The identifier of the invoice.
+    ///
+    /// # Returns
+    /// The hash of the payment, or an error if the invoice does not exist, is paid or expired, or
+    /// the transfer is invalid.
+    pub fn pay_invoice(&mut self, from: &Address, id: Uuid) -> Result<Hash, BlockchainError> {
+        let now = self.environment.now();
+        let invoice = self
+            .invoices
+            .get_mut(&id)
+            .ok_or(BlockchainError::InvoiceNotFound)?;
+
+        invoice.expire(now);
+        if !invoice.is_payable(now) {
+            return Err(BlockchainError::InvoiceClosed);
+        }
+
+        let (to, amount) = (invoice.to.to_owned(), invoice.amount);
+        let hash = self.transfer_with_data(
+            from,
+            &to,
+            amount,
+            slice::from_ref(from),
+            id.as_bytes().to_vec(),
+        )?;
+
+        if let Some(invoice) = self.invoices.get_mut(&id) {
+            invoice.settle(hash, now);
+        }
+
+        Ok(hash)
+    }
+
+    /// Get an invoice.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier of the invoice.
+    ///
+    /// # Returns
+    /// The invoice, or an error if it does not exist.
+    pub fn get_invoice(&self, id: Uuid) -> Result<&Invoice, BlockchainError> {
+        self.invoices
+            .get(&id)
+            .ok_or(BlockchainError::InvoiceNotFound)
+    }
+
+    /// Get the invoices of a receiving wallet.
+    ///
+    /// # Arguments
+    /// - `to`: The address of the receiving wallet.
+    /// - `status`: The status of the returned invoices, or `None` for all of them.
+    ///
+    /// # Returns
+    /// The matching invoices, oldest first.
+    pub fn get_invoices(&self, to: &Address, status: Option<InvoiceStatus>) -> Vec<&Invoice> {
+        let mut invoices: Vec<&Invoice> = self
+            .invoices
+            .values()
+            .filter(|invoice| invoice.to == *to)
+            .filter(|invoice| status.is_none_or(|status| invoice.status == status))
+            .collect();
+        invoices.sort_by_key(|invoice| (invoice.created_at, invoice.id));
+
+        invoices
+    }
+
+    /// Mark the open invoices past their expiry as expired.
+    ///
+    /// Invoices are also expired when a block is generated.
+    ///
+    /// # Returns
+    /// The number of invoices that expired.
+    pub fn expire_invoices(&mut self) -> usize {
+        let now = self.environment.now();
+
+        self.invoices
+            .values_mut()
+            .map(|invoice| invoice.expire(now))
+            .filter(|expired| *expired)
+            .count()
+    }
+
+    /// Subscribe to new blocks and transactions.
+    ///
+    /// # Arguments
+    /// - `filter`: The events delivered to the subscription.
+    ///
+    /// # Returns
+    /// A subscription receiving the matching events until it is dropped.
+    pub fn subscribe(&mut self, filter: SubscriptionFilter) -> Subscription {
+        self.subscribers.subscribe(filter)
+    }
+
+    /// Watch an address for balance changes, e.g. to monitor exchange deposits.
+    ///
+    /// Subscribers with the `Watched` filter, or the filter of the address, then receive a
+    /// `BalanceChanged` event whenever the native balance of the address changes, and a
+    /// `WatchedBlock` event whenever a block appended to the chain involves the address.
+    ///
+    /// # Arguments
+    /// - `address`: The address to watch.
+    ///
+    /// # Returns
+    /// An error if the wallet does not exist.
+    pub fn watch_address(&mut self, address: &Address) -> Result<(), BlockchainError> {
+        let wallet = self
+            .wallets
+            .get(address)
+            .ok_or_else(|| BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            })?;
+
+        self.watchlist.watch(address, wallet.balance);
+
+        Ok(())
+    }
+
+    /// Stop watching an address.
+    ///
+    /// # Arguments
+    /// - `address`: The address to stop watching.
+    ///
+    /// # Returns
+    /// `true` if the address was watched, `false` otherwise.
+    pub fn unwatch_address(&mut self, address: &Address) -> bool {
+        self.watchlist.unwatch(address)
+    }
+
+    /// Get the watched addresses.
+    ///
+    /// # Returns
+    /// An iterator over the watched addresses, in order.
+    pub fn watched_addresses(&self) -> impl Iterator<Item = &Address> {
+        self.watchlist.addresses()
+    }
+
+    /// Register a hook run around each transfer, e.g. for compliance checks or notifications.
+    ///
+    /// # Arguments
+    /// - `hook`: The hook, run after the already registered ones.
+    pub fn register_hook(&mut self, hook: impl TransactionHook + 'static) {
+        self.hooks.register(Arc::new(hook));
+    }
+
+    /// Add a transaction to the current transactions and notify the subscribers.
+    ///
+    /// # Arguments
+    /// - `transaction`: The new transaction.
+    fn record_transaction(&mut self, transaction: Transaction) {
+        let transaction = transaction.with_size();
+
+        if !self.subscribers.is_empty() {
+            let event = ChainEvent::Transaction(transaction.clone());
+            self.subscribers.notify(&event);
+        }
+
+        if !self.watchlist.is_empty() {
+            let parties: Vec<&Address> = [&transaction.from, &transaction.to]
+                .into_iter()
+                .chain(transaction.outputs.iter().map(|output| &output.to))
+                .collect();
+            self.notify_balances(&parties);
+        }
+
+        self.pending.push(QueuedTransaction {
+            hash: transaction.hash,
+            since: self.chain.len(),
+        });
+        self.transactions.insert(transaction.hash, transaction);
+    }
+
+    /// Notify the subscribers of the last block.
+    fn notify_block(&mut self) {
+        if let Some(block) = self.chain.last().filter(|_| !self.subscribers.is_empty()) {
+            let event = ChainEvent::Block(block.clone());
+            self.subscribers.notify(&event);
+        }
+
+        self.notify_watched_block();
+    }
+
+    /// Notify the subscribers of the watched addresses involved in the last block, and of the
+    /// balance changes of all watched addresses, e.g. from block rewards.
+    fn notify_watched_block(&mut self) {
+        let Some(block) = self.chain.last().filter(|_| !self.watchlist.is_empty()) else {
+            return;
+        };
+
+        let watched: Vec<Address> = self.watchlist.addresses().cloned().collect();
+        let events: Vec<ChainEvent> = watched
+            .iter()
+            .filter_map(|address| {
+                let mut transactions: Vec<Hash> = block
+                    .transactions
+                    .values()
+                    .filter(|tx| tx.involves(address))
+                    .map(|tx| tx.hash)
+                    .collect();
+                transactions.sort();
+
+                (!transactions.is_empty()).then(|| ChainEvent::WatchedBlock {
+                    address: address.to_owned(),
+                    height: block.height,
+                    hash: block.hash,
+                    transactions,
+                })
+            })
+            .collect();
+
+        for event in events {
+            self.notify(event);
+        }
+
+        self.notify_balances(&watched.iter().collect::<Vec<_>>());
+    }
+
+    /// Notify the subscribers of the balance changes of the watched addresses among some
+    /// addresses.
+    ///
+    /// # Arguments
+    /// - `addresses`: The addresses whose balance may have changed.
+    fn notify_balances(&mut self, addresses: &[&Address]) {
+        for address in addresses {
+            let balance = self
+                .wallets
+                .get(*address)
+                .map_or(0.0, |wallet| wallet.balance);
+
+            if let Some(event) = self.watchlist.update(address, balance) {
+                self.notify(event);
+            }
+        }
+    }
+
+    /// Notify the subscribers of an event.
+    fn notify(&mut self, event: ChainEvent) {
+        if !self.subscribers.is_empty() {
+            self.subscribers.notify(&event);
+        }
+    }
+
+    /// Remove the blocks above a height, e.g. to roll back a failed synchronization.
+    ///
+    /// The transactions of the removed blocks are pending again, keeping the balance changes
+    /// applied when they were added, and the rewards, treasury and burned shares credited when
+    /// the blocks were mined locally are reverted, so mining them again does not count them
+    /// twice. The difficulty returns to the one of the last kept block. Subscribers are notified
+    /// with a `Reorg` event if any block was removed.
+    ///
+    /// # Arguments
+    /// - `height`: The number of blocks to keep.
+    ///
+    /// # Returns
+    /// The removed blocks, from the lowest height up.
+    pub fn truncate(&mut self, height: usize) -> Vec<Block> {
+        if height >= self.chain.len() {
+            return vec![];
+        }
+
+        let removed = self.chain.split_off(height);
+        let hashes: Vec<Hash> = removed.iter().map(|block| block.hash).collect();
+        self.validation = ComponentStatus::Unknown;
+
+        if let Some(last) = self.chain.last() {
+            self.difficulty = last.header.difficulty;
+        }
+
+        // Return the transactions of the removed blocks to the pending ones
+        let mut returned: Vec<(&Transaction, usize)> = removed
+            .iter()
+            .flat_map(|block| block.transactions.values().map(|tx| (tx, block.height)))
+            .filter(|(tx, _)| {
+                !tx.kind.is_block_production() && self.transactions.contains_key(&tx.hash)
+            })
+            .collect();
+        returned.sort_by_key(|(tx, _)| (tx.timestamp, tx.hash));
+        self.pending.splice(
+            0..0,
+            returned.into_iter().map(|(tx, since)| QueuedTransaction {
+                hash: tx.hash,
+                since,
+            }),
+        );
+
+        // Revert the credits of the block production transactions
+        let reverted: Vec<Address> = removed
+            .iter()
+            .flat_map(|block| block.transactions.values())
+            .filter(|tx| tx.kind.is_block_production())
+            .filter_map(|tx| self.revert_credit(tx))
+            .collect();
+        self.notify_balances(&reverted.iter().collect::<Vec<_>>());
+
+        self.audit_log.append_at(
+            AuditAction::Rollback {
+                height,
+                removed: hashes.clone(),
+            },
+            self.environment.now(),
+        );
+        self.notify(ChainEvent::Reorg {
+            height,
+            removed: hashes,
+        });
+
+        removed
+    }
+
+    /// Revert the credit of a block production transaction mined locally, imported blocks never
+    /// being applied to the balances.
+    ///
+    /// # Arguments
+    /// - `transaction`: The reward, treasury or fee burn transaction.
+    ///
+    /// # Returns
+    /// The credited address, or `None` if the transaction was not credited.
+    fn revert_credit(&mut self, transaction: &Transaction) -> Option<Address> {
+        let wallet = self.wallets.get_mut(&transaction.to)?;
+        let position = wallet
+            .transaction_hashes
+            .iter()
+            .rposition(|hash| *hash == transaction.hash)?;
+
+        wallet.transaction_hashes.remove(position);
+        *wallet.balance_of_mut(&transaction.asset) -= transaction.amount;
+        self.transactions.remove(&transaction.hash);
+
+        Some(transaction.to.to_owned())
+    }
+
+    /// Sum the work of the blocks from a height up.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the first block.
+    ///
+    /// # Returns
+    /// The total work of the blocks, see `Block::work`.
+    fn work_since(&self, height: usize) -> f64 {
+        self.chain.iter().skip(height).map(Block::work).sum()
+    }
+
+    /// Create an independent branch sharing the blocks up to a height, e.g. to test reorg handling
+    /// deterministically.
+    ///
+    /// The branch starts with the current wallet state and environment, without subscribers.
+    /// Its blocks above the height are removed and their transactions are pending again, so it
+    /// can be extended separately and later fed back with `reorganize`.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the last shared block.
+    ///
+    /// # Returns
+    /// The branch, or `BlockNotFound` if the block does not exist.
+    pub fn fork_at(&self, height: usize) -> Result<Chain, BlockchainError> {
+        if height >= self.chain.len() {
+            return Err(BlockchainError::BlockNotFound);
+        }
+
+        let mut fork = self.clone();
+        fork.truncate(height + 1);
+
+        Ok(fork)
+    }
+
+    /// Create a lazy fork to dry-run transactions against, e.g. to preview the balances after a
+    /// batch of transfers before submitting it.
+    ///
+    /// The fork reads from the chain until its first mutation, which clones the whole chain,
+    /// blocks, wallets, contracts and audit log included, as with `fork_at` but keeping every
+    /// block. The copy has no hooks or subscribers.
+    ///
+    /// # Returns
+    /// The fork, reading from the chain until it is mutated.
+    pub fn lazy_fork(&self) -> LazyFork<'_> {
+        LazyFork::new(self)
+    }
+
+    /// Preview a transfer without changing the chain, e.g. for client-side previews and
+    /// pre-flight checks.
+    ///
+    /// The transfer is applied to a `lazy_fork` with every validation of `add_transaction`. The
+    /// `before_validate` callbacks of the hooks still run, but not the `after_apply` ones, as
+    /// the transfer never happens.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transfer.
+    ///
+    /// # Returns
+    /// The projected fee and balances, or the error the transfer would be rejected with.
+    pub fn simulate_transaction(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: f64,
+    ) -> TransactionSimulation {
+        let request = TransactionRequest::new(from, to, amount);
+        let mut fork = self.lazy_fork();
+        let result = self
+            .hooks
+            .before_validate(self, &request)
+            .and_then(|_| fork.transfer(from, to, amount, slice::from_ref(from)));
+
+        TransactionSimulation {
+            fee: result
+                .as_ref()
+                .ok()
+                .and_then(|hash| fork.transactions.get(hash))
+                .map_or(self.fee, |transaction| transaction.fee),
+            from_balance: fork.get_wallet_balance(from),
+            to_balance: fork.get_wallet_balance(to),
+            error: result.err(),
+        }
+    }
+
+    /// Clone the chain without its hooks, e.g. for dry runs whose transfers never happen.
+    ///
+    /// # Returns
+    /// The copy, without hooks or subscribers.
+    pub(crate) fn clone_without_hooks(&self) -> Chain {
+        Chain {
+            hooks: Hooks::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Switch to a competing branch if it holds more work than the local blocks it replaces, see
+    /// `Block::work`.
+    ///
+    /// The branch is first imported into a copy of the chain, so an invalid block, e.g. one not
+    /// declaring the difficulty expected at its height, leaves the chain unchanged. The local
+    /// blocks above the fork point are then removed as with `truncate` and the branch is
+    /// imported as with `import_block`.
+    ///
+    /// # Arguments
+    /// - `blocks`: The blocks of the branch after the fork point, from the lowest height up.
+    ///
+    /// # Returns
+    /// The removed blocks, `InvalidHeader` if the branch does not link to a local block or does
+    /// not hold more work than the local blocks, or the error of the invalid block.
+    pub fn reorganize(&mut self, blocks: Vec<Block>) -> Result<Vec<Block>, BlockchainError> {
+        let Some(first) = blocks.first() else {
+            return Ok(vec![]);
+        };
+
+        let height = first.height;
+        if height == 0 || Some(first.header.previous_hash) != self.get_block_hash(height - 1) {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        // Validate the branch against a copy without subscribers, at the difficulty of the fork
+        // point
+        let mut branch = self.clone();
+        branch.truncate(height);
+        for block in blocks {
+            branch.import_block(block)?;
+        }
+
+        if branch.work_since(height) <= self.work_since(height) {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        let removed = self.truncate(height);
+        for block in branch.chain.split_off(height) {
+            self.import_block(block)?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Get the hash of the last block in the blockchain.
+    ///
+    /// # Returns
+    /// The hash of the last block in the blockchain, or the zero hash for an empty chain.
+    pub fn get_last_hash(&self) -> Hash {
+        self.chain
+            .len()
+            .checked_sub(1)
+            .and_then(|height| self.get_block_hash(height))
+            .unwrap_or_else(Hash::zero)
+    }
+
+    /// Get the hash of the block at a height.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    /// The block hash, or `None` if there is no block at the given height.
+    pub fn get_block_hash(&self, height: usize) -> Option<Hash> {
+        let block = self.chain.get(height)?;
+
+        // Fall back to hashing the header for blocks created before hashes were cached
+        match block.hash.is_zero() {
+            true => Some(Chain::hash(&block.header)),
+            false => Some(block.hash),
+        }
+    }
+
+    /// Verify the cached block hashes, e.g. after loading a chain.
+    ///
+    /// Missing hashes, from blocks created before hashes were cached, are computed and stored.
+    ///
+    /// # Returns
+    /// The number of hashes computed, or `InvalidHeader` if a cached hash does not match its header.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(blocks = self.chain.len()), err(level = "warn"))
+    )]
+    pub fn verify_block_hashes(&mut self) -> Result<usize, BlockchainError> {
+        let mut computed = 0;
+
+        for block in self.chain.iter_mut() {
+            if block.hash.is_zero() {
+                block.refresh_hash();
+                computed += 1;
+            } else if !block.has_valid_hash() {
+                return Err(BlockchainError::InvalidHeader);
+            }
+        }
+
+        Ok(computed)
+    }
+
+    /// Build a block locator describing the local chain to a peer.
+    ///
+    /// The locator holds the hashes of the most recent blocks, then of blocks further apart
+    /// at exponentially increasing steps, and always ends with the genesis block.
+    ///
+    /// # Returns
+    /// The block hashes, from the last block back to the genesis block.
+    pub fn locator(&self) -> Vec<Hash> {
+        let mut locator = vec![];
+        let mut height = match self.chain.len().checked_sub(1) {
+            Some(height) => height,
+            None => return locator,
+        };
+        let mut step = 1;
+
+        loop {
+            locator.extend(self.get_block_hash(height));
+
+            if height == 0 {
+                break;
+            }
+
+            // Double the step once the ten most recent blocks are listed
+            if locator.len() >= LOCATOR_DENSE_SPAN {
+                step *= 2;
+            }
+
+            height = height.saturating_sub(step);
+        }
+
+        locator
+    }
+
+    /// Find the most recent block of a locator that is part of the local chain.
+    ///
+    /// # Arguments
+    /// - `locator`: The block hashes, most recent first.
+    ///
+    /// # Returns
+    /// The height of the first known block of the locator, or `None` if no block is known.
+    pub fn find_fork(&self, locator: &[Hash]) -> Option<usize> {
+        locator.iter().find_map(|hash| {
+            (0..self.chain.len())
+                .rev()
+                .find(|&height| self.get_block_hash(height).as_ref() == Some(hash))
+        })
+    }
+
+    /// Get a range of block headers for header synchronization.
+    ///
+    /// # Arguments
+    /// - `start`: The height of the first header.
+    /// - `count`: The maximum number of headers to return.
+    ///
+    /// # Returns
+    /// The block headers starting at the given height.
+    pub fn get_headers(&self, start: usize, count: usize) -> Vec<BlockHeader> {
+        self.chain
+            .iter()
+            .skip(start)
+            .take(count)
+            .map(|block| block.header.to_owned())
+            .collect()
+    }
+
+    /// Calculate the median timestamp of the most recent blocks.
+    ///
+    /// # Returns
+    /// The median time past, or `None` if the chain has no blocks.
+    pub fn median_time_past(&self) -> Option<i64> {
+        let timestamps: Vec<i64> = self
+            .chain
+            .iter()
+            .rev()
+            .take(MEDIAN_TIME_SPAN)
+            .map(|block| block.header.timestamp)
+            .collect();
+
+        Block::median_time_past(&timestamps)
+    }
+
+    /// Append a block header without its body.
+    ///
+    /// The header must declare and satisfy the difficulty expected of the next block, see
+    /// `expected_difficulty`, which the chain then follows as if it had mined the block.
+    ///
+    /// # Arguments
+    /// - `header`: The header of the next block.
+    ///
+    /// # Returns
+    /// An error if the header does not satisfy the expected proof-of-work or does not link to the
+    /// last block.
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<(), BlockchainError> {
+        self.check_header(&header)?;
+
+        let difficulty = header.difficulty;
+        let height = self.chain.len();
+        self.chain.push(Block::from_header(header, height));
+        self.follow_difficulty(difficulty);
+
+        Ok(())
+    }
+
+    /// Check that a header can be appended as the next block.
+    ///
+    /// # Arguments
+    /// - `header`: The header of the next block.
+    ///
+    /// # Returns
+    /// `InvalidHeader` if the header does not satisfy the expected proof-of-work or does not link
+    /// to the last block, or `InvalidTimestamp`.
+    fn check_header(&self, header: &BlockHeader) -> Result<(), BlockchainError> {
+        if !Block::validate_proof_of_work(header, self.expected_difficulty())
+            || header.previous_hash != self.get_last_hash()
+        {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        match Block::validate_timestamp_at(
+            header.timestamp,
+            self.median_time_past(),
+            self.environment.now(),
+        ) {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidTimestamp),
+        }
+    }
+
+    /// Append a full block received from another node.
+    ///
+    /// The header is validated as in `add_header`, the body must match its Merkle root, its
+    /// block production transactions must be valid, see `Block::validate_block_production`, and
+    /// its transactions must use the hash versions active at its height.
+    /// The state snapshot is kept only if it matches the state root of the header.
+    ///
+    /// # Arguments
+    /// - `block`: The next block.
+    ///
+    /// # Returns
+    /// An error if the header or the body is invalid, in which case the chain is left unchanged.
+    pub fn import_block(&mut self, block: Block) -> Result<(), BlockchainError> {
+        if !block.pruned {
+            self.consensus.block_limits.check(&block)?;
+        }
+
+        self.check_header(&block.header)?;
+
+        let difficulty = block.header.difficulty;
+        let height = self.chain.len();
+        self.chain.push(Block::from_header(block.header, height));
+
+        if let Err(error) = self
+            .restore_body(height, block.transactions)
+            .and_then(|_| self.chain[height].validate_block_production())
+            .and_then(|_| self.check_hash_versions(&self.chain[height], height))
+        {
+            self.chain.pop();
+            return Err(error);
+        }
+
+        if let Some(last) = self.chain.last_mut() {
+            if state::accounts_root(&block.state) == last.header.state_root {
+                last.state = block.state;
+            }
+        }
+
+        // Transactions mined by the imported block are no longer pending
+        if let Some(last) = self.chain.last() {
+            self.pending
+                .retain(|queued| !last.transactions.contains_key(&queued.hash));
+        }
+
+        self.follow_difficulty(difficulty);
+        self.validation = ComponentStatus::Unknown;
+        self.notify_block();
+
+        Ok(())
+    }
+
+    /// Write the blocks of the chain to a block archive.
+    ///
+    /// # Arguments
+    /// - `writer`: The destination of the archive, e.g. a buffered file.
+    ///
+    /// # Returns
+    /// The destination, or `StorageError` if a block cannot be written.
+    pub fn export_archive<W: Write>(&self, writer: W) -> Result<W, BlockchainError> {
+        let flags = match self.chain.iter().any(|block| block.pruned) {
+            true => archive::FLAG_PRUNED,
+            false => 0,
+        };
+
+        let mut archive = ArchiveWriter::new(writer, flags)?;
+        for block in &self.chain {
+            archive.write_block(block)?;
+        }
+
+        archive.finish()
+    }
+
+    /// Append the blocks of a block archive that the chain does not have yet.
+    ///
+    /// The blocks the chain already has must match the archive, and the following ones are
+    /// imported as with `import_block`.
+    ///
+    /// # Arguments
+    /// - `reader`: The source of the archive.
+    ///
+    /// # Returns
+    /// The number of imported blocks, the archive error, `InvalidHeader` if the archive belongs
+    /// to another chain, or the error of `import_block`; the blocks imported before an error
+    /// are kept.
+    pub fn import_archive<R: Read>(&mut self, reader: R) -> Result<usize, BlockchainError> {
+        let mut imported = 0;
+
+        for (height, block) in ArchiveReader::new(reader)?.enumerate() {
+            let block = block?;
+
+            match self.chain.get(height) {
+                Some(local) if local.hash == block.hash => {}
+                Some(_) => return Err(BlockchainError::InvalidHeader),
+                None => {
+                    self.import_block(block)?;
+                    imported += 1;
+                }
+            }
+        }
+
+        Ok(imported)
+    }
+
+    /// Validate the whole chain.
+    ///
+    /// Every block after the genesis block is first validated on its own, in parallel when the
+    /// `parallel` feature is enabled: cached hash, proof-of-work, transaction hashes and Merkle root. The blocks
+    /// are then checked sequentially to link to the previous block and to have a timestamp greater
+    /// than the median time past of the preceding blocks, and blocks with a body must be within the
+    /// block limits and hold transactions of the hash versions active at their height.
+    ///
+    /// # Returns
+    /// An error describing an invalid block, if any.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(blocks = self.chain.len()), err(level = "warn"))
+    )]
+    pub fn validate_chain(&self) -> Result<(), BlockchainError> {
+        let blocks = self.chain.get(1..).unwrap_or_default();
+
+        #[cfg(feature = "parallel")]
+        blocks.par_iter().try_for_each(Block::validate)?;
+
+        #[cfg(not(feature = "parallel"))]
+        blocks.iter().try_for_each(Block::validate)?;
+
+        for height in 1..self.chain.len() {
+            let block = &self.chain[height];
+
+            if !block.pruned {
+                self.consensus.block_limits.check(block)?;
+                self.check_hash_versions(block, height)?;
+            }
+
+            if Some(block.header.previous_hash) != self.get_block_hash(height - 1) {
+                return Err(BlockchainError::InvalidHeader);
+            }
+
+            let timestamps: Vec<i64> = self.chain[height.saturating_sub(MEDIAN_TIME_SPAN)..height]
+                .iter()
+                .map(|block| block.header.timestamp)
+                .collect();
+            if !Block::validate_timestamp_at(
+                block.header.timestamp,
+                Block::median_time_past(&timestamps),
+                self.environment.now(),
+            ) {
+                return Err(BlockchainError::InvalidTimestamp);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the bodies of all blocks except the most recent ones.
+    ///
+    /// # Arguments
+    /// - `keep`: The number of most recent blocks whose bodies are kept.
+    ///
+    /// # Returns
+    /// The number of blocks that were pruned.
+    pub fn prune_bodies(&mut self, keep: usize) -> usize {
+        let end = self.chain.len().saturating_sub(keep);
+
+        self.chain[..end]
+            .iter_mut()
+            .filter(|block| !block.pruned)
+            .map(|block| block.prune())
+            .count()
+    }
+
+    /// Restore the body of a pruned block.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block.
+    /// - `transactions`: The transactions of the block.
+    ///
+    /// # Returns
+    /// An error if the block does not exist or the transactions do not match its Merkle root.
+    pub fn restore_body(
+        &mut self,
+        height: usize,
+        transactions: ChainTransactions,
+    ) -> Result<(), BlockchainError> {
+        let block = self
+            .chain
+            .get_mut(height)
+            .ok_or(BlockchainError::BlockNotFound)?;
+
+        if !block.restore(transactions) {
+            return Err(BlockchainError::InvalidBlockBody);
+        }
+
+        self.validation = ComponentStatus::Unknown;
+
+        Ok(())
+    }
+
+    /// Update the mining difficulty of the blockchain.
+    ///
+    /// # Arguments
+    /// - `difficulty`: The new mining difficulty level.
+    pub fn update_difficulty(&mut self, difficulty: f64) {
+        let previous = mem::replace(&mut self.difficulty, difficulty);
+        self.record_change(ProposalChange::Difficulty(difficulty), previous);
+    }
+
+    /// Update the block reward.
+    ///
+    /// # Arguments
+    /// - `reward`: The new block reward value.
+    pub fn update_reward(&mut self, reward: f64) {
+        let previous = mem::replace(&mut self.reward, reward);
+        self.record_change(ProposalChange::Reward(reward), previous);
+    }
+
+    /// Update the transaction fee.
+    ///
+    /// # Arguments
+    /// - `fee`: The new transaction fee value.
+    pub fn update_fee(&mut self, fee: f64) {
+        let previous = mem::replace(&mut self.fee, fee);
+        self.record_change(ProposalChange::Fee(fee), previous);
+    }
+
+    /// Get the difficulty expected of the next block, retargeted at the end of a retarget
+    /// interval.
+    ///
+    /// Blocks are mined at this difficulty, and headers received from other nodes must declare
+    /// it, so a peer cannot lower the proof-of-work of its blocks.
+    ///
+    /// # Returns
+    /// The difficulty of the next block.
+    pub fn expected_difficulty(&self) -> f64 {
+        let Some(target) = self.consensus.difficulty_target else {
+            return self.difficulty;
+        };
+
+        let height = self.chain.len();
+        if height <= target.interval || height % target.interval != 0 {
+            return self.difficulty;
+        }
+
+        let elapsed = self.chain[height - 1].header.timestamp
+            - self.chain[height - 1 - target.interval].header.timestamp;
+
+        target.retarget(self.difficulty, elapsed)
+    }
+
+    /// Retarget the difficulty before mining a block at the end of a retarget interval.
+    fn retarget_difficulty(&mut self) {
+        self.follow_difficulty(self.expected_difficulty());
+    }
+
+    /// Set the difficulty reached by the chain, notifying the subscribers if it was retargeted.
+    ///
+    /// # Arguments
+    /// - `difficulty`: The difficulty of the last block.
+    fn follow_difficulty(&mut self, difficulty: f64) {
+        if difficulty != self.difficulty {
+            self.difficulty = difficulty;
+            self.notify(ChainEvent::ParameterChanged(ProposalChange::Difficulty(
+                difficulty,
+            )));
+        }
+    }
+
+    /// Record a parameter change in the audit log and notify the subscribers.
+    ///
+    /// # Arguments
+    /// - `change`: The new value of the parameter.
+    /// - `previous`: The value of the parameter before the change.
+    fn record_change(&mut self, change: ProposalChange, previous: f64) {
+        self.audit_log.append_at(
+            AuditAction::ParameterChanged { change, previous },
+            self.environment.now(),
+        );
+        self.notify(ChainEvent::ParameterChanged(change));
+    }
+
+    /// Summarize the health of the chain, e.g. to back liveness and readiness probes.
+    ///
+    /// The validation status is the one cached by the last `revalidate`, run when a chain is
+    /// loaded, so the summary is cheap enough for frequent probes; it is `Unknown` until the
+    /// first validation, and again once blocks are imported, restored or removed, e.g. by
+    /// `import_block`, `restore_body`, `truncate` or `reorganize`. The mempool depth and storage
+    /// status are reported by the application with `Health::with_mempool` and
+    /// `Health::with_storage`.
+    ///
+    /// # Returns
+    /// The height, tip age and validation status of the chain.
+    pub fn health(&self) -> Health {
+        let tip = self.chain.last().map(|block| block.header.timestamp);
+
+        Health {
+            height: self.chain.len(),
+            tip_age: tip.map_or(0, |timestamp| self.environment.now() - timestamp),
+            mempool_depth: None,
+            storage: ComponentStatus::Unknown,
+            validation: self.validation.clone(),
+        }
+    }
+
+    /// Validate the whole chain as with `validate_chain` and cache the outcome reported by
+    /// `health`, e.g. periodically from a background task rather than on every probe.
+    ///
+    /// # Returns
+    /// The error of `validate_chain`, if any.
+    pub fn revalidate(&mut self) -> Result<(), BlockchainError> {
+        let result = self.validate_chain();
+        self.validation = result.clone().into();
+
+        result
+    }
+
+    /// Get the log of administrative mutations: parameter updates and rollbacks.
+    ///
+    /// # Returns
+    /// The append-only, hash-chained audit log.
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit_log
+    }
+
+    /// Record rejected transfer attempts with their reason, keeping the most recent ones, so
+    /// that failed submissions can be diagnosed per wallet. Previously recorded attempts are
+    /// discarded.
+    ///
+    /// # Arguments
+    /// - `capacity`: The maximum number of recorded attempts.
+    ///
+    /// # Returns
+    /// `InvalidConfiguration` if the capacity is zero.
+    pub fn enable_dead_letters(&mut self, capacity: usize) -> Result<(), BlockchainError> {
+        self.dead_letters = Some(DeadLetterQueue::new(capacity)?);
+
+        Ok(())
+    }
+
+    /// Stop recording rejected transfer attempts and discard the recorded ones.
+    pub fn disable_dead_letters(&mut self) {
+        self.dead_letters = None;
+    }
+
+    /// Get the rejected transfer attempts sent or received by a wallet.
+    ///
+    /// # Arguments
+    /// - `address`: The address of the wallet.
+    ///
+    /// # Returns
+    /// The recorded attempts involving the wallet, oldest first, or none if recording is
+    /// disabled.
+    pub fn dead_letters(&self, address: &Address) -> Vec<&DeadLetter> {
+        self.dead_letters
+            .as_ref()
+            .map_or_else(Vec::new, |dead_letters| dead_letters.for_wallet(address))
+    }
+
+    /// Issue a new admin key granting administrative permissions.
+    ///
+    /// Only the hash of the key is kept, so the key must be stored by the caller.
+    ///
+    /// # Arguments
+    /// - `permissions`: The permissions granted by the key.
+    ///
+    /// # Returns
+    /// The new admin key.
+    pub fn issue_admin_key(&mut self, permissions: &[Permission]) -> String {
+        let key = self.environment.uuid().simple().to_string();
+        self.access.grant(&key, permissions);
+
+        key
+    }
+
+    /// Revoke an admin key.
+    ///
+    /// # Arguments
+    /// - `key`: The admin key.
+    ///
+    /// # Returns
+    /// `true` if the key was known, `false` otherwise.
+    pub fn revoke_admin_key(&mut self, key: &str) -> bool {
+        self.access.revoke(key)
+    }
+
+    /// Get the administrative operations allowed by an admin key.
+    ///
+    /// # Arguments
+    /// - `key`: The admin key.
+    ///
+    /// # Returns
+    /// The operations, checked against the permissions of the key, or `Unauthenticated` if the
+    /// key is unknown.
+    pub fn admin(&mut self, key: &str) -> Result<Admin<'_>, BlockchainError> {
+        let permissions = self.access.permissions(key)?.to_owned();
+
+        Ok(Admin::new(self, permissions))
+    }
+
+    /// Issue a new API key delegating access to a wallet, e.g. to an application acting for its
+    /// owner with bounded authority.
+    ///
+    /// Only the hash of the key is kept, so the key must be stored by the caller.
+    ///
+    /// # Arguments
+    /// - `wallet`: The address of the wallet.
+    /// - `scope`: The authority delegated by the key.
+    ///
+    /// # Returns
+    /// The new wallet API key, or `WalletNotFound`.
+    pub fn issue_wallet_key(
+        &mut self,
+        wallet: &Address,
+        scope: KeyScope,
+    ) -> Result<String, BlockchainError> {
+        if !self.wallets.contains_key(wallet) {
+            return Err(BlockchainError::WalletNotFound {
+                address: wallet.to_owned(),
+            });
+        }
+
+        let key = self.environment.uuid().simple().to_string();
+        self.access.issue_wallet_key(&key, wallet, scope);
+
+        Ok(key)
+    }
+
+    /// Revoke a wallet API key.
+    ///
+    /// # Arguments
+    /// - `key`: The wallet API key.
+    ///
+    /// # Returns
+    /// `true` if the key was known, `false` otherwise.
+    pub fn revoke_wallet_key(&mut self, key: &str) -> bool {
+        self.access.revoke_wallet_key(key)
+    }
+
+    /// Get the wallet and scope of a wallet API key.
+    ///
+    /// # Arguments
+    /// - `key`: The wallet API key.
+    ///
+    /// # Returns
+    /// The key, or `Unauthenticated` if the key is unknown.
+    pub fn wallet_key(&self, key: &str) -> Result<&WalletKey, BlockchainError> {
+        self.access.wallet_key(key)
+    }
+
+    /// Send from the wallet of an API key, counting the amount against the limit of the key.
+    ///
+    /// The transfer is validated and applied as with `add_transaction`.
+    ///
+    /// # Arguments
+    /// - `key`: The wallet API key.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount to send.
+    ///
+    /// # Returns
+    /// The hash of the transaction, `Unauthenticated` if the key is unknown, `Unauthorized` if
+    /// it is read-only or the amount exceeds its remaining limit, or the error of the transfer.
+    pub fn send_with_key(
+        &mut self,
+        key: &str,
+        to: &Address,
+        amount: f64,
+    ) -> Result<Hash, BlockchainError> {
+        let wallet_key = self.access.wallet_key(key)?;
+        if !amount.is_finite() || !wallet_key.can_send(amount) {
+            return Err(BlockchainError::Unauthorized);
+        }
+
+        let from = wallet_key.wallet.to_owned();
+        let hash = self.transfer(&from, to, amount, slice::from_ref(&from))?;
+        self.access.record_spend(key, amount);
+
+        Ok(hash)
+    }
+
+    /// Set the default recipient of the block rewards.
+    ///
+    /// # Arguments
+    /// - `beneficiary`: The address rewarded by `generate_new_block`, or `None` for the genesis
+    ///   address.
+    ///
+    /// # Returns
+    /// `InvalidAddress` if the address is the root or the burn address.
+    pub fn set_beneficiary(&mut self, beneficiary: Option<Address>) -> Result<(), BlockchainError> {
+        if beneficiary
+            .as_ref()
+            .is_some_and(|address| address.is_root() || address.is_burn())
+        {
+            return Err(BlockchainError::InvalidAddress);
+        }
+
+        self.beneficiary = beneficiary;
+
+        Ok(())
+    }
+
+    /// Get the default recipient of the block rewards.
+    ///
+    /// # Returns
+    /// The address set with `set_beneficiary`, or the genesis address.
+    pub fn beneficiary(&self) -> &Address {
+        self.beneficiary.as_ref().unwrap_or(&self.address)
+    }
+
+    /// Generate a new block and append it to the blockchain, rewarding the `beneficiary`.
+    ///
+    /// # Returns
+    /// `true` if a new block is successfully generated and added to the blockchain.
+    pub fn generate_new_block(&mut self) -> bool {
+        let miner = self.beneficiary().to_owned();
+
+        self.generate_new_block_for(&miner)
+    }
+
+    /// Generate a new block rewarding a miner and append it to the blockchain.
+    ///
+    /// The block holds the reward transaction followed by the pending transactions, in the order of
+    /// the block assembler or else of the transaction priority, up to the block limits; the
+    /// transactions that do not fit or are left out stay pending for the next block. The reward
+    /// is credited to the wallet of the miner, if any.
+    ///
+    /// # Arguments
+    /// - `miner`: The address receiving the block reward.
+    ///
+    /// # Returns
+    /// `true` if a new block is successfully generated and added to the blockchain, `false` if
+    /// the miner is the root or the burn address.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "generate_new_block",
+            skip_all,
+            fields(
+                height = self.chain.len(),
+                difficulty = self.difficulty,
+                hash = tracing::field::Empty,
+            ),
+        )
+    )]
+    pub fn generate_new_block_for(&mut self, miner: &Address) -> bool {
+        if miner.is_root() || miner.is_burn() {
+            return false;
+        }
+
+        // Apply the governance proposals taking effect at this block
+        self.apply_proposals(self.chain.len());
+
+        // Hold the target block time at the end of each retarget interval
+        self.retarget_difficulty();
+
+        // Close the invoices that were not paid in time
+        self.expire_invoices();
+
+        // Create a new block
+        let mut block = Block::new(self.get_last_hash(), self.difficulty);
+        block.header.timestamp = self.environment.now();
+        block.height = self.chain.len();
+
+        // Keep the timestamp strictly greater than the median time past
+        if let Some(median) = self.median_time_past() {
+            block.header.timestamp = block.header.timestamp.max(median + 1);
+        }
+
+        // Create a reward transaction following the emission schedule
+        let reward = self.block_reward(block.height);
+        let transaction = self
+            .new_transaction(&Address::root(), miner, self.fee, reward, NATIVE_ASSET)
+            .with_kind(TransactionKind::Reward)
+            .with_size();
+
+        // Credit the miner's wallet, if any, keeping the reward in its history
+        if let Some(wallet) = self.wallets.get_mut(miner) {
+            wallet.balance += reward;
+            wallet.transaction_hashes.push(transaction.hash);
+            self.transactions
+                .insert(transaction.hash, transaction.to_owned());
+        }
+
+        // Add the reward transaction to the block
+        let mut size = Block::transaction_size(&transaction);
+        block.transactions.insert(transaction.hash, transaction);
+
+        // Reserve room for the treasury and burned shares of the fees, whose encodings have a
+        // fixed size
+        let treasury = self.consensus.treasury.clone();
+        let fee_burn = self.consensus.fee_burn;
+        let reserved: Vec<(Address, TransactionKind)> = treasury
+            .iter()
+            .map(|treasury| (treasury.address.to_owned(), TransactionKind::Treasury))
+            .chain((fee_burn > 0.0).then(|| (Address::burn(), TransactionKind::FeeBurn)))
+            .collect();
+        let reserved_count = reserved.len();
+        size += reserved
+            .into_iter()
+            .map(|(to, kind)| {
+                Block::transaction_size(
+                    &Transaction::from_parts(
+                        Uuid::nil(),
+                        0,
+                        Address::root(),
+                        to,
+                        0.0,
+                        0.0,
+                        NATIVE_ASSET,
+                    )
+                    .with_hash_version(
+                        self.consensus.hash_version(block.height),
+                        &self.consensus.chain_id,
+                    )
+                    .with_kind(kind),
+                )
+            })
+            .sum::<usize>();
+
+        // Add the pending transactions by priority, leaving the ones over the limits pending
+        let order = match self.assembler.0.clone() {
+            Some(assembler) => assembler.assemble(self, block.height),
+            None => self.consensus.priority.assemble(self, block.height),
+        };
+        let mut included = vec![false; self.pending.len()];
+
+        for index in order {
+            if included.get(index) != Some(&false) {
+                continue;
+            }
+
+            let hash = self.pending[index].hash;
+            let Some(transaction) = self.transactions.get(&hash) else {
+                included[index] = true;
+                continue;
+            };
+
+            let next = size + Block::transaction_size(transaction);
+            if !self
+                .consensus
+                .block_limits
+                .allows(block.transactions.len() + reserved_count + 1, next)
+            {
+                continue;
+            }
+
+            size = next;
+            included[index] = true;
+            block.transactions.insert(hash, transaction.to_owned());
+        }
+
+        let mut included = included.into_iter();
+        self.pending
+            .retain(|_| !included.next().unwrap_or_default());
+
+        // Route the treasury share of the fees
+        let fees = block.fees();
+        if let Some(treasury) = treasury {
+            let amount = fees * treasury.share;
+
+            if amount > 0.0 {
+                let transaction = self
+                    .new_transaction(
+                        &Address::root(),
+                        &treasury.address,
+                        0.0,
+                        amount,
+                        NATIVE_ASSET,
+                    )
+                    .with_kind(TransactionKind::Treasury)
+                    .with_size();
+
+                if let Some(wallet) = self.wallets.get_mut(&treasury.address) {
+                    wallet.balance += amount;
+                    wallet.transaction_hashes.push(transaction.hash);
+                }
+
+                self.transactions
+                    .insert(transaction.hash, transaction.to_owned());
+                block.transactions.insert(transaction.hash, transaction);
+            }
+        }
+
+        // Destroy the burned share of the fees
+        let amount = fees * fee_burn;
+        if amount > 0.0 {
+            let transaction = self
+                .new_transaction(
+                    &Address::root(),
+                    &Address::burn(),
+                    0.0,
+                    amount,
+                    NATIVE_ASSET,
+                )
+                .with_kind(TransactionKind::FeeBurn)
+                .with_size();
+
+            self.credit_burn(NATIVE_ASSET, amount, transaction.hash);
+            self.transactions
+                .insert(transaction.hash, transaction.to_owned());
+            block.transactions.insert(transaction.hash, transaction);
+        }
+
+        // Update the block count, the Merkle root hash, the bloom filter, the state root and the
+        // contract root
+        block.header.merkle = Chain::get_merkle(&block.transactions);
+        block.header.bloom = Bloom::of_transactions(block.transactions.values());
+        block.state = state::accounts(&self.wallets);
+        block.header.state_root = state::accounts_root(&block.state);
+        block.header.contract_root = self.contract_root();
+
+        // Perform the proof-of-work process and cache the block hash
+        block.mine();
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("hash", tracing::field::display(block.hash));
+            tracing::info!(nonce = block.header.nonce, "mined block");
+        }
+
+        // Add the block to the blockchain and notify the subscribers
+        self.chain.push(block);
+        self.notify_block();
+
+        true
+    }
+
+    /// Calculate the state trie root of the current wallet state.
+    ///
+    /// # Returns
+    /// The state root, or the zero hash if there are no wallets.
+    pub fn state_root(&self) -> Hash {
+        state::root(&self.wallets)
+    }
+
+    /// Build a proof of a wallet's state at a given block height.
+    ///
+    /// # Arguments
+    /// - `address`: The unique wallet address.
+    /// - `height`: The height of the block whose state root the proof is checked against.
+    ///
+    /// # Returns
+    /// The account proof, or an error if the block or the wallet state does not exist.
+    pub fn prove_balance(
+        &self,
+        address: &Address,
+        height: usize,
+    ) -> Result<AccountProof, BlockchainError> {
+        let block = self
+            .chain
+            .get(height)
+            .ok_or(BlockchainError::BlockNotFound)?;
+
+        state::prove(&block.state, address, height).ok_or_else(|| BlockchainError::WalletNotFound {
+            address: address.to_owned(),
+        })
+    }
+
+    /// Build the state trie of the wallets at a given block height.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    /// The state trie whose root is the state root of the block, or `BlockNotFound`.
+    pub fn state_trie(&self, height: usize) -> Result<StateTrie, BlockchainError> {
+        let block = self
+            .chain
+            .get(height)
+            .ok_or(BlockchainError::BlockNotFound)?;
+
+        Ok(StateTrie::from_accounts(&block.state))
+    }
+
+    /// Find the wallets whose state changed between two block heights.
+    ///
+    /// # Arguments
+    /// - `from`: The height of the first block.
+    /// - `to`: The height of the second block.
+    ///
+    /// # Returns
+    /// The addresses of the wallets created or changed in between, sorted, or `BlockNotFound`.
+    pub fn state_diff(&self, from: usize, to: usize) -> Result<Vec<Address>, BlockchainError> {
+        Ok(self.state_trie(from)?.diff(&self.state_trie(to)?))
+    }
+
+    /// Calculate the Merkle root hash for a list of transactions.
+    ///
+    /// Leaves are hashed and combined level by level, in parallel for large blocks when the
+    /// `parallel` feature is enabled.
+    ///
+    /// # Arguments
+    /// - `transactions`: The transactions for which the Merkle root hash is calculated.
+    ///
+    /// # Returns
+    /// The Merkle root hash.
+    pub fn get_merkle(transactions: &ChainTransactions) -> Hash {
+        let transactions: Vec<_> = transactions.values().collect();
+
+        header::merkle_root(&transactions)
+    }
+
+    /// Build a Merkle inclusion proof for a transaction mined in a block.
+    ///
+    /// # Arguments
+    /// - `hash`: The hash of the transaction to prove.
+    ///
+    /// # Returns
+    /// The height of the block containing the transaction and the proof against its Merkle root.
+    pub fn get_merkle_proof(&self, hash: &Hash) -> Result<(usize, MerkleProof), BlockchainError> {
+        self.chain
+            .iter()
+            .enumerate()
+            .find_map(|(height, block)| block.merkle_proof(hash).map(|proof| (height, proof)))
+            .ok_or(BlockchainError::TransactionNotFound { hash: *hash })
+    }
+
+    /// Create a transaction identified and timestamped by the environment of the chain.
+    ///
+    /// # Arguments
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `fee`: The transaction fee.
+    /// - `amount`: The amount of the transaction.
+    /// - `asset`: The transferred asset.
+    ///
+    /// # Returns
+    /// A new transaction.
+    fn new_transaction(
+        &mut self,
+        from: &Address,
+        to: &Address,
+        fee: f64,
+        amount: f64,
+        asset: &str,
+    ) -> Transaction {
+        let (id, timestamp) = (self.environment.uuid(), self.environment.now());
+
+        let transaction = Transaction::from_parts(
+            id,
+            timestamp,
+            from.to_owned(),
+            to.to_owned(),
+            fee,
+            amount,
+            asset,
+        );
+
+        // Hash with the version active at the next block
+        match self.consensus.hash_version(self.chain.len()) {
+            HashVersion::V1 => transaction,
+            version => transaction.with_hash_version(version, &self.consensus.chain_id),
+        }
+    }
+
+    /// Calculate the hash of a serializable item.
+    ///
+    /// The item is streamed into the hasher in its canonical binary encoding, see `encoding::Encoder`.
+    ///
+    /// # Arguments
+    /// - `item`: A serializable item to be hashed.
+    ///
+    /// # Returns
+    /// The hash of the item.
+    pub fn hash<T: serde::Serialize>(item: &T) -> Hash {
+        Hash::of(item)
+    }
+
+    /// Generates a random alphanumeric string of a specified length.
+    ///
+    /// # Arguments
+    /// - `length`: The length of the generated string.
+    ///
+    /// # Returns
+    /// A `String` containing the generated alphanumeric string.
+    pub fn generate_address(length: usize) -> String {
+        Chain::generate_address_from(&mut rand::thread_rng(), length)
+    }
+
+    /// Generates a random alphanumeric string of a specified length from a random number generator.
+    ///
+    /// # Arguments
+    /// - `rng`: The random number generator.
+    /// - `length`: The length of the generated string.
+    ///
+    /// # Returns
+    /// A `String` containing the generated alphanumeric string.
+    pub fn generate_address_from<R: Rng + ?Sized>(rng: &mut R, length: usize) -> String {
         let address: String = iter::repeat(())
             .map(|()| rng.sample(rand::distributions::Alphanumeric) as char)
             .take(length)
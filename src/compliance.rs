@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Address;
+
+/// Record of a denied address.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeniedAddress {
+    /// Jurisdiction of the sanction or regulation the address is denied under, e.g. `"US-OFAC"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jurisdiction: Option<String>,
+
+    /// Time the address was denied.
+    pub timestamp: i64,
+}
+
+/// Allow and deny lists screening the parties of transfers.
+///
+/// Transfers touching a denied address are rejected. When the allow list is enabled, transfers
+/// are also rejected unless both parties are allowed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Compliance {
+    /// Denied addresses.
+    #[serde(default)]
+    pub denied: BTreeMap<Address, DeniedAddress>,
+
+    /// Allowed addresses, or `None` if the allow list is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<BTreeSet<Address>>,
+}
+
+impl Compliance {
+    /// Check whether an address may take part in transfers.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the address is not denied and, with the allow list enabled, is allowed.
+    pub fn permits(&self, address: &Address) -> bool {
+        !self.denied.contains_key(address)
+            && self
+                .allowed
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(address))
+    }
+
+    /// Screen the parties of a transfer.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    ///
+    /// # Returns
+    ///
+    /// The first party that is not permitted, if any.
+    pub fn screen<'a>(&self, from: &'a Address, to: &'a Address) -> Option<&'a Address> {
+        [from, to]
+            .into_iter()
+            .find(|address| !self.permits(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen() {
+        let (from, to) = (Address::random(), Address::random());
+        let mut compliance = Compliance::default();
+
+        assert_eq!(compliance.screen(&from, &to), None);
+
+        compliance
+            .denied
+            .insert(to.clone(), DeniedAddress::default());
+        assert_eq!(compliance.screen(&from, &to), Some(&to));
+
+        compliance.denied.clear();
+        compliance.allowed = Some(BTreeSet::from([to.clone()]));
+        assert_eq!(compliance.screen(&from, &to), Some(&from));
+    }
+}
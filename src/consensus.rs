@@ -0,0 +1,553 @@
+use std::{cmp::Ordering, fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockLimits, BlockchainError, Chain, ChainTransactions, Hash, HashVersion};
+
+/// Transaction waiting to be included in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct QueuedTransaction {
+    /// Hash of the transaction.
+    pub hash: Hash,
+
+    /// Height of the first block the transaction could be included in.
+    pub since: usize,
+}
+
+impl QueuedTransaction {
+    /// Get the number of blocks the transaction has been waiting for.
+    ///
+    /// # Arguments
+    ///
+    /// - `height`: The height of the block being assembled.
+    ///
+    /// # Returns
+    ///
+    /// The number of blocks assembled without the transaction.
+    pub fn age(&self, height: usize) -> usize {
+        height.saturating_sub(self.since)
+    }
+}
+
+/// Order in which pending transactions are included in a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TransactionPriority {
+    /// Highest fee first, oldest first among equal fees.
+    #[default]
+    Fee,
+
+    /// Oldest first, regardless of the fees.
+    Fifo,
+
+    /// Transactions waiting for at least `max_wait` blocks first, oldest first, then the others
+    /// by fee, so low-fee transactions are not starved by a steady flow of higher fees.
+    AgeBoost {
+        /// Number of blocks after which a transaction is boosted.
+        max_wait: usize,
+    },
+}
+
+impl TransactionPriority {
+    /// Order pending transactions for inclusion in a block.
+    ///
+    /// # Arguments
+    ///
+    /// - `pending`: The pending transactions, oldest first.
+    /// - `transactions`: The transactions of the chain, by hash.
+    /// - `height`: The height of the block being assembled.
+    ///
+    /// # Returns
+    ///
+    /// The indexes of the pending transactions, highest priority first.
+    pub fn order(
+        &self,
+        pending: &[QueuedTransaction],
+        transactions: &ChainTransactions,
+        height: usize,
+    ) -> Vec<usize> {
+        let fee = |index: usize| {
+            transactions
+                .get(&pending[index].hash)
+                .map_or(0.0, |tx| tx.fee)
+        };
+        let by_fee = |a: usize, b: usize| fee(b).total_cmp(&fee(a)).then(a.cmp(&b));
+
+        let mut order: Vec<usize> = (0..pending.len()).collect();
+
+        match self {
+            TransactionPriority::Fee => order.sort_by(|&a, &b| by_fee(a, b)),
+            TransactionPriority::Fifo => {}
+            TransactionPriority::AgeBoost { max_wait } => {
+                let boosted = |index: usize| pending[index].age(height) >= *max_wait;
+
+                order.sort_by(|&a, &b| match (boosted(a), boosted(b)) {
+                    (true, true) => a.cmp(&b),
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                    (false, false) => by_fee(a, b),
+                })
+            }
+        }
+
+        order
+    }
+}
+
+/// Custom policy selecting the pending transactions included in new blocks, e.g. to reserve
+/// room for some senders or to hold back transactions under business rules.
+///
+/// The chain still enforces the block limits: transactions that do not fit, and pending
+/// transactions left out by the assembler, stay pending for the next block.
+pub trait BlockAssembler: Send + Sync {
+    /// Select the pending transactions of the next block.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain, whose `pending` transactions are oldest first.
+    /// - `height`: The height of the block being assembled.
+    ///
+    /// # Returns
+    ///
+    /// The indexes in `chain.pending` of the transactions to include, highest priority first.
+    /// Unknown and repeated indexes are ignored.
+    fn assemble(&self, chain: &Chain, height: usize) -> Vec<usize>;
+}
+
+impl BlockAssembler for TransactionPriority {
+    fn assemble(&self, chain: &Chain, height: usize) -> Vec<usize> {
+        self.order(&chain.pending, &chain.transactions, height)
+    }
+}
+
+/// Block assembler of a chain, replacing its transaction priority when set.
+///
+/// The assembler is not persisted and is shared with cloned chains.
+#[derive(Clone, Default)]
+pub(crate) struct Assembler(pub(crate) Option<Arc<dyn BlockAssembler>>);
+
+impl fmt::Debug for Assembler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Assembler({})", self.0.is_some())
+    }
+}
+
+/// Curve followed by the block reward as the chain grows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EmissionCurve {
+    /// Every block pays the base reward.
+    #[default]
+    Constant,
+
+    /// The reward is halved every `interval` blocks.
+    Halving {
+        /// Number of blocks between two halvings.
+        interval: usize,
+    },
+
+    /// The reward decreases by `rate` of itself at every block.
+    ExponentialDecay {
+        /// Share of the reward removed at every block, from 0 inclusive to 1 exclusive.
+        rate: f64,
+    },
+}
+
+impl EmissionCurve {
+    /// Calculate the reward of a block, before any supply cap.
+    ///
+    /// # Arguments
+    ///
+    /// - `base`: The reward of the genesis block.
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    ///
+    /// The reward of the block.
+    pub fn reward(&self, base: f64, height: usize) -> f64 {
+        match *self {
+            EmissionCurve::Constant => base,
+            EmissionCurve::Halving { interval } => base * halvings(height / interval.max(1)),
+            EmissionCurve::ExponentialDecay { rate } => base * (1.0 - rate).powf(height as f64),
+        }
+    }
+
+    /// Calculate the total reward of the blocks up to a height, before any supply cap.
+    ///
+    /// # Arguments
+    ///
+    /// - `base`: The reward of the genesis block.
+    /// - `height`: The height of the last block.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the rewards of the blocks from the genesis block to `height` inclusive.
+    pub fn supply(&self, base: f64, height: usize) -> f64 {
+        let blocks = height as f64 + 1.0;
+
+        match *self {
+            EmissionCurve::Constant => base * blocks,
+            EmissionCurve::Halving { interval } => {
+                let interval = interval.max(1);
+                let (eras, rest) = ((height + 1) / interval, (height + 1) % interval);
+
+                // Each full era pays half the previous one, summing to twice the first era
+                base * interval as f64 * 2.0 * (1.0 - halvings(eras))
+                    + base * rest as f64 * halvings(eras)
+            }
+            EmissionCurve::ExponentialDecay { rate } if rate > 0.0 => {
+                base * (1.0 - (1.0 - rate).powf(blocks)) / rate
+            }
+            EmissionCurve::ExponentialDecay { .. } => base * blocks,
+        }
+    }
+}
+
+/// Reward multiplier after a number of halvings.
+fn halvings(count: usize) -> f64 {
+    0.5f64.powi(i32::try_from(count).unwrap_or(i32::MAX))
+}
+
+/// Emission schedule of the block reward, defined at genesis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EmissionSchedule {
+    /// Curve followed by the block reward.
+    #[serde(default)]
+    pub curve: EmissionCurve,
+
+    /// Maximum total reward ever paid, or `None` if the supply is not capped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_supply: Option<f64>,
+}
+
+impl EmissionSchedule {
+    /// Check that the parameters of the schedule are usable.
+    ///
+    /// # Returns
+    ///
+    /// `InvalidConfiguration` if the halving interval is zero, the decay rate is outside
+    /// `[0, 1)` or the supply cap is negative.
+    pub fn validate(&self) -> Result<(), BlockchainError> {
+        let curve = match self.curve {
+            EmissionCurve::Constant => true,
+            EmissionCurve::Halving { interval } => interval > 0,
+            EmissionCurve::ExponentialDecay { rate } => (0.0..1.0).contains(&rate),
+        };
+
+        match curve && self.max_supply.is_none_or(|max| max >= 0.0) {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidConfiguration),
+        }
+    }
+
+    /// Calculate the reward of a block.
+    ///
+    /// # Arguments
+    ///
+    /// - `base`: The reward of the genesis block.
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    ///
+    /// The reward of the curve, reduced so the total never exceeds the supply cap.
+    pub fn reward(&self, base: f64, height: usize) -> f64 {
+        let reward = self.curve.reward(base, height);
+
+        match (self.max_supply, height.checked_sub(1)) {
+            (Some(max), Some(previous)) => {
+                reward.min((max - self.curve.supply(base, previous)).max(0.0))
+            }
+            (Some(max), None) => reward.min(max),
+            (None, _) => reward,
+        }
+    }
+
+    /// Calculate the total reward of the blocks up to a height.
+    ///
+    /// # Arguments
+    ///
+    /// - `base`: The reward of the genesis block.
+    /// - `height`: The height of the last block.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the rewards from the genesis block to `height` inclusive, within the cap.
+    pub fn supply(&self, base: f64, height: usize) -> f64 {
+        let supply = self.curve.supply(base, height);
+
+        self.max_supply.map_or(supply, |max| supply.min(max))
+    }
+}
+
+/// Difficulty retargeting holding a target time between blocks.
+///
+/// The difficulty is the number of leading zero bits of block hashes, so the expected mining
+/// work doubles with every unit: a retarget adds the base-2 logarithm of the ratio between the
+/// target and the observed block time.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DifficultyTarget {
+    /// Target number of seconds between two blocks.
+    pub block_time: i64,
+
+    /// Number of blocks between two retargets, over which the block time is observed.
+    pub interval: usize,
+
+    /// Maximum change of the difficulty at a single retarget.
+    pub max_adjustment: f64,
+}
+
+impl DifficultyTarget {
+    /// Check that the parameters of the target are usable.
+    ///
+    /// # Returns
+    ///
+    /// `InvalidConfiguration` if the block time or the interval is not positive, or the maximum
+    /// adjustment is negative.
+    pub fn validate(&self) -> Result<(), BlockchainError> {
+        match self.block_time > 0 && self.interval > 0 && self.max_adjustment >= 0.0 {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidConfiguration),
+        }
+    }
+
+    /// Calculate the difficulty after a retarget.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The current difficulty.
+    /// - `elapsed`: The number of seconds taken by the last `interval` blocks.
+    ///
+    /// # Returns
+    ///
+    /// The new difficulty, never negative.
+    pub fn retarget(&self, difficulty: f64, elapsed: i64) -> f64 {
+        let observed = elapsed.max(1) as f64 / self.interval.max(1) as f64;
+        let adjustment = (self.block_time as f64 / observed)
+            .log2()
+            .clamp(-self.max_adjustment, self.max_adjustment);
+
+        (difficulty + adjustment).max(0.0)
+    }
+}
+
+/// Treasury wallet receiving a share of the fees of every block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Treasury {
+    /// Address of the treasury wallet.
+    pub address: Address,
+
+    /// Share of the fees routed to the treasury, from 0 to 1.
+    pub share: f64,
+}
+
+/// Rule of the protocol changed by an upgrade.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ProtocolFeature {
+    /// New transactions are hashed with `HashVersion::V2`.
+    HashV2,
+
+    /// Transfers debit the amount plus the fee instead of the amount times the fee.
+    FlatFee,
+}
+
+/// Activation of a protocol feature from a block height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ProtocolUpgrade {
+    /// Activated feature.
+    pub feature: ProtocolFeature,
+
+    /// Height of the first block following the new rule.
+    pub height: usize,
+}
+
+/// Rules applied by a chain when assembling and validating blocks.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConsensusRules {
+    /// Limits on the number of transactions and the size of each block.
+    #[serde(default)]
+    pub block_limits: BlockLimits,
+
+    /// Order in which pending transactions are included in a block.
+    #[serde(default)]
+    pub priority: TransactionPriority,
+
+    /// Emission schedule of the block reward.
+    #[serde(default)]
+    pub emission: EmissionSchedule,
+
+    /// Treasury receiving a share of the fees, or `None` if fees are not routed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury: Option<Treasury>,
+
+    /// Share of the fees of every block destroyed, from 0 to 1 minus the treasury share.
+    #[serde(default)]
+    pub fee_burn: f64,
+
+    /// Target block time of the difficulty retargeting, or `None` to keep the difficulty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty_target: Option<DifficultyTarget>,
+
+    /// Fee charged for each byte of the canonical binary encoding of a transfer, in addition
+    /// to the transaction fee.
+    #[serde(default)]
+    pub fee_per_byte: f64,
+
+    /// Whether the genesis and treasury addresses may take part in user transactions.
+    #[serde(default)]
+    pub reserved_transfers: bool,
+
+    /// Identifier of the chain, committed by the transaction hashes from `HashVersion::V2`.
+    #[serde(default)]
+    pub chain_id: String,
+
+    /// Scheduled protocol upgrades, at most one per feature.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub upgrades: Vec<ProtocolUpgrade>,
+}
+
+impl ConsensusRules {
+    /// Check whether a protocol feature is active at a height.
+    ///
+    /// # Arguments
+    ///
+    /// - `feature`: The protocol feature.
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the feature is scheduled at or below the height.
+    pub fn is_active(&self, feature: ProtocolFeature, height: usize) -> bool {
+        self.upgrades
+            .iter()
+            .any(|upgrade| upgrade.feature == feature && upgrade.height <= height)
+    }
+
+    /// Get the transaction hash version active at a height.
+    ///
+    /// # Arguments
+    ///
+    /// - `height`: The height of the block.
+    ///
+    /// # Returns
+    ///
+    /// `HashVersion::V2` once `ProtocolFeature::HashV2` is active, `HashVersion::V1` before.
+    pub fn hash_version(&self, height: usize) -> HashVersion {
+        match self.is_active(ProtocolFeature::HashV2, height) {
+            true => HashVersion::V2,
+            false => HashVersion::V1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{Address, Transaction};
+
+    fn pending(fees: &[(f64, usize)]) -> (Vec<QueuedTransaction>, ChainTransactions) {
+        let mut transactions = HashMap::default();
+        let pending = fees
+            .iter()
+            .map(|&(fee, since)| {
+                let tx = Transaction::new(Address::random(), Address::random(), fee, 1.0);
+                let hash = tx.hash;
+                transactions.insert(hash, tx);
+
+                QueuedTransaction { hash, since }
+            })
+            .collect();
+
+        (pending, transactions)
+    }
+
+    #[test]
+    fn test_order_by_fee() {
+        let (pending, transactions) = pending(&[(0.1, 1), (0.5, 2), (0.1, 3)]);
+        let order = TransactionPriority::Fee.order(&pending, &transactions, 5);
+
+        assert_eq!(order, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_order_fifo() {
+        let (pending, transactions) = pending(&[(0.1, 1), (0.5, 2), (0.3, 3)]);
+        let order = TransactionPriority::Fifo.order(&pending, &transactions, 5);
+
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_emission_supply() {
+        let halving = EmissionCurve::Halving { interval: 2 };
+        let decay = EmissionCurve::ExponentialDecay { rate: 0.5 };
+
+        assert_eq!(halving.reward(8.0, 5), 2.0);
+        assert_eq!(halving.supply(8.0, 4), 8.0 + 8.0 + 4.0 + 4.0 + 2.0);
+        assert_eq!(decay.reward(8.0, 2), 2.0);
+        assert_eq!(decay.supply(8.0, 2), 8.0 + 4.0 + 2.0);
+
+        let capped = EmissionSchedule {
+            curve: EmissionCurve::Constant,
+            max_supply: Some(25.0),
+        };
+        assert_eq!(capped.reward(10.0, 2), 5.0);
+        assert_eq!(capped.reward(10.0, 3), 0.0);
+        assert_eq!(capped.supply(10.0, 9), 25.0);
+    }
+
+    #[test]
+    fn test_protocol_upgrades() {
+        let rules = ConsensusRules {
+            upgrades: vec![ProtocolUpgrade {
+                feature: ProtocolFeature::HashV2,
+                height: 5,
+            }],
+            ..ConsensusRules::default()
+        };
+
+        assert!(!rules.is_active(ProtocolFeature::HashV2, 4));
+        assert!(rules.is_active(ProtocolFeature::HashV2, 5));
+        assert!(!rules.is_active(ProtocolFeature::FlatFee, 5));
+        assert_eq!(rules.hash_version(4), HashVersion::V1);
+        assert_eq!(rules.hash_version(9), HashVersion::V2);
+    }
+
+    #[test]
+    fn test_retarget() {
+        let target = DifficultyTarget {
+            block_time: 60,
+            interval: 10,
+            max_adjustment: 2.0,
+        };
+
+        assert_eq!(target.retarget(4.0, 600), 4.0);
+        assert_eq!(target.retarget(4.0, 300), 5.0);
+        assert_eq!(target.retarget(4.0, 1200), 3.0);
+        assert_eq!(target.retarget(4.0, 0), 6.0);
+        assert_eq!(target.retarget(1.0, 60_000), 0.0);
+        assert!(DifficultyTarget {
+            interval: 0,
+            ..target
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_order_age_boost() {
+        let (pending, transactions) = pending(&[(0.5, 4), (0.1, 1), (0.2, 4), (0.1, 2)]);
+        let priority = TransactionPriority::AgeBoost { max_wait: 3 };
+
+        assert_eq!(pending[1].age(5), 4);
+        assert_eq!(priority.order(&pending, &transactions, 5), vec![1, 3, 0, 2]);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{merkle, Address, Chain, ChainContracts, Hash, PublicKeyScheme};
+
+/// Key-value store of a contract.
+pub type ContractStorage = BTreeMap<i64, i64>;
+
+/// Default amount of gas available to a contract call.
+pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
+
+/// WebAssembly smart contract deployed on the chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Contract {
+    /// Address of the contract.
+    pub address: Address,
+
+    /// Address of the wallet that deployed the contract.
+    pub owner: Address,
+
+    /// Hash of the WebAssembly module.
+    pub code_hash: Hash,
+
+    /// WebAssembly module of the contract.
+    pub code: Vec<u8>,
+
+    /// Persistent key-value store of the contract.
+    pub storage: ContractStorage,
+
+    /// Timestamp at which the contract was deployed.
+    pub timestamp: i64,
+}
+
+/// Result of a contract call.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContractCall {
+    /// Hash of the call transaction.
+    pub hash: Hash,
+
+    /// Value returned by the called function, if any.
+    pub result: Option<i64>,
+
+    /// Amount of gas consumed by the call.
+    pub gas_used: u64,
+}
+
+impl Contract {
+    /// Create a new contract with an empty store.
+    ///
+    /// # Arguments
+    ///
+    /// - `owner`: The address of the deploying wallet.
+    /// - `code`: The WebAssembly module of the contract.
+    /// - `deployment`: The hash of the deploy transaction.
+    /// - `timestamp`: The timestamp of the deploy transaction.
+    ///
+    /// # Returns
+    ///
+    /// A new contract at the address derived from its owner and deploy transaction.
+    pub fn new(owner: &Address, code: Vec<u8>, deployment: &Hash, timestamp: i64) -> Self {
+        Contract {
+            address: Contract::derive_address(owner, deployment),
+            owner: owner.to_owned(),
+            code_hash: Hash::digest(&code),
+            code,
+            storage: ContractStorage::new(),
+            timestamp,
+        }
+    }
+
+    /// Derive the address of a contract, so that replaying a chain deploys its contracts at the
+    /// same addresses.
+    ///
+    /// # Arguments
+    ///
+    /// - `owner`: The address of the deploying wallet.
+    /// - `deployment`: The hash of the deploy transaction.
+    ///
+    /// # Returns
+    ///
+    /// The address, the same for the same owner and transaction.
+    pub fn derive_address(owner: &Address, deployment: &Hash) -> Address {
+        let mut seed = owner.as_str().as_bytes().to_vec();
+        seed.extend_from_slice(deployment.as_bytes());
+
+        PublicKeyScheme::derive(&seed)
+    }
+
+    /// Hash the contract state as a leaf of the contract tree.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the contract address, code hash, and store.
+    pub fn state_hash(&self) -> Hash {
+        Chain::hash(&(&self.address, &self.code_hash, &self.storage))
+    }
+}
+
+/// Calculate the Merkle root of the state of all contracts.
+///
+/// # Arguments
+///
+/// - `contracts`: The contracts of a chain.
+///
+/// # Returns
+///
+/// The contract root, or the zero hash if there are no contracts.
+pub fn root(contracts: &ChainContracts) -> Hash {
+    let mut sorted: Vec<&Contract> = contracts.values().collect();
+    sorted.sort_by(|a, b| a.address.cmp(&b.address));
+
+    merkle::root(sorted.into_iter().map(Contract::state_hash).collect())
+}
+
+#[cfg(feature = "contracts")]
+pub(crate) mod runtime {
+    use wasmi::{core::TrapCode, Caller, Config, Engine, Linker, Module, Store, Val};
+
+    use super::ContractStorage;
+    use crate::BlockchainError;
+
+    /// Validate a WebAssembly module.
+    pub fn validate(code: &[u8]) -> Result<(), BlockchainError> {
+        Module::new(&Engine::default(), code)
+            .map(|_| ())
+            .map_err(|_| BlockchainError::InvalidContract)
+    }
+
+    /// Execute an exported function of a module against a copy of its store.
+    ///
+    /// Host functions `env.storage_get(key: i64) -> i64` and `env.storage_set(key: i64, value: i64)`
+    /// give the module access to its store. Missing keys read as zero.
+    ///
+    /// Returns the updated store, the function result, and the gas used.
+    pub fn execute(
+        code: &[u8],
+        storage: ContractStorage,
+        function: &str,
+        args: &[i64],
+        gas_limit: u64,
+    ) -> Result<(ContractStorage, Option<i64>, u64), BlockchainError> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, code).map_err(|_| BlockchainError::InvalidContract)?;
+
+        let mut store = Store::new(&engine, storage);
+        store
+            .set_fuel(gas_limit)
+            .map_err(|_| BlockchainError::ContractFailed)?;
+
+        let mut linker = <Linker<ContractStorage>>::new(&engine);
+        linker
+            .func_wrap(
+                "env",
+                "storage_get",
+                |caller: Caller<'_, ContractStorage>, key: i64| -> i64 {
+                    caller.data().get(&key).copied().unwrap_or_default()
+                },
+            )
+            .map_err(|_| BlockchainError::ContractFailed)?;
+        linker
+            .func_wrap(
+                "env",
+                "storage_set",
+                |mut caller: Caller<'_, ContractStorage>, key: i64, value: i64| {
+                    caller.data_mut().insert(key, value);
+                },
+            )
+            .map_err(|_| BlockchainError::ContractFailed)?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|_| BlockchainError::InvalidContract)?;
+
+        let func = instance
+            .get_func(&store, function)
+            .ok_or(BlockchainError::ContractFailed)?;
+
+        let params: Vec<Val> = args.iter().map(|arg| Val::I64(*arg)).collect();
+        let mut results = vec![Val::I64(0); func.ty(&store).results().len()];
+
+        if let Err(error) = func.call(&mut store, &params, &mut results) {
+            return match error.as_trap_code() {
+                Some(TrapCode::OutOfFuel) => Err(BlockchainError::OutOfGas),
+                _ => Err(BlockchainError::ContractFailed),
+            };
+        }
+
+        let gas_used = gas_limit - store.get_fuel().unwrap_or_default();
+        let result = results.first().and_then(Val::i64);
+
+        Ok((store.into_data(), result, gas_used))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_new_contract() {
+        let owner = Address::random();
+        let deployment = Hash::digest(b"deploy");
+        let contract = Contract::new(&owner, vec![0, 97, 115, 109], &deployment, 0);
+
+        assert_eq!(contract.owner, owner);
+        assert_eq!(contract.code_hash, Hash::digest(&[0, 97, 115, 109]));
+        assert!(contract.storage.is_empty());
+        assert_eq!(
+            contract.address,
+            Contract::derive_address(&owner, &deployment)
+        );
+        assert_ne!(
+            contract.address,
+            Contract::derive_address(&owner, &Hash::digest(b"other"))
+        );
+    }
+
+    #[test]
+    fn test_root() {
+        let mut contract = Contract::new(&Address::random(), vec![], &Hash::zero(), 0);
+        let mut contracts: ChainContracts = HashMap::default();
+
+        assert!(root(&contracts).is_zero());
+
+        contracts.insert(contract.address.to_owned(), contract.to_owned());
+        let before = root(&contracts);
+
+        contract.storage.insert(1, 1);
+        contracts.insert(contract.address.to_owned(), contract);
+
+        assert_ne!(before, root(&contracts));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod address;
+pub mod bloom;
+pub mod encoding;
+pub mod error;
+pub mod hash;
+pub mod header;
+pub mod merkle;
+pub mod transaction;
@@ -0,0 +1,362 @@
+use alloc::string::{String, ToString};
+use core::{fmt, iter, str::FromStr};
+
+use rand::{distributions::Alphanumeric, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{BlockchainError, Hash};
+
+/// Length of a wallet address.
+pub const ADDRESS_LENGTH: usize = 42;
+
+/// Reserved sender address of block reward transactions.
+pub const ROOT_ADDRESS: &str = "Root";
+
+/// Reserved unspendable address credited with burned funds.
+pub const BURN_ADDRESS: &str = "Burn";
+
+/// Characters of the addresses derived from public keys.
+const ADDRESS_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Validated address of a wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Address(String);
+
+impl Address {
+    /// Create a new address after validating its format.
+    ///
+    /// # Arguments
+    /// - `value`: The address as a string.
+    ///
+    /// # Returns
+    /// The address, or an error if it is not `ADDRESS_LENGTH` alphanumeric characters long.
+    pub fn new(value: &str) -> Result<Self, BlockchainError> {
+        if value == ROOT_ADDRESS || value == BURN_ADDRESS {
+            return Ok(Address(value.to_string()));
+        }
+
+        if value.len() != ADDRESS_LENGTH || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(BlockchainError::InvalidAddress);
+        }
+
+        Ok(Address(value.to_string()))
+    }
+
+    /// Generate a new random address.
+    ///
+    /// # Returns
+    /// A random address of `ADDRESS_LENGTH` alphanumeric characters.
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        Address::from_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate a new address from a random number generator, e.g. a seeded one for reproducible
+    /// wallets.
+    ///
+    /// # Arguments
+    /// - `rng`: The random number generator.
+    ///
+    /// # Returns
+    /// A random address of `ADDRESS_LENGTH` alphanumeric characters.
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let address = iter::repeat_with(|| rng.sample(Alphanumeric) as char)
+            .take(ADDRESS_LENGTH)
+            .collect();
+
+        Address(address)
+    }
+
+    /// Get the reserved sender address of block reward transactions.
+    ///
+    /// # Returns
+    /// The root address.
+    pub fn root() -> Self {
+        Address(ROOT_ADDRESS.to_string())
+    }
+
+    /// Check whether this is the reserved root address.
+    ///
+    /// # Returns
+    /// `true` if the address is the root address, `false` otherwise.
+    pub fn is_root(&self) -> bool {
+        self.0 == ROOT_ADDRESS
+    }
+
+    /// Get the reserved unspendable address credited with burned funds.
+    ///
+    /// # Returns
+    /// The burn address.
+    pub fn burn() -> Self {
+        Address(BURN_ADDRESS.to_string())
+    }
+
+    /// Check whether this is the reserved burn address.
+    ///
+    /// # Returns
+    /// `true` if the address is the burn address, `false` otherwise.
+    pub fn is_burn(&self) -> bool {
+        self.0 == BURN_ADDRESS
+    }
+
+    /// Get the address as a string slice.
+    ///
+    /// # Returns
+    /// The address string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Strategy generating the addresses of new wallets and contracts.
+///
+/// Every scheme produces `ADDRESS_LENGTH` alphanumeric characters, so addresses generated by
+/// different schemes coexist in the same ledger.
+pub trait AddressScheme: Send + Sync {
+    /// Generate a new address.
+    ///
+    /// # Arguments
+    /// - `rng`: The random number generator of the chain environment.
+    ///
+    /// # Returns
+    /// A new address.
+    fn generate(&self, rng: &mut dyn RngCore) -> Address;
+}
+
+/// Scheme generating random alphanumeric addresses, the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomScheme;
+
+impl AddressScheme for RandomScheme {
+    fn generate(&self, rng: &mut dyn RngCore) -> Address {
+        Address::from_rng(rng)
+    }
+}
+
+/// Scheme deriving addresses from the hash of a public key.
+///
+/// Wallets created by the chain have no key pair, so `generate` derives the address from random
+/// key material; `derive` gives the address of an existing key.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PublicKeyScheme;
+
+impl PublicKeyScheme {
+    /// Derive the address of a public key.
+    ///
+    /// # Arguments
+    /// - `public_key`: The encoded public key.
+    ///
+    /// # Returns
+    /// The address, the same for the same key.
+    pub fn derive(public_key: &[u8]) -> Address {
+        let first = Hash::digest(public_key);
+        let second = Hash::digest(first.as_bytes());
+
+        let address = first
+            .as_bytes()
+            .iter()
+            .chain(second.as_bytes())
+            .take(ADDRESS_LENGTH)
+            .map(|byte| ADDRESS_ALPHABET[usize::from(*byte) % ADDRESS_ALPHABET.len()] as char)
+            .collect();
+
+        Address(address)
+    }
+}
+
+impl AddressScheme for PublicKeyScheme {
+    fn generate(&self, rng: &mut dyn RngCore) -> Address {
+        let mut public_key = [0u8; 32];
+        rng.fill_bytes(&mut public_key);
+
+        PublicKeyScheme::derive(&public_key)
+    }
+}
+
+/// Scheme generating random addresses starting with a fixed prefix.
+#[derive(Clone, Debug)]
+pub struct VanityScheme {
+    /// Prefix of every generated address.
+    prefix: String,
+}
+
+impl VanityScheme {
+    /// Create a vanity scheme.
+    ///
+    /// # Arguments
+    /// - `prefix`: The prefix of the addresses.
+    ///
+    /// # Returns
+    /// The scheme, or `InvalidAddress` if the prefix is not alphanumeric or leaves no random
+    /// characters.
+    pub fn new(prefix: &str) -> Result<Self, BlockchainError> {
+        if prefix.len() >= ADDRESS_LENGTH || !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(BlockchainError::InvalidAddress);
+        }
+
+        Ok(VanityScheme {
+            prefix: prefix.to_string(),
+        })
+    }
+}
+
+impl AddressScheme for VanityScheme {
+    fn generate(&self, rng: &mut dyn RngCore) -> Address {
+        let address = self
+            .prefix
+            .chars()
+            .chain(iter::repeat_with(|| rng.sample(Alphanumeric) as char))
+            .take(ADDRESS_LENGTH)
+            .collect();
+
+        Address(address)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Address {
+    /// Generate a new random address.
+    fn default() -> Self {
+        Address::random()
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Address {
+    type Err = BlockchainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::new(s)
+    }
+}
+
+impl TryFrom<String> for Address {
+    type Error = BlockchainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Address::new(&value)
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Address {
+    fn schema_name() -> String {
+        "Address".to_string()
+    }
+
+    /// Describe the accepted forms, `ROOT_ADDRESS` or `ADDRESS_LENGTH` alphanumeric characters.
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some(format!(
+                    "^({}|[A-Za-z0-9]{{{}}})$",
+                    ROOT_ADDRESS, ADDRESS_LENGTH
+                )),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_address() {
+        let address = Address::random();
+
+        assert_eq!(address.as_str().len(), ADDRESS_LENGTH);
+        assert_eq!(Address::new(address.as_str()), Ok(address));
+    }
+
+    #[test]
+    fn test_address_from_seeded_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let first = Address::from_rng(&mut StdRng::seed_from_u64(7));
+        let second = Address::from_rng(&mut StdRng::seed_from_u64(7));
+
+        assert_eq!(first, second);
+        assert_eq!(Address::new(first.as_str()), Ok(first));
+    }
+
+    #[test]
+    fn test_address_schemes() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let derived = PublicKeyScheme.generate(&mut rng);
+        let vanity = VanityScheme::new("Bank").unwrap().generate(&mut rng);
+
+        assert_eq!(Address::new(derived.as_str()), Ok(derived));
+        assert_eq!(
+            PublicKeyScheme::derive(b"key"),
+            PublicKeyScheme::derive(b"key")
+        );
+        assert!(vanity.as_str().starts_with("Bank"));
+        assert_eq!(Address::new(vanity.as_str()), Ok(vanity));
+        assert!(VanityScheme::new("Bank-").is_err());
+    }
+
+    #[test]
+    fn test_invalid_address() {
+        assert_eq!(
+            Address::new("s@mail.com"),
+            Err(BlockchainError::InvalidAddress)
+        );
+        assert_eq!(
+            Address::new(&"-".repeat(ADDRESS_LENGTH)),
+            Err(BlockchainError::InvalidAddress)
+        );
+    }
+
+    #[test]
+    fn test_root_address() {
+        let address: Address = ROOT_ADDRESS.parse().unwrap();
+
+        assert!(address.is_root());
+        assert!(!Address::random().is_root());
+    }
+
+    #[test]
+    fn test_burn_address() {
+        let address: Address = BURN_ADDRESS.parse().unwrap();
+
+        assert!(address.is_burn());
+        assert!(!address.is_root());
+        assert!(!Address::random().is_burn());
+    }
+
+    #[test]
+    fn test_serde() {
+        let address = Address::random();
+        let json = serde_json::to_string(&address).unwrap();
+
+        assert_eq!(json, format!("\"{}\"", address));
+        assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), address);
+        assert!(serde_json::from_str::<Address>("\"invalid\"").is_err());
+    }
+}
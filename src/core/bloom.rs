@@ -0,0 +1,239 @@
+use alloc::{vec, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Hash, Transaction};
+
+/// Size in bytes of a block bloom filter.
+pub const BLOOM_SIZE: usize = 256;
+
+/// Number of bits set in a bloom filter for each address.
+pub const BLOOM_HASHES: usize = 3;
+
+/// Number of bits of a rolling bloom filter generation per hash it holds.
+const ROLLING_BITS_PER_HASH: usize = 10;
+
+/// Number of bits set in a rolling bloom filter for each hash.
+const ROLLING_HASHES: usize = 7;
+
+/// Bloom filter of the addresses involved in the transactions of a block.
+///
+/// A filter never misses an inserted address but may report addresses that were not inserted.
+/// An empty filter, as in blocks mined before filters were added, may contain any address.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Bloom(Vec<u8>);
+
+impl Bloom {
+    /// Create a filter of the senders and receivers of transactions.
+    ///
+    /// # Arguments
+    ///
+    /// - `transactions`: The transactions of a block.
+    ///
+    /// # Returns
+    ///
+    /// A filter containing every address sending or receiving one of the transactions.
+    pub fn of_transactions<'a>(transactions: impl IntoIterator<Item = &'a Transaction>) -> Self {
+        let mut bloom = Bloom(vec![0; BLOOM_SIZE]);
+
+        for transaction in transactions {
+            bloom.insert(&transaction.from);
+            bloom.insert(&transaction.to);
+
+            for output in &transaction.outputs {
+                bloom.insert(&output.to);
+            }
+        }
+
+        bloom
+    }
+
+    /// Add an address to the filter.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to add.
+    pub fn insert(&mut self, address: &Address) {
+        if self.0.is_empty() {
+            self.0 = vec![0; BLOOM_SIZE];
+        }
+
+        for bit in Bloom::bits(address) {
+            self.0[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Check whether the filter may contain an address.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to look for.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the address was certainly not inserted, `true` otherwise.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.len() != BLOOM_SIZE
+            || Bloom::bits(address).all(|bit| self.0[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Check whether the filter is empty, as in blocks mined before filters were added.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the filter has no bits, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the positions of the bits of an address.
+    fn bits(address: &Address) -> impl Iterator<Item = usize> {
+        let hash = Hash::digest(address.as_str().as_bytes());
+        let bytes = *hash.as_bytes();
+
+        (0..BLOOM_HASHES).map(move |i| {
+            usize::from(u16::from_be_bytes([bytes[2 * i], bytes[2 * i + 1]])) % (BLOOM_SIZE * 8)
+        })
+    }
+}
+
+/// Rolling bloom filter of recently seen hashes, e.g. to deduplicate gossiped transactions.
+///
+/// Hashes are added to the current generation; once it holds `capacity` hashes it replaces the
+/// previous generation and a new one starts, so the filter remembers at least the latest
+/// `capacity` hashes in constant memory. It may report hashes that were not added, with a
+/// probability of about 1%.
+#[derive(Clone, Debug)]
+pub struct RollingBloom {
+    /// Number of hashes per generation.
+    capacity: usize,
+
+    /// Number of hashes in the current generation.
+    count: usize,
+
+    /// Bits of the current generation.
+    current: Vec<u64>,
+
+    /// Bits of the previous generation.
+    previous: Vec<u64>,
+}
+
+impl RollingBloom {
+    /// Create an empty filter.
+    ///
+    /// # Arguments
+    ///
+    /// - `capacity`: The number of hashes per generation.
+    ///
+    /// # Returns
+    ///
+    /// A new filter.
+    pub fn new(capacity: usize) -> Self {
+        let words = (capacity.max(1) * ROLLING_BITS_PER_HASH).div_ceil(64);
+
+        RollingBloom {
+            capacity: capacity.max(1),
+            count: 0,
+            current: vec![0; words],
+            previous: vec![0; words],
+        }
+    }
+
+    /// Add a hash to the filter.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash to add.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hash was not in the filter, `false` otherwise.
+    pub fn insert(&mut self, hash: &Hash) -> bool {
+        if self.contains(hash) {
+            return false;
+        }
+
+        if self.count == self.capacity {
+            self.previous = core::mem::replace(&mut self.current, vec![0; self.previous.len()]);
+            self.count = 0;
+        }
+
+        for bit in self.bits(hash) {
+            self.current[bit / 64] |= 1 << (bit % 64);
+        }
+        self.count += 1;
+
+        true
+    }
+
+    /// Check whether the filter may contain a hash.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash to look for.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the hash was not added recently, `true` otherwise.
+    pub fn contains(&self, hash: &Hash) -> bool {
+        [&self.current, &self.previous].into_iter().any(|words| {
+            self.bits(hash)
+                .all(|bit| words[bit / 64] & (1 << (bit % 64)) != 0)
+        })
+    }
+
+    /// Get the positions of the bits of a hash.
+    fn bits(&self, hash: &Hash) -> impl Iterator<Item = usize> {
+        let (bytes, size) = (*hash.as_bytes(), self.current.len() * 64);
+
+        (0..ROLLING_HASHES).map(move |i| {
+            let word = u32::from_be_bytes([
+                bytes[4 * i],
+                bytes[4 * i + 1],
+                bytes[4 * i + 2],
+                bytes[4 * i + 3],
+            ]);
+
+            word as usize % size
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_bloom() {
+        let hashes: Vec<Hash> = (0..30u32).map(|i| Hash::digest(&i.to_be_bytes())).collect();
+        let mut seen = RollingBloom::new(10);
+
+        assert!(seen.insert(&hashes[0]));
+        assert!(!seen.insert(&hashes[0]));
+
+        for hash in &hashes[1..20] {
+            seen.insert(hash);
+        }
+        assert!(seen.contains(&hashes[19]) && seen.contains(&hashes[10]));
+
+        for hash in &hashes[20..] {
+            seen.insert(hash);
+        }
+        assert!(!seen.contains(&hashes[0]));
+        assert!(seen.contains(&hashes[29]));
+    }
+
+    #[test]
+    fn test_contains() {
+        let (member, other) = (Address::random(), Address::random());
+        let mut bloom = Bloom::default();
+
+        assert!(bloom.is_empty());
+        assert!(bloom.contains(&other));
+
+        bloom.insert(&member);
+        assert!(bloom.contains(&member));
+        assert!(!bloom.contains(&other));
+    }
+}
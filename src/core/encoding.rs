@@ -0,0 +1,473 @@
+use alloc::vec::Vec;
+
+use serde::{ser, Serialize};
+
+use crate::{hash::Hasher, BlockchainError, Hash};
+
+/// Destination of the encoded bytes.
+pub trait Sink {
+    /// Append bytes to the output.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: The bytes to append.
+    fn put(&mut self, bytes: &[u8]);
+}
+
+impl Sink for Vec<u8> {
+    fn put(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+impl Sink for Hasher {
+    fn put(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// Encode an item into its canonical binary form.
+///
+/// # Arguments
+///
+/// - `item`: The item to encode.
+///
+/// # Returns
+///
+/// The encoded bytes, or `EncodingError` if the item cannot be encoded.
+pub fn to_bytes<T: Serialize + ?Sized>(item: &T) -> Result<Vec<u8>, BlockchainError> {
+    let mut encoder = Encoder::new(Vec::new());
+    item.serialize(&mut encoder)?;
+
+    Ok(encoder.into_inner())
+}
+
+/// Calculate the hash of the canonical binary form of an item, without buffering it.
+///
+/// # Arguments
+///
+/// - `item`: The item to hash.
+///
+/// # Returns
+///
+/// The hash, or `EncodingError` if the item cannot be encoded.
+pub fn hash<T: Serialize + ?Sized>(item: &T) -> Result<Hash, BlockchainError> {
+    let mut encoder = Encoder::new(Hasher::new());
+    item.serialize(&mut encoder)?;
+
+    Ok(encoder.into_inner().finalize())
+}
+
+/// Serializer producing the canonical binary encoding used for hashing.
+///
+/// - Integers and floats are written as fixed-size little-endian values, `bool` as one byte.
+/// - Strings, byte arrays, sequences and maps are prefixed with their length as a `u64`.
+/// - Options are prefixed with `0` for `None` and `1` for `Some`.
+/// - Enum variants are prefixed with their index as a `u32`.
+/// - Struct fields are prefixed with their name, so skipped fields cannot be confused.
+/// - Tuples and fixed-size arrays are written as their elements only.
+///
+/// Sequences and maps must report their length.
+pub struct Encoder<S: Sink> {
+    /// Destination of the encoded bytes.
+    sink: S,
+}
+
+impl<S: Sink> Encoder<S> {
+    /// Create a new encoder.
+    ///
+    /// # Arguments
+    ///
+    /// - `sink`: The destination of the encoded bytes.
+    ///
+    /// # Returns
+    ///
+    /// A new encoder.
+    pub fn new(sink: S) -> Self {
+        Encoder { sink }
+    }
+
+    /// Get the destination of the encoded bytes.
+    ///
+    /// # Returns
+    ///
+    /// The sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    /// Write a length prefix.
+    fn length(&mut self, len: usize) {
+        self.sink.put(&(len as u64).to_le_bytes());
+    }
+
+    /// Write a length-prefixed byte string.
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.length(bytes.len());
+        self.sink.put(bytes);
+    }
+
+    /// Write an enum variant index.
+    fn variant(&mut self, index: u32) {
+        self.sink.put(&index.to_le_bytes());
+    }
+}
+
+impl ser::Error for BlockchainError {
+    fn custom<T: core::fmt::Display>(_: T) -> Self {
+        BlockchainError::EncodingError
+    }
+}
+
+impl<S: Sink> ser::Serializer for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), BlockchainError> {
+        self.sink.put(&[v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BlockchainError> {
+        self.sink.put(&[v]);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), BlockchainError> {
+        self.sink.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), BlockchainError> {
+        self.sink.put(&(v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BlockchainError> {
+        self.bytes(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), BlockchainError> {
+        self.bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BlockchainError> {
+        self.sink.put(&[0]);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BlockchainError> {
+        self.sink.put(&[1]);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BlockchainError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), BlockchainError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+    ) -> Result<(), BlockchainError> {
+        self.variant(index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), BlockchainError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), BlockchainError> {
+        self.variant(index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, BlockchainError> {
+        self.length(len.ok_or(BlockchainError::EncodingError)?);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, BlockchainError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self, BlockchainError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, BlockchainError> {
+        self.variant(index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, BlockchainError> {
+        self.length(len.ok_or(BlockchainError::EncodingError)?);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, BlockchainError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self, BlockchainError> {
+        self.variant(index);
+        Ok(self)
+    }
+}
+
+impl<S: Sink> ser::SerializeSeq for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTuple for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTupleStruct for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTupleVariant for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeMap for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeStruct for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeStructVariant for &mut Encoder<S> {
+    type Ok = ();
+    type Error = BlockchainError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.bytes(key.as_bytes());
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Item {
+        id: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let item = Item {
+            id: 7,
+            note: None,
+            tags: vec!["a".to_string()],
+        };
+
+        let mut expected = vec![2, 0, 0, 0, 0, 0, 0, 0];
+        expected.extend_from_slice(b"id");
+        expected.extend_from_slice(&7u32.to_le_bytes());
+        expected.extend_from_slice(&[4, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(b"tags");
+        expected.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(b"a");
+
+        assert_eq!(to_bytes(&item).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_hash_matches_digest_of_bytes() {
+        let item = ("sender", 1.5f64, Some(Hash::digest(b"block")));
+
+        assert_eq!(
+            hash(&item).unwrap(),
+            Hash::digest(&to_bytes(&item).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_unambiguous_strings() {
+        assert_ne!(to_bytes(&("ab", "c")), to_bytes(&("a", "bc")));
+    }
+
+    #[test]
+    fn test_unknown_length() {
+        struct Unsized;
+
+        impl Serialize for Unsized {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq((0..3).filter(|i| i % 2 == 0))
+            }
+        }
+
+        assert_eq!(to_bytes(&Unsized), Err(BlockchainError::EncodingError));
+    }
+}
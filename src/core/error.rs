@@ -0,0 +1,280 @@
+use alloc::string::String;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Address, Hash};
+
+/// Blockchain error.
+#[derive(Clone, Debug, Error, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BlockchainError {
+    /// Transaction not found.
+    #[error("Transaction {hash} not found.")]
+    TransactionNotFound {
+        /// Hash of the missing transaction.
+        hash: Hash,
+    },
+
+    /// Transaction is invalid.
+    #[error("Invalid transaction.")]
+    InvalidTransaction,
+
+    /// Invalid configuration during blockchain creation.
+    #[error("Invalid configuration during blockchain creation.")]
+    InvalidConfiguration,
+
+    /// Insufficient funds.
+    #[error("Insufficient funds: wallet {address} needs {required} but has {available}.")]
+    InsufficientFunds {
+        /// Address of the wallet.
+        address: Address,
+
+        /// Amount needed by the operation.
+        required: f64,
+
+        /// Amount available in the wallet.
+        available: f64,
+    },
+
+    /// Wallet not found.
+    #[error("Wallet {address} not found.")]
+    WalletNotFound {
+        /// Address of the missing wallet.
+        address: Address,
+    },
+
+    /// Block header is invalid.
+    #[error("Invalid block header.")]
+    InvalidHeader,
+
+    /// Block not found.
+    #[error("Block not found.")]
+    BlockNotFound,
+
+    /// Block body does not match its header.
+    #[error("Invalid block body.")]
+    InvalidBlockBody,
+
+    /// Block has too many transactions or is too large.
+    #[error("Block limit exceeded.")]
+    BlockLimitExceeded,
+
+    /// Block timestamp violates the drift rules.
+    #[error("Invalid block timestamp.")]
+    InvalidTimestamp,
+
+    /// Hash is not a valid hex-encoded 32-byte hash.
+    #[error("Invalid hash.")]
+    InvalidHash,
+
+    /// Address has an invalid length or charset.
+    #[error("Invalid address.")]
+    InvalidAddress,
+
+    /// Token is invalid.
+    #[error("Invalid token.")]
+    InvalidToken,
+
+    /// Token not found.
+    #[error("Token not found.")]
+    TokenNotFound,
+
+    /// Token already exists.
+    #[error("Token already exists.")]
+    TokenAlreadyExists,
+
+    /// Caller is not allowed to perform the operation.
+    #[error("Unauthorized.")]
+    Unauthorized,
+
+    /// Operation would exceed the token supply cap.
+    #[error("Supply cap exceeded.")]
+    SupplyCapExceeded,
+
+    /// Non-fungible token not found.
+    #[error("NFT not found.")]
+    NftNotFound,
+
+    /// Script is malformed.
+    #[error("Invalid script.")]
+    InvalidScript,
+
+    /// Script condition is not satisfied.
+    #[error("Script condition is not satisfied.")]
+    ScriptFailed,
+
+    /// Contract module is invalid.
+    #[error("Invalid contract.")]
+    InvalidContract,
+
+    /// Contract not found.
+    #[error("Contract not found.")]
+    ContractNotFound,
+
+    /// Contract execution trapped or the function does not exist.
+    #[error("Contract execution failed.")]
+    ContractFailed,
+
+    /// Contract execution ran out of gas.
+    #[error("Out of gas.")]
+    OutOfGas,
+
+    /// Data payload is empty or too large.
+    #[error("Invalid data payload.")]
+    InvalidData,
+
+    /// Governance proposal not found.
+    #[error("Proposal not found.")]
+    ProposalNotFound,
+
+    /// Governance proposal is invalid.
+    #[error("Invalid proposal.")]
+    InvalidProposal,
+
+    /// Governance proposal is no longer open for voting.
+    #[error("Proposal is closed.")]
+    ProposalClosed,
+
+    /// Invoice not found.
+    #[error("Invoice not found.")]
+    InvoiceNotFound,
+
+    /// Invoice is already paid or has expired.
+    #[error("Invoice is closed.")]
+    InvoiceClosed,
+
+    /// Oracle data feed not found.
+    #[error("Feed not found.")]
+    FeedNotFound,
+
+    /// Peer-to-peer network failure.
+    #[error("Network error.")]
+    NetworkError,
+
+    /// Peer sent a malformed or unexpected message.
+    #[error("Protocol error.")]
+    ProtocolError,
+
+    /// API key is missing or unknown.
+    #[error("Missing or invalid API key.")]
+    Unauthenticated,
+
+    /// Client exceeded its request rate limit.
+    #[error("Rate limit exceeded.")]
+    RateLimited,
+
+    /// Chain could not be read from or written to storage.
+    #[error("Storage error.")]
+    StorageError,
+
+    /// Item cannot be encoded in the canonical binary form.
+    #[error("Encoding error.")]
+    EncodingError,
+
+    /// Audit log entry was altered, removed or reordered.
+    #[error("Invalid audit log.")]
+    InvalidAuditLog,
+
+    /// Amount is not a positive finite number.
+    #[error("Invalid amount {amount}.")]
+    InvalidAmount {
+        /// The rejected amount.
+        amount: f64,
+    },
+
+    /// Genesis or treasury address takes part in a user transaction.
+    #[error("Address {address} is reserved.")]
+    ReservedAddress {
+        /// The reserved address.
+        address: Address,
+    },
+
+    /// Address is denied or not allowed by the compliance lists.
+    #[error("Address {address} is denied.")]
+    AddressDenied {
+        /// The denied address.
+        address: Address,
+    },
+
+    /// Wallet must be verified to send, or to receive more while restricted.
+    #[error("Wallet {address} must be verified.")]
+    KycRequired {
+        /// Address of the restricted wallet.
+        address: Address,
+    },
+
+    /// Address claimed funds from the faucet too recently.
+    #[error("Faucet cooldown of {address} ends at {available_at}.")]
+    FaucetCooldown {
+        /// The claiming address.
+        address: Address,
+
+        /// Time the address may claim funds again.
+        available_at: i64,
+    },
+
+    /// Transaction with the same hash was already added.
+    #[error("Transaction {hash} already exists.")]
+    DuplicateTransaction {
+        /// Hash of the existing transaction.
+        hash: Hash,
+    },
+
+    /// Block archive is truncated, corrupted or uses unknown features.
+    #[error("Corrupt block archive at byte {offset}.")]
+    CorruptArchive {
+        /// Offset of the invalid header or record.
+        offset: u64,
+    },
+
+    /// Exchange rate of an asset to a currency is not available.
+    #[error("No exchange rate from {asset} to {currency}.")]
+    RateUnavailable {
+        /// The converted asset.
+        asset: String,
+
+        /// The target currency.
+        currency: String,
+    },
+
+    /// No address starting with the vanity prefix was found before the timeout.
+    #[error("Vanity address not found.")]
+    VanityNotFound,
+
+    /// State snapshot does not match the state root of its checkpoint block.
+    #[error("Invalid state snapshot.")]
+    InvalidSnapshot,
+
+    /// Bridge proof does not match a mined bridge transaction of the expected chain.
+    #[error("Invalid bridge proof.")]
+    InvalidBridgeProof,
+
+    /// Bridge proof was already used to mint or release funds.
+    #[error("Bridge proof for transaction {hash} already claimed.")]
+    ProofAlreadyClaimed {
+        /// Hash of the proven transaction.
+        hash: Hash,
+    },
+
+    /// No chain is registered under the name.
+    #[error("Chain {name} not found.")]
+    ChainNotFound {
+        /// Name of the missing chain.
+        name: String,
+    },
+
+    /// A chain is already registered under the name.
+    #[error("Chain {name} already exists.")]
+    ChainExists {
+        /// Name of the existing chain.
+        name: String,
+    },
+
+    /// Persisted chain was written by a newer version of the crate.
+    #[error("Unsupported state version {version}.")]
+    UnsupportedStateVersion {
+        /// Format version of the persisted chain.
+        version: u64,
+    },
+}
@@ -0,0 +1,323 @@
+use alloc::string::String;
+use core::{cmp::Ordering, fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(not(feature = "blake3"))]
+use sha2::{Digest, Sha256};
+
+use crate::{encoding, BlockchainError};
+
+/// Lowercase hexadecimal digits.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Hash function selected at compile time.
+#[cfg(not(feature = "blake3"))]
+type HashFunction = Sha256;
+
+/// Encode bytes as lowercase hex, two digits per byte.
+///
+/// # Arguments
+/// - `bytes`: The bytes to encode.
+///
+/// # Returns
+/// The hex string.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+
+    for b in bytes {
+        hex.push(HEX_DIGITS[(b >> 4) as usize] as char);
+        hex.push(HEX_DIGITS[(b & 0x0f) as usize] as char);
+    }
+
+    hex
+}
+
+/// Hash function selected at compile time.
+#[cfg(feature = "blake3")]
+type HashFunction = blake3::Hasher;
+
+/// Incremental hasher producing a `Hash`.
+///
+/// Uses SHA-256, or BLAKE3 when the `blake3` feature is enabled. Nodes must be built with the
+/// same hash function to agree on block and transaction hashes.
+#[derive(Clone, Default)]
+pub struct Hasher(HashFunction);
+
+impl Hasher {
+    /// Create a new hasher.
+    ///
+    /// # Returns
+    /// A hasher with no input.
+    pub fn new() -> Self {
+        Hasher::default()
+    }
+
+    /// Feed bytes to the hasher.
+    ///
+    /// # Arguments
+    /// - `bytes`: The bytes to hash.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// Finish hashing.
+    ///
+    /// # Returns
+    /// The hash of all the bytes fed to the hasher.
+    #[cfg(not(feature = "blake3"))]
+    pub fn finalize(self) -> Hash {
+        Hash(self.0.finalize().into())
+    }
+
+    /// Finish hashing.
+    ///
+    /// # Returns
+    /// The hash of all the bytes fed to the hasher.
+    #[cfg(feature = "blake3")]
+    pub fn finalize(self) -> Hash {
+        Hash(*self.0.finalize().as_bytes())
+    }
+}
+
+/// SHA-256 (or BLAKE3, see `Hasher`) hash of a transaction, block header, or Merkle node.
+#[derive(Clone, Copy, Default)]
+pub struct Hash(pub [u8; 32]);
+
+impl Hash {
+    /// Calculate the hash of a byte slice.
+    ///
+    /// # Arguments
+    /// - `bytes`: The bytes to hash.
+    ///
+    /// # Returns
+    /// The hash of the bytes.
+    pub fn digest(bytes: &[u8]) -> Self {
+        let mut hasher = Hasher::new();
+        hasher.update(bytes);
+
+        hasher.finalize()
+    }
+
+    /// Calculate the hash of the canonical binary form of a serializable item, see
+    /// `encoding::Encoder`.
+    ///
+    /// # Arguments
+    /// - `item`: The item to hash.
+    ///
+    /// # Returns
+    /// The hash of the item.
+    pub fn of<T: Serialize + ?Sized>(item: &T) -> Self {
+        encoding::hash(item).unwrap()
+    }
+
+    /// Get the hash consisting of zero bytes only.
+    ///
+    /// # Returns
+    /// The zero hash, used as the previous hash of the genesis block.
+    pub fn zero() -> Self {
+        Hash::default()
+    }
+
+    /// Check whether the hash consists of zero bytes only.
+    ///
+    /// # Returns
+    /// `true` if every byte of the hash is zero, `false` otherwise.
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+
+    /// Count the leading zero hex digits of the hash.
+    ///
+    /// # Returns
+    /// The number of leading zero hex digits.
+    pub fn leading_zeros(&self) -> usize {
+        let bits: u32 = self
+            .0
+            .iter()
+            .position(|b| *b != 0)
+            .map(|i| i as u32 * 8 + self.0[i].leading_zeros())
+            .unwrap_or(256);
+
+        bits as usize / 4
+    }
+
+    /// Get the raw bytes of the hash.
+    ///
+    /// # Returns
+    /// A reference to the hash bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl PartialEq for Hash {
+    /// Compare two hashes in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+impl Eq for Hash {}
+
+impl core::hash::Hash for Hash {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl PartialOrd for Hash {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hash {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&encode_hex(&self.0))
+    }
+}
+
+impl fmt::Debug for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hash({})", self)
+    }
+}
+
+impl FromStr for Hash {
+    type Err = BlockchainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Reject signs, which `from_str_radix` would accept
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(BlockchainError::InvalidHash);
+        }
+
+        let mut bytes = [0u8; 32];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16)
+                .map_err(|_| BlockchainError::InvalidHash)?;
+        }
+
+        Ok(Hash(bytes))
+    }
+}
+
+impl Serialize for Hash {
+    /// Serialize as a hex string in human-readable formats and as raw bytes otherwise.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.collect_str(self),
+            false => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Hash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return <[u8; 32]>::deserialize(deserializer).map(Hash);
+        }
+
+        let s = String::deserialize(deserializer)?;
+
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Hash {
+    fn schema_name() -> String {
+        "Hash".to_string()
+    }
+
+    /// Describe the human-readable form, a hex string of 32 bytes.
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                pattern: Some("^[0-9a-fA-F]{64}$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_display_and_parse() {
+        let hash = Hash::digest(b"block");
+        let hex = hash.to_string();
+
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex.parse::<Hash>().unwrap(), hash);
+    }
+
+    #[test]
+    fn test_display_leading_zero_bytes() {
+        let mut bytes = [0xab; 32];
+        bytes[0] = 0x01;
+
+        assert!(Hash(bytes).to_string().starts_with("01ab"));
+    }
+
+    #[test]
+    fn test_display_all_digits() {
+        let bytes: [u8; 32] = std::array::from_fn(|i| (i * 8) as u8);
+        let expected = bytes.iter().fold(String::new(), |mut hex, b| {
+            write!(hex, "{:02x}", b).unwrap();
+            hex
+        });
+
+        assert_eq!(Hash(bytes).to_string(), expected);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!("xyz".parse::<Hash>(), Err(BlockchainError::InvalidHash));
+        assert_eq!(
+            "z".repeat(64).parse::<Hash>(),
+            Err(BlockchainError::InvalidHash)
+        );
+        assert_eq!(
+            format!("+f{}", "0".repeat(62)).parse::<Hash>(),
+            Err(BlockchainError::InvalidHash)
+        );
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0x00;
+        bytes[1] = 0x0f;
+
+        assert_eq!(Hash(bytes).leading_zeros(), 3);
+        assert_eq!(Hash::zero().leading_zeros(), 64);
+    }
+
+    #[test]
+    fn test_serde() {
+        let hash = Hash::digest(b"transaction");
+        let json = serde_json::to_string(&hash).unwrap();
+
+        assert_eq!(json, format!("\"{}\"", hash));
+        assert_eq!(serde_json::from_str::<Hash>(&json).unwrap(), hash);
+    }
+}
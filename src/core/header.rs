@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{merkle, BlockchainError, Bloom, Hash, Transaction};
+
+/// Identifier of a particular block on an entire blockchain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BlockHeader {
+    /// Timestamp at which a block was mined.
+    pub timestamp: i64,
+
+    /// Integer to achieve the network's difficulty.
+    pub nonce: u32,
+
+    /// Hash of a previous block.
+    pub previous_hash: Hash,
+
+    /// Merkel root hash.
+    pub merkle: Hash,
+
+    /// Current difficulty level of the network.
+    pub difficulty: f64,
+
+    /// Merkle root of the wallet state at the time the block was mined.
+    #[serde(default)]
+    pub state_root: Hash,
+
+    /// Merkle root of the contract state at the time the block was mined.
+    #[serde(default)]
+    pub contract_root: Hash,
+
+    /// Bloom filter of the addresses involved in the transactions, empty in older blocks.
+    #[serde(default, skip_serializing_if = "Bloom::is_empty")]
+    pub bloom: Bloom,
+}
+
+impl BlockHeader {
+    /// Calculate the hash of the header, which is the hash of the block.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the header.
+    pub fn hash(&self) -> Hash {
+        Hash::of(self)
+    }
+
+    /// Check whether the header satisfies the proof-of-work difficulty expected of it.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The difficulty expected at the height of the header.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the header declares the expected difficulty and its hash starts with the
+    /// required number of zeros, `false` otherwise.
+    pub fn meets_difficulty(&self, difficulty: f64) -> bool {
+        self.difficulty == difficulty && self.hash().leading_zeros() >= difficulty as usize
+    }
+
+    /// Verify the header against the hash of its block.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The claimed hash of the block.
+    /// - `difficulty`: The difficulty expected at the height of the header.
+    ///
+    /// # Returns
+    ///
+    /// `InvalidHeader` if the hash does not match the header, or the header does not declare or
+    /// does not satisfy the expected difficulty.
+    pub fn verify(&self, hash: &Hash, difficulty: f64) -> Result<(), BlockchainError> {
+        let computed = self.hash();
+
+        match computed == *hash
+            && self.difficulty == difficulty
+            && computed.leading_zeros() >= difficulty as usize
+        {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidHeader),
+        }
+    }
+
+    /// Verify the transactions of the block against the header.
+    ///
+    /// # Arguments
+    ///
+    /// - `transactions`: The transactions of the block, in any order.
+    ///
+    /// # Returns
+    ///
+    /// `InvalidTransaction` if a transaction does not match its hash or its recorded size, or
+    /// `InvalidBlockBody` if the transactions do not match the Merkle root or the bloom filter of
+    /// the header.
+    pub fn verify_transactions(
+        &self,
+        transactions: &[&Transaction],
+    ) -> Result<(), BlockchainError> {
+        if transactions.iter().any(|tx| {
+            tx.hash != tx.compute_hash() || (tx.size != 0 && tx.size != tx.encoded_size())
+        }) {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        match merkle_root(transactions) == self.merkle && self.matches_bloom(transactions) {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidBlockBody),
+        }
+    }
+
+    /// Check the bloom filter of the header against the transactions of the block.
+    ///
+    /// # Arguments
+    ///
+    /// - `transactions`: The transactions of the block, in any order.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the header has no filter or the filter of the transactions, `false` otherwise.
+    pub fn matches_bloom(&self, transactions: &[&Transaction]) -> bool {
+        self.bloom.is_empty() || self.bloom == Bloom::of_transactions(transactions.iter().copied())
+    }
+}
+
+/// Calculate the Merkle root of the transactions of a block.
+///
+/// # Arguments
+///
+/// - `transactions`: The transactions of the block, in any order.
+///
+/// # Returns
+///
+/// The Merkle root of the transactions sorted by their hash.
+pub fn merkle_root(transactions: &[&Transaction]) -> Hash {
+    let mut sorted: Vec<&Transaction> = transactions.to_vec();
+    sorted.sort_by_key(|tx| tx.hash);
+
+    merkle::root(merkle::leaves(&sorted, Hash::of))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::Address;
+
+    fn transactions() -> Vec<Transaction> {
+        (0..3)
+            .map(|i| {
+                Transaction::from_parts(
+                    Uuid::nil(),
+                    i,
+                    Address::root(),
+                    Address::root(),
+                    0.0,
+                    1.0,
+                    "",
+                )
+            })
+            .collect()
+    }
+
+    fn header(transactions: &[&Transaction]) -> BlockHeader {
+        BlockHeader {
+            timestamp: 0,
+            nonce: 0,
+            previous_hash: Hash::zero(),
+            merkle: merkle_root(transactions),
+            difficulty: 0.0,
+            state_root: Hash::zero(),
+            contract_root: Hash::zero(),
+            bloom: Bloom::default(),
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let header = header(&[]);
+
+        assert_eq!(header.verify(&header.hash(), 0.0), Ok(()));
+        assert_eq!(
+            header.verify(&Hash::zero(), 0.0),
+            Err(BlockchainError::InvalidHeader)
+        );
+
+        // A header declaring less than the expected difficulty is rejected
+        assert!(!header.meets_difficulty(1.0));
+        assert_eq!(
+            header.verify(&header.hash(), 1.0),
+            Err(BlockchainError::InvalidHeader)
+        );
+    }
+
+    #[test]
+    fn test_verify_transactions() {
+        let transactions = transactions();
+        let mut refs: Vec<&Transaction> = transactions.iter().collect();
+        let header = header(&refs);
+
+        // The order of the transactions does not matter
+        refs.reverse();
+        assert_eq!(header.verify_transactions(&refs), Ok(()));
+
+        assert_eq!(
+            header.verify_transactions(&refs[1..]),
+            Err(BlockchainError::InvalidBlockBody)
+        );
+
+        let mut tampered = transactions[0].clone();
+        tampered.amount = 2.0;
+        assert_eq!(
+            header.verify_transactions(&[&tampered]),
+            Err(BlockchainError::InvalidTransaction)
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Hash;
+#[cfg(feature = "std")]
+use crate::{ChainTransactions, Transaction};
+
+/// Minimum number of nodes for a tree level to be hashed in parallel.
+pub const PARALLEL_THRESHOLD: usize = 512;
+
+/// Position of a sibling hash relative to the hash being proven.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MerkleSide {
+    /// The sibling is concatenated on the left.
+    Left,
+
+    /// The sibling is concatenated on the right.
+    Right,
+}
+
+/// A single step of a Merkle inclusion proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleStep {
+    /// Hash of the sibling node.
+    pub hash: Hash,
+
+    /// Side on which the sibling is placed.
+    pub side: MerkleSide,
+}
+
+/// Proof that a leaf is included in a Merkle tree with a known root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Hash of the proven leaf.
+    pub leaf: Hash,
+
+    /// Path of sibling hashes from the leaf up to the root.
+    pub path: Vec<MerkleStep>,
+}
+
+impl MerkleProof {
+    /// Compute the root implied by the proof.
+    ///
+    /// # Returns
+    /// The Merkle root hash.
+    pub fn root(&self) -> Hash {
+        self.path
+            .iter()
+            .fold(self.leaf, |hash, step| match step.side {
+                MerkleSide::Left => combine(&step.hash, &hash),
+                MerkleSide::Right => combine(&hash, &step.hash),
+            })
+    }
+
+    /// Verify the proof against a Merkle root.
+    ///
+    /// # Arguments
+    /// - `root`: The expected Merkle root hash.
+    ///
+    /// # Returns
+    /// `true` if the proof leads to the given root, `false` otherwise.
+    pub fn verify(&self, root: &Hash) -> bool {
+        self.root() == *root
+    }
+}
+
+/// Calculate the Merkle root hash for a list of leaf hashes.
+///
+/// # Arguments
+/// - `leaves`: The leaf hashes in tree order.
+///
+/// # Returns
+/// The Merkle root hash, or the zero hash if there are no leaves.
+pub fn root(leaves: Vec<Hash>) -> Hash {
+    let mut level = leaves;
+
+    while level.len() > 1 {
+        level = next_level(level);
+    }
+
+    level.pop().unwrap_or_default()
+}
+
+/// Build an inclusion proof for the leaf at the given index.
+///
+/// # Arguments
+/// - `leaves`: The leaf hashes in tree order.
+/// - `index`: The index of the leaf to prove.
+///
+/// # Returns
+/// The Merkle proof, or `None` if the index is out of bounds.
+pub fn proof(leaves: Vec<Hash>, index: usize) -> Option<MerkleProof> {
+    let leaf = *leaves.get(index)?;
+    let mut level = leaves;
+    let mut index = index;
+    let mut path = vec![];
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+
+        let step = match index % 2 {
+            0 => MerkleStep {
+                hash: level[index + 1],
+                side: MerkleSide::Right,
+            },
+            _ => MerkleStep {
+                hash: level[index - 1],
+                side: MerkleSide::Left,
+            },
+        };
+
+        path.push(step);
+        level = next_level(level);
+        index /= 2;
+    }
+
+    Some(MerkleProof { leaf, path })
+}
+
+/// Hash items into Merkle leaves.
+///
+/// With the `parallel` feature, large inputs are hashed on the rayon thread pool.
+///
+/// # Arguments
+/// - `items`: The items in tree order.
+/// - `hash`: The function hashing an item.
+///
+/// # Returns
+/// The leaf hashes, in the order of the items.
+pub fn leaves<T, F>(items: &[T], hash: F) -> Vec<Hash>
+where
+    T: Sync,
+    F: Fn(&T) -> Hash + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if items.len() >= PARALLEL_THRESHOLD {
+        return items.par_iter().map(hash).collect();
+    }
+
+    items.iter().map(hash).collect()
+}
+
+/// Order the transactions of a block the way they appear as Merkle leaves.
+///
+/// # Arguments
+/// - `transactions`: The transactions of a block.
+///
+/// # Returns
+/// The transactions sorted by their hash.
+#[cfg(feature = "std")]
+pub fn sorted_transactions(transactions: &ChainTransactions) -> Vec<&Transaction> {
+    let mut sorted: Vec<&Transaction> = transactions.values().collect();
+    sorted.sort_by_key(|tx| tx.hash);
+
+    sorted
+}
+
+/// Combine each pair of hashes into the next level of the tree.
+fn next_level(mut level: Vec<Hash>) -> Vec<Hash> {
+    // Duplicate the last hash if the level has an odd number of nodes
+    if level.len() % 2 == 1 {
+        level.push(level[level.len() - 1]);
+    }
+
+    #[cfg(feature = "parallel")]
+    if level.len() >= PARALLEL_THRESHOLD {
+        return level
+            .par_chunks(2)
+            .map(|pair| combine(&pair[0], &pair[1]))
+            .collect();
+    }
+
+    level
+        .chunks(2)
+        .map(|pair| combine(&pair[0], &pair[1]))
+        .collect()
+}
+
+/// Hash the concatenation of two nodes.
+fn combine(left: &Hash, right: &Hash) -> Hash {
+    Hash::digest(&[left.0, right.0].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: usize) -> Vec<Hash> {
+        (0..count).map(|i| Hash::digest(&[i as u8])).collect()
+    }
+
+    #[test]
+    fn test_root_single_leaf() {
+        let leaves = leaves(1);
+
+        assert_eq!(root(leaves.clone()), leaves[0]);
+    }
+
+    #[test]
+    fn test_root_empty() {
+        assert!(root(vec![]).is_zero());
+    }
+
+    #[test]
+    fn test_proof_verify() {
+        for count in 1..8 {
+            let leaves = leaves(count);
+            let expected = root(leaves.clone());
+
+            for index in 0..count {
+                let proof = proof(leaves.clone(), index).unwrap();
+
+                assert!(proof.verify(&expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_verify_invalid_root() {
+        let proof = proof(leaves(4), 2).unwrap();
+
+        assert!(!proof.verify(&Hash::zero()));
+    }
+
+    #[test]
+    fn test_root_large_tree() {
+        let leaves: Vec<Hash> = (0..PARALLEL_THRESHOLD * 3 + 1)
+            .map(|i| Hash::digest(&i.to_be_bytes()))
+            .collect();
+
+        let expected = proof(leaves.clone(), leaves.len() - 1).unwrap().root();
+
+        assert_eq!(root(leaves), expected);
+    }
+
+    #[test]
+    fn test_leaves_keep_order() {
+        let items: Vec<usize> = (0..PARALLEL_THRESHOLD * 2).collect();
+
+        let hashes = super::leaves(&items, |i| Hash::digest(&i.to_be_bytes()));
+
+        assert_eq!(hashes.len(), items.len());
+        assert_eq!(
+            hashes[PARALLEL_THRESHOLD],
+            Hash::digest(&PARALLEL_THRESHOLD.to_be_bytes())
+        );
+    }
+
+    #[test]
+    fn test_proof_out_of_bounds() {
+        assert!(proof(leaves(3), 3).is_none());
+    }
+}
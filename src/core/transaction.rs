@@ -0,0 +1,648 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "std")]
+use crate::BlockchainError;
+use crate::{encoding, hash, Address, Hash};
+
+/// Identifier of the native coin of the chain.
+pub const NATIVE_ASSET: &str = "NATIVE";
+
+/// Maximum size in bytes of the data payload of a transaction.
+pub const MAX_DATA_SIZE: usize = 256;
+
+/// Fee charged in addition to the transaction fee for each byte of data payload.
+pub const DATA_FEE_PER_BYTE: f64 = 0.001;
+
+/// Purpose of a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TransactionKind {
+    /// Transfer of an asset between two wallets.
+    #[default]
+    Transfer,
+
+    /// Block reward paid to the miner.
+    Reward,
+
+    /// Issuance of new token supply.
+    Mint,
+
+    /// Destruction of token supply.
+    Burn,
+
+    /// Creation of a non-fungible token.
+    NftMint,
+
+    /// Transfer of a non-fungible token.
+    NftTransfer,
+
+    /// Deployment of a smart contract.
+    ContractDeploy,
+
+    /// Call of a smart contract function.
+    ContractCall,
+
+    /// Zero-value transaction anchoring a data payload on the chain.
+    Anchor,
+
+    /// Value of an external data feed reported by an oracle, carried as the amount.
+    Oracle,
+
+    /// Distribution of native coins to many wallets, carried as the outputs.
+    Airdrop,
+
+    /// Share of the fees of a block routed to the treasury.
+    Treasury,
+
+    /// Share of the fees of a block destroyed.
+    FeeBurn,
+
+    /// Coins locked, wrapped tokens burned or coins released by a cross-chain bridge, with the
+    /// counterpart on the other chain as data.
+    Bridge,
+}
+
+impl TransactionKind {
+    /// Check whether transactions of this kind are only created by block production.
+    ///
+    /// # Returns
+    ///
+    /// `true` for the reward and the treasury and burned shares of the fees.
+    pub fn is_block_production(&self) -> bool {
+        matches!(
+            self,
+            TransactionKind::Reward | TransactionKind::Treasury | TransactionKind::FeeBurn
+        )
+    }
+}
+
+/// Version of the preimage of transaction hashes.
+///
+/// Versions are activated at explicit heights, see `ProtocolFeature`, so the hash of existing
+/// transactions never changes silently.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum HashVersion {
+    /// Original preimage: parties, amount and timestamp, with the asset, kind, data payload and
+    /// outputs only when they differ from the defaults.
+    #[default]
+    V1,
+
+    /// Preimage of every field: the chain identifier, the transaction identifier as a unique
+    /// nonce, the parties, fee, amount, asset, kind, data payload as a memo, outputs and
+    /// timestamp.
+    V2,
+}
+
+impl HashVersion {
+    /// Check whether this is the original version, which is not serialized.
+    ///
+    /// # Returns
+    ///
+    /// `true` for `V1`.
+    pub fn is_v1(&self) -> bool {
+        *self == HashVersion::V1
+    }
+}
+
+/// Credit of a multi-output transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransactionOutput {
+    /// Receiver wallet address.
+    pub to: Address,
+
+    /// Amount credited to the receiver.
+    pub amount: f64,
+}
+
+/// Request to transfer native coins, validated and applied by the chain later.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransactionRequest {
+    /// Sender wallet address.
+    pub from: Address,
+
+    /// Receiver wallet address.
+    pub to: Address,
+
+    /// Amount to transfer.
+    pub amount: f64,
+}
+
+impl TransactionRequest {
+    /// Create a new transfer request.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender address.
+    /// - `to`: The receiver address.
+    /// - `amount`: The amount to transfer.
+    ///
+    /// # Returns
+    ///
+    /// A new transfer request.
+    pub fn new(from: &Address, to: &Address, amount: f64) -> Self {
+        TransactionRequest {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        }
+    }
+}
+
+/// Exchange of assets between two parties.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Transaction {
+    /// Identifier of the transaction.
+    pub id: Uuid,
+
+    /// Transaction hash.
+    pub hash: Hash,
+
+    /// Transaction sender wallet address.
+    pub from: Address,
+
+    /// Transaction receiver wallet address.
+    pub to: Address,
+
+    /// Transaction fee.
+    pub fee: f64,
+
+    /// Transaction amount.
+    pub amount: f64,
+
+    /// Identifier of the transferred asset.
+    #[serde(default = "native_asset")]
+    pub asset: String,
+
+    /// Purpose of the transaction.
+    #[serde(default)]
+    pub kind: TransactionKind,
+
+    /// Arbitrary data payload, e.g. the hash of a notarized document.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<u8>,
+
+    /// Credits of a multi-output transaction, whose amount is their total.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<TransactionOutput>,
+
+    /// Transaction timestamp.
+    pub timestamp: i64,
+
+    /// Version of the preimage of the hash.
+    #[serde(default, skip_serializing_if = "HashVersion::is_v1")]
+    pub hash_version: HashVersion,
+
+    /// Identifier of the chain the transaction belongs to, committed by `HashVersion::V2`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub chain_id: String,
+
+    /// Size in bytes of the canonical binary encoding of the transaction, see `encoded_size`,
+    /// recorded when it enters the mempool or is created by block production, or zero if not
+    /// recorded. Committed by the Merkle root of the block.
+    #[serde(default, skip_serializing_if = "is_unset")]
+    pub size: usize,
+}
+
+impl Transaction {
+    /// Create a new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The transaction sender address.
+    /// - `to`: The transaction receiver address.
+    /// - `fee`: The transaction fee.
+    /// - `amount`: The transaction amount.
+    ///
+    /// # Returns
+    ///
+    /// A new transaction with the given hash, sender, receiver, fee, amount, and timestamp.
+    #[cfg(feature = "std")]
+    pub fn new(from: Address, to: Address, fee: f64, amount: f64) -> Self {
+        Transaction::new_asset(from, to, fee, amount, NATIVE_ASSET)
+    }
+
+    /// Create a new transaction transferring a specific asset.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The transaction sender address.
+    /// - `to`: The transaction receiver address.
+    /// - `fee`: The transaction fee.
+    /// - `amount`: The transaction amount.
+    /// - `asset`: The identifier of the transferred asset.
+    ///
+    /// # Returns
+    ///
+    /// A new transaction of the given asset.
+    #[cfg(feature = "std")]
+    pub fn new_asset(from: Address, to: Address, fee: f64, amount: f64, asset: &str) -> Self {
+        let (id, timestamp) = (Uuid::new_v4(), Utc::now().timestamp());
+
+        Transaction::from_parts(id, timestamp, from, to, fee, amount, asset)
+    }
+
+    /// Create a new transaction from an identifier and a timestamp supplied by the caller,
+    /// e.g. without a random number generator or a system clock.
+    ///
+    /// # Arguments
+    ///
+    /// - `id`: The identifier of the transaction.
+    /// - `timestamp`: The timestamp of the transaction, in seconds since the Unix epoch.
+    /// - `from`: The transaction sender address.
+    /// - `to`: The transaction receiver address.
+    /// - `fee`: The transaction fee.
+    /// - `amount`: The transaction amount.
+    /// - `asset`: The identifier of the transferred asset.
+    ///
+    /// # Returns
+    ///
+    /// A new transaction of the given asset.
+    pub fn from_parts(
+        id: Uuid,
+        timestamp: i64,
+        from: Address,
+        to: Address,
+        fee: f64,
+        amount: f64,
+        asset: &str,
+    ) -> Self {
+        let mut transaction = Transaction {
+            id,
+            hash: Hash::zero(),
+            from,
+            to,
+            fee,
+            amount,
+            asset: asset.to_string(),
+            kind: TransactionKind::Transfer,
+            data: Vec::new(),
+            outputs: Vec::new(),
+            timestamp,
+            hash_version: HashVersion::V1,
+            chain_id: String::new(),
+            size: 0,
+        };
+
+        // Create a hash of the transaction
+        transaction.hash = transaction.compute_hash();
+
+        transaction
+    }
+
+    /// Decode a transaction from untrusted JSON bytes, e.g. received from a peer.
+    ///
+    /// This never panics on malformed input, which makes it a fuzzing entry point for the wire
+    /// format.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: The JSON encoding of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction, `ProtocolError` if it cannot be parsed, or `InvalidTransaction` if its
+    /// amounts are not finite, its data payload exceeds `MAX_DATA_SIZE`, its hash or recorded
+    /// size does not match or it is of a kind only created by block production.
+    #[cfg(feature = "std")]
+    pub fn decode_untrusted(bytes: &[u8]) -> Result<Self, BlockchainError> {
+        let transaction: Transaction =
+            serde_json::from_slice(bytes).map_err(|_| BlockchainError::ProtocolError)?;
+
+        if !transaction.fee.is_finite()
+            || !transaction.amount.is_finite()
+            || transaction.data.len() > MAX_DATA_SIZE
+            || transaction.hash != transaction.compute_hash()
+            || (transaction.size != 0 && transaction.size != transaction.encoded_size())
+            || transaction.kind.is_block_production()
+        {
+            return Err(BlockchainError::InvalidTransaction);
+        }
+
+        Ok(transaction)
+    }
+
+    /// Set the purpose of the transaction and update its hash.
+    ///
+    /// # Arguments
+    ///
+    /// - `kind`: The purpose of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction with the given kind.
+    pub fn with_kind(mut self, kind: TransactionKind) -> Self {
+        self.kind = kind;
+        self.hash = self.compute_hash();
+
+        self
+    }
+
+    /// Set the version of the hash preimage and the chain identifier, and update the hash.
+    ///
+    /// # Arguments
+    ///
+    /// - `version`: The version of the hash preimage.
+    /// - `chain_id`: The identifier of the chain, committed from `HashVersion::V2`.
+    ///
+    /// # Returns
+    ///
+    /// The transaction hashed with the given version.
+    pub fn with_hash_version(mut self, version: HashVersion, chain_id: &str) -> Self {
+        self.hash_version = version;
+        self.chain_id = match version {
+            HashVersion::V1 => String::new(),
+            HashVersion::V2 => chain_id.to_string(),
+        };
+        self.hash = self.compute_hash();
+
+        self
+    }
+
+    /// Attach a data payload to the transaction and update its hash.
+    ///
+    /// # Arguments
+    ///
+    /// - `data`: The data payload.
+    ///
+    /// # Returns
+    ///
+    /// The transaction with the given data payload.
+    pub fn with_data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self.hash = self.compute_hash();
+
+        self
+    }
+
+    /// Attach the credits of a multi-output transaction and update its hash.
+    ///
+    /// # Arguments
+    ///
+    /// - `outputs`: The credits of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction with the given outputs.
+    pub fn with_outputs(mut self, outputs: Vec<TransactionOutput>) -> Self {
+        self.outputs = outputs;
+        self.hash = self.compute_hash();
+
+        self
+    }
+
+    /// Calculate the size of the canonical binary encoding of the transaction.
+    ///
+    /// The recorded `size` is left out of the encoding, so the result does not depend on it.
+    ///
+    /// # Returns
+    ///
+    /// The size in bytes of the encoding.
+    pub fn encoded_size(&self) -> usize {
+        let size = |transaction: &Transaction| {
+            encoding::to_bytes(transaction).map_or(0, |bytes| bytes.len())
+        };
+
+        match self.size {
+            0 => size(self),
+            _ => size(&Transaction {
+                size: 0,
+                ..self.clone()
+            }),
+        }
+    }
+
+    /// Record the size of the canonical binary encoding of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction with its `size` set.
+    pub fn with_size(mut self) -> Self {
+        self.size = self.encoded_size();
+
+        self
+    }
+
+    /// Check whether an address sends or receives the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the address is the sender, the receiver or the receiver of an output.
+    pub fn involves(&self, address: &Address) -> bool {
+        self.from == *address
+            || self.to == *address
+            || self.outputs.iter().any(|output| output.to == *address)
+    }
+
+    /// Encode the data payload as lowercase hex.
+    ///
+    /// # Returns
+    ///
+    /// The hex-encoded data payload.
+    pub fn data_hex(&self) -> String {
+        hash::encode_hex(&self.data)
+    }
+
+    /// Calculate the hash of the transaction with the preimage of its hash version.
+    ///
+    /// Under `HashVersion::V1`, native coin transfers keep their original preimage, while other
+    /// assets, kinds, data payloads and outputs are included only when they differ from the
+    /// defaults.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the transaction.
+    pub fn compute_hash(&self) -> Hash {
+        let (from, to, amount, timestamp) = (&self.from, &self.to, self.amount, self.timestamp);
+
+        if self.hash_version == HashVersion::V2 {
+            return Hash::of(&(
+                self.hash_version,
+                &self.chain_id,
+                self.id,
+                from,
+                to,
+                self.fee,
+                amount,
+                &self.asset,
+                self.kind,
+                &self.data,
+                &self.outputs,
+                timestamp,
+            ));
+        }
+
+        match (self.asset == NATIVE_ASSET, self.kind) {
+            (true, TransactionKind::Transfer) => Hash::of(&(from, to, amount, timestamp)),
+            (false, TransactionKind::Transfer) => {
+                Hash::of(&(from, to, amount, timestamp, &self.asset))
+            }
+            (_, kind) if self.data.is_empty() && self.outputs.is_empty() => {
+                Hash::of(&(from, to, amount, timestamp, &self.asset, kind))
+            }
+            (_, kind) if self.outputs.is_empty() => {
+                Hash::of(&(from, to, amount, timestamp, &self.asset, kind, &self.data))
+            }
+            (_, kind) => Hash::of(&(
+                from,
+                to,
+                amount,
+                timestamp,
+                &self.asset,
+                kind,
+                &self.data,
+                &self.outputs,
+            )),
+        }
+    }
+}
+
+/// Identifier of the native coin, used for transactions created before assets were tracked.
+fn native_asset() -> String {
+    NATIVE_ASSET.to_string()
+}
+
+/// Check whether the size of a transaction was not recorded.
+fn is_unset(size: &usize) -> bool {
+    *size == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_transaction() {
+        let from = Address::random();
+        let to = Address::random();
+        let fee = 0.1;
+        let amount = 100.0;
+        let transaction = Transaction::new(from.to_owned(), to.to_owned(), fee, amount);
+
+        assert_eq!(transaction.from, from);
+        assert_eq!(transaction.to, to);
+        assert_eq!(transaction.fee, fee);
+        assert_eq!(transaction.amount, amount);
+        assert_eq!(transaction.asset, NATIVE_ASSET);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let (from, to) = (Address::root(), Address::random());
+        let id = Uuid::nil();
+        let transaction =
+            Transaction::from_parts(id, 1, from.clone(), to.clone(), 0.1, 1.0, "GOLD");
+
+        assert_eq!(transaction.id, id);
+        assert_eq!(transaction.timestamp, 1);
+        assert_eq!(transaction.asset, "GOLD");
+        assert_eq!(transaction.hash, transaction.compute_hash());
+        assert_eq!(
+            transaction.hash,
+            Transaction::from_parts(id, 1, from, to, 0.1, 1.0, "GOLD").hash
+        );
+    }
+
+    #[test]
+    fn test_new_asset_transaction() {
+        let from = Address::random();
+        let to = Address::random();
+        let native = Transaction::new(from.to_owned(), to.to_owned(), 0.0, 1.0);
+        let token = Transaction::new_asset(from, to, 0.0, 1.0, "GOLD");
+
+        assert_eq!(token.asset, "GOLD");
+        assert_ne!(token.hash, native.hash);
+    }
+
+    #[test]
+    fn test_with_kind() {
+        let transaction = Transaction::new(Address::root(), Address::random(), 0.0, 1.0);
+        let reward = transaction.clone().with_kind(TransactionKind::Reward);
+
+        assert_eq!(transaction.kind, TransactionKind::Transfer);
+        assert_eq!(reward.kind, TransactionKind::Reward);
+        assert_eq!(reward.hash, reward.compute_hash());
+        assert_ne!(reward.hash, transaction.hash);
+    }
+
+    #[test]
+    fn test_with_hash_version() {
+        let transaction = Transaction::new(Address::random(), Address::random(), 0.1, 1.0);
+        let v2 = transaction
+            .clone()
+            .with_hash_version(HashVersion::V2, "main");
+
+        assert_ne!(v2.hash, transaction.hash);
+        assert_eq!(v2.hash, v2.compute_hash());
+        assert_ne!(
+            v2.clone().with_hash_version(HashVersion::V2, "test").hash,
+            v2.hash
+        );
+
+        let mut fee = v2.clone();
+        fee.fee = 0.2;
+        assert_ne!(fee.compute_hash(), v2.hash);
+
+        let v1 = v2.with_hash_version(HashVersion::V1, "main");
+        assert_eq!(v1.hash, transaction.hash);
+        assert!(v1.chain_id.is_empty());
+    }
+
+    #[test]
+    fn test_with_data() {
+        let transaction = Transaction::new(Address::random(), Address::root(), 0.0, 0.0)
+            .with_kind(TransactionKind::Anchor);
+        let anchor = transaction.clone().with_data(vec![0x0a, 0xff]);
+
+        assert_eq!(anchor.data_hex(), "0aff");
+        assert_eq!(anchor.hash, anchor.compute_hash());
+        assert_ne!(anchor.hash, transaction.hash);
+    }
+
+    #[test]
+    fn test_decode_untrusted() {
+        let transaction = Transaction::new(Address::random(), Address::random(), 0.1, 1.0);
+        let bytes = serde_json::to_vec(&transaction).unwrap();
+
+        assert_eq!(
+            Transaction::decode_untrusted(&bytes).unwrap().hash,
+            transaction.hash
+        );
+        assert_eq!(
+            Transaction::decode_untrusted(&bytes[1..]).unwrap_err(),
+            BlockchainError::ProtocolError
+        );
+
+        let mut tampered = transaction.clone();
+        tampered.amount = 2.0;
+        let bytes = serde_json::to_vec(&tampered).unwrap();
+
+        assert_eq!(
+            Transaction::decode_untrusted(&bytes).unwrap_err(),
+            BlockchainError::InvalidTransaction
+        );
+
+        let reward = transaction.with_kind(TransactionKind::Reward);
+        let bytes = serde_json::to_vec(&reward).unwrap();
+
+        assert_eq!(
+            Transaction::decode_untrusted(&bytes).unwrap_err(),
+            BlockchainError::InvalidTransaction
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError};
+
+/// Transfer attempt rejected by the chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeadLetter {
+    /// The sender's address.
+    pub from: Address,
+
+    /// The receiver's address.
+    pub to: Address,
+
+    /// The amount of the attempted transfer.
+    pub amount: f64,
+
+    /// Description of the error the transfer was rejected with.
+    pub reason: String,
+
+    /// Time of the attempt.
+    pub timestamp: i64,
+}
+
+/// Bounded store of rejected transfer attempts, dropping the oldest attempt when full.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeadLetterQueue {
+    /// Maximum number of stored attempts.
+    capacity: usize,
+
+    /// Stored attempts, oldest first.
+    entries: VecDeque<DeadLetter>,
+}
+
+impl DeadLetterQueue {
+    /// Create an empty queue.
+    ///
+    /// # Arguments
+    ///
+    /// - `capacity`: The maximum number of stored attempts.
+    ///
+    /// # Returns
+    ///
+    /// The queue, or `InvalidConfiguration` if the capacity is zero.
+    pub fn new(capacity: usize) -> Result<Self, BlockchainError> {
+        if capacity == 0 {
+            return Err(BlockchainError::InvalidConfiguration);
+        }
+
+        Ok(DeadLetterQueue {
+            capacity,
+            entries: VecDeque::new(),
+        })
+    }
+
+    /// Store a rejected attempt, dropping the oldest one if the queue is full.
+    ///
+    /// # Arguments
+    ///
+    /// - `letter`: The rejected attempt.
+    pub fn push(&mut self, letter: DeadLetter) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(letter);
+    }
+
+    /// Get the attempts sent or received by a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// The attempts involving the wallet, oldest first.
+    pub fn for_wallet(&self, address: &Address) -> Vec<&DeadLetter> {
+        self.entries
+            .iter()
+            .filter(|letter| letter.from == *address || letter.to == *address)
+            .collect()
+    }
+
+    /// Get all stored attempts.
+    ///
+    /// # Returns
+    ///
+    /// The attempts, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &DeadLetter> {
+        self.entries.iter()
+    }
+
+    /// Get the maximum number of stored attempts.
+    ///
+    /// # Returns
+    ///
+    /// The capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the number of stored attempts.
+    ///
+    /// # Returns
+    ///
+    /// The number of attempts.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the queue is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no attempt is stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letter(from: &Address, timestamp: i64) -> DeadLetter {
+        DeadLetter {
+            from: from.to_owned(),
+            to: Address::random(),
+            amount: 1.0,
+            reason: "Invalid amount".to_string(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_push_drops_oldest() {
+        assert!(DeadLetterQueue::new(0).is_err());
+
+        let (alice, bob) = (Address::random(), Address::random());
+        let mut queue = DeadLetterQueue::new(2).unwrap();
+        queue.push(letter(&alice, 1));
+        queue.push(letter(&bob, 2));
+        queue.push(letter(&alice, 3));
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.entries().next().unwrap().timestamp, 2);
+        assert_eq!(queue.for_wallet(&alice).len(), 1);
+        assert_eq!(queue.for_wallet(&bob)[0].timestamp, 2);
+    }
+}
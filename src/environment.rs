@@ -0,0 +1,230 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+
+use chrono::Utc;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use uuid::{Builder, Uuid};
+
+use crate::{Address, AddressScheme, RandomScheme};
+
+/// Source of the current time of a chain.
+pub trait Clock: Send + Sync {
+    /// Get the current time.
+    ///
+    /// # Returns
+    ///
+    /// The number of seconds since the Unix epoch.
+    fn now(&self) -> i64;
+}
+
+/// Clock reading the system time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        Utc::now().timestamp()
+    }
+}
+
+/// Clock only moving when it is set or advanced, for tests and simulations.
+#[derive(Debug, Default)]
+pub struct ManualClock(AtomicI64);
+
+impl ManualClock {
+    /// Create a new clock.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: The initial time, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// A new clock.
+    pub fn new(now: i64) -> Self {
+        ManualClock(AtomicI64::new(now))
+    }
+
+    /// Set the time.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: The new time, in seconds since the Unix epoch.
+    pub fn set(&self, now: i64) {
+        self.0.store(now, Ordering::SeqCst);
+    }
+
+    /// Move the time forward.
+    ///
+    /// # Arguments
+    ///
+    /// - `seconds`: The number of seconds to add.
+    pub fn advance(&self, seconds: i64) {
+        self.0.fetch_add(seconds, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Sources of randomness and time used by a chain to generate addresses, identifiers and
+/// timestamps.
+///
+/// The default environment uses the thread-local random number generator and the system clock.
+/// A seeded environment makes a sequence of operations reproducible; cloning it clones the state
+/// of its random number generator, so a cloned chain generates the same values as the original.
+#[derive(Clone, Default)]
+pub struct Environment {
+    /// Random number generator, or `None` for the thread-local one.
+    rng: Option<StdRng>,
+
+    /// Clock, or `None` for the system clock.
+    clock: Option<Arc<dyn Clock>>,
+
+    /// Address scheme, or `None` for random addresses.
+    address_scheme: Option<Arc<dyn AddressScheme>>,
+}
+
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Environment")
+            .field("seeded", &self.rng.is_some())
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+impl Environment {
+    /// Create a deterministic environment.
+    ///
+    /// # Arguments
+    ///
+    /// - `seed`: The seed of the random number generator.
+    /// - `clock`: The clock.
+    ///
+    /// # Returns
+    ///
+    /// A new environment.
+    pub fn seeded(seed: u64, clock: Arc<dyn Clock>) -> Self {
+        Environment {
+            rng: Some(StdRng::seed_from_u64(seed)),
+            clock: Some(clock),
+            address_scheme: None,
+        }
+    }
+
+    /// Replace the clock.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock`: The new clock.
+    ///
+    /// # Returns
+    ///
+    /// The environment with the given clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Replace the address scheme.
+    ///
+    /// # Arguments
+    ///
+    /// - `scheme`: The new address scheme.
+    ///
+    /// # Returns
+    ///
+    /// The environment with the given address scheme.
+    pub fn with_address_scheme(mut self, scheme: Arc<dyn AddressScheme>) -> Self {
+        self.address_scheme = Some(scheme);
+        self
+    }
+
+    /// Check whether the environment is deterministic.
+    ///
+    /// # Returns
+    ///
+    /// `true` if both the random number generator and the clock were provided, `false` otherwise.
+    pub fn is_deterministic(&self) -> bool {
+        self.rng.is_some() && self.clock.is_some()
+    }
+
+    /// Get the current time.
+    ///
+    /// # Returns
+    ///
+    /// The number of seconds since the Unix epoch.
+    pub fn now(&self) -> i64 {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => SystemClock.now(),
+        }
+    }
+
+    /// Generate a new address.
+    ///
+    /// # Returns
+    ///
+    /// A new address of the address scheme, random by default.
+    pub fn address(&mut self) -> Address {
+        let scheme = self.address_scheme.as_deref().unwrap_or(&RandomScheme);
+
+        match &mut self.rng {
+            Some(rng) => scheme.generate(rng),
+            None => scheme.generate(&mut rand::thread_rng()),
+        }
+    }
+
+    /// Generate a new version 4 UUID.
+    ///
+    /// # Returns
+    ///
+    /// A random UUID.
+    pub fn uuid(&mut self) -> Uuid {
+        match &mut self.rng {
+            Some(rng) => Builder::from_random_bytes(rng.gen()).into_uuid(),
+            None => Uuid::new_v4(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let clock = Arc::new(ManualClock::new(10));
+        let mut first = Environment::seeded(1, clock.clone());
+        let mut second = Environment::seeded(1, clock.clone());
+
+        assert!(first.is_deterministic());
+        assert_eq!(first.address(), second.address());
+        assert_eq!(first.uuid(), second.uuid());
+        assert_ne!(first.uuid(), first.uuid());
+        assert_eq!(first.uuid().get_version_num(), 4);
+
+        clock.advance(5);
+        assert_eq!(first.now(), 15);
+        clock.set(3);
+        assert_eq!(second.now(), 3);
+    }
+
+    #[test]
+    fn test_default() {
+        let mut environment = Environment::default();
+
+        assert!(!environment.is_deterministic());
+        assert_ne!(environment.address(), environment.address());
+        assert!((environment.now() - Utc::now().timestamp()).abs() <= 1);
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Address;
+
+/// Faucet funding test wallets on development chains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Faucet {
+    /// Address of the wallet the funds are sent from.
+    pub address: Address,
+
+    /// Minimum number of seconds between two claims of the same address.
+    pub cooldown: i64,
+
+    /// Maximum amount of a single claim.
+    pub max_amount: f64,
+
+    /// Time of the last claim of each address.
+    #[serde(default)]
+    pub claims: BTreeMap<Address, i64>,
+}
+
+impl Faucet {
+    /// Create a faucet sending funds from a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the funded wallet.
+    /// - `cooldown`: The minimum number of seconds between two claims of the same address.
+    /// - `max_amount`: The maximum amount of a single claim.
+    ///
+    /// # Returns
+    ///
+    /// A new faucet without claims.
+    pub fn new(address: Address, cooldown: i64, max_amount: f64) -> Self {
+        Faucet {
+            address,
+            cooldown,
+            max_amount,
+            claims: BTreeMap::default(),
+        }
+    }
+
+    /// Get the time an address may claim funds again.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The claiming address.
+    ///
+    /// # Returns
+    ///
+    /// The time the cooldown of the address ends, or `None` if it never claimed funds.
+    pub fn next_claim(&self, address: &Address) -> Option<i64> {
+        self.claims
+            .get(address)
+            .map(|claimed| claimed.saturating_add(self.cooldown))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_claim() {
+        let address = Address::random();
+        let mut faucet = Faucet::new(Address::random(), 60, 10.0);
+
+        assert_eq!(faucet.next_claim(&address), None);
+
+        faucet.claims.insert(address.clone(), 100);
+        assert_eq!(faucet.next_claim(&address), Some(160));
+    }
+}
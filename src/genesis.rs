@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Address;
+
+/// Parameters of a network, shared by its nodes so that they all derive the same genesis block.
+///
+/// Nothing in the genesis block is random or read from the clock: the reward goes to the
+/// configured address, at the configured time, in a transaction with the nil identifier.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GenesisConfig {
+    /// Initial mining difficulty level of the network.
+    pub difficulty: f64,
+
+    /// Initial block reward for miners.
+    pub reward: f64,
+
+    /// Transaction fee.
+    pub fee: f64,
+
+    /// Genesis address, credited with the block rewards.
+    pub address: Address,
+
+    /// Timestamp of the genesis block, in seconds since the Unix epoch.
+    pub timestamp: i64,
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Address, ChainWallets};
+
+/// Minimum share of the total wallet balance that must vote for a proposal to be valid.
+pub const GOVERNANCE_QUORUM: f64 = 0.5;
+
+/// Chain parameter change requested by a proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ProposalChange {
+    /// Set the transaction fee.
+    Fee(f64),
+
+    /// Set the block reward.
+    Reward(f64),
+
+    /// Set the mining difficulty.
+    Difficulty(f64),
+}
+
+impl ProposalChange {
+    /// Validate the requested parameter value.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the value is finite and not negative.
+    pub fn is_valid(&self) -> bool {
+        let value = match self {
+            ProposalChange::Fee(value)
+            | ProposalChange::Reward(value)
+            | ProposalChange::Difficulty(value) => *value,
+        };
+
+        value.is_finite() && value >= 0.0
+    }
+}
+
+/// Lifecycle state of a proposal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ProposalStatus {
+    /// Proposal is accepting votes.
+    #[default]
+    Open,
+
+    /// Proposal passed and its change was applied.
+    Passed,
+
+    /// Proposal did not reach the quorum or a majority.
+    Rejected,
+}
+
+/// Proposal to change a chain parameter, decided by balance-weighted voting.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Proposal {
+    /// Unique identifier of the proposal.
+    pub id: Uuid,
+
+    /// Address of the wallet that submitted the proposal.
+    pub proposer: Address,
+
+    /// Requested parameter change.
+    pub change: ProposalChange,
+
+    /// Height of the block at which votes are tallied and the change takes effect.
+    pub activation_height: usize,
+
+    /// Votes cast, `true` for approval, keyed by voter address.
+    pub votes: BTreeMap<Address, bool>,
+
+    /// Lifecycle state of the proposal.
+    pub status: ProposalStatus,
+
+    /// Timestamp at which the proposal was submitted.
+    pub timestamp: i64,
+}
+
+impl Proposal {
+    /// Create a new open proposal.
+    ///
+    /// # Arguments
+    ///
+    /// - `proposer`: The address of the submitting wallet.
+    /// - `change`: The requested parameter change.
+    /// - `activation_height`: The height of the block at which the change takes effect.
+    ///
+    /// # Returns
+    ///
+    /// A new proposal without votes.
+    pub fn new(proposer: &Address, change: ProposalChange, activation_height: usize) -> Self {
+        Proposal {
+            id: Uuid::new_v4(),
+            proposer: proposer.to_owned(),
+            change,
+            activation_height,
+            votes: BTreeMap::new(),
+            status: ProposalStatus::Open,
+            timestamp: Utc::now().timestamp(),
+        }
+    }
+
+    /// Tally the votes weighted by the current wallet balances.
+    ///
+    /// Balances are read at tally time, so coins moved to another wallet after voting
+    /// are only counted once.
+    ///
+    /// # Arguments
+    ///
+    /// - `wallets`: The wallets of the chain.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the votes reach the quorum and the approving weight exceeds the rejecting weight.
+    pub fn tally(&self, wallets: &ChainWallets) -> bool {
+        let total: f64 = wallets.values().map(|wallet| wallet.balance).sum();
+        let (mut yes, mut no) = (0.0, 0.0);
+
+        for (address, approve) in self.votes.iter() {
+            let weight = wallets.get(address).map_or(0.0, |wallet| wallet.balance);
+
+            match approve {
+                true => yes += weight,
+                false => no += weight,
+            }
+        }
+
+        total > 0.0 && (yes + no) / total >= GOVERNANCE_QUORUM && yes > no
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::Wallet;
+
+    fn wallets(balances: &[f64]) -> (ChainWallets, Vec<Address>) {
+        let mut wallets: ChainWallets = HashMap::default();
+        let mut addresses = vec![];
+
+        for balance in balances {
+            let mut wallet = Wallet::new("a@mail.com", &Address::random());
+            wallet.balance = *balance;
+            addresses.push(wallet.address.to_owned());
+            wallets.insert(wallet.address.to_owned(), wallet);
+        }
+
+        (wallets, addresses)
+    }
+
+    #[test]
+    fn test_change_is_valid() {
+        assert!(ProposalChange::Fee(0.0).is_valid());
+        assert!(!ProposalChange::Reward(-1.0).is_valid());
+        assert!(!ProposalChange::Difficulty(f64::NAN).is_valid());
+    }
+
+    #[test]
+    fn test_tally_weighted_majority() {
+        let (wallets, addresses) = wallets(&[10.0, 3.0, 3.0]);
+        let mut proposal = Proposal::new(&addresses[1], ProposalChange::Fee(0.2), 5);
+
+        proposal.votes.insert(addresses[0].to_owned(), true);
+        proposal.votes.insert(addresses[1].to_owned(), false);
+        proposal.votes.insert(addresses[2].to_owned(), false);
+
+        assert!(proposal.tally(&wallets));
+    }
+
+    #[test]
+    fn test_tally_quorum() {
+        let (wallets, addresses) = wallets(&[1.0, 10.0]);
+        let mut proposal = Proposal::new(&addresses[0], ProposalChange::Fee(0.2), 5);
+
+        proposal.votes.insert(addresses[0].to_owned(), true);
+
+        assert!(!proposal.tally(&wallets));
+    }
+}
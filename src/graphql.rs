@@ -0,0 +1,308 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema};
+use axum::{extract::State, routing::post, Json, Router};
+
+use crate::{Address, Block, Chain, Hash, Transaction, Wallet};
+
+/// Default number of items returned by list fields.
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Maximum number of items returned by list fields.
+pub const MAX_LIMIT: usize = 100;
+
+/// Chain shared between the resolvers.
+pub type GraphqlState = Arc<Mutex<Chain>>;
+
+/// GraphQL schema over the chain.
+pub type ChainSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the GraphQL schema over a shared chain.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+///
+/// # Returns
+///
+/// A new schema.
+pub fn schema(chain: GraphqlState) -> ChainSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(chain)
+        .finish()
+}
+
+/// Build an HTTP router serving GraphQL queries on `POST /graphql`.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+///
+/// # Returns
+///
+/// A new router.
+pub fn router(chain: GraphqlState) -> Router {
+    Router::new()
+        .route("/graphql", post(serve_http))
+        .with_state(schema(chain))
+}
+
+/// Execute a GraphQL query received over HTTP.
+async fn serve_http(
+    State(schema): State<ChainSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+/// Lock the shared chain of the context, recovering from a poisoned lock.
+fn lock<'a>(ctx: &Context<'a>) -> Result<MutexGuard<'a, Chain>> {
+    let chain = ctx.data::<GraphqlState>()?;
+
+    Ok(chain.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Clamp the requested number of items of a list field.
+fn clamp(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+}
+
+/// Root of the explorer queries.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Number of blocks in the chain.
+    async fn height(&self, ctx: &Context<'_>) -> Result<usize> {
+        Ok(lock(ctx)?.chain.len())
+    }
+
+    /// Block at a height.
+    async fn block(&self, ctx: &Context<'_>, height: usize) -> Result<Option<BlockNode>> {
+        Ok(lock(ctx)?.chain.get(height).cloned().map(BlockNode))
+    }
+
+    /// Most recent blocks, newest first.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<BlockNode>> {
+        let chain = lock(ctx)?;
+
+        Ok(chain
+            .chain
+            .iter()
+            .rev()
+            .skip(offset)
+            .take(clamp(limit))
+            .cloned()
+            .map(BlockNode)
+            .collect())
+    }
+
+    /// Transaction by hash.
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> Result<Option<TransactionNode>> {
+        let hash: Hash = hash.parse()?;
+
+        Ok(lock(ctx)?
+            .transactions
+            .get(&hash)
+            .cloned()
+            .map(TransactionNode))
+    }
+
+    /// Wallet by address.
+    async fn wallet(&self, ctx: &Context<'_>, address: String) -> Result<Option<WalletNode>> {
+        let address: Address = address.parse()?;
+
+        Ok(lock(ctx)?.wallets.get(&address).cloned().map(WalletNode))
+    }
+}
+
+/// Block exposed to GraphQL.
+pub struct BlockNode(pub Block);
+
+#[Object(name = "Block")]
+impl BlockNode {
+    /// Position of the block in the chain.
+    async fn height(&self) -> usize {
+        self.0.height
+    }
+
+    /// Hash of the block header.
+    async fn hash(&self) -> String {
+        self.0.hash.to_string()
+    }
+
+    /// Hash of the previous block.
+    async fn previous_hash(&self) -> String {
+        self.0.header.previous_hash.to_string()
+    }
+
+    /// Merkle root of the transactions.
+    async fn merkle(&self) -> String {
+        self.0.header.merkle.to_string()
+    }
+
+    /// Merkle root of the wallet state.
+    async fn state_root(&self) -> String {
+        self.0.header.state_root.to_string()
+    }
+
+    /// Timestamp at which the block was mined.
+    async fn timestamp(&self) -> i64 {
+        self.0.header.timestamp
+    }
+
+    /// Proof-of-work nonce.
+    async fn nonce(&self) -> u32 {
+        self.0.header.nonce
+    }
+
+    /// Difficulty the block was mined at.
+    async fn difficulty(&self) -> f64 {
+        self.0.header.difficulty
+    }
+
+    /// Whether the block body is not available locally.
+    async fn pruned(&self) -> bool {
+        self.0.pruned
+    }
+
+    /// Size in bytes of the header and the transactions in their canonical binary encoding.
+    async fn size(&self) -> usize {
+        self.0.size
+    }
+
+    /// Transactions of the block, oldest first.
+    async fn transactions(&self) -> Vec<TransactionNode> {
+        let mut transactions: Vec<Transaction> = self.0.transactions.values().cloned().collect();
+        transactions.sort_by_key(|tx| (tx.timestamp, tx.hash));
+
+        transactions.into_iter().map(TransactionNode).collect()
+    }
+}
+
+/// Transaction exposed to GraphQL.
+pub struct TransactionNode(pub Transaction);
+
+#[Object(name = "Transaction")]
+impl TransactionNode {
+    /// Hash of the transaction.
+    async fn hash(&self) -> String {
+        self.0.hash.to_string()
+    }
+
+    /// Sender address.
+    async fn from(&self) -> String {
+        self.0.from.to_string()
+    }
+
+    /// Receiver address.
+    async fn to(&self) -> String {
+        self.0.to.to_string()
+    }
+
+    /// Transaction fee.
+    async fn fee(&self) -> f64 {
+        self.0.fee
+    }
+
+    /// Transaction amount.
+    async fn amount(&self) -> f64 {
+        self.0.amount
+    }
+
+    /// Identifier of the transferred asset.
+    async fn asset(&self) -> &str {
+        &self.0.asset
+    }
+
+    /// Purpose of the transaction.
+    async fn kind(&self) -> String {
+        format!("{:?}", self.0.kind)
+    }
+
+    /// Data payload, hex-encoded.
+    async fn data(&self) -> String {
+        self.0.data_hex()
+    }
+
+    /// Timestamp of the transaction.
+    async fn timestamp(&self) -> i64 {
+        self.0.timestamp
+    }
+
+    /// Size in bytes of the transaction in its canonical binary encoding.
+    async fn size(&self) -> usize {
+        self.0.encoded_size()
+    }
+
+    /// Wallet of the sender, if it is known to the chain.
+    async fn sender(&self, ctx: &Context<'_>) -> Result<Option<WalletNode>> {
+        Ok(lock(ctx)?
+            .wallets
+            .get(&self.0.from)
+            .cloned()
+            .map(WalletNode))
+    }
+
+    /// Wallet of the receiver, if it is known to the chain.
+    async fn receiver(&self, ctx: &Context<'_>) -> Result<Option<WalletNode>> {
+        Ok(lock(ctx)?.wallets.get(&self.0.to).cloned().map(WalletNode))
+    }
+}
+
+/// Wallet exposed to GraphQL.
+pub struct WalletNode(pub Wallet);
+
+#[Object(name = "Wallet")]
+impl WalletNode {
+    /// Address of the wallet.
+    async fn address(&self) -> String {
+        self.0.address.to_string()
+    }
+
+    /// Email of the wallet owner.
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    /// Native balance of the wallet.
+    async fn balance(&self) -> f64 {
+        self.0.balance
+    }
+
+    /// Balance of a registered token.
+    async fn asset_balance(&self, asset: String) -> f64 {
+        self.0.balance_of(&asset)
+    }
+
+    /// Most recent transactions of the wallet, newest first.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<TransactionNode>> {
+        let chain = lock(ctx)?;
+
+        Ok(self
+            .0
+            .transaction_hashes
+            .iter()
+            .rev()
+            .filter_map(|hash| chain.transactions.get(hash))
+            .skip(offset)
+            .take(clamp(limit))
+            .cloned()
+            .map(TransactionNode)
+            .collect())
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tonic::{transport::Server, Request, Response, Status};
+
+use crate::{Address, Block, BlockchainError, Chain, Hash, Transaction};
+
+use self::proto::blockchain_server::{Blockchain, BlockchainServer};
+
+/// Messages and services generated from `proto/blockchain.proto`.
+pub mod proto {
+    tonic::include_proto!("blockchain");
+}
+
+/// Number of blocks buffered for each subscriber before older blocks are dropped.
+pub const BLOCK_CHANNEL_CAPACITY: usize = 64;
+
+/// Stream of blocks returned by `SubscribeBlocks`.
+pub type BlockStream = Pin<Box<dyn Stream<Item = Result<proto::Block, Status>> + Send>>;
+
+impl From<BlockchainError> for Status {
+    fn from(error: BlockchainError) -> Self {
+        let message = error.to_string();
+
+        match error {
+            BlockchainError::TransactionNotFound { .. }
+            | BlockchainError::WalletNotFound { .. }
+            | BlockchainError::BlockNotFound
+            | BlockchainError::TokenNotFound
+            | BlockchainError::NftNotFound
+            | BlockchainError::ContractNotFound
+            | BlockchainError::ProposalNotFound
+            | BlockchainError::FeedNotFound
+            | BlockchainError::ChainNotFound { .. } => Status::not_found(message),
+            BlockchainError::Unauthorized | BlockchainError::ScriptFailed => {
+                Status::permission_denied(message)
+            }
+            BlockchainError::NetworkError => Status::unavailable(message),
+            BlockchainError::Unauthenticated => Status::unauthenticated(message),
+            BlockchainError::RateLimited | BlockchainError::FaucetCooldown { .. } => {
+                Status::resource_exhausted(message)
+            }
+            _ => Status::invalid_argument(message),
+        }
+    }
+}
+
+impl From<&Transaction> for proto::Transaction {
+    fn from(transaction: &Transaction) -> Self {
+        proto::Transaction {
+            hash: transaction.hash.to_string(),
+            from: transaction.from.to_string(),
+            to: transaction.to.to_string(),
+            fee: transaction.fee,
+            amount: transaction.amount,
+            asset: transaction.asset.clone(),
+            kind: format!("{:?}", transaction.kind),
+            data: transaction.data.clone(),
+            timestamp: transaction.timestamp,
+        }
+    }
+}
+
+impl From<&Block> for proto::Block {
+    fn from(block: &Block) -> Self {
+        let mut transactions: Vec<&Transaction> = block.transactions.values().collect();
+        transactions.sort_by_key(|tx| (tx.timestamp, tx.hash));
+
+        proto::Block {
+            height: block.height as u64,
+            hash: block.hash.to_string(),
+            previous_hash: block.header.previous_hash.to_string(),
+            merkle: block.header.merkle.to_string(),
+            timestamp: block.header.timestamp,
+            nonce: block.header.nonce,
+            difficulty: block.header.difficulty,
+            state_root: block.header.state_root.to_string(),
+            contract_root: block.header.contract_root.to_string(),
+            transactions: transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// gRPC service wrapping a shared chain.
+///
+/// Implements the `blockchain.Blockchain` service of `proto/blockchain.proto`, including
+/// `SubscribeBlocks`, which streams every block mined through the service or announced
+/// with `publish_block`.
+#[derive(Clone)]
+pub struct GrpcService {
+    /// Chain shared with the application.
+    chain: Arc<Mutex<Chain>>,
+
+    /// Sender of the blocks streamed to the subscribers.
+    blocks: broadcast::Sender<Block>,
+}
+
+impl GrpcService {
+    /// Create a new service.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain shared with the application.
+    ///
+    /// # Returns
+    ///
+    /// A new service.
+    pub fn new(chain: Arc<Mutex<Chain>>) -> Self {
+        let (blocks, _) = broadcast::channel(BLOCK_CHANNEL_CAPACITY);
+
+        GrpcService { chain, blocks }
+    }
+
+    /// Announce a block added to the chain outside of the service, e.g. imported from a peer.
+    ///
+    /// # Arguments
+    ///
+    /// - `block`: The new block.
+    pub fn publish_block(&self, block: &Block) {
+        // Sending only fails when there are no subscribers
+        let _ = self.blocks.send(block.clone());
+    }
+
+    /// Wrap the service in a tonic server.
+    ///
+    /// # Returns
+    ///
+    /// The service ready to be added to a `tonic::transport::Server`.
+    pub fn into_server(self) -> BlockchainServer<Self> {
+        BlockchainServer::new(self)
+    }
+
+    /// Serve gRPC requests until the server fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to listen on.
+    ///
+    /// # Returns
+    ///
+    /// An error if the server cannot be started.
+    pub async fn serve(self, address: SocketAddr) -> Result<(), BlockchainError> {
+        Server::builder()
+            .add_service(self.into_server())
+            .serve(address)
+            .await
+            .map_err(|_| BlockchainError::NetworkError)
+    }
+
+    /// Lock the shared chain, recovering from a poisoned lock.
+    fn lock(&self) -> MutexGuard<'_, Chain> {
+        self.chain.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[tonic::async_trait]
+impl Blockchain for GrpcService {
+    type SubscribeBlocksStream = BlockStream;
+
+    async fn get_block_count(
+        &self,
+        _: Request<proto::GetBlockCountRequest>,
+    ) -> Result<Response<proto::GetBlockCountResponse>, Status> {
+        let count = self.lock().chain.len() as u64;
+
+        Ok(Response::new(proto::GetBlockCountResponse { count }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::Block>, Status> {
+        let chain = self.lock();
+        let block = usize::try_from(request.into_inner().height)
+            .ok()
+            .and_then(|height| chain.chain.get(height))
+            .ok_or(BlockchainError::BlockNotFound)?;
+
+        Ok(Response::new(block.into()))
+    }
+
+    async fn get_last_hash(
+        &self,
+        _: Request<proto::GetLastHashRequest>,
+    ) -> Result<Response<proto::GetLastHashResponse>, Status> {
+        let hash = self.lock().get_last_hash().to_string();
+
+        Ok(Response::new(proto::GetLastHashResponse { hash }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<proto::GetTransactionRequest>,
+    ) -> Result<Response<proto::Transaction>, Status> {
+        let hash: Hash = request.into_inner().hash.parse()?;
+        let chain = self.lock();
+
+        Ok(Response::new(chain.get_transaction(&hash)?.into()))
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<proto::GetBalanceRequest>,
+    ) -> Result<Response<proto::GetBalanceResponse>, Status> {
+        let address: Address = request.into_inner().address.parse()?;
+        let balance = self.lock().get_wallet_balance(&address).ok_or_else(|| {
+            BlockchainError::WalletNotFound {
+                address: address.to_owned(),
+            }
+        })?;
+
+        Ok(Response::new(proto::GetBalanceResponse { balance }))
+    }
+
+    async fn create_wallet(
+        &self,
+        request: Request<proto::CreateWalletRequest>,
+    ) -> Result<Response<proto::CreateWalletResponse>, Status> {
+        let address = self.lock().create_wallet(&request.into_inner().email);
+
+        Ok(Response::new(proto::CreateWalletResponse {
+            address: address.to_string(),
+        }))
+    }
+
+    async fn send_transaction(
+        &self,
+        request: Request<proto::SendTransactionRequest>,
+    ) -> Result<Response<proto::SendTransactionResponse>, Status> {
+        let request = request.into_inner();
+        let from: Address = request.from.parse()?;
+        let to: Address = request.to.parse()?;
+
+        let mut chain = self.lock();
+        chain.add_transaction(&from, &to, request.amount)?;
+
+        // The new transaction is the last one recorded for the sender
+        let hash = chain
+            .wallets
+            .get(&from)
+            .and_then(|wallet| wallet.transaction_hashes.last())
+            .map(ToString::to_string)
+            .unwrap_or_default();
+
+        Ok(Response::new(proto::SendTransactionResponse { hash }))
+    }
+
+    async fn generate_block(
+        &self,
+        _: Request<proto::GenerateBlockRequest>,
+    ) -> Result<Response<proto::Block>, Status> {
+        let block = {
+            let mut chain = self.lock();
+            chain.generate_new_block();
+            chain
+                .chain
+                .last()
+                .cloned()
+                .ok_or(BlockchainError::BlockNotFound)?
+        };
+
+        self.publish_block(&block);
+
+        Ok(Response::new((&block).into()))
+    }
+
+    // The item type of the stream is dictated by tonic
+    #[allow(clippy::result_large_err)]
+    async fn subscribe_blocks(
+        &self,
+        _: Request<proto::SubscribeBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+        // Subscribers that fall behind skip the blocks dropped from the channel
+        let stream = BroadcastStream::new(self.blocks.subscribe())
+            .filter_map(|block| block.ok())
+            .map(|block| Ok(proto::Block::from(&block)));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
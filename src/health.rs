@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{BlockchainError, Mempool};
+
+/// Status of a component of a node.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ComponentStatus {
+    /// Status not reported.
+    #[default]
+    Unknown,
+
+    /// Component is working.
+    Healthy,
+
+    /// Component failed with the given error.
+    Unhealthy(BlockchainError),
+}
+
+impl ComponentStatus {
+    /// Check whether the component failed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the status is `Unhealthy`.
+    pub fn is_unhealthy(&self) -> bool {
+        matches!(self, ComponentStatus::Unhealthy(_))
+    }
+}
+
+impl<T> From<Result<T, BlockchainError>> for ComponentStatus {
+    fn from(result: Result<T, BlockchainError>) -> Self {
+        match result {
+            Ok(_) => ComponentStatus::Healthy,
+            Err(error) => ComponentStatus::Unhealthy(error),
+        }
+    }
+}
+
+/// Health summary of a chain, to back liveness and readiness probes.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    /// Number of blocks, including the genesis block.
+    pub height: usize,
+
+    /// Seconds elapsed since the timestamp of the last block.
+    pub tip_age: i64,
+
+    /// Number of transfers waiting in the mempool, if one was reported.
+    pub mempool_depth: Option<usize>,
+
+    /// Status of the storage, as reported by the application.
+    pub storage: ComponentStatus,
+
+    /// Result of the last validation of the whole chain, see `Chain::revalidate`.
+    pub validation: ComponentStatus,
+}
+
+impl Health {
+    /// Report the depth of the mempool feeding the chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `mempool`: The mempool.
+    ///
+    /// # Returns
+    ///
+    /// The summary with the mempool depth.
+    pub fn with_mempool(mut self, mempool: &Mempool) -> Self {
+        self.mempool_depth = Some(mempool.len());
+        self
+    }
+
+    /// Report the outcome of the last storage operation, e.g. `AsyncChain::save`.
+    ///
+    /// # Arguments
+    ///
+    /// - `result`: The outcome of the operation.
+    ///
+    /// # Returns
+    ///
+    /// The summary with the storage status.
+    pub fn with_storage<T>(mut self, result: Result<T, BlockchainError>) -> Self {
+        self.storage = result.into();
+        self
+    }
+
+    /// Check whether the node should be kept running, i.e. its chain is valid.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the validation did not fail.
+    pub fn is_live(&self) -> bool {
+        !self.validation.is_unhealthy()
+    }
+
+    /// Check whether the node can serve requests.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_tip_age`: The maximum number of seconds since the last block.
+    /// - `max_mempool_depth`: The maximum number of waiting transfers.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the node is live, the storage did not fail and the tip and mempool are within
+    /// the limits.
+    pub fn is_ready(&self, max_tip_age: i64, max_mempool_depth: usize) -> bool {
+        self.is_live()
+            && !self.storage.is_unhealthy()
+            && self.tip_age <= max_tip_age
+            && self.mempool_depth.unwrap_or_default() <= max_mempool_depth
+    }
+}
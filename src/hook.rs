@@ -0,0 +1,99 @@
+use std::{fmt, sync::Arc};
+
+use crate::{BlockchainError, Chain, Transaction, TransactionRequest};
+
+/// Custom policy run by the chain around each transfer.
+///
+/// Hooks are called for every transfer added with `add_transaction`, `add_authorized_transaction`,
+/// `add_transactions` or `apply_pending`, in the order they were registered.
+pub trait TransactionHook: Send + Sync {
+    /// Inspect a transfer before it is validated.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain, before the transfer.
+    /// - `request`: The requested transfer.
+    ///
+    /// # Returns
+    ///
+    /// An error to reject the transfer, returned as is to the caller.
+    fn before_validate(
+        &self,
+        _chain: &Chain,
+        _request: &TransactionRequest,
+    ) -> Result<(), BlockchainError> {
+        Ok(())
+    }
+
+    /// React to a transfer once it has been applied.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain, after the transfer.
+    /// - `transaction`: The applied transaction.
+    fn after_apply(&self, _chain: &Chain, _transaction: &Transaction) {}
+}
+
+/// Transaction hooks registered on a chain.
+///
+/// Hooks are shared with cloned chains.
+#[derive(Clone, Default)]
+pub struct Hooks(Vec<Arc<dyn TransactionHook>>);
+
+impl Hooks {
+    /// Register a new hook, run after the already registered ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `hook`: The hook.
+    pub fn register(&mut self, hook: Arc<dyn TransactionHook>) {
+        self.0.push(hook);
+    }
+
+    /// Run the `before_validate` callbacks, stopping at the first rejection.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain.
+    /// - `request`: The requested transfer.
+    ///
+    /// # Returns
+    ///
+    /// The error of the first hook rejecting the transfer.
+    pub fn before_validate(
+        &self,
+        chain: &Chain,
+        request: &TransactionRequest,
+    ) -> Result<(), BlockchainError> {
+        self.0
+            .iter()
+            .try_for_each(|hook| hook.before_validate(chain, request))
+    }
+
+    /// Run the `after_apply` callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain.
+    /// - `transaction`: The applied transaction.
+    pub fn after_apply(&self, chain: &Chain, transaction: &Transaction) {
+        for hook in &self.0 {
+            hook.after_apply(chain, transaction);
+        }
+    }
+
+    /// Check whether there are no hooks.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no hook is registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hooks({})", self.0.len())
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Address, Hash};
+
+/// Status of an invoice.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum InvoiceStatus {
+    /// Waiting for its payment.
+    #[default]
+    Open,
+
+    /// Paid by a transfer referencing the invoice.
+    Paid,
+
+    /// Expired before it was paid.
+    Expired,
+}
+
+/// Payment request for an amount to a wallet, valid until it expires.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Invoice {
+    /// Identifier of the invoice, referenced by its payment.
+    pub id: Uuid,
+
+    /// Address of the wallet receiving the payment.
+    pub to: Address,
+
+    /// Amount to pay.
+    pub amount: f64,
+
+    /// Time the invoice was created.
+    pub created_at: i64,
+
+    /// Time after which the invoice can no longer be paid.
+    pub expires_at: i64,
+
+    /// Status of the invoice.
+    #[serde(default)]
+    pub status: InvoiceStatus,
+
+    /// Hash of the transaction paying the invoice, once paid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<Hash>,
+
+    /// Time the invoice was paid, once paid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paid_at: Option<i64>,
+}
+
+impl Invoice {
+    /// Create an open invoice.
+    ///
+    /// # Arguments
+    ///
+    /// - `id`: The identifier of the invoice.
+    /// - `to`: The address of the receiving wallet.
+    /// - `amount`: The amount to pay.
+    /// - `created_at`: The current time.
+    /// - `expiry`: The number of seconds the invoice can be paid for.
+    ///
+    /// # Returns
+    ///
+    /// A new open invoice.
+    pub fn new(id: Uuid, to: Address, amount: f64, created_at: i64, expiry: i64) -> Self {
+        Invoice {
+            id,
+            to,
+            amount,
+            created_at,
+            expires_at: created_at.saturating_add(expiry),
+            status: InvoiceStatus::Open,
+            payment: None,
+            paid_at: None,
+        }
+    }
+
+    /// Check whether the invoice can still be paid.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: The current time.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the invoice is open and not past its expiry.
+    pub fn is_payable(&self, now: i64) -> bool {
+        self.status == InvoiceStatus::Open && now <= self.expires_at
+    }
+
+    /// Mark the invoice as expired if it is open and past its expiry.
+    ///
+    /// # Arguments
+    ///
+    /// - `now`: The current time.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the invoice just expired.
+    pub fn expire(&mut self, now: i64) -> bool {
+        let expired = self.status == InvoiceStatus::Open && now > self.expires_at;
+
+        if expired {
+            self.status = InvoiceStatus::Expired;
+        }
+
+        expired
+    }
+
+    /// Mark the invoice as paid.
+    ///
+    /// # Arguments
+    ///
+    /// - `payment`: The hash of the paying transaction.
+    /// - `now`: The current time.
+    pub fn settle(&mut self, payment: Hash, now: i64) {
+        self.status = InvoiceStatus::Paid;
+        self.payment = Some(payment);
+        self.paid_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_lifecycle() {
+        let mut invoice = Invoice::new(Uuid::nil(), Address::random(), 5.0, 100, 60);
+
+        assert!(invoice.is_payable(160));
+        assert!(!invoice.expire(160));
+        assert!(invoice.expire(161));
+        assert_eq!(invoice.status, InvoiceStatus::Expired);
+        assert!(!invoice.is_payable(120));
+
+        let mut invoice = Invoice::new(Uuid::nil(), Address::random(), 5.0, 100, 60);
+        invoice.settle(Hash::default(), 120);
+        assert_eq!(invoice.status, InvoiceStatus::Paid);
+        assert!(!invoice.expire(500));
+    }
+}
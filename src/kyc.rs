@@ -0,0 +1,109 @@
+use std::{fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Wallet;
+
+/// Verification tier of a wallet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum KycTier {
+    /// Receive-only wallet whose balance is capped, waiting for verification.
+    Restricted,
+
+    /// Wallet without restrictions.
+    #[default]
+    Verified,
+}
+
+/// Identity verification service called when wallets are created.
+///
+/// `create_wallet` does not wait for the verification: a provider checking identities
+/// asynchronously returns `KycTier::Restricted` and reports the outcome later with
+/// `Chain::set_kyc_tier`, e.g. from a task holding the chain behind an `AsyncChain`.
+pub trait KycProvider: Send + Sync {
+    /// Start the verification of a new wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `wallet`: The new wallet.
+    ///
+    /// # Returns
+    ///
+    /// The initial tier of the wallet, `Verified` if it could be verified immediately.
+    fn request_verification(&self, wallet: &Wallet) -> KycTier;
+}
+
+/// Identity verification settings of a chain.
+///
+/// The provider is not persisted and is shared with cloned chains.
+#[derive(Clone, Default)]
+pub struct Kyc {
+    /// Provider called for new wallets, or `None` if new wallets are verified.
+    provider: Option<Arc<dyn KycProvider>>,
+
+    /// Maximum native balance of a restricted wallet.
+    restricted_cap: f64,
+}
+
+impl Kyc {
+    /// Create the settings for a provider.
+    ///
+    /// # Arguments
+    ///
+    /// - `provider`: The verification provider.
+    /// - `restricted_cap`: The maximum native balance of a restricted wallet.
+    ///
+    /// # Returns
+    ///
+    /// New verification settings.
+    pub fn new(provider: Arc<dyn KycProvider>, restricted_cap: f64) -> Self {
+        Kyc {
+            provider: Some(provider),
+            restricted_cap,
+        }
+    }
+
+    /// Get the tier of a new wallet from the provider.
+    ///
+    /// # Arguments
+    ///
+    /// - `wallet`: The new wallet.
+    ///
+    /// # Returns
+    ///
+    /// The tier returned by the provider, or `Verified` without a provider.
+    pub fn request_verification(&self, wallet: &Wallet) -> KycTier {
+        match &self.provider {
+            Some(provider) => provider.request_verification(wallet),
+            None => KycTier::Verified,
+        }
+    }
+
+    /// Check whether a wallet may receive an amount.
+    ///
+    /// # Arguments
+    ///
+    /// - `wallet`: The receiving wallet.
+    /// - `amount`: The native amount received.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the wallet is verified, or if the amount keeps its balance within the cap of
+    /// restricted wallets.
+    pub fn can_receive(&self, wallet: &Wallet, amount: f64) -> bool {
+        match wallet.kyc {
+            KycTier::Verified => true,
+            KycTier::Restricted => wallet.balance + amount <= self.restricted_cap,
+        }
+    }
+}
+
+impl fmt::Debug for Kyc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Kyc")
+            .field("provider", &self.provider.is_some())
+            .field("restricted_cap", &self.restricted_cap)
+            .finish()
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError, Chain};
+
+/// Outcome of a transfer previewed without changing the chain.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransactionSimulation {
+    /// Fee the transaction would pay, or the transaction fee of the chain if it is rejected.
+    pub fee: f64,
+
+    /// Balance of the sender after the transfer, or `None` if the wallet does not exist.
+    pub from_balance: Option<f64>,
+
+    /// Balance of the receiver after the transfer, or `None` if the wallet does not exist.
+    pub to_balance: Option<f64>,
+
+    /// Reason the transfer would be rejected, in which case the balances are unchanged.
+    pub error: Option<BlockchainError>,
+}
+
+impl TransactionSimulation {
+    /// Check whether the transfer would be accepted.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the simulation found no error.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Disposable copy of a chain for dry runs, e.g. to preview the balances after a batch of
+/// transactions before submitting it.
+///
+/// Reads go to the original chain until the first mutation, which clones the whole chain, every
+/// block, wallet, contract and audit entry included, without its hooks; mutations never affect
+/// the original. The first mutation costs as much as `Chain::clone`, so a fork is meant for
+/// occasional dry runs rather than for every request of a busy node.
+#[derive(Debug)]
+pub struct LazyFork<'a> {
+    /// The chain the fork was created from.
+    original: &'a Chain,
+
+    /// The original chain, or its copy once mutated.
+    chain: Cow<'a, Chain>,
+}
+
+impl<'a> LazyFork<'a> {
+    /// Create a lazy fork of a chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `original`: The chain to dry-run against.
+    ///
+    /// # Returns
+    ///
+    /// A fork that has not copied the chain yet.
+    pub fn new(original: &'a Chain) -> Self {
+        LazyFork {
+            original,
+            chain: Cow::Borrowed(original),
+        }
+    }
+
+    /// Check whether the fork was mutated, i.e. holds its own copy of the chain.
+    ///
+    /// # Returns
+    ///
+    /// `true` once the chain was copied.
+    pub fn is_modified(&self) -> bool {
+        matches!(self.chain, Cow::Owned(_))
+    }
+
+    /// Compare the wallet balances of the fork with the original chain.
+    ///
+    /// # Returns
+    ///
+    /// The change of the balance of every wallet whose balance differs, wallets created in the
+    /// fork starting from zero.
+    pub fn balance_changes(&self) -> BTreeMap<Address, f64> {
+        if !self.is_modified() {
+            return BTreeMap::new();
+        }
+
+        self.chain
+            .wallets
+            .iter()
+            .filter_map(|(address, wallet)| {
+                let before = self
+                    .original
+                    .wallets
+                    .get(address)
+                    .map_or(0.0, |wallet| wallet.balance);
+
+                (wallet.balance != before).then(|| (address.to_owned(), wallet.balance - before))
+            })
+            .collect()
+    }
+
+    /// Take the chain of the fork, e.g. to keep the outcome of a dry run.
+    ///
+    /// # Returns
+    ///
+    /// The mutated copy, or a clone of the original chain if the fork was not mutated.
+    pub fn into_chain(self) -> Chain {
+        self.chain.into_owned()
+    }
+}
+
+impl Deref for LazyFork<'_> {
+    type Target = Chain;
+
+    fn deref(&self) -> &Chain {
+        &self.chain
+    }
+}
+
+impl DerefMut for LazyFork<'_> {
+    fn deref_mut(&mut self) -> &mut Chain {
+        if let Cow::Borrowed(chain) = self.chain {
+            self.chain = Cow::Owned(chain.clone_without_hooks());
+        }
+
+        self.chain.to_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_on_write() {
+        let mut chain = Chain::new(1.0, 100.0, 0.1);
+        let from = chain.create_wallet("s@mail.com");
+        let to = chain.create_wallet("r@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+        let mut fork = LazyFork::new(&chain);
+        assert_eq!(fork.get_wallet_balance(&from), Some(10.0));
+        assert!(!fork.is_modified());
+        assert!(fork.balance_changes().is_empty());
+
+        fork.add_transaction(&from, &to, 5.0).unwrap();
+        assert!(fork.is_modified());
+        assert_eq!(fork.balance_changes()[&to], 5.0);
+        assert!(fork.balance_changes()[&from] < 0.0);
+        assert_eq!(chain.get_wallet_balance(&to), Some(0.0));
+    }
+}
@@ -1,37 +1,211 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod access;
+#[cfg(feature = "api")]
+pub mod api;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "std")]
+pub mod archive;
+#[cfg(feature = "async")]
+pub mod async_chain;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(any(feature = "api", feature = "rpc"))]
+pub mod auth;
+#[cfg(feature = "std")]
 pub mod block;
+#[cfg(feature = "std")]
+pub mod bridge;
+#[cfg(feature = "std")]
 pub mod chain;
-pub mod transaction;
+#[cfg(feature = "std")]
+pub mod compliance;
+#[cfg(feature = "std")]
+pub mod consensus;
+#[cfg(feature = "std")]
+pub mod contract;
+/// Hashing, block header and transaction primitives, usable with `no_std` and `alloc` by
+/// disabling the default `std` feature.
+pub mod core;
+#[cfg(feature = "std")]
+pub mod dead_letter;
+#[cfg(feature = "std")]
+pub mod environment;
+#[cfg(feature = "std")]
+pub mod faucet;
+#[cfg(feature = "std")]
+pub mod genesis;
+#[cfg(feature = "std")]
+pub mod governance;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "std")]
+pub mod health;
+#[cfg(feature = "std")]
+pub mod hook;
+#[cfg(feature = "std")]
+pub mod invoice;
+#[cfg(feature = "std")]
+pub mod kyc;
+#[cfg(feature = "std")]
+pub mod lazy_fork;
+#[cfg(feature = "std")]
+pub mod light;
+#[cfg(feature = "std")]
+pub mod memory;
+#[cfg(feature = "std")]
+pub mod mempool;
+#[cfg(feature = "std")]
+pub mod migration;
+#[cfg(feature = "napi")]
+pub mod napi;
+#[cfg(feature = "std")]
+pub mod nft;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "std")]
+pub mod oracle;
+#[cfg(feature = "std")]
+pub mod rates;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "std")]
+pub mod script;
+#[cfg(feature = "std")]
+pub mod settlement;
+#[cfg(feature = "testing")]
+pub mod simulator;
+#[cfg(feature = "std")]
+pub mod state;
+#[cfg(feature = "std")]
+pub mod subscription;
+#[cfg(feature = "sync")]
+pub mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod throttle;
+#[cfg(feature = "std")]
+pub mod token;
+#[cfg(feature = "std")]
+pub mod trie;
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
 pub mod wallet;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod wire;
+
+pub use crate::core::{address, bloom, encoding, hash, merkle, transaction};
 
+pub use crate::core::error::BlockchainError;
+pub use crate::core::header::BlockHeader;
+#[cfg(feature = "std")]
+pub use access::{AccessControl, Admin, KeyScope, Permission, WalletKey};
+pub use address::*;
+#[cfg(feature = "std")]
+pub use archive::{ArchiveHeader, ArchiveReader, ArchiveWriter};
+#[cfg(feature = "async")]
+pub use async_chain::AsyncChain;
+#[cfg(feature = "std")]
+pub use audit::{AuditAction, AuditEntry, AuditLog};
+#[cfg(feature = "std")]
 pub use block::*;
+pub use bloom::{Bloom, RollingBloom};
+#[cfg(feature = "std")]
+pub use bridge::{Bridge, BridgeProof};
+#[cfg(feature = "std")]
 pub use chain::*;
-use serde::{Deserialize, Serialize};
-use thiserror::Error;
+#[cfg(feature = "std")]
+pub use compliance::{Compliance, DeniedAddress};
+#[cfg(feature = "std")]
+pub use consensus::{
+    BlockAssembler, ConsensusRules, DifficultyTarget, EmissionCurve, EmissionSchedule,
+    ProtocolFeature, ProtocolUpgrade, QueuedTransaction, TransactionPriority, Treasury,
+};
+#[cfg(feature = "std")]
+pub use contract::{Contract, ContractCall, ContractStorage, DEFAULT_GAS_LIMIT};
+#[cfg(feature = "std")]
+pub use dead_letter::{DeadLetter, DeadLetterQueue};
+#[cfg(feature = "std")]
+pub use environment::{Clock, Environment, ManualClock, SystemClock};
+#[cfg(feature = "std")]
+pub use faucet::Faucet;
+#[cfg(feature = "std")]
+pub use genesis::GenesisConfig;
+#[cfg(feature = "std")]
+pub use governance::*;
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcService;
+pub use hash::Hash;
+#[cfg(feature = "std")]
+pub use health::{ComponentStatus, Health};
+#[cfg(feature = "std")]
+pub use hook::{Hooks, TransactionHook};
+#[cfg(feature = "std")]
+pub use invoice::{Invoice, InvoiceStatus};
+#[cfg(feature = "std")]
+pub use kyc::{Kyc, KycProvider, KycTier};
+#[cfg(feature = "std")]
+pub use lazy_fork::{LazyFork, TransactionSimulation};
+#[cfg(feature = "std")]
+pub use light::*;
+#[cfg(feature = "std")]
+pub use memory::{ChainCapacity, MemoryUsage};
+#[cfg(feature = "std")]
+pub use mempool::{Mempool, PendingTransaction};
+pub use merkle::{MerkleProof, MerkleSide, MerkleStep};
+#[cfg(feature = "std")]
+pub use migration::{Migration, STATE_VERSION};
+#[cfg(feature = "std")]
+pub use nft::*;
+#[cfg(feature = "node")]
+pub use node::*;
+#[cfg(feature = "std")]
+pub use oracle::*;
+#[cfg(feature = "std")]
+pub use rates::{FixedRates, RateProvider, Rates};
+#[cfg(feature = "std")]
+pub use registry::{ChainRegistry, SharedChain};
+#[cfg(feature = "std")]
+pub use replay::{ReplayFailure, ReplayFormat, ReplayReport};
+#[cfg(feature = "rpc")]
+pub use rpc::{RpcError, RpcRequest, RpcResponse, RpcServer, TraceContext};
+#[cfg(feature = "std")]
+pub use script::*;
+#[cfg(feature = "std")]
+pub use settlement::{Settlement, SettlementPeriod};
+#[cfg(feature = "testing")]
+pub use simulator::{Simulator, WorkloadConfig};
+#[cfg(feature = "std")]
+pub use state::{AccountProof, AccountState, StateSnapshot};
+#[cfg(feature = "std")]
+pub use subscription::*;
+#[cfg(feature = "testing")]
+pub use testing::TestChain;
+#[cfg(feature = "std")]
+pub use throttle::RateLimiter;
+#[cfg(feature = "std")]
+pub use token::*;
 pub use transaction::*;
+#[cfg(feature = "std")]
+pub use trie::{StateTrie, TrieProof};
+#[cfg(feature = "std")]
+pub use vanity::VanityAddress;
+#[cfg(feature = "std")]
 pub use wallet::*;
-
-/// Blockchain error.
-#[derive(Debug, Error, Serialize, Deserialize, PartialEq)]
-pub enum BlockchainError {
-    /// Transaction not found.
-    #[error("Transaction not found.")]
-    TransactionNotFound,
-
-    /// Transaction is invalid.
-    #[error("Invalid transaction.")]
-    InvalidTransaction,
-
-    /// Invalid configuration during blockchain creation.
-    #[error("Invalid configuration during blockchain creation.")]
-    InvalidConfiguration,
-
-    /// Insufficient funds.
-    #[error("Insufficient funds.")]
-    InsufficientFunds,
-
-    /// Wallet not found.
-    #[error("Wallet not found.")]
-    WalletNotFound,
-}
+#[cfg(feature = "std")]
+pub use wire::{FrameHeader, MessageType, SyncMessage, WireMessage};
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AccountProof, Address, Block, BlockHeader, BlockchainError, Chain, DifficultyTarget, Hash,
+    MerkleProof, Transaction, MEDIAN_TIME_SPAN,
+};
+
+/// Client that keeps only block headers and verifies data against them.
+///
+/// Headers must declare the difficulty expected at their height: the initial difficulty of the
+/// network for the genesis block, then the difficulty of the previous header, retargeted at the
+/// end of each retarget interval.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LightClient {
+    /// Chain of verified block headers.
+    pub headers: Vec<BlockHeader>,
+
+    /// Initial mining difficulty of the network, expected of the genesis block.
+    #[serde(default)]
+    pub difficulty: f64,
+
+    /// Difficulty retargeting of the network, if any.
+    #[serde(default)]
+    pub difficulty_target: Option<DifficultyTarget>,
+}
+
+impl LightClient {
+    /// Create a new light client without any headers.
+    ///
+    /// # Arguments
+    /// - `difficulty`: The initial mining difficulty of the network.
+    ///
+    /// # Returns
+    /// A new `LightClient` instance.
+    pub fn new(difficulty: f64) -> Self {
+        LightClient {
+            difficulty,
+            ..LightClient::default()
+        }
+    }
+
+    /// Follow the difficulty retargeting of the network, see `Chain::set_difficulty_target`.
+    ///
+    /// # Arguments
+    /// - `target`: The difficulty target of the network.
+    ///
+    /// # Returns
+    /// The light client expecting retargeted difficulties.
+    pub fn with_difficulty_target(mut self, target: DifficultyTarget) -> Self {
+        self.difficulty_target = Some(target);
+
+        self
+    }
+
+    /// Get the number of headers known to the client.
+    ///
+    /// # Returns
+    /// The number of stored headers.
+    pub fn height(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Get the hash of the last known header.
+    ///
+    /// # Returns
+    /// The hash of the last header, or `None` if no headers are stored.
+    pub fn get_last_hash(&self) -> Option<Hash> {
+        self.headers.last().map(Chain::hash)
+    }
+
+    /// Get the difficulty expected of the next header.
+    ///
+    /// # Returns
+    /// The initial difficulty for the genesis block, else the difficulty of the last header,
+    /// retargeted at the end of a retarget interval.
+    pub fn expected_difficulty(&self) -> f64 {
+        let height = self.height();
+        let Some(last) = self.headers.last() else {
+            return self.difficulty;
+        };
+
+        match self.difficulty_target {
+            Some(target) if height > target.interval && height % target.interval == 0 => {
+                let elapsed = last.timestamp - self.headers[height - 1 - target.interval].timestamp;
+                target.retarget(last.difficulty, elapsed)
+            }
+            _ => last.difficulty,
+        }
+    }
+
+    /// Verify and append a block header.
+    ///
+    /// # Arguments
+    /// - `header`: The next block header.
+    ///
+    /// # Returns
+    /// An error if the header does not satisfy the expected proof-of-work, see
+    /// `expected_difficulty`, or does not link to the last header.
+    pub fn add_header(&mut self, header: BlockHeader) -> Result<(), BlockchainError> {
+        if !Block::validate_proof_of_work(&header, self.expected_difficulty()) {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        if let Some(hash) = self.get_last_hash() {
+            if header.previous_hash != hash {
+                return Err(BlockchainError::InvalidHeader);
+            }
+        }
+
+        let timestamps: Vec<i64> = self
+            .headers
+            .iter()
+            .rev()
+            .take(MEDIAN_TIME_SPAN)
+            .map(|header| header.timestamp)
+            .collect();
+
+        if !Block::validate_timestamp(header.timestamp, Block::median_time_past(&timestamps)) {
+            return Err(BlockchainError::InvalidTimestamp);
+        }
+
+        self.headers.push(header);
+
+        Ok(())
+    }
+
+    /// Download the headers the client does not know yet from a chain.
+    ///
+    /// # Arguments
+    /// - `chain`: The chain to read headers from.
+    ///
+    /// # Returns
+    /// The number of headers added, or an error if any header is invalid.
+    pub fn sync(&mut self, chain: &Chain) -> Result<usize, BlockchainError> {
+        let start = self.height();
+
+        for header in chain.get_headers(start, usize::MAX) {
+            self.add_header(header)?;
+        }
+
+        Ok(self.height() - start)
+    }
+
+    /// Verify that a transaction is included in the block at the given height.
+    ///
+    /// # Arguments
+    /// - `height`: The height of the block containing the transaction.
+    /// - `transaction`: The transaction to verify.
+    /// - `proof`: The Merkle inclusion proof of the transaction.
+    ///
+    /// # Returns
+    /// `true` if the proof matches the transaction and the header Merkle root, `false` otherwise.
+    pub fn verify_transaction(
+        &self,
+        height: usize,
+        transaction: &Transaction,
+        proof: &MerkleProof,
+    ) -> bool {
+        let header = match self.headers.get(height) {
+            Some(header) => header,
+            None => return false,
+        };
+
+        proof.leaf == Chain::hash(transaction) && proof.verify(&header.merkle)
+    }
+
+    /// Find the blocks that may involve an address, skipping the others by their bloom filter.
+    ///
+    /// # Arguments
+    /// - `address`: The address to look for.
+    ///
+    /// # Returns
+    /// The heights of the headers whose bloom filter may contain the address.
+    pub fn blocks_involving(&self, address: &Address) -> Vec<usize> {
+        self.headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| header.bloom.contains(address))
+            .map(|(height, _)| height)
+            .collect()
+    }
+
+    /// Verify a wallet state proof against the state root of the corresponding header.
+    ///
+    /// # Arguments
+    /// - `proof`: The account proof.
+    ///
+    /// # Returns
+    /// `true` if the proof matches the state root of the header at its height, `false` otherwise.
+    pub fn verify_balance(&self, proof: &AccountProof) -> bool {
+        match self.headers.get(proof.height) {
+            Some(header) => proof.verify(&header.state_root),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_light_client() {
+        let client = LightClient::new(1.0);
+
+        assert_eq!(client.height(), 0);
+        assert!(client.get_last_hash().is_none());
+    }
+}
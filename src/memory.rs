@@ -0,0 +1,114 @@
+use std::mem::size_of;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountState, Block, Hash, Transaction, Wallet};
+
+/// Expected number of items used to preallocate the chain collections.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainCapacity {
+    /// Expected number of wallets.
+    pub wallets: usize,
+
+    /// Expected number of transactions.
+    pub transactions: usize,
+
+    /// Expected number of blocks.
+    pub blocks: usize,
+}
+
+/// Approximate memory footprint of a chain, in bytes.
+///
+/// The figures account for the allocated capacity of the collections and the heap data of
+/// their items, but not for allocator overhead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// Blocks, including their transactions and state snapshots.
+    pub blocks: usize,
+
+    /// Transaction map of the chain.
+    pub transactions: usize,
+
+    /// Wallet map of the chain.
+    pub wallets: usize,
+
+    /// Sum of the other figures.
+    pub total: usize,
+}
+
+/// Approximate the size of a hash map with the given capacity, excluding the heap data of its entries.
+///
+/// # Arguments
+///
+/// - `capacity`: The capacity of the map.
+///
+/// # Returns
+///
+/// The size in bytes of the entries and their control bytes.
+pub fn map_size<K, V>(capacity: usize) -> usize {
+    capacity * (size_of::<K>() + size_of::<V>() + 1)
+}
+
+/// Approximate the heap data of a transaction.
+///
+/// # Arguments
+///
+/// - `transaction`: The transaction.
+///
+/// # Returns
+///
+/// The size in bytes of the data owned by the transaction outside of its struct.
+pub fn transaction_heap_size(transaction: &Transaction) -> usize {
+    transaction.from.as_str().len()
+        + transaction.to.as_str().len()
+        + transaction.asset.capacity()
+        + transaction.data.capacity()
+}
+
+/// Approximate the heap data of a wallet.
+///
+/// # Arguments
+///
+/// - `wallet`: The wallet.
+///
+/// # Returns
+///
+/// The size in bytes of the data owned by the wallet outside of its struct.
+pub fn wallet_heap_size(wallet: &Wallet) -> usize {
+    wallet.email.capacity()
+        + wallet.address.as_str().len()
+        + map_size::<String, f64>(wallet.balances.capacity())
+        + wallet.balances.keys().map(String::capacity).sum::<usize>()
+        + wallet.transaction_hashes.capacity() * size_of::<Hash>()
+}
+
+/// Approximate the heap data of a block.
+///
+/// # Arguments
+///
+/// - `block`: The block.
+///
+/// # Returns
+///
+/// The size in bytes of the data owned by the block outside of its struct.
+pub fn block_heap_size(block: &Block) -> usize {
+    map_size::<Hash, Transaction>(block.transactions.capacity())
+        + block
+            .transactions
+            .values()
+            .map(transaction_heap_size)
+            .sum::<usize>()
+        + block.state.capacity() * size_of::<AccountState>()
+        + block
+            .state
+            .iter()
+            .map(|account| {
+                account.address.as_str().len()
+                    + account
+                        .balances
+                        .keys()
+                        .map(|asset| asset.capacity() + size_of::<(String, f64)>())
+                        .sum::<usize>()
+            })
+            .sum::<usize>()
+}
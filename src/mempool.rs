@@ -0,0 +1,222 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{Chain, Hash, TransactionRequest};
+
+/// Transfer waiting in the mempool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    /// Identifier of the pending transfer.
+    pub hash: Hash,
+
+    /// Requested transfer.
+    pub request: TransactionRequest,
+
+    /// Order in which the transfer was received.
+    pub sequence: u64,
+
+    /// Timestamp at which the transfer was received.
+    pub received: i64,
+}
+
+/// Concurrent pool of pending transfers.
+///
+/// The pool is sharded and can be shared between threads without a global lock: RPC handlers
+/// insert transfers while the miner drains them with `Chain::apply_pending`.
+#[derive(Debug, Default)]
+pub struct Mempool {
+    /// Pending transfers by hash.
+    pending: DashMap<Hash, PendingTransaction>,
+
+    /// Sequence number of the next transfer.
+    sequence: AtomicU64,
+}
+
+impl Mempool {
+    /// Create an empty mempool.
+    ///
+    /// # Returns
+    ///
+    /// A new mempool.
+    pub fn new() -> Self {
+        Mempool::default()
+    }
+
+    /// Add a transfer to the pool.
+    ///
+    /// # Arguments
+    ///
+    /// - `request`: The transfer.
+    ///
+    /// # Returns
+    ///
+    /// The hash identifying the pending transfer.
+    pub fn insert(&self, request: TransactionRequest) -> Hash {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let received = Utc::now().timestamp();
+        let hash = Chain::hash(&(&request, sequence, received));
+
+        self.pending.insert(
+            hash,
+            PendingTransaction {
+                hash,
+                request,
+                sequence,
+                received,
+            },
+        );
+
+        hash
+    }
+
+    /// Get a pending transfer.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash of the transfer.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the transfer, or `None` if it is not pending.
+    pub fn get(&self, hash: &Hash) -> Option<PendingTransaction> {
+        self.pending.get(hash).map(|entry| entry.value().to_owned())
+    }
+
+    /// Check whether a transfer is pending.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash of the transfer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the transfer is in the pool, `false` otherwise.
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.pending.contains_key(hash)
+    }
+
+    /// Remove a pending transfer.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash of the transfer.
+    ///
+    /// # Returns
+    ///
+    /// The removed transfer, or `None` if it was not pending.
+    pub fn remove(&self, hash: &Hash) -> Option<PendingTransaction> {
+        self.pending.remove(hash).map(|(_, pending)| pending)
+    }
+
+    /// Get the number of pending transfers.
+    ///
+    /// # Returns
+    ///
+    /// The number of transfers in the pool.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Check whether the pool is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no transfer is pending, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Remove the oldest pending transfers.
+    ///
+    /// Transfers inserted concurrently are either returned or left in the pool for a later drain.
+    ///
+    /// # Arguments
+    ///
+    /// - `limit`: The maximum number of transfers to remove.
+    ///
+    /// # Returns
+    ///
+    /// The removed transfers, oldest first.
+    pub fn drain(&self, limit: usize) -> Vec<PendingTransaction> {
+        let mut oldest: Vec<(u64, Hash)> = self
+            .pending
+            .iter()
+            .map(|entry| (entry.sequence, entry.hash))
+            .collect();
+        oldest.sort_unstable();
+
+        oldest
+            .into_iter()
+            .take(limit)
+            .filter_map(|(_, hash)| self.remove(&hash))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::*;
+    use crate::Address;
+
+    fn request(amount: f64) -> TransactionRequest {
+        TransactionRequest::new(&Address::random(), &Address::random(), amount)
+    }
+
+    #[test]
+    fn test_insert_and_drain_in_order() {
+        let mempool = Mempool::new();
+        let first = mempool.insert(request(1.0));
+        let second = mempool.insert(request(1.0));
+        let third = mempool.insert(request(1.0));
+
+        assert_eq!(mempool.len(), 3);
+        assert!(mempool.contains(&second));
+
+        let drained = mempool.drain(2);
+
+        assert_eq!(
+            drained.iter().map(|p| p.hash).collect::<Vec<_>>(),
+            vec![first, second]
+        );
+        assert_eq!(mempool.len(), 1);
+        assert!(mempool.get(&third).is_some());
+    }
+
+    #[test]
+    fn test_identical_requests_are_distinct() {
+        let mempool = Mempool::new();
+        let request = request(5.0);
+
+        assert_ne!(mempool.insert(request.clone()), mempool.insert(request));
+        assert_eq!(mempool.len(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_insert() {
+        let mempool = Arc::new(Mempool::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mempool = mempool.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        mempool.insert(request(1.0));
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(mempool.len(), 400);
+        assert_eq!(mempool.drain(usize::MAX).len(), 400);
+        assert!(mempool.is_empty());
+    }
+}
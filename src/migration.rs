@@ -0,0 +1,344 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    Block, BlockchainError, Chain, ChainTransactions, Hash, QueuedTransaction, Transaction,
+};
+
+/// Version of the persisted chain format written by this crate.
+pub const STATE_VERSION: u64 = 1;
+
+/// Key of the format version in a persisted chain; chains saved without it have version 0.
+pub const VERSION_KEY: &str = "version";
+
+/// Step upgrading a persisted chain from one format version to the next.
+#[derive(Clone, Copy, Debug)]
+pub struct Migration {
+    /// Version upgraded by the step, the result has version `from + 1`.
+    pub from: u64,
+
+    /// Change made by the step.
+    pub description: &'static str,
+
+    /// Rewrite the persisted chain in place.
+    pub apply: fn(&mut Value) -> Result<(), BlockchainError>,
+}
+
+/// Migrations in order, one for each version before `STATE_VERSION`.
+///
+/// Fields added with a serde default do not need a migration; a step is only needed when
+/// existing data must be rewritten.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "Store the height of each block, which defaulted to 0 before, and recompute the \
+                  hashes written by the first releases, whose hex encoding dropped leading zeros.",
+    apply: upgrade_unversioned,
+}];
+
+/// Get the format version of a persisted chain.
+///
+/// # Arguments
+///
+/// - `value`: The persisted chain.
+///
+/// # Returns
+///
+/// The version, 0 if it is missing, or `StorageError` if it is not an integer.
+pub fn version(value: &Value) -> Result<u64, BlockchainError> {
+    match value.get(VERSION_KEY) {
+        Some(version) => version.as_u64().ok_or(BlockchainError::StorageError),
+        None => Ok(0),
+    }
+}
+
+/// Apply the migrations needed to bring a persisted chain to `STATE_VERSION`.
+///
+/// # Arguments
+///
+/// - `value`: The persisted chain, rewritten in place.
+///
+/// # Returns
+///
+/// The version the chain was persisted with, or `UnsupportedStateVersion` if it was written by
+/// a newer version of the crate.
+pub fn migrate(value: &mut Value) -> Result<u64, BlockchainError> {
+    if !value.is_object() {
+        return Err(BlockchainError::StorageError);
+    }
+
+    let persisted = version(value)?;
+    if persisted > STATE_VERSION {
+        return Err(BlockchainError::UnsupportedStateVersion { version: persisted });
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= persisted) {
+        (migration.apply)(value)?;
+        value[VERSION_KEY] = (migration.from + 1).into();
+    }
+
+    Ok(persisted)
+}
+
+/// Serialize a chain to JSON, tagged with `STATE_VERSION`.
+///
+/// # Arguments
+///
+/// - `chain`: The chain.
+///
+/// # Returns
+///
+/// The JSON bytes, or `StorageError` if the chain cannot be serialized.
+pub fn encode(chain: &Chain) -> Result<Vec<u8>, BlockchainError> {
+    let mut value = serde_json::to_value(chain).map_err(|_| BlockchainError::StorageError)?;
+    value[VERSION_KEY] = STATE_VERSION.into();
+
+    serde_json::to_vec(&value).map_err(|_| BlockchainError::StorageError)
+}
+
+/// Deserialize a chain from JSON, migrating it from the version it was persisted with.
+///
+/// # Arguments
+///
+/// - `bytes`: The JSON bytes.
+///
+/// # Returns
+///
+/// The chain, `StorageError` if it cannot be parsed, or `UnsupportedStateVersion` if it was
+/// written by a newer version of the crate.
+pub fn decode(bytes: &[u8]) -> Result<Chain, BlockchainError> {
+    let mut value: Value =
+        serde_json::from_slice(bytes).map_err(|_| BlockchainError::StorageError)?;
+    migrate(&mut value)?;
+
+    serde_json::from_value(value).map_err(|_| BlockchainError::StorageError)
+}
+
+/// Upgrade a chain persisted without a format version.
+fn upgrade_unversioned(value: &mut Value) -> Result<(), BlockchainError> {
+    set_block_heights(value)?;
+    recompute_hashes(value)
+}
+
+/// Set the height of each block to its position in the chain.
+fn set_block_heights(value: &mut Value) -> Result<(), BlockchainError> {
+    let blocks = value
+        .get_mut("chain")
+        .and_then(Value::as_array_mut)
+        .ok_or(BlockchainError::StorageError)?;
+
+    for (height, block) in blocks.iter_mut().enumerate() {
+        let block = block.as_object_mut().ok_or(BlockchainError::StorageError)?;
+        block.insert("height".to_string(), height.into());
+    }
+
+    Ok(())
+}
+
+/// Recompute the transaction and block hashes, which the first releases computed from a JSON
+/// preimage and encoded in hex without the leading zero of each byte.
+///
+/// Transactions are rehashed and the wallet histories rewritten. Blocks are relinked, their
+/// Merkle roots recomputed and, if their header no longer meets its difficulty, mined again with
+/// their timestamps kept. Transfers of chains saved before the mempool, which were never mined,
+/// are queued for the next block. Consistent hashes are left unchanged.
+fn recompute_hashes(value: &mut Value) -> Result<(), BlockchainError> {
+    let mut rehashed = HashMap::new();
+    let submitted = rehash_transactions(field(value, "transactions")?, &mut rehashed)?;
+
+    let blocks = field(value, "chain")?
+        .as_array_mut()
+        .ok_or(BlockchainError::StorageError)?;
+    let (mut previous, mut mined) = (Hash::zero(), HashSet::new());
+
+    for block in blocks.iter_mut() {
+        let relinked = relink_block(block, previous, &mut rehashed)?;
+        previous = relinked.hash;
+        mined.extend(relinked.transactions.keys().copied());
+        *block = to_value(&relinked)?;
+    }
+
+    let height = blocks.len();
+    if value.get("pending").is_none() {
+        let mut queued: Vec<&Transaction> = submitted
+            .values()
+            .filter(|tx| !mined.contains(&tx.hash))
+            .collect();
+        queued.sort_by_key(|tx| (tx.timestamp, tx.hash));
+
+        let queued: Vec<QueuedTransaction> = queued
+            .into_iter()
+            .map(|tx| QueuedTransaction {
+                hash: tx.hash,
+                since: height,
+            })
+            .collect();
+        value["pending"] = to_value(&queued)?;
+    }
+
+    value["transactions"] = to_value(&submitted)?;
+
+    let wallets = field(value, "wallets")?
+        .as_object_mut()
+        .ok_or(BlockchainError::StorageError)?;
+
+    for history in wallets
+        .values_mut()
+        .filter_map(|wallet| wallet.get_mut("transaction_hashes"))
+        .filter_map(Value::as_array_mut)
+    {
+        for hash in history.iter_mut() {
+            let old = hash.as_str().ok_or(BlockchainError::StorageError)?;
+            let new = match rehashed.get(old) {
+                Some(new) => *new,
+                None => old.parse().map_err(|_| BlockchainError::StorageError)?,
+            };
+
+            *hash = new.to_string().into();
+        }
+    }
+
+    Ok(())
+}
+
+/// Rehash a map of transactions, recording the new hash of each old key.
+fn rehash_transactions(
+    transactions: &mut Value,
+    rehashed: &mut HashMap<String, Hash>,
+) -> Result<ChainTransactions, BlockchainError> {
+    let entries = transactions
+        .as_object_mut()
+        .ok_or(BlockchainError::StorageError)?;
+    let mut result = ChainTransactions::default();
+
+    for (key, mut transaction) in mem::take(entries) {
+        clear_legacy_hash(field(&mut transaction, "hash")?);
+
+        let mut transaction: Transaction = from_value(transaction)?;
+        transaction.hash = transaction.compute_hash();
+
+        rehashed.insert(key, transaction.hash);
+        result.insert(transaction.hash, transaction);
+    }
+
+    Ok(result)
+}
+
+/// Rehash the transactions of a block and link it to the previous block.
+///
+/// # Returns
+///
+/// The block, or `StorageError` if it is pruned and its Merkle root cannot be recomputed.
+fn relink_block(
+    value: &mut Value,
+    previous: Hash,
+    rehashed: &mut HashMap<String, Hash>,
+) -> Result<Block, BlockchainError> {
+    let transactions = rehash_transactions(field(value, "transactions")?, rehashed)?;
+    let header = field(value, "header")?;
+    let legacy = clear_legacy_hash(field(header, "merkle")?);
+    clear_legacy_hash(field(header, "previous_hash")?);
+
+    if let Some(hash) = value.get_mut("hash") {
+        clear_legacy_hash(hash);
+    }
+
+    let mut block: Block = from_value(mem::take(value))?;
+    block.transactions = transactions;
+    block.header.previous_hash = previous;
+
+    match block.pruned {
+        true if legacy => return Err(BlockchainError::StorageError),
+        true => {}
+        false => block.header.merkle = Chain::get_merkle(&block.transactions),
+    }
+
+    match block.header.meets_difficulty(block.header.difficulty) {
+        true => block.refresh_hash(),
+        false => block.mine(),
+    }
+
+    Ok(block)
+}
+
+/// Replace a hash that cannot be decoded, e.g. in the legacy encoding, with the zero hash.
+///
+/// # Returns
+///
+/// `true` if the hash was replaced.
+fn clear_legacy_hash(hash: &mut Value) -> bool {
+    if hash
+        .as_str()
+        .is_some_and(|hash| hash.parse::<Hash>().is_ok())
+    {
+        return false;
+    }
+
+    *hash = Hash::zero().to_string().into();
+
+    true
+}
+
+/// Get a field of a persisted object.
+fn field<'a>(value: &'a mut Value, key: &str) -> Result<&'a mut Value, BlockchainError> {
+    value.get_mut(key).ok_or(BlockchainError::StorageError)
+}
+
+/// Deserialize a persisted item.
+fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, BlockchainError> {
+    serde_json::from_value(value).map_err(|_| BlockchainError::StorageError)
+}
+
+/// Serialize an item to persist it.
+fn to_value<T: Serialize + ?Sized>(item: &T) -> Result<Value, BlockchainError> {
+    serde_json::to_value(item).map_err(|_| BlockchainError::StorageError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for (version, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from, version as u64);
+        }
+
+        assert_eq!(MIGRATIONS.len() as u64, STATE_VERSION);
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut chain = Chain::new(1.0, 10.0, 0.1);
+        chain.generate_new_block();
+        chain.generate_new_block();
+
+        let mut value = serde_json::to_value(&chain).unwrap();
+        value["chain"][2]["height"] = 0.into();
+
+        assert_eq!(migrate(&mut value), Ok(0));
+        assert_eq!(value[VERSION_KEY], STATE_VERSION);
+        assert_eq!(value["chain"][2]["height"], 2);
+
+        // Consistent hashes are left unchanged
+        assert_eq!(value["chain"][2]["hash"], chain.chain[2].hash.to_string());
+
+        // Migrating again is a no-op
+        assert_eq!(migrate(&mut value), Ok(STATE_VERSION));
+
+        value[VERSION_KEY] = (STATE_VERSION + 1).into();
+        assert_eq!(
+            migrate(&mut value),
+            Err(BlockchainError::UnsupportedStateVersion {
+                version: STATE_VERSION + 1
+            })
+        );
+
+        value[VERSION_KEY] = "1".into();
+        assert_eq!(migrate(&mut value), Err(BlockchainError::StorageError));
+    }
+}
@@ -0,0 +1,323 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use napi::{bindgen_prelude::AsyncTask, Env, Error, Result, Status, Task};
+use napi_derive::napi;
+
+use crate::{migration, Address, BlockchainError, Chain, Hash, Transaction, Wallet};
+
+impl From<BlockchainError> for Error {
+    fn from(error: BlockchainError) -> Self {
+        Error::new(Status::GenericFailure, error.to_string())
+    }
+}
+
+/// Chain exposed to Node.js as `Chain`.
+///
+/// Addresses and hashes are passed as strings, and failures are thrown as `Error`s carrying the
+/// message of the `BlockchainError`.
+#[napi(js_name = "Chain")]
+pub struct NapiChain {
+    /// The wrapped chain, shared with the mining tasks.
+    chain: Arc<Mutex<Chain>>,
+}
+
+#[napi]
+impl NapiChain {
+    /// Initialize a new blockchain with a genesis block.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The initial mining difficulty level of the network.
+    /// - `reward`: The initial block reward for miners.
+    /// - `fee`: The transaction fee.
+    ///
+    /// # Returns
+    ///
+    /// A new chain.
+    #[napi(constructor)]
+    pub fn new(difficulty: f64, reward: f64, fee: f64) -> Self {
+        NapiChain::from(Chain::new(difficulty, reward, fee))
+    }
+
+    /// Restore a chain serialized with `toJSON`, migrating it to the current format, verifying its
+    /// block hashes and validating it.
+    ///
+    /// # Arguments
+    ///
+    /// - `json`: The serialized chain.
+    ///
+    /// # Returns
+    ///
+    /// The restored chain.
+    #[napi(factory, js_name = "fromJSON")]
+    pub fn from_json(json: String) -> Result<Self> {
+        let mut chain = migration::decode(json.as_bytes())?;
+        chain.verify_block_hashes()?;
+        chain.validate_chain()?;
+
+        Ok(NapiChain::from(chain))
+    }
+
+    /// Serialize the chain.
+    ///
+    /// # Returns
+    ///
+    /// The chain as JSON.
+    #[napi(js_name = "toJSON")]
+    pub fn to_json(&self) -> Result<String> {
+        let bytes = migration::encode(&self.lock())?;
+
+        Ok(String::from_utf8(bytes).map_err(|_| BlockchainError::StorageError)?)
+    }
+
+    /// Get the number of blocks, including the genesis block.
+    #[napi(getter)]
+    pub fn height(&self) -> u32 {
+        self.lock().chain.len() as u32
+    }
+
+    /// Get the mining difficulty.
+    #[napi(getter)]
+    pub fn difficulty(&self) -> f64 {
+        self.lock().difficulty
+    }
+
+    /// Get the block reward.
+    #[napi(getter)]
+    pub fn reward(&self) -> f64 {
+        self.lock().reward
+    }
+
+    /// Get the transaction fee.
+    #[napi(getter)]
+    pub fn fee(&self) -> f64 {
+        self.lock().fee
+    }
+
+    /// Create a new wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `email`: The email of the wallet owner.
+    ///
+    /// # Returns
+    ///
+    /// The address of the new wallet.
+    #[napi]
+    pub fn create_wallet(&self, email: String) -> String {
+        self.lock().create_wallet(&email).to_string()
+    }
+
+    /// Get a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// A snapshot of the wallet.
+    #[napi]
+    pub fn get_wallet(&self, address: String) -> Result<NapiWallet> {
+        let address: Address = address.parse()?;
+
+        match self.lock().wallets.get(&address) {
+            Some(wallet) => Ok(wallet.into()),
+            None => Err(BlockchainError::WalletNotFound { address }.into()),
+        }
+    }
+
+    /// Add a new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    #[napi]
+    pub fn add_transaction(&self, from: String, to: String, amount: f64) -> Result<()> {
+        let (from, to): (Address, Address) = (from.parse()?, to.parse()?);
+
+        Ok(self.lock().add_transaction(&from, &to, amount)?)
+    }
+
+    /// Get a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction.
+    #[napi]
+    pub fn get_transaction(&self, hash: String) -> Result<NapiTransaction> {
+        let hash: Hash = hash.parse()?;
+
+        Ok(self.lock().get_transaction(&hash)?.into())
+    }
+
+    /// Get a page of transactions.
+    ///
+    /// # Arguments
+    ///
+    /// - `page`: The page number, starting at 1.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    ///
+    /// The transactions of the page.
+    #[napi]
+    pub fn get_transactions(&self, page: u32, size: u32) -> Vec<NapiTransaction> {
+        self.lock()
+            .iter_transactions(page as usize, size as usize)
+            .map(NapiTransaction::from)
+            .collect()
+    }
+
+    /// Mine a new block on the calling thread.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the new block.
+    #[napi]
+    pub fn generate_new_block(&self) -> String {
+        mine(&self.chain)
+    }
+
+    /// Mine a new block on the libuv thread pool, without blocking the event loop.
+    ///
+    /// # Returns
+    ///
+    /// A promise resolving to the hash of the new block.
+    #[napi]
+    pub fn mine(&self) -> AsyncTask<MineTask> {
+        AsyncTask::new(MineTask {
+            chain: self.chain.clone(),
+        })
+    }
+
+    /// Get the hash of the last block.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the last block.
+    #[napi]
+    pub fn get_last_hash(&self) -> String {
+        self.lock().get_last_hash().to_string()
+    }
+
+    /// Validate the whole chain.
+    #[napi]
+    pub fn validate_chain(&self) -> Result<()> {
+        Ok(self.lock().validate_chain()?)
+    }
+}
+
+impl NapiChain {
+    /// Lock the chain, recovering from a poisoned lock.
+    fn lock(&self) -> MutexGuard<'_, Chain> {
+        self.chain.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl From<Chain> for NapiChain {
+    fn from(chain: Chain) -> Self {
+        NapiChain {
+            chain: Arc::new(Mutex::new(chain)),
+        }
+    }
+}
+
+/// Background task mining a block for `Chain.mine`.
+pub struct MineTask {
+    /// The chain to extend.
+    chain: Arc<Mutex<Chain>>,
+}
+
+impl Task for MineTask {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(mine(&self.chain))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Snapshot of a wallet exposed to Node.js as a plain object.
+#[napi(object, js_name = "Wallet")]
+pub struct NapiWallet {
+    /// Address of the wallet.
+    pub address: String,
+
+    /// Email of the wallet owner.
+    pub email: String,
+
+    /// Native coin balance.
+    pub balance: f64,
+
+    /// Hashes of the transactions of the wallet, oldest first.
+    pub transaction_hashes: Vec<String>,
+}
+
+impl From<&Wallet> for NapiWallet {
+    fn from(wallet: &Wallet) -> Self {
+        NapiWallet {
+            address: wallet.address.to_string(),
+            email: wallet.email.clone(),
+            balance: wallet.balance,
+            transaction_hashes: wallet
+                .transaction_hashes
+                .iter()
+                .map(Hash::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Transaction exposed to Node.js as a plain object.
+#[napi(object, js_name = "Transaction")]
+pub struct NapiTransaction {
+    /// Hash of the transaction.
+    pub hash: String,
+
+    /// Sender's address.
+    pub from: String,
+
+    /// Receiver's address.
+    pub to: String,
+
+    /// Amount of the transaction.
+    pub amount: f64,
+
+    /// Fee of the transaction.
+    pub fee: f64,
+
+    /// Timestamp of the transaction, in seconds since the Unix epoch.
+    pub timestamp: i64,
+}
+
+impl From<&Transaction> for NapiTransaction {
+    fn from(transaction: &Transaction) -> Self {
+        NapiTransaction {
+            hash: transaction.hash.to_string(),
+            from: transaction.from.to_string(),
+            to: transaction.to.to_string(),
+            amount: transaction.amount,
+            fee: transaction.fee,
+            timestamp: transaction.timestamp,
+        }
+    }
+}
+
+/// Mine a new block on a shared chain.
+fn mine(chain: &Arc<Mutex<Chain>>) -> String {
+    let mut chain = chain.lock().unwrap_or_else(|e| e.into_inner());
+    chain.generate_new_block();
+
+    chain.get_last_hash().to_string()
+}
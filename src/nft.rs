@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Address, Hash};
+
+/// Metadata attached to a non-fungible token.
+pub type NftMetadata = BTreeMap<String, String>;
+
+/// Unique, non-fungible token owned by a single wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Nft {
+    /// Unique identifier of the token.
+    pub id: Uuid,
+
+    /// Address of the wallet that minted the token.
+    pub creator: Address,
+
+    /// Address of the wallet currently owning the token.
+    pub owner: Address,
+
+    /// Metadata describing the token.
+    pub metadata: NftMetadata,
+
+    /// Hashes of the transactions that minted and transferred the token, oldest first.
+    pub history: Vec<Hash>,
+
+    /// Timestamp at which the token was minted.
+    pub timestamp: i64,
+}
+
+impl Nft {
+    /// Create a new non-fungible token.
+    ///
+    /// # Arguments
+    ///
+    /// - `creator`: The address of the minting wallet, which becomes the first owner.
+    /// - `metadata`: The metadata describing the token.
+    ///
+    /// # Returns
+    ///
+    /// A new token with a unique identifier and an empty history.
+    pub fn new(creator: &Address, metadata: NftMetadata) -> Self {
+        Nft {
+            id: Uuid::new_v4(),
+            creator: creator.to_owned(),
+            owner: creator.to_owned(),
+            metadata,
+            history: vec![],
+            timestamp: Utc::now().timestamp(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_nft() {
+        let creator = Address::random();
+        let metadata = NftMetadata::from([("name".to_string(), "Art".to_string())]);
+        let nft = Nft::new(&creator, metadata.clone());
+
+        assert_eq!(nft.id.get_version(), Some(uuid::Version::Random));
+        assert_eq!(nft.owner, creator);
+        assert_eq!(nft.metadata, metadata);
+        assert!(nft.history.is_empty());
+    }
+}
@@ -0,0 +1,501 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use libp2p::{
+    gossipsub::{self, IdentTopic, MessageAcceptance, MessageAuthenticity},
+    noise,
+    request_response::{self, ProtocolSupport},
+    swarm::{NetworkBehaviour, SwarmEvent},
+    tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm, SwarmBuilder,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::{Block, BlockchainError, Chain, Hash, RollingBloom, Transaction, WireMessage};
+
+/// Protocol used by peers to exchange handshakes.
+pub const HANDSHAKE_PROTOCOL: &str = "/blockchain/handshake/1.0.0";
+
+/// Number of seconds an idle connection is kept open.
+pub const IDLE_CONNECTION_TIMEOUT: u64 = 60;
+
+/// Minimum number of recent transactions remembered to avoid validating and relaying them again.
+pub const SEEN_TRANSACTIONS: usize = 10_000;
+
+/// Configuration of a peer-to-peer node.
+#[derive(Clone, Debug)]
+pub struct NodeConfig {
+    /// Identifier of the network; peers on a different network are disconnected.
+    pub chain_id: String,
+
+    /// Address to listen on, e.g. `/ip4/0.0.0.0/tcp/4001`.
+    pub listen_address: Multiaddr,
+
+    /// Addresses of the peers to connect to at startup.
+    pub peers: Vec<Multiaddr>,
+}
+
+/// Information exchanged by peers when they connect.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Handshake {
+    /// Identifier of the network.
+    pub chain_id: String,
+
+    /// Number of blocks in the chain of the peer.
+    pub height: usize,
+
+    /// Hash of the last block in the chain of the peer.
+    pub tip: Hash,
+}
+
+/// Request sent from the application to a running node.
+#[derive(Clone, Debug)]
+pub enum NodeCommand {
+    /// Broadcast a new block to the network.
+    PublishBlock(Block),
+
+    /// Broadcast a new transaction to the network.
+    PublishTransaction(Transaction),
+
+    /// Stop the node.
+    Shutdown,
+}
+
+/// Notification sent from a running node to the application.
+#[derive(Debug)]
+pub enum NodeEvent {
+    /// Node started listening on an address.
+    Listening(Multiaddr),
+
+    /// Peer on the same network completed the handshake.
+    PeerConnected {
+        /// Identifier of the peer.
+        peer: PeerId,
+
+        /// Handshake sent by the peer.
+        handshake: Handshake,
+    },
+
+    /// Peer on a different network was rejected and disconnected.
+    PeerRejected {
+        /// Identifier of the peer.
+        peer: PeerId,
+
+        /// Handshake sent by the peer.
+        handshake: Handshake,
+    },
+
+    /// Block received from a peer was appended to the chain.
+    BlockImported {
+        /// Identifier of the peer that propagated the block.
+        peer: PeerId,
+
+        /// Height of the imported block.
+        height: usize,
+    },
+
+    /// Block received from a peer was rejected.
+    BlockRejected {
+        /// Identifier of the peer that propagated the block.
+        peer: PeerId,
+
+        /// Reason the block was rejected.
+        error: BlockchainError,
+    },
+
+    /// Transaction with a valid hash was received from a peer.
+    TransactionReceived {
+        /// Identifier of the peer that propagated the transaction.
+        peer: PeerId,
+
+        /// Received transaction.
+        transaction: Transaction,
+    },
+}
+
+/// Network behaviour of a node.
+#[derive(NetworkBehaviour)]
+struct Behaviour {
+    /// Propagation of blocks and transactions.
+    gossipsub: gossipsub::Behaviour,
+
+    /// Exchange of handshakes with newly connected peers.
+    handshake: request_response::json::Behaviour<Handshake, Handshake>,
+}
+
+/// Handle used by the application to control a running node.
+pub struct NodeHandle {
+    /// Sender of commands to the node.
+    commands: mpsc::UnboundedSender<NodeCommand>,
+
+    /// Receiver of events from the node.
+    events: mpsc::UnboundedReceiver<NodeEvent>,
+}
+
+impl NodeHandle {
+    /// Broadcast a new block to the network.
+    ///
+    /// # Arguments
+    ///
+    /// - `block`: The block to broadcast.
+    ///
+    /// # Returns
+    ///
+    /// An error if the node has stopped.
+    pub fn publish_block(&self, block: Block) -> Result<(), BlockchainError> {
+        self.send(NodeCommand::PublishBlock(block))
+    }
+
+    /// Broadcast a new transaction to the network.
+    ///
+    /// # Arguments
+    ///
+    /// - `transaction`: The transaction to broadcast.
+    ///
+    /// # Returns
+    ///
+    /// An error if the node has stopped.
+    pub fn publish_transaction(&self, transaction: Transaction) -> Result<(), BlockchainError> {
+        self.send(NodeCommand::PublishTransaction(transaction))
+    }
+
+    /// Stop the node.
+    ///
+    /// # Returns
+    ///
+    /// An error if the node has already stopped.
+    pub fn shutdown(&self) -> Result<(), BlockchainError> {
+        self.send(NodeCommand::Shutdown)
+    }
+
+    /// Wait for the next event of the node.
+    ///
+    /// # Returns
+    ///
+    /// The next event, or `None` if the node has stopped.
+    pub async fn next_event(&mut self) -> Option<NodeEvent> {
+        self.events.recv().await
+    }
+
+    /// Send a command to the node.
+    fn send(&self, command: NodeCommand) -> Result<(), BlockchainError> {
+        self.commands
+            .send(command)
+            .map_err(|_| BlockchainError::NetworkError)
+    }
+}
+
+/// Peer-to-peer node exchanging blocks and transactions over libp2p gossipsub.
+pub struct Node {
+    /// Chain shared with the application.
+    chain: Arc<Mutex<Chain>>,
+
+    /// Identifier of the network.
+    chain_id: String,
+
+    /// libp2p swarm driving the network behaviour.
+    swarm: Swarm<Behaviour>,
+
+    /// Topic blocks are published on.
+    blocks: IdentTopic,
+
+    /// Topic transactions are published on.
+    transactions: IdentTopic,
+
+    /// Receiver of commands from the application.
+    commands: mpsc::UnboundedReceiver<NodeCommand>,
+
+    /// Sender of events to the application.
+    events: mpsc::UnboundedSender<NodeEvent>,
+
+    /// Hashes of the transactions recently published or received.
+    seen: RollingBloom,
+}
+
+impl Node {
+    /// Create a new node with a random identity.
+    ///
+    /// Must be called within a Tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain shared with the application.
+    /// - `config`: The node configuration.
+    ///
+    /// # Returns
+    ///
+    /// The node and the handle controlling it, or an error if the network cannot be set up.
+    pub fn new(
+        chain: Arc<Mutex<Chain>>,
+        config: NodeConfig,
+    ) -> Result<(Self, NodeHandle), BlockchainError> {
+        let mut swarm = SwarmBuilder::with_new_identity()
+            .with_tokio()
+            .with_tcp(
+                tcp::Config::default(),
+                noise::Config::new,
+                yamux::Config::default,
+            )
+            .map_err(|_| BlockchainError::NetworkError)?
+            .with_behaviour(|key| {
+                // Messages are only forwarded once the node accepted them
+                let config = gossipsub::ConfigBuilder::default()
+                    .validate_messages()
+                    .build()?;
+                let gossipsub =
+                    gossipsub::Behaviour::new(MessageAuthenticity::Signed(key.clone()), config)?;
+                let handshake = request_response::json::Behaviour::new(
+                    [(
+                        StreamProtocol::new(HANDSHAKE_PROTOCOL),
+                        ProtocolSupport::Full,
+                    )],
+                    request_response::Config::default(),
+                );
+
+                Ok(Behaviour {
+                    gossipsub,
+                    handshake,
+                })
+            })
+            .map_err(|_| BlockchainError::NetworkError)?
+            .with_swarm_config(|config| {
+                config.with_idle_connection_timeout(Duration::from_secs(IDLE_CONNECTION_TIMEOUT))
+            })
+            .build();
+
+        let blocks = IdentTopic::new(format!("{}/blocks", config.chain_id));
+        let transactions = IdentTopic::new(format!("{}/transactions", config.chain_id));
+
+        for topic in [&blocks, &transactions] {
+            swarm
+                .behaviour_mut()
+                .gossipsub
+                .subscribe(topic)
+                .map_err(|_| BlockchainError::NetworkError)?;
+        }
+
+        swarm
+            .listen_on(config.listen_address)
+            .map_err(|_| BlockchainError::NetworkError)?;
+
+        for peer in config.peers {
+            swarm
+                .dial(peer)
+                .map_err(|_| BlockchainError::NetworkError)?;
+        }
+
+        let (command_sender, command_receiver) = mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+
+        let node = Node {
+            chain,
+            chain_id: config.chain_id,
+            swarm,
+            blocks,
+            transactions,
+            commands: command_receiver,
+            events: event_sender,
+            seen: RollingBloom::new(SEEN_TRANSACTIONS),
+        };
+        let handle = NodeHandle {
+            commands: command_sender,
+            events: event_receiver,
+        };
+
+        Ok((node, handle))
+    }
+
+    /// Get the identifier of the node.
+    ///
+    /// # Returns
+    ///
+    /// The libp2p peer identifier.
+    pub fn peer_id(&self) -> PeerId {
+        *self.swarm.local_peer_id()
+    }
+
+    /// Build the handshake describing the local chain.
+    ///
+    /// # Returns
+    ///
+    /// The handshake with the network identifier and the current tip.
+    pub fn handshake(&self) -> Handshake {
+        let chain = self.chain.lock().unwrap_or_else(|e| e.into_inner());
+
+        Handshake {
+            chain_id: self.chain_id.to_owned(),
+            height: chain.chain.len(),
+            tip: chain.get_last_hash(),
+        }
+    }
+
+    /// Run the node until it is shut down or its handle is dropped.
+    pub async fn run(mut self) {
+        loop {
+            tokio::select! {
+                command = self.commands.recv() => match command {
+                    Some(NodeCommand::PublishBlock(block)) => {
+                        self.publish(self.blocks.clone(), WireMessage::Block(block))
+                    }
+                    Some(NodeCommand::PublishTransaction(transaction)) => {
+                        self.seen.insert(&transaction.hash);
+                        self.publish(self.transactions.clone(), WireMessage::Transaction(transaction))
+                    }
+                    Some(NodeCommand::Shutdown) | None => break,
+                },
+                event = self.swarm.select_next_some() => self.handle_swarm_event(event),
+            }
+        }
+    }
+
+    /// Publish a message on a topic, encoded as a wire frame.
+    ///
+    /// Publishing fails silently when no peer is subscribed yet, as gossip is best effort.
+    fn publish(&mut self, topic: IdentTopic, message: WireMessage) {
+        if let Ok(data) = message.encode() {
+            let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, data);
+        }
+    }
+
+    /// Handle an event of the swarm.
+    fn handle_swarm_event(&mut self, event: SwarmEvent<BehaviourEvent>) {
+        match event {
+            SwarmEvent::NewListenAddr { address, .. } => self.emit(NodeEvent::Listening(address)),
+            SwarmEvent::ConnectionEstablished {
+                peer_id, endpoint, ..
+            } if endpoint.is_dialer() => {
+                let handshake = self.handshake();
+                self.swarm
+                    .behaviour_mut()
+                    .handshake
+                    .send_request(&peer_id, handshake);
+            }
+            SwarmEvent::Behaviour(BehaviourEvent::Handshake(
+                request_response::Event::Message { peer, message },
+            )) => match message {
+                request_response::Message::Request {
+                    request, channel, ..
+                } => {
+                    let handshake = self.handshake();
+                    let _ = self
+                        .swarm
+                        .behaviour_mut()
+                        .handshake
+                        .send_response(channel, handshake);
+
+                    self.handle_handshake(peer, request);
+                }
+                request_response::Message::Response { response, .. } => {
+                    // The dialer closes the connection, so the peer can deliver its response first
+                    if !self.handle_handshake(peer, response) {
+                        let _ = self.swarm.disconnect_peer_id(peer);
+                    }
+                }
+            },
+            SwarmEvent::Behaviour(BehaviourEvent::Gossipsub(gossipsub::Event::Message {
+                propagation_source,
+                message_id,
+                message,
+                ..
+            })) => {
+                let acceptance = if message.topic == self.blocks.hash() {
+                    self.handle_block(propagation_source, &message.data)
+                } else if message.topic == self.transactions.hash() {
+                    self.handle_transaction(propagation_source, &message.data)
+                } else {
+                    MessageAcceptance::Ignore
+                };
+
+                let _ = self
+                    .swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(&message_id, &propagation_source, acceptance);
+            }
+            _ => {}
+        }
+    }
+
+    /// Accept or reject a peer based on its handshake.
+    ///
+    /// Returns whether the peer is on the same network.
+    fn handle_handshake(&mut self, peer: PeerId, handshake: Handshake) -> bool {
+        if handshake.chain_id != self.chain_id {
+            self.emit(NodeEvent::PeerRejected { peer, handshake });
+            return false;
+        }
+
+        self.emit(NodeEvent::PeerConnected { peer, handshake });
+
+        true
+    }
+
+    /// Import a block received from a peer.
+    ///
+    /// Returns whether the block should be relayed: only imported blocks are.
+    fn handle_block(&mut self, peer: PeerId, data: &[u8]) -> MessageAcceptance {
+        let block = match WireMessage::decode(data) {
+            Ok(WireMessage::Block(block)) => block,
+            _ => {
+                self.emit(NodeEvent::BlockRejected {
+                    peer,
+                    error: BlockchainError::InvalidBlockBody,
+                });
+
+                return MessageAcceptance::Reject;
+            }
+        };
+
+        let result = {
+            let mut chain = self.chain.lock().unwrap_or_else(|e| e.into_inner());
+            chain
+                .import_block(block)
+                .map(|_| chain.chain.len().saturating_sub(1))
+        };
+
+        match result {
+            Ok(height) => {
+                self.emit(NodeEvent::BlockImported { peer, height });
+                MessageAcceptance::Accept
+            }
+            Err(error) => {
+                self.emit(NodeEvent::BlockRejected { peer, error });
+                MessageAcceptance::Ignore
+            }
+        }
+    }
+
+    /// Forward a transaction received from a peer if its hash is valid and it is not a block
+    /// production transaction, which only travels inside blocks.
+    ///
+    /// Returns whether the transaction should be relayed: transactions seen recently are
+    /// ignored without being validated or forwarded to the application again.
+    fn handle_transaction(&mut self, peer: PeerId, data: &[u8]) -> MessageAcceptance {
+        let Ok(WireMessage::Transaction(transaction)) = WireMessage::decode(data) else {
+            return MessageAcceptance::Reject;
+        };
+
+        if self.seen.contains(&transaction.hash) {
+            return MessageAcceptance::Ignore;
+        }
+
+        if transaction.hash != transaction.compute_hash() || transaction.kind.is_block_production()
+        {
+            return MessageAcceptance::Reject;
+        }
+
+        // Only valid transactions are remembered, so a forged hash cannot shadow the real one
+        self.seen.insert(&transaction.hash);
+        self.emit(NodeEvent::TransactionReceived { peer, transaction });
+
+        MessageAcceptance::Accept
+    }
+
+    /// Send an event to the application, ignoring a dropped handle.
+    fn emit(&self, event: NodeEvent) {
+        let _ = self.events.send(event);
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Hash};
+
+/// Source of external data, e.g. a price feed.
+pub trait Oracle {
+    /// Get the address of the wallet the oracle reports from.
+    ///
+    /// # Returns
+    ///
+    /// The oracle address.
+    fn address(&self) -> &Address;
+
+    /// Fetch the current value of a feed.
+    ///
+    /// # Arguments
+    ///
+    /// - `feed`: The name of the feed, e.g. `BTC/USD`.
+    ///
+    /// # Returns
+    ///
+    /// The current value, or `None` if the oracle does not provide the feed.
+    fn fetch(&self, feed: &str) -> Option<f64>;
+}
+
+/// Value reported by an oracle and recorded on the chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OracleReport {
+    /// Name of the feed.
+    pub feed: String,
+
+    /// Reported value.
+    pub value: f64,
+
+    /// Address of the reporting oracle.
+    pub oracle: Address,
+
+    /// Hash of the oracle transaction recording the report.
+    pub hash: Hash,
+
+    /// Timestamp of the report.
+    pub timestamp: i64,
+}
+
+/// External data feed and the oracles allowed to report it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OracleFeed {
+    /// Addresses of the oracles allowed to report the feed.
+    pub oracles: BTreeSet<Address>,
+
+    /// Most recent report of the feed.
+    pub latest: Option<OracleReport>,
+}
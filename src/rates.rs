@@ -0,0 +1,126 @@
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use crate::BlockchainError;
+
+/// Source of exchange rates supplied by the embedding application, e.g. to annotate amounts
+/// with fiat values in explorers and statements.
+///
+/// The crate does not fetch any price: the provider decides where the rates come from and how
+/// fresh they are.
+pub trait RateProvider: Send + Sync {
+    /// Get the value of one unit of an asset in a currency.
+    ///
+    /// # Arguments
+    ///
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `currency`: The target currency, e.g. `USD`.
+    ///
+    /// # Returns
+    ///
+    /// The exchange rate, or `None` if the provider does not know it.
+    fn rate(&self, asset: &str, currency: &str) -> Option<f64>;
+}
+
+/// Exchange rates fixed by the application, e.g. for tests or statements at a given date.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FixedRates(BTreeMap<(String, String), f64>);
+
+impl FixedRates {
+    /// Add an exchange rate.
+    ///
+    /// # Arguments
+    ///
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `currency`: The target currency.
+    /// - `rate`: The value of one unit of the asset in the currency.
+    ///
+    /// # Returns
+    ///
+    /// The rates with the new one, replacing any previous rate of the pair.
+    pub fn with_rate(mut self, asset: &str, currency: &str, rate: f64) -> Self {
+        self.0
+            .insert((asset.to_string(), currency.to_string()), rate);
+
+        self
+    }
+}
+
+impl RateProvider for FixedRates {
+    fn rate(&self, asset: &str, currency: &str) -> Option<f64> {
+        self.0
+            .get(&(asset.to_string(), currency.to_string()))
+            .copied()
+    }
+}
+
+/// Exchange rate provider of a chain.
+///
+/// The provider is not persisted and is shared with cloned chains.
+#[derive(Clone, Default)]
+pub struct Rates(Option<Arc<dyn RateProvider>>);
+
+impl Rates {
+    /// Create the rates of a provider.
+    ///
+    /// # Arguments
+    ///
+    /// - `provider`: The exchange rate provider.
+    ///
+    /// # Returns
+    ///
+    /// New exchange rates.
+    pub fn new(provider: Arc<dyn RateProvider>) -> Self {
+        Rates(Some(provider))
+    }
+
+    /// Convert an amount of an asset to a currency.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount`: The amount of the asset.
+    /// - `asset`: `NATIVE_ASSET` or the token symbol.
+    /// - `currency`: The target currency.
+    ///
+    /// # Returns
+    ///
+    /// The value of the amount in the currency, or `RateUnavailable` if there is no provider or
+    /// it does not know a finite rate for the pair.
+    pub fn convert(
+        &self,
+        amount: f64,
+        asset: &str,
+        currency: &str,
+    ) -> Result<f64, BlockchainError> {
+        self.0
+            .as_ref()
+            .and_then(|provider| provider.rate(asset, currency))
+            .filter(|rate| rate.is_finite())
+            .map(|rate| amount * rate)
+            .ok_or_else(|| BlockchainError::RateUnavailable {
+                asset: asset.to_string(),
+                currency: currency.to_string(),
+            })
+    }
+}
+
+impl fmt::Debug for Rates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rates({})", self.0.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let rates = Rates::new(Arc::new(
+            FixedRates::default().with_rate("GOLD", "USD", 2.5),
+        ));
+
+        assert_eq!(rates.convert(4.0, "GOLD", "USD"), Ok(10.0));
+        assert!(rates.convert(4.0, "GOLD", "EUR").is_err());
+        assert!(Rates::default().convert(4.0, "GOLD", "USD").is_err());
+    }
+}
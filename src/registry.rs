@@ -0,0 +1,296 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::{migration, BlockchainError, Chain};
+
+/// Chain shared between the registry and its users, e.g. an API router.
+pub type SharedChain = Arc<Mutex<Chain>>;
+
+/// Named chains of one process, e.g. a mainnet and a testnet or one chain per tenant.
+///
+/// Every chain has its own state; with a storage directory, they are persisted side by side as
+/// `<name>.json` files in the format of `AsyncChain::save`.
+#[derive(Debug, Default)]
+pub struct ChainRegistry {
+    /// Registered chains by name.
+    chains: BTreeMap<String, SharedChain>,
+
+    /// Directory the chains are saved to and loaded from, if any.
+    storage: Option<PathBuf>,
+}
+
+impl ChainRegistry {
+    /// Create an empty registry without storage.
+    ///
+    /// # Returns
+    ///
+    /// A registry whose chains only live in memory.
+    pub fn new() -> Self {
+        ChainRegistry::default()
+    }
+
+    /// Create an empty registry persisting its chains to a directory.
+    ///
+    /// # Arguments
+    ///
+    /// - `directory`: The directory of the chain files, created when the first chain is saved.
+    ///
+    /// # Returns
+    ///
+    /// A registry with the given storage.
+    pub fn with_storage(directory: impl AsRef<Path>) -> Self {
+        ChainRegistry {
+            chains: BTreeMap::new(),
+            storage: Some(directory.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Register a chain under a name.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain, made of ASCII letters, digits, `-` and `_`.
+    /// - `chain`: The chain.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain, `InvalidConfiguration` if the name is invalid, or `ChainExists` if the
+    /// name is taken.
+    pub fn create(&mut self, name: &str, chain: Chain) -> Result<SharedChain, BlockchainError> {
+        check_name(name)?;
+
+        if self.chains.contains_key(name) {
+            return Err(BlockchainError::ChainExists {
+                name: name.to_string(),
+            });
+        }
+
+        let chain = Arc::new(Mutex::new(chain));
+        self.chains.insert(name.to_string(), chain.clone());
+
+        Ok(chain)
+    }
+
+    /// Look up a chain by name.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain, or `ChainNotFound`.
+    pub fn get(&self, name: &str) -> Result<SharedChain, BlockchainError> {
+        self.chains
+            .get(name)
+            .cloned()
+            .ok_or_else(|| not_found(name))
+    }
+
+    /// Get the names of the registered chains.
+    ///
+    /// # Returns
+    ///
+    /// The names, in alphabetical order.
+    pub fn names(&self) -> Vec<&str> {
+        self.chains.keys().map(String::as_str).collect()
+    }
+
+    /// Check whether a chain is registered under a name.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the chain is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.chains.contains_key(name)
+    }
+
+    /// Get the number of registered chains.
+    ///
+    /// # Returns
+    ///
+    /// The number of chains.
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+
+    /// Check whether the registry is empty.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no chain is registered.
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// Save a chain to the storage directory.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// `ChainNotFound`, or `StorageError` if the registry has no storage or the chain cannot be
+    /// written.
+    pub fn save(&self, name: &str) -> Result<(), BlockchainError> {
+        let path = self.path(name)?;
+        let bytes = {
+            let chain = self.get(name)?;
+            let chain = chain.lock().unwrap_or_else(|e| e.into_inner());
+            migration::encode(&chain)?
+        };
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|_| BlockchainError::StorageError)?;
+        }
+
+        fs::write(path, bytes).map_err(|_| BlockchainError::StorageError)
+    }
+
+    /// Save every chain to the storage directory.
+    ///
+    /// # Returns
+    ///
+    /// The first error of `save`, if any.
+    pub fn save_all(&self) -> Result<(), BlockchainError> {
+        self.chains.keys().try_for_each(|name| self.save(name))
+    }
+
+    /// Load a chain from the storage directory and register it, migrating it to the current
+    /// format and validating it as `AsyncChain::load` does.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain, `ChainExists` if the name is taken, `StorageError` if the registry has
+    /// no storage or the file cannot be read or parsed, or the validation error.
+    pub fn load(&mut self, name: &str) -> Result<SharedChain, BlockchainError> {
+        if self.chains.contains_key(name) {
+            return Err(BlockchainError::ChainExists {
+                name: name.to_string(),
+            });
+        }
+
+        let bytes = fs::read(self.path(name)?).map_err(|_| BlockchainError::StorageError)?;
+        let mut chain = migration::decode(&bytes)?;
+
+        chain.verify_block_hashes()?;
+        chain.audit_log().verify()?;
+        chain.revalidate()?;
+
+        self.create(name, chain)
+    }
+
+    /// Unregister a chain, saving it first if the registry has storage.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain, `ChainNotFound`, or the error of `save`, in which case the chain stays
+    /// registered.
+    pub fn unload(&mut self, name: &str) -> Result<SharedChain, BlockchainError> {
+        if self.storage.is_some() {
+            self.save(name)?;
+        }
+
+        self.chains.remove(name).ok_or_else(|| not_found(name))
+    }
+
+    /// Unregister a chain and delete its file from the storage directory, if any.
+    ///
+    /// # Arguments
+    ///
+    /// - `name`: The name of the chain.
+    ///
+    /// # Returns
+    ///
+    /// The shared chain, `ChainNotFound`, or `StorageError` if the file cannot be deleted.
+    pub fn delete(&mut self, name: &str) -> Result<SharedChain, BlockchainError> {
+        let chain = self.chains.remove(name).ok_or_else(|| not_found(name))?;
+
+        if let Ok(path) = self.path(name) {
+            match fs::remove_file(path) {
+                Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(BlockchainError::StorageError);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(chain)
+    }
+
+    /// Get the path of the file of a chain.
+    fn path(&self, name: &str) -> Result<PathBuf, BlockchainError> {
+        check_name(name)?;
+
+        self.storage
+            .as_ref()
+            .map(|directory| directory.join(format!("{name}.json")))
+            .ok_or(BlockchainError::StorageError)
+    }
+}
+
+/// Check that a chain name is not empty and is safe to use as a file name.
+fn check_name(name: &str) -> Result<(), BlockchainError> {
+    match !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        true => Ok(()),
+        false => Err(BlockchainError::InvalidConfiguration),
+    }
+}
+
+/// Build the error of a missing chain.
+fn not_found(name: &str) -> BlockchainError {
+    BlockchainError::ChainNotFound {
+        name: name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lifecycle() {
+        let mut registry = ChainRegistry::new();
+        registry
+            .create("mainnet", Chain::new(1.0, 100.0, 0.1))
+            .unwrap();
+        registry
+            .create("testnet", Chain::new(1.0, 10.0, 0.1))
+            .unwrap();
+
+        assert_eq!(registry.names(), vec!["mainnet", "testnet"]);
+        assert!(registry.create("mainnet", Chain::default()).is_err());
+        assert!(registry.create("../mainnet", Chain::default()).is_err());
+        assert_eq!(
+            registry.get("testnet").unwrap().lock().unwrap().reward,
+            10.0
+        );
+        assert_eq!(registry.save("mainnet"), Err(BlockchainError::StorageError));
+
+        registry.delete("testnet").unwrap();
+        assert!(!registry.contains("testnet"));
+        assert!(registry.get("testnet").is_err());
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError, TransactionRequest};
+
+/// Encoding of an exported transaction stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayFormat {
+    /// One JSON transfer request per line, e.g. `{"from":"...","to":"...","amount":1.0}`.
+    Jsonl,
+
+    /// One `from,to,amount` record per line, with an optional `from,to,amount` header.
+    Csv,
+}
+
+impl ReplayFormat {
+    /// Parse a line of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `line`: The line, without its line break.
+    ///
+    /// # Returns
+    ///
+    /// The transfer request, `None` for blank lines and CSV headers, or `ProtocolError` if the
+    /// line is malformed.
+    pub fn parse(&self, line: &str) -> Result<Option<TransactionRequest>, BlockchainError> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        match self {
+            ReplayFormat::Jsonl => serde_json::from_str(line)
+                .map(Some)
+                .map_err(|_| BlockchainError::ProtocolError),
+            ReplayFormat::Csv => {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+                match fields[..] {
+                    ["from", "to", "amount"] => Ok(None),
+                    [from, to, amount] => Ok(Some(TransactionRequest {
+                        from: from.parse()?,
+                        to: to.parse()?,
+                        amount: amount.parse().map_err(|_| BlockchainError::ProtocolError)?,
+                    })),
+                    _ => Err(BlockchainError::ProtocolError),
+                }
+            }
+        }
+    }
+}
+
+/// Record of a stream that could not be replayed.
+#[derive(Debug, PartialEq)]
+pub struct ReplayFailure {
+    /// 1-based line number of the record.
+    pub line: usize,
+
+    /// Reason the record was rejected.
+    pub error: BlockchainError,
+}
+
+/// Outcome of a replayed transaction stream.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Number of records applied.
+    pub applied: usize,
+
+    /// Records that were malformed or rejected by the chain, in stream order.
+    pub failures: Vec<ReplayFailure>,
+
+    /// Wallets created for addresses the chain did not know.
+    pub wallets_created: Vec<Address>,
+}
+
+impl ReplayReport {
+    /// Check whether every record was applied.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no record failed, `false` otherwise.
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let (from, to) = (Address::random(), Address::random());
+        let line = format!("{from}, {to}, 2.5");
+
+        assert_eq!(ReplayFormat::Csv.parse("from,to,amount"), Ok(None));
+        assert_eq!(
+            ReplayFormat::Csv.parse(&line),
+            Ok(Some(TransactionRequest::new(&from, &to, 2.5)))
+        );
+        assert_eq!(
+            ReplayFormat::Csv.parse("a,b"),
+            Err(BlockchainError::ProtocolError)
+        );
+        assert_eq!(ReplayFormat::Jsonl.parse("  "), Ok(None));
+    }
+}
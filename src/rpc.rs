@@ -0,0 +1,724 @@
+use std::{
+    fmt,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    auth::{self, Auth, Role},
+    Address, BlockchainError, Chain, Hash,
+};
+
+/// Version of the JSON-RPC protocol.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Error code of a request that is not valid JSON.
+pub const PARSE_ERROR: i64 = -32700;
+
+/// Error code of a request that is not a valid JSON-RPC request.
+pub const INVALID_REQUEST: i64 = -32600;
+
+/// Error code of a request calling an unknown method.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Error code of a request with invalid parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// Error code of a request rejected by the chain.
+pub const CHAIN_ERROR: i64 = -32000;
+
+/// Methods that require the `Admin` role when authentication is enabled.
+pub const ADMIN_METHODS: &[&str] = &[
+    "createWallet",
+    "generateBlock",
+    "updateDifficulty",
+    "updateReward",
+    "updateFee",
+];
+
+/// Methods available to the holders of wallet API keys, besides `sendTransaction` from the
+/// wallet of the key.
+pub const WALLET_KEY_METHODS: &[&str] = &[
+    "getBlockCount",
+    "getBlockByHeight",
+    "getLastHash",
+    "getTransaction",
+    "getBalance",
+];
+
+/// Name of the W3C Trace Context header carrying the trace of an HTTP request.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// W3C trace context of an incoming HTTP request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Identifier of the trace, as 32 lowercase hex digits.
+    pub trace_id: String,
+
+    /// Identifier of the caller's span, as 16 lowercase hex digits.
+    pub parent_id: String,
+
+    /// Whether the caller records the trace.
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Parse a `traceparent` header value.
+    ///
+    /// # Arguments
+    ///
+    /// - `value`: The header value, e.g. `00-<trace id>-<parent id>-01`.
+    ///
+    /// # Returns
+    ///
+    /// The trace context, or `None` if the value is malformed or uses an all-zero identifier.
+    pub fn parse(value: &str) -> Option<Self> {
+        let hex = |field: &str, len: usize| {
+            field.len() == len
+                && field
+                    .bytes()
+                    .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        };
+        let zero = |field: &str| field.bytes().all(|b| b == b'0');
+
+        let mut fields = value.trim().split('-');
+        let (version, trace_id, parent_id, flags) = (
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+        );
+
+        // Version `ff` is forbidden and version `00` has exactly four fields
+        if !hex(version, 2) || version == "ff" || (version == "00" && fields.next().is_some()) {
+            return None;
+        }
+
+        if !hex(trace_id, 32) || !hex(parent_id, 16) || zero(trace_id) || zero(parent_id) {
+            return None;
+        }
+
+        let flags = match hex(flags, 2) {
+            true => u8::from_str_radix(flags, 16).ok()?,
+            false => return None,
+        };
+
+        Some(TraceContext {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            sampled: flags & 1 == 1,
+        })
+    }
+
+    /// Extract the trace context from the headers of a request.
+    ///
+    /// # Arguments
+    ///
+    /// - `headers`: The request headers.
+    ///
+    /// # Returns
+    ///
+    /// The trace context, or `None` if the header is missing or malformed.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(TraceContext::parse)
+    }
+}
+
+/// JSON-RPC request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RpcRequest {
+    /// Protocol version, must be `2.0`.
+    pub jsonrpc: String,
+
+    /// Name of the called method.
+    pub method: String,
+
+    /// Parameters of the method, by position or by name.
+    #[serde(default)]
+    pub params: Value,
+
+    /// Identifier of the request, absent for notifications.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+}
+
+/// JSON-RPC error object.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    /// Error code.
+    pub code: i64,
+
+    /// Short description of the error.
+    pub message: String,
+
+    /// Additional information, e.g. the `BlockchainError` variant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Create a new error without additional information.
+    ///
+    /// # Arguments
+    ///
+    /// - `code`: The error code.
+    /// - `message`: The error description.
+    ///
+    /// # Returns
+    ///
+    /// A new error object.
+    pub fn new(code: i64, message: &str) -> Self {
+        RpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+}
+
+impl From<BlockchainError> for RpcError {
+    fn from(error: BlockchainError) -> Self {
+        RpcError {
+            code: CHAIN_ERROR,
+            message: error.to_string(),
+            data: serde_json::to_value(&error).ok(),
+        }
+    }
+}
+
+/// JSON-RPC response.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse {
+    /// Protocol version, always `2.0`.
+    pub jsonrpc: String,
+
+    /// Result of the method, present on success.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+
+    /// Error of the method, present on failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+
+    /// Identifier of the request.
+    pub id: Value,
+}
+
+impl RpcResponse {
+    /// Create a response from the outcome of a method.
+    ///
+    /// # Arguments
+    ///
+    /// - `id`: The identifier of the request.
+    /// - `outcome`: The result or the error of the method.
+    ///
+    /// # Returns
+    ///
+    /// A new response.
+    pub fn new(id: Value, outcome: Result<Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        RpcResponse {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            result,
+            error,
+            id,
+        }
+    }
+}
+
+/// Parameters of `getBlockByHeight`.
+#[derive(Deserialize)]
+struct HeightParams {
+    height: usize,
+}
+
+/// Parameters of `getTransaction`.
+#[derive(Deserialize)]
+struct HashParams {
+    hash: Hash,
+}
+
+/// Parameters of `getBalance`.
+#[derive(Deserialize)]
+struct AddressParams {
+    address: Address,
+}
+
+/// Parameters of `createWallet`.
+#[derive(Deserialize)]
+struct EmailParams {
+    email: String,
+}
+
+/// Parameters of `sendTransaction`.
+#[derive(Deserialize)]
+struct TransactionParams {
+    from: Address,
+    to: Address,
+    amount: f64,
+}
+
+/// Parameters of `updateDifficulty`.
+#[derive(Deserialize)]
+struct DifficultyParams {
+    difficulty: f64,
+}
+
+/// Parameters of `updateReward`.
+#[derive(Deserialize)]
+struct RewardParams {
+    reward: f64,
+}
+
+/// Parameters of `updateFee`.
+#[derive(Deserialize)]
+struct FeeParams {
+    fee: f64,
+}
+
+/// JSON-RPC 2.0 server wrapping a shared chain.
+///
+/// Supported methods:
+///
+/// - `getBlockCount()`: number of blocks.
+/// - `getBlockByHeight(height)`: block at a height.
+/// - `getLastHash()`: hash of the last block.
+/// - `getTransaction(hash)`: transaction by hash.
+/// - `getBalance(address)`: native balance of a wallet.
+/// - `createWallet(email)`: new wallet address.
+/// - `sendTransaction(from, to, amount)`: hash of the new transaction.
+/// - `generateBlock()`: hash of the new block.
+/// - `updateDifficulty(difficulty)`, `updateReward(reward)`, `updateFee(fee)`: the new value.
+///
+/// Parameters may be passed by position or by name.
+///
+/// When authentication is enabled with `with_auth`, HTTP requests must carry an API key,
+/// are rate limited per key, and the `ADMIN_METHODS` require the `Admin` role. Wallet API keys
+/// issued by the chain are accepted as well, for the `WALLET_KEY_METHODS` and for
+/// `sendTransaction` from their wallet within their limit.
+#[derive(Clone)]
+pub struct RpcServer {
+    /// Chain shared with the application.
+    chain: Arc<Mutex<Chain>>,
+
+    /// Authentication and rate limiting of HTTP requests, if enabled.
+    auth: Option<Arc<Auth>>,
+}
+
+impl RpcServer {
+    /// Create a new server.
+    ///
+    /// # Arguments
+    ///
+    /// - `chain`: The chain shared with the application.
+    ///
+    /// # Returns
+    ///
+    /// A new server.
+    pub fn new(chain: Arc<Mutex<Chain>>) -> Self {
+        RpcServer { chain, auth: None }
+    }
+
+    /// Require an API key on HTTP requests and rate limit them per key.
+    ///
+    /// # Arguments
+    ///
+    /// - `auth`: The API keys and rate limits.
+    ///
+    /// # Returns
+    ///
+    /// The server with authentication enabled.
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
+
+    /// Handle a raw JSON-RPC request or batch of requests from a trusted caller.
+    ///
+    /// # Arguments
+    ///
+    /// - `body`: The request body.
+    ///
+    /// # Returns
+    ///
+    /// The response body, or `None` if the request only contained notifications.
+    pub fn handle(&self, body: &str) -> Option<String> {
+        self.handle_as(body, Role::Admin)
+    }
+
+    /// Handle a raw JSON-RPC request or batch of requests on behalf of a role.
+    ///
+    /// # Arguments
+    ///
+    /// - `body`: The request body.
+    /// - `role`: The role of the caller; `ADMIN_METHODS` fail with `Unauthorized` for users.
+    ///
+    /// # Returns
+    ///
+    /// The response body, or `None` if the request only contained notifications.
+    pub fn handle_as(&self, body: &str, role: Role) -> Option<String> {
+        self.handle_body(body, Caller::Role(role))
+    }
+
+    /// Handle a raw JSON-RPC request or batch of requests on behalf of the holder of a wallet
+    /// API key.
+    ///
+    /// # Arguments
+    ///
+    /// - `body`: The request body.
+    /// - `key`: The wallet API key; methods fail with `Unauthenticated` if it is unknown.
+    ///
+    /// # Returns
+    ///
+    /// The response body, or `None` if the request only contained notifications.
+    pub fn handle_with_wallet_key(&self, body: &str, key: &str) -> Option<String> {
+        self.handle_body(body, Caller::Wallet(key))
+    }
+
+    /// Handle a raw JSON-RPC request or batch of requests on behalf of a caller.
+    fn handle_body(&self, body: &str, caller: Caller<'_>) -> Option<String> {
+        let value: Value = match serde_json::from_str(body) {
+            Ok(value) => value,
+            Err(_) => {
+                let error = RpcError::new(PARSE_ERROR, "Parse error.");
+                return serde_json::to_string(&RpcResponse::new(Value::Null, Err(error))).ok();
+            }
+        };
+
+        match value {
+            Value::Array(batch) if !batch.is_empty() => {
+                let responses: Vec<RpcResponse> = batch
+                    .into_iter()
+                    .filter_map(|request| self.handle_value(request, caller))
+                    .collect();
+
+                match responses.is_empty() {
+                    true => None,
+                    false => serde_json::to_string(&responses).ok(),
+                }
+            }
+            value => self
+                .handle_value(value, caller)
+                .and_then(|response| serde_json::to_string(&response).ok()),
+        }
+    }
+
+    /// Handle a single parsed JSON-RPC request from a trusted caller.
+    ///
+    /// # Arguments
+    ///
+    /// - `request`: The request.
+    ///
+    /// # Returns
+    ///
+    /// The response, or `None` if the request is a notification.
+    pub fn handle_request(&self, request: RpcRequest) -> Option<RpcResponse> {
+        self.handle_request_as(request, Role::Admin)
+    }
+
+    /// Handle a single parsed JSON-RPC request on behalf of a role.
+    ///
+    /// # Arguments
+    ///
+    /// - `request`: The request.
+    /// - `role`: The role of the caller.
+    ///
+    /// # Returns
+    ///
+    /// The response, or `None` if the request is a notification.
+    pub fn handle_request_as(&self, request: RpcRequest, role: Role) -> Option<RpcResponse> {
+        self.dispatch(request, Caller::Role(role))
+    }
+
+    /// Handle a single parsed JSON-RPC request on behalf of a caller.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "rpc.call",
+            skip_all,
+            fields(
+                method = %request.method,
+                block = tracing::field::Empty,
+                transaction = tracing::field::Empty,
+            ),
+        )
+    )]
+    fn dispatch(&self, request: RpcRequest, caller: Caller<'_>) -> Option<RpcResponse> {
+        let outcome = match (request.jsonrpc == JSONRPC_VERSION, caller) {
+            (false, _) => Err(RpcError::new(INVALID_REQUEST, "Invalid request.")),
+            (true, Caller::Role(role))
+                if role < Role::Admin && ADMIN_METHODS.contains(&request.method.as_str()) =>
+            {
+                Err(BlockchainError::Unauthorized.into())
+            }
+            (true, Caller::Role(_)) => self.call(&request.method, request.params),
+            (true, Caller::Wallet(key)) => self.call_with_key(key, &request.method, request.params),
+        };
+
+        request.id.map(|id| RpcResponse::new(id, outcome))
+    }
+
+    /// Build an HTTP router serving JSON-RPC requests on `POST /`.
+    ///
+    /// # Returns
+    ///
+    /// A new router.
+    pub fn router(self) -> Router {
+        Router::new().route("/", post(serve_http)).with_state(self)
+    }
+
+    /// Serve JSON-RPC requests over HTTP until the server fails.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to listen on.
+    ///
+    /// # Returns
+    ///
+    /// An error if the server cannot be started.
+    pub async fn serve(self, address: SocketAddr) -> Result<(), BlockchainError> {
+        axum::Server::try_bind(&address)
+            .map_err(|_| BlockchainError::NetworkError)?
+            .serve(self.router().into_make_service())
+            .await
+            .map_err(|_| BlockchainError::NetworkError)
+    }
+
+    /// Authenticate an HTTP request with an API key of the configuration or, failing that, a
+    /// wallet API key of the chain, and count it against the rate limit of the key.
+    fn authorize<'a>(
+        &self,
+        auth: &Auth,
+        headers: &'a HeaderMap,
+    ) -> Result<Caller<'a>, BlockchainError> {
+        match auth.authorize(headers) {
+            Err(BlockchainError::Unauthenticated) => {
+                let key = auth::api_key(headers).ok_or(BlockchainError::Unauthenticated)?;
+                self.chain
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .wallet_key(key)?;
+                auth.check_rate(key)?;
+
+                Ok(Caller::Wallet(key))
+            }
+            result => result.map(Caller::Role),
+        }
+    }
+
+    /// Handle a single request that has not been validated yet.
+    fn handle_value(&self, value: Value, caller: Caller<'_>) -> Option<RpcResponse> {
+        match serde_json::from_value::<RpcRequest>(value) {
+            Ok(request) => self.dispatch(request, caller),
+            Err(_) => Some(RpcResponse::new(
+                Value::Null,
+                Err(RpcError::new(INVALID_REQUEST, "Invalid request.")),
+            )),
+        }
+    }
+
+    /// Dispatch a method call of the holder of a wallet API key to the chain.
+    fn call_with_key(&self, key: &str, method: &str, params: Value) -> Result<Value, RpcError> {
+        if method == "sendTransaction" {
+            let TransactionParams { from, to, amount } = parse(params)?;
+            let mut chain = self.chain.lock().unwrap_or_else(|e| e.into_inner());
+
+            if chain.wallet_key(key)?.wallet != from {
+                return Err(BlockchainError::Unauthorized.into());
+            }
+
+            let hash = chain.send_with_key(key, &to, amount)?;
+            record("transaction", hash);
+
+            return Ok(json!(hash));
+        }
+
+        self.chain
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .wallet_key(key)?;
+
+        match WALLET_KEY_METHODS.contains(&method) {
+            true => self.call(method, params),
+            false => Err(BlockchainError::Unauthorized.into()),
+        }
+    }
+
+    /// Dispatch a method call to the chain.
+    fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let mut chain = self.chain.lock().unwrap_or_else(|e| e.into_inner());
+
+        match method {
+            "getBlockCount" => Ok(json!(chain.chain.len())),
+            "getBlockByHeight" => {
+                let HeightParams { height } = parse(params)?;
+                let block = chain
+                    .chain
+                    .get(height)
+                    .ok_or(BlockchainError::BlockNotFound)?;
+                record("block", block.hash);
+
+                to_value(block)
+            }
+            "getLastHash" => Ok(json!(chain.get_last_hash())),
+            "getTransaction" => {
+                let HashParams { hash } = parse(params)?;
+                record("transaction", hash);
+
+                to_value(chain.get_transaction(&hash)?)
+            }
+            "getBalance" => {
+                let AddressParams { address } = parse(params)?;
+                let balance = chain.get_wallet_balance(&address).ok_or_else(|| {
+                    BlockchainError::WalletNotFound {
+                        address: address.to_owned(),
+                    }
+                })?;
+
+                Ok(json!(balance))
+            }
+            "createWallet" => {
+                let EmailParams { email } = parse(params)?;
+
+                Ok(json!(chain.create_wallet(&email)))
+            }
+            "sendTransaction" => {
+                let TransactionParams { from, to, amount } = parse(params)?;
+                chain.add_transaction(&from, &to, amount)?;
+
+                // The new transaction is the last one recorded for the sender
+                let hash = chain
+                    .wallets
+                    .get(&from)
+                    .and_then(|wallet| wallet.transaction_hashes.last());
+
+                if let Some(hash) = hash {
+                    record("transaction", hash);
+                }
+
+                Ok(json!(hash))
+            }
+            "generateBlock" => {
+                chain.generate_new_block();
+                record("block", chain.get_last_hash());
+
+                Ok(json!(chain.get_last_hash()))
+            }
+            "updateDifficulty" => {
+                let DifficultyParams { difficulty } = parse(params)?;
+                chain.update_difficulty(difficulty);
+
+                Ok(json!(chain.difficulty))
+            }
+            "updateReward" => {
+                let RewardParams { reward } = parse(params)?;
+                chain.update_reward(reward);
+
+                Ok(json!(chain.reward))
+            }
+            "updateFee" => {
+                let FeeParams { fee } = parse(params)?;
+                chain.update_fee(fee);
+
+                Ok(json!(chain.fee))
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, "Method not found.")),
+        }
+    }
+}
+
+/// Caller of a JSON-RPC method.
+#[derive(Clone, Copy)]
+enum Caller<'a> {
+    /// Trusted caller or holder of an API key of the authentication configuration.
+    Role(Role),
+
+    /// Holder of a wallet API key issued by the chain.
+    Wallet(&'a str),
+}
+
+/// Deserialize method parameters given by position or by name.
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|_| RpcError::new(INVALID_PARAMS, "Invalid params."))
+}
+
+/// Record a block or transaction identifier on the span of the current call.
+#[cfg(feature = "tracing")]
+fn record(field: &str, value: impl fmt::Display) {
+    tracing::Span::current().record(field, tracing::field::display(value));
+}
+
+/// Record a block or transaction identifier on the span of the current call.
+#[cfg(not(feature = "tracing"))]
+fn record(_field: &str, _value: impl fmt::Display) {}
+
+/// Serialize a method result.
+fn to_value<T: Serialize>(item: &T) -> Result<Value, RpcError> {
+    serde_json::to_value(item).map_err(|_| RpcError::new(CHAIN_ERROR, "Serialization failed."))
+}
+
+/// Serve a JSON-RPC request received over HTTP.
+async fn serve_http(State(server): State<RpcServer>, headers: HeaderMap, body: String) -> Response {
+    let caller = match &server.auth {
+        Some(auth) => match server.authorize(auth, &headers) {
+            Ok(caller) => caller,
+            Err(error) => {
+                let status = match error {
+                    BlockchainError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+                    _ => StatusCode::UNAUTHORIZED,
+                };
+                let response = RpcResponse::new(Value::Null, Err(error.into()));
+
+                return (status, axum::Json(response)).into_response();
+            }
+        },
+        None => Caller::Role(Role::Admin),
+    };
+
+    let handle = || server.handle_body(&body, caller);
+
+    // Run the request in a span continuing the trace of the caller, if any
+    #[cfg(feature = "tracing")]
+    let response = {
+        let context = TraceContext::from_headers(&headers);
+        let span = tracing::info_span!(
+            "rpc.request",
+            otel.kind = "server",
+            trace_id = context.as_ref().map(|context| context.trace_id.as_str()),
+            parent_id = context.as_ref().map(|context| context.parent_id.as_str()),
+            sampled = context.as_ref().map(|context| context.sampled),
+        );
+
+        span.in_scope(handle)
+    };
+
+    #[cfg(not(feature = "tracing"))]
+    let response = handle();
+
+    match response {
+        Some(response) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/json")],
+            response,
+        )
+            .into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError};
+
+/// Maximum number of operations in a script.
+pub const MAX_SCRIPT_LENGTH: usize = 64;
+
+/// Single operation of a spend condition script.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum OpCode {
+    /// Push whether the given address authorized the spend.
+    CheckSig(Address),
+
+    /// Push whether at least `threshold` of the given addresses authorized the spend.
+    CheckMultiSig {
+        /// Number of required authorizations.
+        threshold: usize,
+
+        /// Addresses allowed to authorize.
+        keys: Vec<Address>,
+    },
+
+    /// Push whether the current timestamp is at or after the given timestamp.
+    CheckTimeLock(i64),
+
+    /// Push whether the next block height is at or after the given height.
+    CheckHeightLock(usize),
+
+    /// Pop two values and push whether both are true.
+    And,
+
+    /// Pop two values and push whether either is true.
+    Or,
+
+    /// Pop a value and push its negation.
+    Not,
+
+    /// Pop a value and fail the script if it is false.
+    Verify,
+}
+
+/// Data a script is evaluated against.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptContext {
+    /// Addresses that authorized the spend.
+    ///
+    /// The chain has no key material, so authorizations are asserted by the caller,
+    /// e.g. an API layer that has authenticated the signers.
+    pub signers: Vec<Address>,
+
+    /// Current timestamp.
+    pub timestamp: i64,
+
+    /// Height of the next block.
+    pub height: usize,
+}
+
+/// Stack-based spend condition script.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Script(pub Vec<OpCode>);
+
+impl Script {
+    /// Create a script requiring the authorization of a single address.
+    ///
+    /// # Arguments
+    /// - `address`: The address that must authorize.
+    ///
+    /// # Returns
+    /// A new script.
+    pub fn signature(address: &Address) -> Self {
+        Script(vec![OpCode::CheckSig(address.to_owned())])
+    }
+
+    /// Create a script requiring `threshold` of the given addresses to authorize.
+    ///
+    /// # Arguments
+    /// - `threshold`: The number of required authorizations.
+    /// - `keys`: The addresses allowed to authorize.
+    ///
+    /// # Returns
+    /// A new script.
+    pub fn multisig(threshold: usize, keys: Vec<Address>) -> Self {
+        Script(vec![OpCode::CheckMultiSig { threshold, keys }])
+    }
+
+    /// Evaluate the script.
+    ///
+    /// # Arguments
+    /// - `context`: The data the script is evaluated against.
+    ///
+    /// # Returns
+    /// An error if the script is malformed or its condition is not satisfied.
+    pub fn evaluate(&self, context: &ScriptContext) -> Result<(), BlockchainError> {
+        if self.0.len() > MAX_SCRIPT_LENGTH {
+            return Err(BlockchainError::InvalidScript);
+        }
+
+        let mut stack: Vec<bool> = vec![];
+
+        for op in self.0.iter() {
+            match op {
+                OpCode::CheckSig(address) => stack.push(context.signers.contains(address)),
+                OpCode::CheckMultiSig { threshold, keys } => {
+                    let count = keys
+                        .iter()
+                        .filter(|key| context.signers.contains(key))
+                        .count();
+
+                    stack.push(count >= *threshold);
+                }
+                OpCode::CheckTimeLock(timestamp) => stack.push(context.timestamp >= *timestamp),
+                OpCode::CheckHeightLock(height) => stack.push(context.height >= *height),
+                OpCode::And | OpCode::Or => {
+                    let (a, b) = match (stack.pop(), stack.pop()) {
+                        (Some(a), Some(b)) => (a, b),
+                        _ => return Err(BlockchainError::InvalidScript),
+                    };
+
+                    stack.push(match op {
+                        OpCode::And => a && b,
+                        _ => a || b,
+                    });
+                }
+                OpCode::Not => match stack.pop() {
+                    Some(value) => stack.push(!value),
+                    None => return Err(BlockchainError::InvalidScript),
+                },
+                OpCode::Verify => match stack.pop() {
+                    Some(true) => {}
+                    Some(false) => return Err(BlockchainError::ScriptFailed),
+                    None => return Err(BlockchainError::InvalidScript),
+                },
+            }
+        }
+
+        // The script succeeds if every value left on the stack is true
+        match stack.iter().all(|value| *value) {
+            true => Ok(()),
+            false => Err(BlockchainError::ScriptFailed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(signers: Vec<Address>) -> ScriptContext {
+        ScriptContext {
+            signers,
+            timestamp: 100,
+            height: 10,
+        }
+    }
+
+    #[test]
+    fn test_signature() {
+        let address = Address::random();
+        let script = Script::signature(&address);
+
+        assert!(script.evaluate(&context(vec![address])).is_ok());
+        assert_eq!(
+            script.evaluate(&context(vec![])),
+            Err(BlockchainError::ScriptFailed)
+        );
+    }
+
+    #[test]
+    fn test_multisig() {
+        let keys: Vec<Address> = (0..3).map(|_| Address::random()).collect();
+        let script = Script::multisig(2, keys.clone());
+
+        assert!(script.evaluate(&context(keys[..2].to_vec())).is_ok());
+        assert!(script.evaluate(&context(keys[..1].to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_time_and_height_locks() {
+        let unlocked = Script(vec![
+            OpCode::CheckTimeLock(100),
+            OpCode::CheckHeightLock(10),
+            OpCode::And,
+        ]);
+        let locked = Script(vec![OpCode::CheckTimeLock(101), OpCode::Verify]);
+
+        assert!(unlocked.evaluate(&context(vec![])).is_ok());
+        assert_eq!(
+            locked.evaluate(&context(vec![])),
+            Err(BlockchainError::ScriptFailed)
+        );
+    }
+
+    #[test]
+    fn test_or_not() {
+        let address = Address::random();
+        let script = Script(vec![
+            OpCode::CheckSig(address),
+            OpCode::CheckTimeLock(1000),
+            OpCode::Not,
+            OpCode::Or,
+        ]);
+
+        assert!(script.evaluate(&context(vec![])).is_ok());
+    }
+
+    #[test]
+    fn test_malformed() {
+        let script = Script(vec![OpCode::And]);
+
+        assert_eq!(
+            script.evaluate(&context(vec![])),
+            Err(BlockchainError::InvalidScript)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Block, BlockchainError, NATIVE_ASSET};
+
+/// Number of seconds in a settlement day.
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Grouping of the blocks of a settlement report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SettlementPeriod {
+    /// UTC calendar days of the block timestamps.
+    Daily,
+
+    /// Consecutive ranges of a number of blocks, starting with the genesis block.
+    Blocks(usize),
+}
+
+/// Settlement summary of the native coin movements of a period, for back-office
+/// reconciliation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Settlement {
+    /// Start of the period: midnight UTC for daily periods, the timestamp of the first block
+    /// otherwise.
+    pub start: i64,
+
+    /// Height of the first block of the period.
+    pub first_height: usize,
+
+    /// Height of the last block of the period.
+    pub last_height: usize,
+
+    /// Number of user transactions, block production excluded.
+    pub transactions: usize,
+
+    /// Total amount of the user transactions.
+    pub volume: f64,
+
+    /// Total fees paid by the user transactions.
+    pub fees: f64,
+
+    /// Amount received minus amount sent by every wallet involved, block rewards and routed
+    /// fees included.
+    pub net_flows: BTreeMap<Address, f64>,
+}
+
+impl Settlement {
+    /// Create an empty settlement starting with a block.
+    ///
+    /// # Arguments
+    ///
+    /// - `start`: The start of the period.
+    /// - `height`: The height of the first block.
+    ///
+    /// # Returns
+    ///
+    /// A settlement without transactions.
+    pub fn new(start: i64, height: usize) -> Self {
+        Settlement {
+            start,
+            first_height: height,
+            last_height: height,
+            ..Settlement::default()
+        }
+    }
+
+    /// Get the number of wallets that sent or received native coins during the period.
+    ///
+    /// # Returns
+    ///
+    /// The number of wallets with a net flow.
+    pub fn active_wallets(&self) -> usize {
+        self.net_flows.len()
+    }
+
+    /// Add the native transactions of a block to the settlement.
+    ///
+    /// Pruned blocks only extend the period, as their transactions are not available.
+    ///
+    /// # Arguments
+    ///
+    /// - `block`: The next block of the period.
+    pub fn add_block(&mut self, block: &Block) {
+        self.last_height = block.height;
+
+        for tx in block
+            .transactions
+            .values()
+            .filter(|tx| tx.asset == NATIVE_ASSET)
+        {
+            if !tx.kind.is_block_production() {
+                self.transactions += 1;
+                self.volume += tx.amount;
+                self.fees += tx.fee;
+            }
+
+            self.flow(&tx.from, -tx.amount);
+            match tx.outputs.is_empty() {
+                true => self.flow(&tx.to, tx.amount),
+                false => tx
+                    .outputs
+                    .iter()
+                    .for_each(|output| self.flow(&output.to, output.amount)),
+            }
+        }
+    }
+
+    /// Record a movement of a wallet, ignoring the root.
+    fn flow(&mut self, address: &Address, amount: f64) {
+        if !address.is_root() {
+            *self.net_flows.entry(address.to_owned()).or_default() += amount;
+        }
+    }
+}
+
+/// Summarize blocks by period.
+///
+/// # Arguments
+///
+/// - `blocks`: The blocks, in chain order.
+/// - `period`: The grouping of the blocks.
+///
+/// # Returns
+///
+/// One settlement per period with blocks, in chain order, or `InvalidConfiguration` for
+/// periods of zero blocks.
+pub fn settle(
+    blocks: &[Block],
+    period: SettlementPeriod,
+) -> Result<Vec<Settlement>, BlockchainError> {
+    if period == SettlementPeriod::Blocks(0) {
+        return Err(BlockchainError::InvalidConfiguration);
+    }
+
+    let mut settlements: Vec<(i64, Settlement)> = Vec::new();
+
+    for block in blocks {
+        let (key, start) = match period {
+            SettlementPeriod::Daily => {
+                let day = block.header.timestamp.div_euclid(SECONDS_PER_DAY);
+                (day, day * SECONDS_PER_DAY)
+            }
+            SettlementPeriod::Blocks(size) => {
+                ((block.height / size) as i64, block.header.timestamp)
+            }
+        };
+
+        match settlements.last_mut() {
+            Some((last, settlement)) if *last == key => settlement.add_block(block),
+            _ => {
+                let mut settlement = Settlement::new(start, block.height);
+                settlement.add_block(block);
+                settlements.push((key, settlement));
+            }
+        }
+    }
+
+    Ok(settlements
+        .into_iter()
+        .map(|(_, settlement)| settlement)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hash, Transaction, TransactionKind};
+
+    fn block(height: usize, timestamp: i64, transactions: Vec<Transaction>) -> Block {
+        let mut block = Block::new(Hash::zero(), 1.0);
+        block.height = height;
+        block.header.timestamp = timestamp;
+        block.transactions = transactions.into_iter().map(|tx| (tx.hash, tx)).collect();
+
+        block
+    }
+
+    #[test]
+    fn test_settle() {
+        let (miner, from, to) = (Address::random(), Address::random(), Address::random());
+        let reward = Transaction::new(Address::root(), miner.to_owned(), 0.0, 10.0)
+            .with_kind(TransactionKind::Reward);
+        let transfer = Transaction::new(from.to_owned(), to.to_owned(), 0.5, 4.0);
+
+        let blocks = vec![
+            block(0, SECONDS_PER_DAY - 1, vec![reward]),
+            block(1, SECONDS_PER_DAY, vec![transfer]),
+            block(2, SECONDS_PER_DAY + 60, vec![]),
+        ];
+
+        let daily = settle(&blocks, SettlementPeriod::Daily).unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].transactions, 0);
+        assert_eq!(daily[0].net_flows[&miner], 10.0);
+        assert_eq!(daily[1].start, SECONDS_PER_DAY);
+        assert_eq!((daily[1].first_height, daily[1].last_height), (1, 2));
+        assert_eq!((daily[1].volume, daily[1].fees), (4.0, 0.5));
+        assert_eq!(daily[1].net_flows[&from], -4.0);
+        assert_eq!(daily[1].net_flows[&to], 4.0);
+        assert_eq!(daily[1].active_wallets(), 2);
+
+        let ranges = settle(&blocks, SettlementPeriod::Blocks(2)).unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].active_wallets(), 3);
+        assert!(settle(&blocks, SettlementPeriod::Blocks(0)).is_err());
+    }
+}
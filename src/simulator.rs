@@ -0,0 +1,328 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{Address, BlockchainError, Chain, Environment, Hash, ManualClock};
+
+/// Time of the genesis block of a simulated chain, in seconds since the Unix epoch.
+pub const SIMULATION_GENESIS_TIME: i64 = 1_700_000_000;
+
+/// How the senders and receivers of simulated transfers are picked among the wallets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransferDistribution {
+    /// Every wallet is equally likely to send or receive.
+    Uniform,
+
+    /// A share of the transfers involves the first wallets only, e.g. an exchange.
+    Hotspot {
+        /// Number of hot wallets.
+        wallets: usize,
+
+        /// Probability that a transfer involves a hot wallet, from 0 to 1.
+        share: f64,
+    },
+}
+
+/// Parameters of a simulated workload.
+#[derive(Clone, Debug)]
+pub struct WorkloadConfig {
+    /// Seed of the chain environment and of the workload.
+    pub seed: u64,
+
+    /// Number of steps, each attempting one transfer.
+    pub steps: usize,
+
+    /// Number of funded wallets created before the first step.
+    pub initial_wallets: usize,
+
+    /// Balance of each new wallet.
+    pub initial_balance: f64,
+
+    /// Probability that a step also creates a wallet, from 0 to 1.
+    pub wallet_rate: f64,
+
+    /// Distribution of the senders and receivers of the transfers.
+    pub distribution: TransferDistribution,
+
+    /// Range of the transfer amounts.
+    pub amount: (f64, f64),
+
+    /// Number of steps between two mined blocks, or zero to never mine.
+    pub block_interval: usize,
+
+    /// Number of simulated seconds per step.
+    pub step_time: i64,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        WorkloadConfig {
+            seed: 0,
+            steps: 1_000,
+            initial_wallets: 10,
+            initial_balance: 1_000.0,
+            wallet_rate: 0.01,
+            distribution: TransferDistribution::Uniform,
+            amount: (1.0, 10.0),
+            block_interval: 100,
+            step_time: 1,
+        }
+    }
+}
+
+/// Invariant broken by a simulated chain.
+#[derive(Debug, PartialEq)]
+pub enum InvariantViolation {
+    /// A wallet balance is negative or not finite.
+    InvalidBalance {
+        /// Address of the wallet.
+        address: Address,
+
+        /// The offending balance.
+        balance: f64,
+    },
+
+    /// A wallet history refers to a transaction the chain does not know.
+    UnknownTransaction {
+        /// Address of the wallet.
+        address: Address,
+
+        /// Hash of the missing transaction.
+        hash: Hash,
+    },
+
+    /// The chain fails `Chain::validate_chain`.
+    InvalidChain(BlockchainError),
+}
+
+/// Outcome of a simulated workload.
+#[derive(Debug, Default)]
+pub struct SimulationReport {
+    /// Number of steps run.
+    pub steps: usize,
+
+    /// Number of wallets created, including the initial ones.
+    pub wallets_created: usize,
+
+    /// Number of transfers accepted by the chain.
+    pub transfers_accepted: usize,
+
+    /// Number of transfers rejected by the chain, e.g. for insufficient funds.
+    pub transfers_rejected: usize,
+
+    /// Number of blocks mined after the genesis block.
+    pub blocks_mined: usize,
+
+    /// Wall-clock time spent running the steps.
+    pub elapsed: Duration,
+
+    /// Invariants broken at the end of the run.
+    pub violations: Vec<InvariantViolation>,
+}
+
+impl SimulationReport {
+    /// Calculate the throughput of the run.
+    ///
+    /// # Returns
+    ///
+    /// The number of attempted transfers per second of wall-clock time.
+    pub fn throughput(&self) -> f64 {
+        let transfers = (self.transfers_accepted + self.transfers_rejected) as f64;
+
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => transfers / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Check whether the chain kept its invariants.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no invariant was broken, `false` otherwise.
+    pub fn is_consistent(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Workload generator running random wallet creations, transfers and mining against a chain.
+///
+/// The chain is built with a seeded `Environment` and a `ManualClock` advanced by `step_time`
+/// every step, so a run is reproduced exactly from its configuration.
+#[derive(Debug)]
+pub struct Simulator {
+    /// The simulated chain.
+    pub chain: Chain,
+
+    /// Addresses of the simulated wallets, in creation order.
+    pub wallets: Vec<Address>,
+
+    /// Parameters of the workload.
+    config: WorkloadConfig,
+
+    /// Random number generator of the workload.
+    rng: StdRng,
+
+    /// Clock of the chain.
+    clock: Arc<ManualClock>,
+}
+
+impl Simulator {
+    /// Create a simulator with a new chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The mining difficulty.
+    /// - `reward`: The block reward.
+    /// - `fee`: The transaction fee.
+    /// - `config`: The parameters of the workload.
+    ///
+    /// # Returns
+    ///
+    /// A new simulator.
+    pub fn new(difficulty: f64, reward: f64, fee: f64, config: WorkloadConfig) -> Self {
+        let clock = Arc::new(ManualClock::new(SIMULATION_GENESIS_TIME));
+        let environment = Environment::seeded(config.seed, clock.clone());
+
+        Simulator {
+            chain: Chain::with_environment(difficulty, reward, fee, environment),
+            wallets: vec![],
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            clock,
+        }
+    }
+
+    /// Run the workload.
+    ///
+    /// # Returns
+    ///
+    /// The counters, throughput and invariant violations of the run.
+    pub fn run(&mut self) -> SimulationReport {
+        let mut report = SimulationReport::default();
+        let started = Instant::now();
+
+        for _ in 0..self.config.initial_wallets {
+            self.create_wallet(&mut report);
+        }
+
+        for step in 1..=self.config.steps {
+            self.clock.advance(self.config.step_time);
+
+            if self.rng.gen_bool(self.config.wallet_rate.clamp(0.0, 1.0)) {
+                self.create_wallet(&mut report);
+            }
+
+            match self.transfer() {
+                Some(Ok(())) => report.transfers_accepted += 1,
+                Some(Err(_)) => report.transfers_rejected += 1,
+                None => {}
+            }
+
+            if self.config.block_interval > 0 && step % self.config.block_interval == 0 {
+                self.chain.generate_new_block();
+                report.blocks_mined += 1;
+            }
+
+            report.steps += 1;
+        }
+
+        report.elapsed = started.elapsed();
+        report.violations = self.check_invariants();
+
+        report
+    }
+
+    /// Check the invariants of the simulated chain.
+    ///
+    /// # Returns
+    ///
+    /// The broken invariants.
+    pub fn check_invariants(&self) -> Vec<InvariantViolation> {
+        let mut violations = vec![];
+
+        for (address, wallet) in &self.chain.wallets {
+            if !wallet.balance.is_finite() || wallet.balance < 0.0 {
+                violations.push(InvariantViolation::InvalidBalance {
+                    address: address.to_owned(),
+                    balance: wallet.balance,
+                });
+            }
+
+            violations.extend(
+                wallet
+                    .transaction_hashes
+                    .iter()
+                    .filter(|hash| !self.chain.transactions.contains_key(*hash))
+                    .map(|hash| InvariantViolation::UnknownTransaction {
+                        address: address.to_owned(),
+                        hash: *hash,
+                    }),
+            );
+        }
+
+        if let Err(error) = self.chain.validate_chain() {
+            violations.push(InvariantViolation::InvalidChain(error));
+        }
+
+        violations
+    }
+
+    /// Create a funded wallet.
+    fn create_wallet(&mut self, report: &mut SimulationReport) {
+        let email = format!("wallet{}@mail.com", self.wallets.len());
+        let address = self.chain.create_wallet(&email);
+
+        if let Some(wallet) = self.chain.wallets.get_mut(&address) {
+            wallet.balance = self.config.initial_balance;
+        }
+
+        self.wallets.push(address);
+        report.wallets_created += 1;
+    }
+
+    /// Attempt a random transfer.
+    ///
+    /// # Returns
+    ///
+    /// The result of the transfer, or `None` if there are less than two wallets.
+    fn transfer(&mut self) -> Option<Result<(), BlockchainError>> {
+        if self.wallets.len() < 2 {
+            return None;
+        }
+
+        let from = self.pick_wallet();
+        let mut to = self.pick_wallet();
+        if to == from {
+            to = (from + 1) % self.wallets.len();
+        }
+
+        let (min, max) = self.config.amount;
+        let amount = match min < max {
+            true => self.rng.gen_range(min..max),
+            false => min,
+        };
+
+        Some(
+            self.chain
+                .add_transaction(&self.wallets[from], &self.wallets[to], amount),
+        )
+    }
+
+    /// Pick the index of a wallet according to the transfer distribution.
+    fn pick_wallet(&mut self) -> usize {
+        let count = self.wallets.len();
+
+        match self.config.distribution {
+            TransferDistribution::Hotspot { wallets, share }
+                if wallets > 0 && self.rng.gen_bool(share.clamp(0.0, 1.0)) =>
+            {
+                self.rng.gen_range(0..wallets.min(count))
+            }
+            _ => self.rng.gen_range(0..count),
+        }
+    }
+}
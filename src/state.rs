@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Address, Block, BlockHeader, BlockchainError, Chain, ChainWallets, Hash, StateTrie, TrieProof,
+    Wallet,
+};
+
+/// Consensus-relevant state of a single wallet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AccountState {
+    /// Address of the wallet.
+    pub address: Address,
+
+    /// Balance of the native coin.
+    pub balance: f64,
+
+    /// Balances of other tokens, ordered by asset identifier.
+    pub balances: BTreeMap<String, f64>,
+}
+
+impl AccountState {
+    /// Hash the account state as a leaf of the state tree.
+    ///
+    /// # Returns
+    /// The hash of the account state.
+    pub fn hash(&self) -> Hash {
+        Chain::hash(self)
+    }
+}
+
+impl From<&Wallet> for AccountState {
+    fn from(wallet: &Wallet) -> Self {
+        AccountState {
+            address: wallet.address.to_owned(),
+            balance: wallet.balance,
+            balances: wallet
+                .balances
+                .iter()
+                .map(|(asset, amount)| (asset.to_owned(), *amount))
+                .collect(),
+        }
+    }
+}
+
+/// Proof that a wallet had a given state at a given block height.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountProof {
+    /// Height of the block whose state root the proof is checked against.
+    pub height: usize,
+
+    /// State of the wallet at that height.
+    pub account: AccountState,
+
+    /// Trie proof of the account state against the state root.
+    pub proof: TrieProof,
+}
+
+impl AccountProof {
+    /// Verify the proof against a state root.
+    ///
+    /// # Arguments
+    /// - `state_root`: The state root committed in the block header.
+    ///
+    /// # Returns
+    /// `true` if the account state is included in the state root, `false` otherwise.
+    pub fn verify(&self, state_root: &Hash) -> bool {
+        self.proof.verify(
+            state_root,
+            &self.account.address,
+            Some(&self.account.hash()),
+        )
+    }
+}
+
+/// Collect the account states of all wallets in state tree order.
+///
+/// # Arguments
+/// - `wallets`: The wallets of a chain.
+///
+/// # Returns
+/// The account states sorted by address.
+pub fn accounts(wallets: &ChainWallets) -> Vec<AccountState> {
+    let mut accounts: Vec<AccountState> = wallets.values().map(AccountState::from).collect();
+    accounts.sort_by(|a, b| a.address.cmp(&b.address));
+
+    accounts
+}
+
+/// State of a chain at a checkpoint block, bootstrapping a node without replaying the blocks.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StateSnapshot {
+    /// Headers from the genesis block up to the checkpoint block, inclusive.
+    pub headers: Vec<BlockHeader>,
+
+    /// Account states at the checkpoint block, ordered by address.
+    pub accounts: Vec<AccountState>,
+
+    /// Mining difficulty of the chain when the snapshot was taken.
+    pub difficulty: f64,
+
+    /// Block reward of the chain when the snapshot was taken.
+    pub reward: f64,
+
+    /// Transaction fee of the chain when the snapshot was taken.
+    pub fee: f64,
+}
+
+impl StateSnapshot {
+    /// Get the height of the checkpoint block.
+    ///
+    /// # Returns
+    /// The height of the last header, or `None` if the snapshot has no headers.
+    pub fn height(&self) -> Option<usize> {
+        self.headers.len().checked_sub(1)
+    }
+
+    /// Verify the snapshot against the trusted hash of its checkpoint block.
+    ///
+    /// # Arguments
+    /// - `trusted`: The hash of the checkpoint block, obtained from a trusted source.
+    ///
+    /// # Returns
+    /// `InvalidHeader` if the headers do not satisfy their proof-of-work, do not link to each other
+    /// or do not end with the trusted block, or `InvalidSnapshot` if the accounts do not match the
+    /// state root of the checkpoint block.
+    pub fn verify(&self, trusted: &Hash) -> Result<(), BlockchainError> {
+        let checkpoint = self.headers.last().ok_or(BlockchainError::InvalidHeader)?;
+        let linked = self
+            .headers
+            .windows(2)
+            .all(|pair| pair[1].previous_hash == Chain::hash(&pair[0]));
+
+        if Chain::hash(checkpoint) != *trusted
+            || !linked
+            || !self
+                .headers
+                .iter()
+                .all(|header| Block::validate_proof_of_work(header, header.difficulty))
+        {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        let ordered = self
+            .accounts
+            .windows(2)
+            .all(|pair| pair[0].address < pair[1].address);
+
+        match ordered && accounts_root(&self.accounts) == checkpoint.state_root {
+            true => Ok(()),
+            false => Err(BlockchainError::InvalidSnapshot),
+        }
+    }
+}
+
+/// Calculate the state trie root of the wallet state.
+///
+/// # Arguments
+/// - `wallets`: The wallets of a chain.
+///
+/// # Returns
+/// The state root, or the zero hash if there are no wallets.
+pub fn root(wallets: &ChainWallets) -> Hash {
+    accounts_root(&accounts(wallets))
+}
+
+/// Calculate the state trie root of a list of account states.
+///
+/// # Arguments
+/// - `accounts`: The account states.
+///
+/// # Returns
+/// The state root, or the zero hash if there are no accounts.
+pub fn accounts_root(accounts: &[AccountState]) -> Hash {
+    StateTrie::from_accounts(accounts).root()
+}
+
+/// Build a proof for the state of a wallet.
+///
+/// # Arguments
+/// - `accounts`: The account states.
+/// - `address`: The address of the wallet.
+/// - `height`: The height of the block the accounts belong to.
+///
+/// # Returns
+/// The account proof, or `None` if the wallet is not part of the state.
+pub fn prove(accounts: &[AccountState], address: &Address, height: usize) -> Option<AccountProof> {
+    let trie = StateTrie::from_accounts(accounts);
+
+    Some(AccountProof {
+        height,
+        account: trie.get(address)?.to_owned(),
+        proof: trie.prove(address),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let first = Wallet::new("a@mail.com", &Address::random());
+        let second = Wallet::new("b@mail.com", &Address::random());
+
+        let mut wallets: ChainWallets = HashMap::default();
+        wallets.insert(first.address.to_owned(), first.to_owned());
+        wallets.insert(second.address.to_owned(), second.to_owned());
+
+        let mut reversed: ChainWallets = HashMap::default();
+        reversed.insert(second.address.to_owned(), second);
+        reversed.insert(first.address.to_owned(), first);
+
+        assert_eq!(root(&wallets), root(&reversed));
+    }
+
+    #[test]
+    fn test_root_changes_with_balance() {
+        let mut wallet = Wallet::new("a@mail.com", &Address::random());
+
+        let mut wallets: ChainWallets = HashMap::default();
+        wallets.insert(wallet.address.to_owned(), wallet.to_owned());
+
+        let before = root(&wallets);
+        wallet.balance = 10.0;
+        wallets.insert(wallet.address.to_owned(), wallet);
+
+        assert_ne!(before, root(&wallets));
+    }
+
+    #[test]
+    fn test_prove() {
+        let wallets: Vec<Wallet> = (0..3)
+            .map(|_| Wallet::new("a@mail.com", &Address::random()))
+            .collect();
+
+        let mut map: ChainWallets = HashMap::default();
+        for wallet in wallets.iter() {
+            map.insert(wallet.address.to_owned(), wallet.to_owned());
+        }
+
+        let accounts = accounts(&map);
+        let proof = prove(&accounts, &wallets[1].address, 0).unwrap();
+
+        assert!(proof.verify(&root(&map)));
+        assert!(prove(&accounts, &Address::random(), 0).is_none());
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::mpsc::{self, Receiver, Sender},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, Block, Hash, ProposalChange, Transaction};
+
+/// Event pushed by the chain to its subscribers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ChainEvent {
+    /// Block appended to the chain, either mined or imported.
+    Block(Block),
+
+    /// Transaction added to the chain.
+    Transaction(Transaction),
+
+    /// Wallet created with the given address.
+    WalletCreated(Address),
+
+    /// Chain parameter updated, directly or by a governance proposal.
+    ParameterChanged(ProposalChange),
+
+    /// Blocks removed from the tip of the chain.
+    Reorg {
+        /// Height of the new tip, i.e. the number of remaining blocks.
+        height: usize,
+
+        /// Hashes of the removed blocks, from the lowest height up.
+        removed: Vec<Hash>,
+    },
+
+    /// Native balance of a watched address changed.
+    BalanceChanged {
+        /// The watched address.
+        address: Address,
+
+        /// Balance last reported for the address.
+        previous: f64,
+
+        /// New balance of the address.
+        balance: f64,
+    },
+
+    /// Block appended to the chain with transactions involving a watched address.
+    WatchedBlock {
+        /// The watched address.
+        address: Address,
+
+        /// Height of the block.
+        height: usize,
+
+        /// Hash of the block.
+        hash: Hash,
+
+        /// Hashes of the transactions of the block involving the address.
+        transactions: Vec<Hash>,
+    },
+}
+
+/// Events delivered to a subscription.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SubscriptionFilter {
+    /// Every event.
+    All,
+
+    /// New blocks and removed blocks.
+    Blocks,
+
+    /// New transactions only.
+    Transactions,
+
+    /// Wallet creations, parameter changes and removed blocks.
+    Lifecycle,
+
+    /// Transactions sent or received by the watched address, the blocks containing them,
+    /// and the creation of the wallet.
+    Address(Address),
+
+    /// Balance changes and blocks of the addresses registered with `Chain::watch_address`.
+    Watched,
+}
+
+impl SubscriptionFilter {
+    /// Check whether an event is delivered by the filter.
+    ///
+    /// # Arguments
+    ///
+    /// - `event`: The event.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the event matches the filter.
+    pub fn matches(&self, event: &ChainEvent) -> bool {
+        match (self, event) {
+            (SubscriptionFilter::All, _) => true,
+            (SubscriptionFilter::Blocks, ChainEvent::Block(_) | ChainEvent::Reorg { .. }) => true,
+            (SubscriptionFilter::Transactions, ChainEvent::Transaction(_)) => true,
+            (
+                SubscriptionFilter::Lifecycle,
+                ChainEvent::WalletCreated(_)
+                | ChainEvent::ParameterChanged(_)
+                | ChainEvent::Reorg { .. },
+            ) => true,
+            (SubscriptionFilter::Address(address), ChainEvent::WalletCreated(created)) => {
+                address == created
+            }
+            (SubscriptionFilter::Address(address), ChainEvent::Transaction(tx)) => {
+                tx.involves(address)
+            }
+            (SubscriptionFilter::Address(address), ChainEvent::Block(block)) => {
+                block.transactions.values().any(|tx| tx.involves(address))
+            }
+            (
+                SubscriptionFilter::Address(address),
+                ChainEvent::BalanceChanged {
+                    address: watched, ..
+                }
+                | ChainEvent::WatchedBlock {
+                    address: watched, ..
+                },
+            ) => address == watched,
+            (
+                SubscriptionFilter::Watched,
+                ChainEvent::BalanceChanged { .. } | ChainEvent::WatchedBlock { .. },
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Receiving end of a chain subscription.
+///
+/// The subscription is cancelled when it is dropped.
+#[derive(Debug)]
+pub struct Subscription {
+    /// Receiver of the events matching the filter.
+    receiver: Receiver<ChainEvent>,
+}
+
+impl Subscription {
+    /// Get the next pending event without waiting.
+    ///
+    /// # Returns
+    ///
+    /// The next event, or `None` if there is no pending event.
+    pub fn try_next(&self) -> Option<ChainEvent> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Wait for the next event.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout`: The maximum time to wait.
+    ///
+    /// # Returns
+    ///
+    /// The next event, or `None` if no event arrived in time or the chain was dropped.
+    pub fn next_timeout(&self, timeout: Duration) -> Option<ChainEvent> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+
+    /// Take all pending events without waiting.
+    ///
+    /// # Returns
+    ///
+    /// The pending events, oldest first.
+    pub fn drain(&self) -> Vec<ChainEvent> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+/// Subscriptions registered on a chain.
+///
+/// Subscriptions are bound to a chain instance: a cloned chain starts without subscribers.
+#[derive(Default)]
+pub struct Subscribers(Vec<(SubscriptionFilter, Sender<ChainEvent>)>);
+
+impl Subscribers {
+    /// Register a new subscription.
+    ///
+    /// # Arguments
+    ///
+    /// - `filter`: The events delivered to the subscription.
+    ///
+    /// # Returns
+    ///
+    /// The new subscription.
+    pub fn subscribe(&mut self, filter: SubscriptionFilter) -> Subscription {
+        let (sender, receiver) = mpsc::channel();
+        self.0.push((filter, sender));
+
+        Subscription { receiver }
+    }
+
+    /// Push an event to the matching subscriptions, removing the dropped ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `event`: The event.
+    pub fn notify(&mut self, event: &ChainEvent) {
+        self.0.retain(|(filter, sender)| {
+            !filter.matches(event) || sender.send(event.clone()).is_ok()
+        });
+    }
+
+    /// Check whether there are no subscriptions.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no subscription is registered.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Clone for Subscribers {
+    fn clone(&self) -> Self {
+        Subscribers::default()
+    }
+}
+
+impl fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Subscribers({})", self.0.len())
+    }
+}
+
+/// Addresses watched for balance changes, with the balance last reported for each of them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Watchlist(BTreeMap<Address, f64>);
+
+impl Watchlist {
+    /// Start watching an address.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to watch.
+    /// - `balance`: The current balance of the address.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the address was not watched yet.
+    pub fn watch(&mut self, address: &Address, balance: f64) -> bool {
+        self.0.insert(address.to_owned(), balance).is_none()
+    }
+
+    /// Stop watching an address.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to stop watching.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the address was watched.
+    pub fn unwatch(&mut self, address: &Address) -> bool {
+        self.0.remove(address).is_some()
+    }
+
+    /// Check whether an address is watched.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the address is watched.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0.contains_key(address)
+    }
+
+    /// Iterate over the watched addresses.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the watched addresses, in order.
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        self.0.keys()
+    }
+
+    /// Check whether there are no watched addresses.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no address is watched.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Record the current balance of a watched address.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address.
+    /// - `balance`: The current balance of the address.
+    ///
+    /// # Returns
+    ///
+    /// A `BalanceChanged` event if the address is watched and its balance changed.
+    pub fn update(&mut self, address: &Address, balance: f64) -> Option<ChainEvent> {
+        let previous = self.0.get_mut(address)?;
+
+        if *previous == balance {
+            return None;
+        }
+
+        let event = ChainEvent::BalanceChanged {
+            address: address.to_owned(),
+            previous: *previous,
+            balance,
+        };
+        *previous = balance;
+
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_filter() {
+        let address = Address::random();
+        let filter = SubscriptionFilter::Address(address.clone());
+
+        let sent = Transaction::new(address, Address::random(), 0.0, 1.0);
+        let other = Transaction::new(Address::random(), Address::random(), 0.0, 1.0);
+
+        assert!(filter.matches(&ChainEvent::Transaction(sent)));
+        assert!(!filter.matches(&ChainEvent::Transaction(other)));
+    }
+
+    #[test]
+    fn test_lifecycle_filter() {
+        let address = Address::random();
+        let created = ChainEvent::WalletCreated(address.clone());
+        let reorg = ChainEvent::Reorg {
+            height: 1,
+            removed: vec![],
+        };
+
+        assert!(SubscriptionFilter::Lifecycle.matches(&created));
+        assert!(SubscriptionFilter::Lifecycle.matches(&reorg));
+        assert!(SubscriptionFilter::Blocks.matches(&reorg));
+        assert!(!SubscriptionFilter::Blocks.matches(&created));
+        assert!(SubscriptionFilter::Address(address).matches(&created));
+        assert!(!SubscriptionFilter::Address(Address::random()).matches(&created));
+    }
+
+    #[test]
+    fn test_watchlist_update() {
+        let address = Address::random();
+        let mut watchlist = Watchlist::default();
+
+        assert!(watchlist.update(&address, 1.0).is_none());
+        assert!(watchlist.watch(&address, 1.0));
+        assert!(watchlist.update(&address, 1.0).is_none());
+
+        let event = watchlist.update(&address, 3.0);
+        assert!(matches!(
+            event,
+            Some(ChainEvent::BalanceChanged { previous, balance, .. })
+                if previous == 1.0 && balance == 3.0
+        ));
+        assert!(SubscriptionFilter::Watched.matches(&event.unwrap()));
+        assert!(watchlist.update(&address, 3.0).is_none());
+    }
+
+    #[test]
+    fn test_dropped_subscription_is_removed() {
+        let mut subscribers = Subscribers::default();
+        let subscription = subscribers.subscribe(SubscriptionFilter::All);
+        let event = ChainEvent::Transaction(Transaction::new(
+            Address::random(),
+            Address::random(),
+            0.0,
+            1.0,
+        ));
+
+        subscribers.notify(&event);
+        assert_eq!(subscription.drain().len(), 1);
+
+        drop(subscription);
+        subscribers.notify(&event);
+        assert!(subscribers.is_empty());
+    }
+}
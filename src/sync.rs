@@ -0,0 +1,222 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    wire::FRAME_HEADER_SIZE, Block, BlockchainError, Chain, FrameHeader, SyncMessage, WireMessage,
+};
+
+/// Maximum number of headers sent in a single `Headers` message.
+pub const MAX_HEADERS_PER_MESSAGE: usize = 2000;
+
+/// Maximum number of block bodies requested in a single `GetBodies` message.
+pub const MAX_BODIES_PER_MESSAGE: usize = 128;
+
+/// Write a message as a wire frame.
+///
+/// # Arguments
+///
+/// - `writer`: The stream to write to.
+/// - `message`: The message.
+///
+/// # Returns
+///
+/// An error if the message cannot be encoded or written.
+pub async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &SyncMessage,
+) -> Result<(), BlockchainError> {
+    let bytes = message.encode()?;
+
+    writer
+        .write_all(&bytes)
+        .await
+        .map_err(|_| BlockchainError::NetworkError)?;
+
+    writer
+        .flush()
+        .await
+        .map_err(|_| BlockchainError::NetworkError)
+}
+
+/// Read a message from a wire frame.
+///
+/// # Arguments
+///
+/// - `reader`: The stream to read from.
+///
+/// # Returns
+///
+/// The message, `None` if the stream was closed, or an error if the frame is malformed or
+/// does not carry a synchronization message.
+pub async fn read_message<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<SyncMessage>, BlockchainError> {
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+
+    match reader.read_exact(&mut header).await {
+        Ok(_) => {}
+        Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(_) => return Err(BlockchainError::NetworkError),
+    }
+
+    let header = FrameHeader::parse(&header)?;
+
+    let mut payload = vec![0u8; header.length];
+    reader
+        .read_exact(&mut payload)
+        .await
+        .map_err(|_| BlockchainError::NetworkError)?;
+
+    match WireMessage::decode_payload(&header, &payload)? {
+        WireMessage::Sync(message) => Ok(Some(message)),
+        _ => Err(BlockchainError::ProtocolError),
+    }
+}
+
+/// Answer the synchronization requests of a peer until it closes the stream.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+/// - `stream`: The stream connected to the peer.
+///
+/// # Returns
+///
+/// An error if the stream fails or the peer sends a message that is not a request.
+pub async fn serve<S: AsyncRead + AsyncWrite + Unpin>(
+    chain: &Arc<Mutex<Chain>>,
+    stream: &mut S,
+) -> Result<(), BlockchainError> {
+    while let Some(message) = read_message(stream).await? {
+        let response = {
+            let chain = lock(chain);
+
+            match message {
+                SyncMessage::GetHeaders(locator) => {
+                    let start = chain.find_fork(&locator).map_or(0, |height| height + 1);
+
+                    SyncMessage::Headers(chain.get_headers(start, MAX_HEADERS_PER_MESSAGE))
+                }
+                SyncMessage::GetBodies(hashes) => SyncMessage::Bodies(
+                    hashes
+                        .iter()
+                        .take(MAX_BODIES_PER_MESSAGE)
+                        .map(|hash| {
+                            chain
+                                .find_fork(std::slice::from_ref(hash))
+                                .and_then(|height| chain.chain.get(height))
+                                .map(|block| block.transactions.clone())
+                                .unwrap_or_default()
+                        })
+                        .collect(),
+                ),
+                SyncMessage::Headers(_) | SyncMessage::Bodies(_) => {
+                    return Err(BlockchainError::ProtocolError)
+                }
+            }
+        };
+
+        write_message(stream, &response).await?;
+    }
+
+    Ok(())
+}
+
+/// Synchronize the chain with a peer, headers first.
+///
+/// Headers are downloaded from the most recent common block and checked to link to the last
+/// block, then the bodies are downloaded and every block is appended with `Chain::import_block`,
+/// so synchronized blocks pass the same checks as imported ones. Only extensions of the local
+/// chain are accepted: headers from a competing branch fail to link to the last block. On any
+/// failure, the blocks added by the synchronization are removed.
+///
+/// # Arguments
+///
+/// - `chain`: The chain shared with the application.
+/// - `stream`: The stream connected to a peer running `serve`.
+///
+/// # Returns
+///
+/// The number of blocks added, or an error if the peer or its blocks are invalid.
+pub async fn sync<S: AsyncRead + AsyncWrite + Unpin>(
+    chain: &Arc<Mutex<Chain>>,
+    stream: &mut S,
+) -> Result<usize, BlockchainError> {
+    let start = lock(chain).chain.len();
+    let result = download(chain, stream).await;
+
+    if result.is_err() {
+        lock(chain).truncate(start);
+    }
+
+    result.map(|_| lock(chain).chain.len() - start)
+}
+
+/// Download and append headers and bodies until the peer has no more headers.
+async fn download<S: AsyncRead + AsyncWrite + Unpin>(
+    chain: &Arc<Mutex<Chain>>,
+    stream: &mut S,
+) -> Result<(), BlockchainError> {
+    loop {
+        let locator = lock(chain).locator();
+        let headers = match request(stream, SyncMessage::GetHeaders(locator)).await? {
+            SyncMessage::Headers(headers) => headers,
+            _ => return Err(BlockchainError::ProtocolError),
+        };
+
+        if headers.is_empty() {
+            return Ok(());
+        }
+
+        // Check that the headers extend the local chain before downloading their bodies
+        let count = headers.len();
+        let linked = headers
+            .windows(2)
+            .all(|pair| pair[1].previous_hash == Chain::hash(&pair[0]));
+
+        if !linked || headers[0].previous_hash != lock(chain).get_last_hash() {
+            return Err(BlockchainError::InvalidHeader);
+        }
+
+        for batch in headers.chunks(MAX_BODIES_PER_MESSAGE) {
+            let hashes = batch.iter().map(Chain::hash).collect();
+            let bodies = match request(stream, SyncMessage::GetBodies(hashes)).await? {
+                SyncMessage::Bodies(bodies) if bodies.len() == batch.len() => bodies,
+                _ => return Err(BlockchainError::ProtocolError),
+            };
+
+            let mut chain = lock(chain);
+            for (header, transactions) in batch.iter().zip(bodies) {
+                let mut block = Block::from_header(header.to_owned(), chain.chain.len());
+
+                if !block.restore(transactions) {
+                    return Err(BlockchainError::InvalidBlockBody);
+                }
+
+                chain.import_block(block)?;
+            }
+        }
+
+        if count < MAX_HEADERS_PER_MESSAGE {
+            return Ok(());
+        }
+    }
+}
+
+/// Send a request and wait for the response of the peer.
+async fn request<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    message: SyncMessage,
+) -> Result<SyncMessage, BlockchainError> {
+    write_message(stream, &message).await?;
+
+    read_message(stream)
+        .await?
+        .ok_or(BlockchainError::NetworkError)
+}
+
+/// Lock the shared chain, recovering from a poisoned lock.
+fn lock(chain: &Arc<Mutex<Chain>>) -> MutexGuard<'_, Chain> {
+    chain.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,188 @@
+use uuid::Uuid;
+
+use crate::{Address, Chain, Transaction, NATIVE_ASSET};
+
+/// Default mining difficulty of a test chain, low enough to mine blocks instantly.
+pub const TEST_DIFFICULTY: f64 = 1.0;
+
+/// Default block reward of a test chain.
+pub const TEST_REWARD: f64 = 100.0;
+
+/// Default transaction fee of a test chain.
+pub const TEST_FEE: f64 = 0.1;
+
+/// Chain fixture for tests, built from a fresh chain with funded wallets, transfers and mined
+/// blocks.
+#[derive(Clone, Debug)]
+pub struct TestChain {
+    /// The chain under test.
+    pub chain: Chain,
+
+    /// Addresses of the wallets created by the fixture, in creation order.
+    pub wallets: Vec<Address>,
+}
+
+impl Default for TestChain {
+    fn default() -> Self {
+        TestChain::new(TEST_DIFFICULTY, TEST_REWARD, TEST_FEE)
+    }
+}
+
+impl TestChain {
+    /// Initialize a fixture with a new chain and no wallets.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The mining difficulty.
+    /// - `reward`: The block reward.
+    /// - `fee`: The transaction fee.
+    ///
+    /// # Returns
+    ///
+    /// A new fixture.
+    pub fn new(difficulty: f64, reward: f64, fee: f64) -> Self {
+        TestChain {
+            chain: Chain::new(difficulty, reward, fee),
+            wallets: vec![],
+        }
+    }
+
+    /// Initialize a fixture with the default parameters and funded wallets.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: The number of wallets.
+    /// - `balance`: The balance of each wallet.
+    ///
+    /// # Returns
+    ///
+    /// A new fixture with `count` wallets.
+    pub fn funded_wallets(count: usize, balance: f64) -> Self {
+        TestChain::default().with_funded_wallets(count, balance)
+    }
+
+    /// Create wallets and credit them a balance.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: The number of wallets to create.
+    /// - `balance`: The balance of each new wallet.
+    ///
+    /// # Returns
+    ///
+    /// The fixture with the new wallets appended to `wallets`.
+    pub fn with_funded_wallets(mut self, count: usize, balance: f64) -> Self {
+        for _ in 0..count {
+            let email = format!("wallet{}@mail.com", self.wallets.len());
+            let address = self.chain.create_wallet(&email);
+
+            fund(&mut self.chain, &address, balance);
+            self.wallets.push(address);
+        }
+
+        self
+    }
+
+    /// Add transfers between the wallets of the fixture, each wallet paying the next one in a
+    /// round-robin.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: The number of transfers.
+    /// - `amount`: The amount of each transfer.
+    ///
+    /// # Returns
+    ///
+    /// The fixture with the transfers pending.
+    ///
+    /// # Panics
+    ///
+    /// If the fixture has less than two wallets or a transfer is rejected, e.g. because a wallet
+    /// cannot afford it, or `count` exceeds the number of wallets so that a transfer repeats
+    /// within the same second and duplicates an earlier hash.
+    pub fn with_transfers(mut self, count: usize, amount: f64) -> Self {
+        assert!(
+            self.wallets.len() >= 2,
+            "transfers need at least two wallets"
+        );
+
+        for i in 0..count {
+            let from = &self.wallets[i % self.wallets.len()];
+            let to = &self.wallets[(i + 1) % self.wallets.len()];
+
+            if let Err(error) = self.chain.add_transaction(from, to, amount) {
+                panic!("transfer {i} from {from} to {to} was rejected: {error}");
+            }
+        }
+
+        self
+    }
+
+    /// Mine blocks on top of the chain, rewarding the miner of the chain.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: The number of blocks.
+    ///
+    /// # Returns
+    ///
+    /// The fixture with the new blocks.
+    pub fn with_blocks(mut self, count: usize) -> Self {
+        for _ in 0..count {
+            self.chain.generate_new_block();
+        }
+
+        self
+    }
+
+    /// Get the chain of the fixture.
+    ///
+    /// # Returns
+    ///
+    /// The chain.
+    pub fn into_chain(self) -> Chain {
+        self.chain
+    }
+}
+
+/// Set the native balance of a wallet, bypassing transactions.
+///
+/// # Arguments
+///
+/// - `chain`: The chain.
+/// - `address`: The address of the wallet.
+/// - `balance`: The new balance.
+///
+/// # Panics
+///
+/// If the wallet does not exist.
+pub fn fund(chain: &mut Chain, address: &Address, balance: f64) {
+    match chain.wallets.get_mut(address) {
+        Some(wallet) => wallet.balance = balance,
+        None => panic!("wallet {address} does not exist"),
+    }
+}
+
+/// Build a native transfer whose id, timestamp and hash only depend on its arguments.
+///
+/// # Arguments
+///
+/// - `seed`: The seed of the id and timestamp of the transaction.
+/// - `from`: The sender's address.
+/// - `to`: The receiver's address.
+/// - `amount`: The amount of the transaction.
+///
+/// # Returns
+///
+/// A transaction without fee.
+pub fn transaction(seed: u64, from: &Address, to: &Address, amount: f64) -> Transaction {
+    Transaction::from_parts(
+        Uuid::from_u128(seed.into()),
+        seed as i64,
+        from.clone(),
+        to.clone(),
+        0.0,
+        amount,
+        NATIVE_ASSET,
+    )
+}
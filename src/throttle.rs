@@ -0,0 +1,137 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::BlockchainError;
+
+/// Minimum number of tracked keys before expired ones are pruned.
+const MIN_PRUNE_KEYS: usize = 64;
+
+/// Sliding window rate limiter, counting requests per key, e.g. per email or per client token.
+///
+/// Keys whose requests all left the window are pruned as the number of tracked keys grows, so
+/// the memory used is bounded by the number of keys active within a window.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    /// Maximum number of requests per key within a window.
+    limit: usize,
+
+    /// Length of the window, in seconds.
+    window: i64,
+
+    /// Times of the requests within the window, oldest first, by key.
+    hits: HashMap<String, VecDeque<i64>>,
+
+    /// Number of tracked keys after the last pruning.
+    pruned_at: usize,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter.
+    ///
+    /// # Arguments
+    ///
+    /// - `limit`: The maximum number of requests per key within a window.
+    /// - `window`: The length of the window, in seconds.
+    ///
+    /// # Returns
+    ///
+    /// A new rate limiter.
+    pub fn new(limit: usize, window: i64) -> Self {
+        RateLimiter {
+            limit,
+            window,
+            hits: HashMap::new(),
+            pruned_at: 0,
+        }
+    }
+
+    /// Count a request against the limits of its keys.
+    ///
+    /// The request is counted for every key, or for none if one of them is over its limit.
+    ///
+    /// # Arguments
+    ///
+    /// - `keys`: The keys identifying the request.
+    /// - `now`: The time of the request, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// `RateLimited` if a key already made `limit` requests within the window.
+    pub fn check(&mut self, keys: &[&str], now: i64) -> Result<(), BlockchainError> {
+        if self.hits.len() >= (2 * self.pruned_at).max(MIN_PRUNE_KEYS) {
+            self.prune(now);
+        }
+
+        let start = now - self.window;
+        for key in keys {
+            if let Some(hits) = self.hits.get_mut(*key) {
+                while hits.front().is_some_and(|&time| time <= start) {
+                    hits.pop_front();
+                }
+
+                if hits.len() >= self.limit {
+                    return Err(BlockchainError::RateLimited);
+                }
+            } else if self.limit == 0 {
+                return Err(BlockchainError::RateLimited);
+            }
+        }
+
+        for key in keys {
+            self.hits.entry(key.to_string()).or_default().push_back(now);
+        }
+
+        Ok(())
+    }
+
+    /// Get the number of keys with requests being tracked.
+    ///
+    /// # Returns
+    ///
+    /// The number of tracked keys.
+    pub fn tracked(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Remove the requests that left the window, and the keys without requests.
+    fn prune(&mut self, now: i64) {
+        let start = now - self.window;
+
+        self.hits.retain(|_, hits| {
+            hits.retain(|&time| time > start);
+            !hits.is_empty()
+        });
+        self.pruned_at = self.hits.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window() {
+        let mut limiter = RateLimiter::new(2, 10);
+
+        assert_eq!(limiter.check(&["a"], 0), Ok(()));
+        assert_eq!(limiter.check(&["a"], 5), Ok(()));
+        assert_eq!(
+            limiter.check(&["a", "b"], 9),
+            Err(BlockchainError::RateLimited)
+        );
+        assert_eq!(limiter.tracked(), 1);
+        assert_eq!(limiter.check(&["a"], 10), Ok(()));
+        assert_eq!(limiter.check(&["a"], 14), Err(BlockchainError::RateLimited));
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut limiter = RateLimiter::new(1, 10);
+
+        for key in 0..MIN_PRUNE_KEYS {
+            limiter.check(&[&key.to_string()], 0).unwrap();
+        }
+        limiter.check(&["late"], 20).unwrap();
+
+        assert_eq!(limiter.tracked(), 1);
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError, NATIVE_ASSET};
+
+/// Maximum length of a token symbol.
+pub const MAX_SYMBOL_LENGTH: usize = 12;
+
+/// Configuration of a newly issued token.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TokenConfig {
+    /// Unique symbol identifying the token.
+    pub symbol: String,
+
+    /// Human-readable name of the token.
+    pub name: String,
+
+    /// Address of the wallet allowed to issue the token.
+    pub issuer: Address,
+
+    /// Amount of the token credited to the issuer at creation.
+    pub initial_supply: f64,
+
+    /// Maximum amount of the token that may ever be in circulation.
+    pub max_supply: Option<f64>,
+}
+
+/// Fungible token tracked on the same ledger as the native coin.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Token {
+    /// Unique symbol identifying the token.
+    pub symbol: String,
+
+    /// Human-readable name of the token.
+    pub name: String,
+
+    /// Address of the wallet allowed to issue the token.
+    pub issuer: Address,
+
+    /// Amount of the token currently in circulation.
+    pub supply: f64,
+
+    /// Maximum amount of the token that may ever be in circulation.
+    pub max_supply: Option<f64>,
+
+    /// Timestamp at which the token was registered.
+    pub timestamp: i64,
+}
+
+impl Token {
+    /// Create a new token.
+    ///
+    /// # Arguments
+    ///
+    /// - `symbol`: The unique token symbol.
+    /// - `name`: The token name.
+    /// - `issuer`: The address of the issuing wallet.
+    /// - `max_supply`: The optional supply cap.
+    ///
+    /// # Returns
+    ///
+    /// A new token with no supply, or an error if the symbol is invalid.
+    pub fn new(
+        symbol: &str,
+        name: &str,
+        issuer: &Address,
+        max_supply: Option<f64>,
+    ) -> Result<Self, BlockchainError> {
+        if !Token::validate_symbol(symbol) {
+            return Err(BlockchainError::InvalidToken);
+        }
+
+        if max_supply.is_some_and(|max| max <= 0.0) {
+            return Err(BlockchainError::InvalidToken);
+        }
+
+        Ok(Token {
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            issuer: issuer.to_owned(),
+            supply: 0.0,
+            max_supply,
+            timestamp: Utc::now().timestamp(),
+        })
+    }
+
+    /// Validate a token symbol.
+    ///
+    /// # Arguments
+    ///
+    /// - `symbol`: The token symbol.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the symbol is made of uppercase alphanumeric characters and is not reserved.
+    pub fn validate_symbol(symbol: &str) -> bool {
+        !symbol.is_empty()
+            && symbol.len() <= MAX_SYMBOL_LENGTH
+            && symbol != NATIVE_ASSET
+            && symbol
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_token() {
+        let issuer = Address::random();
+        let token = Token::new("GOLD", "Gold", &issuer, Some(1000.0)).unwrap();
+
+        assert_eq!(token.symbol, "GOLD");
+        assert_eq!(token.issuer, issuer);
+        assert_eq!(token.supply, 0.0);
+    }
+
+    #[test]
+    fn test_new_token_invalid() {
+        let issuer = Address::random();
+
+        assert!(Token::new("gold", "Gold", &issuer, None).is_err());
+        assert!(Token::new(NATIVE_ASSET, "Native", &issuer, None).is_err());
+        assert!(Token::new("GOLD", "Gold", &issuer, Some(0.0)).is_err());
+    }
+}
@@ -0,0 +1,451 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountState, Address, Hash};
+
+/// Node of a state trie.
+#[derive(Clone, Debug, Default)]
+enum Node {
+    /// Subtree without accounts, hashed as the zero hash.
+    #[default]
+    Empty,
+
+    /// Subtree holding a single account.
+    Leaf {
+        /// Path of the account, the hash of its address.
+        key: Hash,
+
+        /// State of the account.
+        account: AccountState,
+
+        /// Hash of the leaf.
+        hash: Hash,
+    },
+
+    /// Subtree holding several accounts, split by the next bit of their path.
+    Branch {
+        /// Accounts whose next path bit is 0.
+        left: Arc<Node>,
+
+        /// Accounts whose next path bit is 1.
+        right: Arc<Node>,
+
+        /// Hash of the branch.
+        hash: Hash,
+    },
+}
+
+impl Node {
+    /// Create a leaf node.
+    fn leaf(key: Hash, account: AccountState) -> Self {
+        let hash = leaf_hash(&key, &account.hash());
+
+        Node::Leaf { key, account, hash }
+    }
+
+    /// Create a branch node.
+    fn branch(left: Arc<Node>, right: Arc<Node>) -> Self {
+        let hash = branch_hash(&left.hash(), &right.hash());
+
+        Node::Branch { left, right, hash }
+    }
+
+    /// Get the hash of the subtree.
+    fn hash(&self) -> Hash {
+        match self {
+            Node::Empty => Hash::zero(),
+            Node::Leaf { hash, .. } | Node::Branch { hash, .. } => *hash,
+        }
+    }
+
+    /// Build a subtree from accounts sorted by path, all sharing the first `depth` path bits.
+    fn build(entries: &[(Hash, AccountState)], depth: usize) -> Self {
+        match entries {
+            [] => Node::Empty,
+            [(key, account)] => Node::leaf(*key, account.to_owned()),
+            _ => {
+                let split = entries.partition_point(|(key, _)| !bit(key, depth));
+                let (left, right) = entries.split_at(split);
+
+                Node::branch(
+                    Arc::new(Node::build(left, depth + 1)),
+                    Arc::new(Node::build(right, depth + 1)),
+                )
+            }
+        }
+    }
+
+    /// Insert or replace an account, sharing the unchanged subtrees.
+    fn insert(node: &Arc<Node>, depth: usize, key: Hash, account: AccountState) -> Arc<Node> {
+        match node.as_ref() {
+            Node::Empty => Arc::new(Node::leaf(key, account)),
+            Node::Leaf { key: existing, .. } if *existing == key => {
+                Arc::new(Node::leaf(key, account))
+            }
+            Node::Leaf { .. } => {
+                let leaf = Arc::new(Node::leaf(key, account));
+                Arc::new(Node::split(node.clone(), leaf, depth))
+            }
+            Node::Branch { left, right, .. } => match bit(&key, depth) {
+                false => Arc::new(Node::branch(
+                    Node::insert(left, depth + 1, key, account),
+                    right.clone(),
+                )),
+                true => Arc::new(Node::branch(
+                    left.clone(),
+                    Node::insert(right, depth + 1, key, account),
+                )),
+            },
+        }
+    }
+
+    /// Create the branches separating two leaves sharing the first `depth` path bits.
+    fn split(existing: Arc<Node>, leaf: Arc<Node>, depth: usize) -> Self {
+        let path = |node: &Node| match node {
+            Node::Leaf { key, .. } => bit(key, depth),
+            _ => false,
+        };
+
+        match (path(&existing), path(&leaf)) {
+            (false, false) => Node::branch(
+                Arc::new(Node::split(existing, leaf, depth + 1)),
+                Arc::new(Node::Empty),
+            ),
+            (true, true) => Node::branch(
+                Arc::new(Node::Empty),
+                Arc::new(Node::split(existing, leaf, depth + 1)),
+            ),
+            (false, true) => Node::branch(existing, leaf),
+            (true, false) => Node::branch(leaf, existing),
+        }
+    }
+
+    /// Collect the accounts of the subtree by path.
+    fn collect<'a>(&'a self, accounts: &mut BTreeMap<Hash, &'a AccountState>) {
+        match self {
+            Node::Empty => {}
+            Node::Leaf { key, account, .. } => {
+                accounts.insert(*key, account);
+            }
+            Node::Branch { left, right, .. } => {
+                left.collect(accounts);
+                right.collect(accounts);
+            }
+        }
+    }
+
+    /// Collect the addresses whose account differs between two subtrees at the same position.
+    fn diff(&self, other: &Node, changed: &mut Vec<Address>) {
+        if self.hash() == other.hash() {
+            return;
+        }
+
+        if let (
+            Node::Branch { left, right, .. },
+            Node::Branch {
+                left: other_left,
+                right: other_right,
+                ..
+            },
+        ) = (self, other)
+        {
+            left.diff(other_left, changed);
+            right.diff(other_right, changed);
+
+            return;
+        }
+
+        let (mut before, mut after) = (BTreeMap::new(), BTreeMap::new());
+        self.collect(&mut before);
+        other.collect(&mut after);
+
+        for (key, account) in &before {
+            if after.get(key) != Some(account) {
+                changed.push(account.address.to_owned());
+            }
+        }
+
+        for (key, account) in &after {
+            if !before.contains_key(key) {
+                changed.push(account.address.to_owned());
+            }
+        }
+    }
+}
+
+/// Sparse Merkle trie of account states, keyed by the hash of their address.
+///
+/// The trie is persistent: updates share the unchanged subtrees with the previous version, so
+/// versions are cheap to keep and compare. A subtree holding a single account is stored as a
+/// leaf instead of a full path of branches.
+#[derive(Clone, Debug, Default)]
+pub struct StateTrie {
+    /// Root node of the trie.
+    root: Arc<Node>,
+}
+
+impl StateTrie {
+    /// Build a trie from account states.
+    ///
+    /// # Arguments
+    ///
+    /// - `accounts`: The account states, in any order.
+    ///
+    /// # Returns
+    ///
+    /// A trie holding the accounts.
+    pub fn from_accounts(accounts: &[AccountState]) -> Self {
+        let mut entries: Vec<(Hash, AccountState)> = accounts
+            .iter()
+            .map(|account| (path(&account.address), account.to_owned()))
+            .collect();
+        entries.sort_by_key(|(key, _)| *key);
+        entries.dedup_by_key(|(key, _)| *key);
+
+        StateTrie {
+            root: Arc::new(Node::build(&entries, 0)),
+        }
+    }
+
+    /// Calculate the root of the trie.
+    ///
+    /// # Returns
+    ///
+    /// The root hash, or the zero hash if the trie is empty.
+    pub fn root(&self) -> Hash {
+        self.root.hash()
+    }
+
+    /// Insert or replace the state of an account.
+    ///
+    /// # Arguments
+    ///
+    /// - `account`: The account state.
+    pub fn insert(&mut self, account: AccountState) {
+        let key = path(&account.address);
+        self.root = Node::insert(&self.root, 0, key, account);
+    }
+
+    /// Get the state of an account.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the account.
+    ///
+    /// # Returns
+    ///
+    /// The account state, or `None` if the account is not in the trie.
+    pub fn get(&self, address: &Address) -> Option<&AccountState> {
+        let key = path(address);
+        let mut node = self.root.as_ref();
+
+        for depth in 0.. {
+            match node {
+                Node::Branch { left, right, .. } => {
+                    node = if bit(&key, depth) { right } else { left };
+                }
+                Node::Leaf {
+                    key: leaf, account, ..
+                } if *leaf == key => return Some(account),
+                _ => return None,
+            }
+        }
+
+        None
+    }
+
+    /// Build a proof of the presence or absence of an account.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the account.
+    ///
+    /// # Returns
+    ///
+    /// The proof, checked against the root with `TrieProof::verify`.
+    pub fn prove(&self, address: &Address) -> TrieProof {
+        let key = path(address);
+        let mut node = self.root.as_ref();
+        let mut siblings = vec![];
+
+        while let Node::Branch { left, right, .. } = node {
+            let (next, sibling) = match bit(&key, siblings.len()) {
+                false => (left, right),
+                true => (right, left),
+            };
+
+            siblings.push(sibling.hash());
+            node = next;
+        }
+
+        let leaf = match node {
+            Node::Leaf { key, account, .. } => Some((*key, account.hash())),
+            _ => None,
+        };
+
+        TrieProof { siblings, leaf }
+    }
+
+    /// Find the accounts that differ between two versions of the trie.
+    ///
+    /// Subtrees with the same hash are skipped, so the cost grows with the number of changes
+    /// rather than the number of accounts.
+    ///
+    /// # Arguments
+    ///
+    /// - `other`: The other version of the trie.
+    ///
+    /// # Returns
+    ///
+    /// The addresses of the accounts added, removed or changed, sorted.
+    pub fn diff(&self, other: &StateTrie) -> Vec<Address> {
+        let mut changed = vec![];
+        self.root.diff(&other.root, &mut changed);
+        changed.sort();
+
+        changed
+    }
+}
+
+/// Proof of the presence or absence of an account in a state trie.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TrieProof {
+    /// Hashes of the siblings along the path of the account, from the root down.
+    pub siblings: Vec<Hash>,
+
+    /// Path and account hash of the leaf at the end of the path, if any.
+    pub leaf: Option<(Hash, Hash)>,
+}
+
+impl TrieProof {
+    /// Verify the proof against a state root.
+    ///
+    /// # Arguments
+    ///
+    /// - `root`: The state root.
+    /// - `address`: The address of the account.
+    /// - `account`: The hash of the account state, or `None` to prove its absence.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the root holds the account state, or no account at the address when `account`
+    /// is `None`, `false` otherwise.
+    pub fn verify(&self, root: &Hash, address: &Address, account: Option<&Hash>) -> bool {
+        let key = path(address);
+
+        let start = match (account, &self.leaf) {
+            (Some(account), Some((leaf, hash))) if *leaf == key && hash == account => {
+                leaf_hash(&key, account)
+            }
+            (None, Some((leaf, hash))) if *leaf != key => {
+                // Another account ends the path, so it must share the path bits of the siblings
+                if (0..self.siblings.len()).any(|depth| bit(leaf, depth) != bit(&key, depth)) {
+                    return false;
+                }
+
+                leaf_hash(leaf, hash)
+            }
+            (None, None) => Hash::zero(),
+            _ => return false,
+        };
+
+        let computed =
+            self.siblings
+                .iter()
+                .enumerate()
+                .rev()
+                .fold(start, |hash, (depth, sibling)| match bit(&key, depth) {
+                    false => branch_hash(&hash, sibling),
+                    true => branch_hash(sibling, &hash),
+                });
+
+        computed == *root
+    }
+}
+
+/// Get the path of an account in the trie.
+fn path(address: &Address) -> Hash {
+    Hash::digest(address.as_str().as_bytes())
+}
+
+/// Get a bit of a path, most significant bit first.
+fn bit(key: &Hash, depth: usize) -> bool {
+    key.as_bytes()[depth / 8] >> (7 - depth % 8) & 1 == 1
+}
+
+/// Hash a leaf, domain-separated from branches.
+fn leaf_hash(key: &Hash, account: &Hash) -> Hash {
+    Hash::of(&(0u8, key, account))
+}
+
+/// Hash a branch, domain-separated from leaves.
+fn branch_hash(left: &Hash, right: &Hash) -> Hash {
+    Hash::of(&(1u8, left, right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(balance: f64) -> AccountState {
+        AccountState {
+            address: Address::random(),
+            balance,
+            balances: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let accounts: Vec<AccountState> = (0..20).map(|i| account(i as f64)).collect();
+        let mut incremental = StateTrie::default();
+        for account in accounts.iter().rev() {
+            incremental.insert(account.to_owned());
+        }
+
+        assert!(StateTrie::default().root().is_zero());
+        assert_eq!(
+            StateTrie::from_accounts(&accounts).root(),
+            incremental.root()
+        );
+        assert_eq!(incremental.get(&accounts[3].address), Some(&accounts[3]));
+    }
+
+    #[test]
+    fn test_prove() {
+        let accounts: Vec<AccountState> = (0..20).map(|i| account(i as f64)).collect();
+        let trie = StateTrie::from_accounts(&accounts);
+        let (root, address) = (trie.root(), &accounts[7].address);
+        let hash = accounts[7].hash();
+
+        assert!(trie.prove(address).verify(&root, address, Some(&hash)));
+        assert!(!trie.prove(address).verify(&root, address, None));
+        assert!(!trie
+            .prove(address)
+            .verify(&root, address, Some(&account(1.0).hash())));
+
+        let missing = Address::random();
+        assert!(trie.prove(&missing).verify(&root, &missing, None));
+        assert!(!trie.prove(&missing).verify(&root, &missing, Some(&hash)));
+    }
+
+    #[test]
+    fn test_diff() {
+        let accounts: Vec<AccountState> = (0..20).map(|i| account(i as f64)).collect();
+        let before = StateTrie::from_accounts(&accounts);
+        let mut after = before.clone();
+
+        let mut changed = accounts[4].to_owned();
+        changed.balance += 1.0;
+        after.insert(changed);
+        let added = account(1.0);
+        after.insert(added.to_owned());
+
+        let mut expected = vec![accounts[4].address.to_owned(), added.address];
+        expected.sort();
+        assert_eq!(before.diff(&after), expected);
+        assert!(before.diff(&before.clone()).is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, BlockchainError, PublicKeyScheme, ADDRESS_LENGTH};
+
+/// Address found by a vanity search, with the key material it is derived from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VanityAddress {
+    /// Address starting with the searched prefix.
+    pub address: Address,
+
+    /// Public key whose `PublicKeyScheme` address is `address`.
+    pub public_key: [u8; 32],
+}
+
+/// Search for a public key whose derived address starts with a prefix.
+///
+/// Every thread derives the addresses of random keys until one of them matches or the timeout
+/// elapses. Each additional character of the prefix multiplies the expected search time by 62.
+///
+/// # Arguments
+///
+/// - `prefix`: The prefix of the address, case-sensitive.
+/// - `timeout`: The maximum duration of the search.
+/// - `threads`: The number of searching threads, at least one.
+///
+/// # Returns
+///
+/// The first matching address and its key, `InvalidAddress` if the prefix is not alphanumeric
+/// or leaves no derived characters, or `VanityNotFound` if the timeout elapsed first.
+pub fn search(
+    prefix: &str,
+    timeout: Duration,
+    threads: usize,
+) -> Result<VanityAddress, BlockchainError> {
+    if prefix.len() >= ADDRESS_LENGTH || !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(BlockchainError::InvalidAddress);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let found = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| scope.spawn(|| search_keys(prefix, deadline, &found)))
+            .collect();
+
+        workers
+            .into_iter()
+            .filter_map(|worker| worker.join().ok().flatten())
+            .next()
+    })
+    .ok_or(BlockchainError::VanityNotFound)
+}
+
+/// Derive the addresses of random keys until one matches, another thread found one or the
+/// deadline passes.
+fn search_keys(prefix: &str, deadline: Instant, found: &AtomicBool) -> Option<VanityAddress> {
+    let mut rng = rand::thread_rng();
+    let mut public_key = [0u8; 32];
+
+    while !found.load(Ordering::Relaxed) && Instant::now() < deadline {
+        rng.fill_bytes(&mut public_key);
+
+        let address = PublicKeyScheme::derive(&public_key);
+        if address.as_str().starts_with(prefix) {
+            found.store(true, Ordering::Relaxed);
+
+            return Some(VanityAddress {
+                address,
+                public_key,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        let vanity = search("a", Duration::from_secs(10), 2).unwrap();
+
+        assert!(vanity.address.as_str().starts_with('a'));
+        assert_eq!(PublicKeyScheme::derive(&vanity.public_key), vanity.address);
+        assert_eq!(
+            search("a-b", Duration::from_secs(1), 1),
+            Err(BlockchainError::InvalidAddress)
+        );
+        assert_eq!(
+            search("abcdefgh", Duration::ZERO, 1),
+            Err(BlockchainError::VanityNotFound)
+        );
+    }
+}
@@ -1,8 +1,22 @@
+use std::{collections::HashMap, hash::BuildHasherDefault};
+
 use serde::{Deserialize, Serialize};
+use twox_hash::XxHash64;
 use uuid::Uuid;
 
+#[cfg(feature = "qr")]
+use crate::BlockchainError;
+use crate::{Address, Hash, KycTier, Script, NATIVE_ASSET};
+
+/// Scheme of the payment request URIs of wallets.
+pub const PAYMENT_URI_SCHEME: &str = "blockchain";
+
+/// A map of token balances by asset identifier.
+pub type WalletBalances = HashMap<String, f64, BuildHasherDefault<XxHash64>>;
+
 /// A wallet that holds a balance of a cryptocurrency.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Wallet {
     /// Unique identifier of the wallet.
     pub id: Uuid,
@@ -11,13 +25,25 @@ pub struct Wallet {
     pub email: String,
 
     /// Address uniquely identifying the wallet.
-    pub address: String,
+    pub address: Address,
 
     /// The current balance of the wallet.
     pub balance: f64,
 
+    /// The current balances of tokens other than the native coin.
+    #[serde(default)]
+    pub balances: WalletBalances,
+
     /// A history of transactions associated with the wallet.
-    pub transaction_hashes: Vec<String>,
+    pub transaction_hashes: Vec<Hash>,
+
+    /// Script outgoing transactions of the wallet must satisfy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spend_condition: Option<Script>,
+
+    /// Identity verification tier of the wallet.
+    #[serde(default)]
+    pub kyc: KycTier,
 }
 
 impl Wallet {
@@ -31,15 +57,108 @@ impl Wallet {
     /// # Returns
     ///
     /// A new wallet with the given email, address, and balance.
-    pub fn new(email: &str, address: &str) -> Self {
+    pub fn new(email: &str, address: &Address) -> Self {
         Wallet {
             id: Uuid::new_v4(),
             email: email.to_string(),
-            address: address.to_string(),
+            address: address.to_owned(),
             balance: 0.0,
+            balances: HashMap::default(),
             transaction_hashes: vec![],
+            spend_condition: None,
+            kyc: KycTier::Verified,
         }
     }
+
+    /// Get the balance of a specific asset.
+    ///
+    /// # Arguments
+    ///
+    /// - `asset`: The identifier of the asset.
+    ///
+    /// # Returns
+    ///
+    /// The balance of the asset, or zero if the wallet never held it.
+    pub fn balance_of(&self, asset: &str) -> f64 {
+        match asset == NATIVE_ASSET {
+            true => self.balance,
+            false => self.balances.get(asset).copied().unwrap_or_default(),
+        }
+    }
+
+    /// Get a mutable reference to the balance of a specific asset.
+    ///
+    /// # Arguments
+    ///
+    /// - `asset`: The identifier of the asset.
+    ///
+    /// # Returns
+    ///
+    /// The balance of the asset, inserted as zero if the wallet never held it.
+    pub fn balance_of_mut(&mut self, asset: &str) -> &mut f64 {
+        match asset == NATIVE_ASSET {
+            true => &mut self.balance,
+            false => self.balances.entry(asset.to_string()).or_default(),
+        }
+    }
+
+    /// Build a payment request URI for receiving funds on the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount`: The requested amount, or `None` to let the payer choose.
+    /// - `memo`: The memo shown to the payer, or `None`.
+    ///
+    /// # Returns
+    ///
+    /// A URI such as `blockchain:<address>?amount=1.5&memo=Invoice%2042`.
+    pub fn payment_uri(&self, amount: Option<f64>, memo: Option<&str>) -> String {
+        let params: Vec<String> = [
+            amount.map(|amount| format!("amount={amount}")),
+            memo.map(|memo| format!("memo={}", percent_encode(memo))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        match params.is_empty() {
+            true => format!("{PAYMENT_URI_SCHEME}:{}", self.address),
+            false => format!("{PAYMENT_URI_SCHEME}:{}?{}", self.address, params.join("&")),
+        }
+    }
+
+    /// Render the payment request URI of the wallet as a QR code.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount`: The requested amount, or `None` to let the payer choose.
+    /// - `memo`: The memo shown to the payer, or `None`.
+    ///
+    /// # Returns
+    ///
+    /// The QR code of the URI, or `EncodingError` if the URI is too long for a QR code.
+    #[cfg(feature = "qr")]
+    pub fn payment_qr(
+        &self,
+        amount: Option<f64>,
+        memo: Option<&str>,
+    ) -> Result<qrcode::QrCode, BlockchainError> {
+        qrcode::QrCode::new(self.payment_uri(amount, memo))
+            .map_err(|_| BlockchainError::EncodingError)
+    }
+}
+
+/// Percent-encode a URI query value, keeping only unreserved characters as they are.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -49,7 +168,7 @@ mod tests {
     #[test]
     fn test_new_wallet() {
         let email = "email".to_string();
-        let address = "0x 1234".to_string();
+        let address = Address::random();
         let wallet = Wallet::new(&email, &address);
 
         assert_eq!(wallet.id.get_version(), Some(uuid::Version::Random));
@@ -58,4 +177,30 @@ mod tests {
         assert_eq!(wallet.balance, 0.0);
         assert!(wallet.transaction_hashes.is_empty());
     }
+
+    #[test]
+    fn test_balance_of() {
+        let mut wallet = Wallet::new("email", &Address::random());
+        wallet.balance = 5.0;
+        wallet.balances.insert("GOLD".to_string(), 2.0);
+
+        assert_eq!(wallet.balance_of(NATIVE_ASSET), 5.0);
+        assert_eq!(wallet.balance_of("GOLD"), 2.0);
+        assert_eq!(wallet.balance_of("SILVER"), 0.0);
+    }
+
+    #[test]
+    fn test_payment_uri() {
+        let address = Address::random();
+        let wallet = Wallet::new("email", &address);
+
+        assert_eq!(
+            wallet.payment_uri(None, None),
+            format!("blockchain:{address}")
+        );
+        assert_eq!(
+            wallet.payment_uri(Some(1.5), Some("Invoice #42")),
+            format!("blockchain:{address}?amount=1.5&memo=Invoice%20%2342")
+        );
+    }
 }
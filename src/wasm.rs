@@ -0,0 +1,288 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{migration, Address, BlockchainError, Chain, Hash, Transaction, Wallet};
+
+/// Chain exposed to JavaScript as `Chain`.
+///
+/// Addresses and hashes are passed as strings, and failures are thrown as `Error`s carrying the
+/// message of the `BlockchainError`.
+#[wasm_bindgen(js_name = Chain)]
+pub struct WasmChain {
+    /// The wrapped chain.
+    chain: Chain,
+}
+
+#[wasm_bindgen(js_class = Chain)]
+impl WasmChain {
+    /// Initialize a new blockchain with a genesis block.
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty`: The initial mining difficulty level of the network.
+    /// - `reward`: The initial block reward for miners.
+    /// - `fee`: The transaction fee.
+    ///
+    /// # Returns
+    ///
+    /// A new chain.
+    #[wasm_bindgen(constructor)]
+    pub fn new(difficulty: f64, reward: f64, fee: f64) -> WasmChain {
+        WasmChain {
+            chain: Chain::new(difficulty, reward, fee),
+        }
+    }
+
+    /// Restore a chain serialized with `toJSON`, migrating it to the current format, verifying its
+    /// block hashes and validating it.
+    ///
+    /// # Arguments
+    ///
+    /// - `json`: The serialized chain.
+    ///
+    /// # Returns
+    ///
+    /// The restored chain.
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(json: &str) -> Result<WasmChain, JsError> {
+        let mut chain = migration::decode(json.as_bytes())?;
+        chain.verify_block_hashes()?;
+        chain.validate_chain()?;
+
+        Ok(WasmChain { chain })
+    }
+
+    /// Serialize the chain, e.g. to keep it in the browser storage.
+    ///
+    /// # Returns
+    ///
+    /// The chain as JSON.
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        let bytes = migration::encode(&self.chain)?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Get the number of blocks, including the genesis block.
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> usize {
+        self.chain.chain.len()
+    }
+
+    /// Get the mining difficulty.
+    #[wasm_bindgen(getter)]
+    pub fn difficulty(&self) -> f64 {
+        self.chain.difficulty
+    }
+
+    /// Get the block reward.
+    #[wasm_bindgen(getter)]
+    pub fn reward(&self) -> f64 {
+        self.chain.reward
+    }
+
+    /// Get the transaction fee.
+    #[wasm_bindgen(getter)]
+    pub fn fee(&self) -> f64 {
+        self.chain.fee
+    }
+
+    /// Create a new wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `email`: The email of the wallet owner.
+    ///
+    /// # Returns
+    ///
+    /// The address of the new wallet.
+    #[wasm_bindgen(js_name = createWallet)]
+    pub fn create_wallet(&mut self, email: &str) -> String {
+        self.chain.create_wallet(email).to_string()
+    }
+
+    /// Get a wallet.
+    ///
+    /// # Arguments
+    ///
+    /// - `address`: The address of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// The wallet.
+    #[wasm_bindgen(js_name = getWallet)]
+    pub fn get_wallet(&self, address: &str) -> Result<WasmWallet, JsError> {
+        let address: Address = address.parse()?;
+        let wallet = match self.chain.wallets.get(&address) {
+            Some(wallet) => wallet,
+            None => return Err(BlockchainError::WalletNotFound { address }.into()),
+        };
+
+        Ok(WasmWallet {
+            wallet: wallet.clone(),
+        })
+    }
+
+    /// Add a new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `from`: The sender's address.
+    /// - `to`: The receiver's address.
+    /// - `amount`: The amount of the transaction.
+    #[wasm_bindgen(js_name = addTransaction)]
+    pub fn add_transaction(&mut self, from: &str, to: &str, amount: f64) -> Result<(), JsError> {
+        let (from, to): (Address, Address) = (from.parse()?, to.parse()?);
+
+        Ok(self.chain.add_transaction(&from, &to, amount)?)
+    }
+
+    /// Get a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `hash`: The hash of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// The transaction.
+    #[wasm_bindgen(js_name = getTransaction)]
+    pub fn get_transaction(&self, hash: &str) -> Result<WasmTransaction, JsError> {
+        let hash: Hash = hash.parse()?;
+
+        Ok(WasmTransaction {
+            transaction: self.chain.get_transaction(&hash)?.clone(),
+        })
+    }
+
+    /// Get a page of transactions.
+    ///
+    /// # Arguments
+    ///
+    /// - `page`: The page number, starting at 1.
+    /// - `size`: The number of transactions per page.
+    ///
+    /// # Returns
+    ///
+    /// The transactions of the page.
+    #[wasm_bindgen(js_name = getTransactions)]
+    pub fn get_transactions(&self, page: usize, size: usize) -> Vec<WasmTransaction> {
+        self.chain
+            .iter_transactions(page, size)
+            .map(|transaction| WasmTransaction {
+                transaction: transaction.clone(),
+            })
+            .collect()
+    }
+
+    /// Mine a new block and append it to the chain.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the new block.
+    #[wasm_bindgen(js_name = generateNewBlock)]
+    pub fn generate_new_block(&mut self) -> String {
+        self.chain.generate_new_block();
+        self.get_last_hash()
+    }
+
+    /// Get the hash of the last block.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the last block.
+    #[wasm_bindgen(js_name = getLastHash)]
+    pub fn get_last_hash(&self) -> String {
+        self.chain.get_last_hash().to_string()
+    }
+
+    /// Validate the whole chain.
+    #[wasm_bindgen(js_name = validateChain)]
+    pub fn validate_chain(&self) -> Result<(), JsError> {
+        Ok(self.chain.validate_chain()?)
+    }
+}
+
+/// Snapshot of a wallet exposed to JavaScript as `Wallet`.
+#[wasm_bindgen(js_name = Wallet)]
+pub struct WasmWallet {
+    /// The wrapped wallet.
+    wallet: Wallet,
+}
+
+#[wasm_bindgen(js_class = Wallet)]
+impl WasmWallet {
+    /// Get the address of the wallet.
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.wallet.address.to_string()
+    }
+
+    /// Get the email of the wallet owner.
+    #[wasm_bindgen(getter)]
+    pub fn email(&self) -> String {
+        self.wallet.email.clone()
+    }
+
+    /// Get the native coin balance.
+    #[wasm_bindgen(getter)]
+    pub fn balance(&self) -> f64 {
+        self.wallet.balance
+    }
+
+    /// Get the hashes of the transactions of the wallet, oldest first.
+    #[wasm_bindgen(getter, js_name = transactionHashes)]
+    pub fn transaction_hashes(&self) -> Vec<String> {
+        self.wallet
+            .transaction_hashes
+            .iter()
+            .map(Hash::to_string)
+            .collect()
+    }
+}
+
+/// Transaction exposed to JavaScript as `Transaction`.
+#[wasm_bindgen(js_name = Transaction)]
+pub struct WasmTransaction {
+    /// The wrapped transaction.
+    transaction: Transaction,
+}
+
+#[wasm_bindgen(js_class = Transaction)]
+impl WasmTransaction {
+    /// Get the hash of the transaction.
+    #[wasm_bindgen(getter)]
+    pub fn hash(&self) -> String {
+        self.transaction.hash.to_string()
+    }
+
+    /// Get the sender's address.
+    #[wasm_bindgen(getter)]
+    pub fn from(&self) -> String {
+        self.transaction.from.to_string()
+    }
+
+    /// Get the receiver's address.
+    #[wasm_bindgen(getter)]
+    pub fn to(&self) -> String {
+        self.transaction.to.to_string()
+    }
+
+    /// Get the amount of the transaction.
+    #[wasm_bindgen(getter)]
+    pub fn amount(&self) -> f64 {
+        self.transaction.amount
+    }
+
+    /// Get the fee of the transaction.
+    #[wasm_bindgen(getter)]
+    pub fn fee(&self) -> f64 {
+        self.transaction.fee
+    }
+
+    /// Get the timestamp of the transaction, in seconds since the Unix epoch.
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> f64 {
+        self.transaction.timestamp as f64
+    }
+}
@@ -0,0 +1,335 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Block, BlockHeader, BlockchainError, ChainTransactions, Hash, Transaction};
+
+/// Magic bytes opening every frame.
+pub const WIRE_MAGIC: [u8; 4] = *b"BCRS";
+
+/// Version of the wire format, frames of any other version are rejected.
+pub const WIRE_VERSION: u8 = 1;
+
+/// Size in bytes of the frame header: magic, version, type, length and checksum.
+pub const FRAME_HEADER_SIZE: usize = 14;
+
+/// Maximum size in bytes of a frame payload.
+pub const MAX_PAYLOAD_SIZE: usize = 32 * 1024 * 1024;
+
+/// Type of the message carried by a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MessageType {
+    /// Full block.
+    Block = 0x01,
+
+    /// Single transaction.
+    Transaction = 0x02,
+
+    /// Block locator requesting headers.
+    GetHeaders = 0x10,
+
+    /// Block headers.
+    Headers = 0x11,
+
+    /// Block hashes requesting bodies.
+    GetBodies = 0x12,
+
+    /// Block bodies.
+    Bodies = 0x13,
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = BlockchainError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(MessageType::Block),
+            0x02 => Ok(MessageType::Transaction),
+            0x10 => Ok(MessageType::GetHeaders),
+            0x11 => Ok(MessageType::Headers),
+            0x12 => Ok(MessageType::GetBodies),
+            0x13 => Ok(MessageType::Bodies),
+            _ => Err(BlockchainError::ProtocolError),
+        }
+    }
+}
+
+/// Message exchanged during a headers-first synchronization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SyncMessage {
+    /// Request the headers following the most recent block of a locator known to the peer.
+    GetHeaders(Vec<Hash>),
+
+    /// Headers following the most recent common block.
+    Headers(Vec<BlockHeader>),
+
+    /// Request the bodies of blocks by hash.
+    GetBodies(Vec<Hash>),
+
+    /// Transactions of the requested blocks, in the order of the request.
+    Bodies(Vec<ChainTransactions>),
+}
+
+impl SyncMessage {
+    /// Get the wire type of the message.
+    ///
+    /// # Returns
+    ///
+    /// The message type.
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            SyncMessage::GetHeaders(_) => MessageType::GetHeaders,
+            SyncMessage::Headers(_) => MessageType::Headers,
+            SyncMessage::GetBodies(_) => MessageType::GetBodies,
+            SyncMessage::Bodies(_) => MessageType::Bodies,
+        }
+    }
+
+    /// Encode the message into a frame.
+    ///
+    /// # Returns
+    ///
+    /// The frame, or an error if the payload is too large.
+    pub fn encode(&self) -> Result<Vec<u8>, BlockchainError> {
+        match self {
+            SyncMessage::GetHeaders(locator) => frame(self.message_type(), locator),
+            SyncMessage::Headers(headers) => frame(self.message_type(), headers),
+            SyncMessage::GetBodies(hashes) => frame(self.message_type(), hashes),
+            SyncMessage::Bodies(bodies) => frame(self.message_type(), bodies),
+        }
+    }
+}
+
+/// Message carried by a frame.
+#[derive(Clone, Debug)]
+pub enum WireMessage {
+    /// Full block, e.g. announced over gossip.
+    Block(Block),
+
+    /// Single transaction, e.g. announced over gossip.
+    Transaction(Transaction),
+
+    /// Synchronization message.
+    Sync(SyncMessage),
+}
+
+impl WireMessage {
+    /// Get the wire type of the message.
+    ///
+    /// # Returns
+    ///
+    /// The message type.
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            WireMessage::Block(_) => MessageType::Block,
+            WireMessage::Transaction(_) => MessageType::Transaction,
+            WireMessage::Sync(message) => message.message_type(),
+        }
+    }
+
+    /// Encode the message into a frame.
+    ///
+    /// # Returns
+    ///
+    /// The frame, or an error if the payload is too large.
+    pub fn encode(&self) -> Result<Vec<u8>, BlockchainError> {
+        match self {
+            WireMessage::Block(block) => frame(MessageType::Block, block),
+            WireMessage::Transaction(transaction) => frame(MessageType::Transaction, transaction),
+            WireMessage::Sync(message) => message.encode(),
+        }
+    }
+
+    /// Decode a complete frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: The frame, header included.
+    ///
+    /// # Returns
+    ///
+    /// The message, or an error if the header, the length, the checksum or the payload is invalid.
+    pub fn decode(bytes: &[u8]) -> Result<Self, BlockchainError> {
+        let header = bytes
+            .get(..FRAME_HEADER_SIZE)
+            .and_then(|header| header.try_into().ok())
+            .ok_or(BlockchainError::ProtocolError)
+            .and_then(FrameHeader::parse)?;
+
+        let payload = &bytes[FRAME_HEADER_SIZE..];
+        if payload.len() != header.length {
+            return Err(BlockchainError::ProtocolError);
+        }
+
+        WireMessage::decode_payload(&header, payload)
+    }
+
+    /// Decode the payload of a frame whose header was already parsed.
+    ///
+    /// # Arguments
+    ///
+    /// - `header`: The frame header.
+    /// - `payload`: The frame payload.
+    ///
+    /// # Returns
+    ///
+    /// The message, or an error if the checksum or the payload is invalid.
+    pub fn decode_payload(header: &FrameHeader, payload: &[u8]) -> Result<Self, BlockchainError> {
+        if checksum(payload) != header.checksum {
+            return Err(BlockchainError::ProtocolError);
+        }
+
+        Ok(match header.message_type {
+            MessageType::Block => WireMessage::Block(parse(payload)?),
+            MessageType::Transaction => WireMessage::Transaction(parse(payload)?),
+            MessageType::GetHeaders => WireMessage::Sync(SyncMessage::GetHeaders(parse(payload)?)),
+            MessageType::Headers => WireMessage::Sync(SyncMessage::Headers(parse(payload)?)),
+            MessageType::GetBodies => WireMessage::Sync(SyncMessage::GetBodies(parse(payload)?)),
+            MessageType::Bodies => WireMessage::Sync(SyncMessage::Bodies(parse(payload)?)),
+        })
+    }
+}
+
+/// Header of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Type of the message.
+    pub message_type: MessageType,
+
+    /// Size in bytes of the payload.
+    pub length: usize,
+
+    /// First four bytes of the hash of the payload.
+    pub checksum: [u8; 4],
+}
+
+impl FrameHeader {
+    /// Parse and validate a frame header.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: The header bytes.
+    ///
+    /// # Returns
+    ///
+    /// The header, or an error if the magic bytes, the version or the message type is unknown,
+    /// or the payload is too large.
+    pub fn parse(bytes: &[u8; FRAME_HEADER_SIZE]) -> Result<Self, BlockchainError> {
+        if bytes[..4] != WIRE_MAGIC || bytes[4] != WIRE_VERSION {
+            return Err(BlockchainError::ProtocolError);
+        }
+
+        let message_type = MessageType::try_from(bytes[5])?;
+        let length = u32::from_be_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]) as usize;
+        if length > MAX_PAYLOAD_SIZE {
+            return Err(BlockchainError::ProtocolError);
+        }
+
+        Ok(FrameHeader {
+            message_type,
+            length,
+            checksum: [bytes[10], bytes[11], bytes[12], bytes[13]],
+        })
+    }
+
+    /// Serialize the header.
+    ///
+    /// # Returns
+    ///
+    /// The header bytes.
+    pub fn to_bytes(&self) -> [u8; FRAME_HEADER_SIZE] {
+        let mut bytes = [0u8; FRAME_HEADER_SIZE];
+        bytes[..4].copy_from_slice(&WIRE_MAGIC);
+        bytes[4] = WIRE_VERSION;
+        bytes[5] = self.message_type as u8;
+        bytes[6..10].copy_from_slice(&(self.length as u32).to_be_bytes());
+        bytes[10..].copy_from_slice(&self.checksum);
+
+        bytes
+    }
+}
+
+/// Calculate the checksum of a payload.
+///
+/// # Arguments
+///
+/// - `payload`: The payload.
+///
+/// # Returns
+///
+/// The first four bytes of the hash of the payload.
+pub fn checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = Hash::digest(payload);
+
+    [hash.0[0], hash.0[1], hash.0[2], hash.0[3]]
+}
+
+/// Build a frame carrying a JSON payload.
+fn frame<T: Serialize>(message_type: MessageType, item: &T) -> Result<Vec<u8>, BlockchainError> {
+    let payload = serde_json::to_vec(item).map_err(|_| BlockchainError::ProtocolError)?;
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err(BlockchainError::ProtocolError);
+    }
+
+    let header = FrameHeader {
+        message_type,
+        length: payload.len(),
+        checksum: checksum(&payload),
+    };
+
+    let mut bytes = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+    bytes.extend_from_slice(&header.to_bytes());
+    bytes.extend_from_slice(&payload);
+
+    Ok(bytes)
+}
+
+/// Parse a JSON payload.
+fn parse<T: DeserializeOwned>(payload: &[u8]) -> Result<T, BlockchainError> {
+    serde_json::from_slice(payload).map_err(|_| BlockchainError::ProtocolError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let message = WireMessage::Sync(SyncMessage::GetHeaders(vec![Hash::digest(b"tip")]));
+        let bytes = message.encode().unwrap();
+
+        assert_eq!(&bytes[..4], b"BCRS");
+        assert_eq!(bytes[4], WIRE_VERSION);
+        assert_eq!(bytes[5], 0x10);
+        assert_eq!(bytes.len(), FRAME_HEADER_SIZE + bytes[9] as usize);
+
+        match WireMessage::decode(&bytes).unwrap() {
+            WireMessage::Sync(SyncMessage::GetHeaders(locator)) => {
+                assert_eq!(locator, vec![Hash::digest(b"tip")])
+            }
+            message => panic!("unexpected message: {:?}", message),
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let bytes = WireMessage::Sync(SyncMessage::GetBodies(vec![]))
+            .encode()
+            .unwrap();
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+
+        let mut version = bytes.clone();
+        version[4] = WIRE_VERSION + 1;
+
+        let mut kind = bytes.clone();
+        kind[5] = 0xff;
+
+        for frame in [&corrupted, &version, &kind, &bytes[..bytes.len() - 1]] {
+            assert_eq!(
+                WireMessage::decode(frame).unwrap_err(),
+                BlockchainError::ProtocolError
+            );
+        }
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use blockchain::{
+    Address, BlockchainError, Hash, TransactionKind, DATA_FEE_PER_BYTE, MAX_DATA_SIZE,
+};
+
+use crate::common::setup;
+
+#[test]
+fn test_add_anchor() {
+    let mut chain = setup();
+    let notary = chain.create_wallet("n@mail.com");
+    chain.wallets.get_mut(&notary).unwrap().balance += 1.0;
+
+    let document = Hash::digest(b"contract.pdf");
+    let hash = chain
+        .add_anchor(&notary, document.as_bytes().to_vec())
+        .unwrap();
+    let transaction = chain.get_transaction(&hash).unwrap();
+
+    assert_eq!(transaction.kind, TransactionKind::Anchor);
+    assert_eq!(transaction.amount, 0.0);
+    assert_eq!(transaction.data_hex(), document.to_string());
+
+    let fee = 0.1 + 32.0 * DATA_FEE_PER_BYTE;
+    assert_eq!(transaction.fee, fee);
+    assert!((chain.get_wallet_balance(&notary).unwrap() - (1.0 - fee)).abs() < 1e-9);
+}
+
+#[test]
+fn test_add_anchor_invalid_data() {
+    let mut chain = setup();
+    let notary = chain.create_wallet("n@mail.com");
+    chain.wallets.get_mut(&notary).unwrap().balance += 1.0;
+
+    let empty = chain.add_anchor(&notary, vec![]);
+    let large = chain.add_anchor(&notary, vec![0; MAX_DATA_SIZE + 1]);
+
+    assert_eq!(empty, Err(BlockchainError::InvalidData));
+    assert_eq!(large, Err(BlockchainError::InvalidData));
+}
+
+#[test]
+fn test_add_anchor_insufficient_funds() {
+    let mut chain = setup();
+    let notary = chain.create_wallet("n@mail.com");
+
+    let result = chain.add_anchor(&notary, vec![1, 2, 3]);
+
+    assert!(matches!(
+        result,
+        Err(BlockchainError::InsufficientFunds { address, available, .. })
+            if address == notary && available == 0.0
+    ));
+    assert!(chain.find_anchors("").is_empty());
+}
+
+#[test]
+fn test_add_anchor_unknown_wallet() {
+    let mut chain = setup();
+
+    let notary = Address::random();
+    let result = chain.add_anchor(&notary, vec![1, 2, 3]);
+
+    assert_eq!(
+        result,
+        Err(BlockchainError::WalletNotFound { address: notary })
+    );
+}
+
+#[test]
+fn test_find_anchors() {
+    let mut chain = setup();
+    let notary = chain.create_wallet("n@mail.com");
+    chain.wallets.get_mut(&notary).unwrap().balance += 1.0;
+
+    chain.add_anchor(&notary, vec![0xab, 0xcd, 0x01]).unwrap();
+    chain.add_anchor(&notary, vec![0x12, 0x34]).unwrap();
+
+    let found = chain.find_anchors("ABCD");
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].data, vec![0xab, 0xcd, 0x01]);
+    assert_eq!(chain.find_anchors("").len(), 2);
+    assert!(chain.find_anchors("ff").is_empty());
+}
@@ -0,0 +1,308 @@
+#![cfg(feature = "api")]
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use blockchain::{
+    api,
+    auth::{Auth, AuthConfig, Role, API_KEY_HEADER},
+    Chain,
+};
+use hyper::body::HttpBody;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+use tower::ServiceExt;
+
+use crate::common::setup;
+
+fn app() -> (Arc<Mutex<Chain>>, Router) {
+    let chain = Arc::new(Mutex::new(setup()));
+
+    (chain.clone(), api::router(chain))
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        })
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_wallets() {
+    let (_, app) = app();
+
+    let (status, body) = send(
+        &app,
+        Method::POST,
+        "/wallets",
+        Some(json!({ "email": "a@mail.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let address = body["data"].as_str().unwrap();
+    let (status, body) = send(&app, Method::GET, &format!("/wallets/{}", address), None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["email"], "a@mail.com");
+}
+
+#[tokio::test]
+async fn test_wallet_not_found() {
+    let (_, app) = app();
+    let address = blockchain::Address::random();
+
+    let (status, body) = send(&app, Method::GET, &format!("/wallets/{}", address), None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        body["error"]["WalletNotFound"]["address"],
+        address.to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_transactions() {
+    let (chain, app) = app();
+
+    let (from, to) = {
+        let mut chain = chain.lock().unwrap();
+        let from = chain.create_wallet("a@mail.com");
+        let to = chain.create_wallet("b@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+        (from, to)
+    };
+
+    let input = json!({ "from": from, "to": to, "amount": 10.0 });
+    let (status, body) = send(&app, Method::POST, "/transactions", Some(input)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let hash = body["data"]["hash"].as_str().unwrap();
+    let (status, _) = send(&app, Method::GET, &format!("/transactions/{}", hash), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = send(
+        &app,
+        Method::GET,
+        &format!("/wallets/{}/transactions", to),
+        None,
+    )
+    .await;
+    assert_eq!(body["data"]["total"], 1);
+
+    let input = json!({ "from": from, "to": to, "amount": 1000.0 });
+    let (status, body) = send(&app, Method::POST, "/transactions", Some(input)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"]["InsufficientFunds"]["address"],
+        from.to_string()
+    );
+    assert_eq!(body["error"]["InsufficientFunds"]["required"], 100.0);
+}
+
+#[tokio::test]
+async fn test_blocks_and_pagination() {
+    let (_, app) = app();
+
+    for _ in 0..3 {
+        let (status, _) = send(&app, Method::POST, "/blocks", None).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (_, body) = send(&app, Method::GET, "/blocks?page=2&size=3", None).await;
+    assert_eq!(body["data"]["total"], 4);
+    assert_eq!(body["data"]["items"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"]["items"][0]["height"], 0);
+
+    let (status, _) = send(&app, Method::GET, "/blocks/3", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, Method::GET, "/blocks/9", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "BlockNotFound");
+}
+
+#[tokio::test]
+async fn test_stats() {
+    let (chain, app) = app();
+
+    let (status, body) = send(&app, Method::GET, "/stats", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["height"], 1);
+    assert_eq!(
+        body["data"]["last_hash"],
+        json!(chain.lock().unwrap().get_last_hash())
+    );
+    assert_eq!(body["data"]["size"], chain.lock().unwrap().chain[0].size);
+}
+
+#[tokio::test]
+async fn test_openapi() {
+    let (_, app) = app();
+
+    let (status, body) = send(&app, Method::GET, "/openapi.json", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, api::openapi());
+    assert_eq!(body["info"]["version"], env!("CARGO_PKG_VERSION"));
+
+    // Every documented route is served
+    for (path, operations) in body["paths"].as_object().unwrap() {
+        let uri = path
+            .replace("{address}", &"a".repeat(42))
+            .replace("{hash}", &"0".repeat(64))
+            .replace("{height}", "0");
+
+        for method in operations.as_object().unwrap().keys() {
+            if path == "/events" {
+                continue;
+            }
+
+            let method = Method::from_bytes(method.to_uppercase().as_bytes()).unwrap();
+            let (status, body) = send(&app, method.clone(), &uri, Some(json!({}))).await;
+            assert_ne!(
+                status,
+                StatusCode::METHOD_NOT_ALLOWED,
+                "{} {}",
+                method,
+                path
+            );
+            assert!(
+                status != StatusCode::NOT_FOUND || !body["error"].is_null(),
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+
+    // Every referenced schema is defined
+    let schemas = &body["components"]["schemas"];
+    let document = body.to_string();
+    for reference in document.split("\"$ref\":\"").skip(1) {
+        let name = reference
+            .split('"')
+            .next()
+            .unwrap()
+            .trim_start_matches("#/components/schemas/");
+        assert!(schemas.get(name).is_some(), "missing schema: {}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_events() {
+    let (chain, app) = app();
+
+    let request = Request::builder()
+        .uri("/events?kind=blocks")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    let hash = {
+        let mut chain = chain.lock().unwrap();
+        chain.generate_new_block();
+        chain.get_last_hash()
+    };
+
+    let mut body = response.into_body();
+    let chunk = timeout(Duration::from_secs(5), body.data())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8(chunk.to_vec()).unwrap();
+
+    assert!(text.starts_with("event:block\n"));
+    assert!(text.contains(&hash.to_string()));
+
+    let (status, body) = send(&app, Method::GET, "/events?kind=unknown", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "InvalidData");
+}
+
+#[tokio::test]
+async fn test_authentication_and_rate_limit() {
+    let auth = Auth::new(AuthConfig {
+        keys: HashMap::from([
+            ("user".to_string(), Role::User),
+            ("admin".to_string(), Role::Admin),
+        ]),
+        rate_limit: 3,
+        ..AuthConfig::default()
+    });
+    let app = api::router_with_auth(Arc::new(Mutex::new(setup())), auth);
+
+    let request = |method: Method, key: Option<&str>| {
+        let mut builder = Request::builder()
+            .method(method)
+            .uri("/wallets")
+            .header("content-type", "application/json");
+        if let Some(key) = key {
+            builder = builder.header(API_KEY_HEADER, key);
+        }
+
+        builder
+            .body(Body::from(json!({ "email": "a@mail.com" }).to_string()))
+            .unwrap()
+    };
+
+    let status = |request| {
+        let app = app.clone();
+        async move { app.oneshot(request).await.unwrap().status() }
+    };
+
+    assert_eq!(
+        status(request(Method::POST, None)).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        status(request(Method::POST, Some("user"))).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(request(Method::POST, Some("admin"))).await,
+        StatusCode::CREATED
+    );
+
+    let stats = |key| {
+        Request::builder()
+            .uri("/stats")
+            .header(API_KEY_HEADER, key)
+            .body(Body::empty())
+            .unwrap()
+    };
+    assert_eq!(status(stats("user")).await, StatusCode::OK);
+    assert_eq!(status(stats("user")).await, StatusCode::OK);
+    assert_eq!(status(stats("user")).await, StatusCode::TOO_MANY_REQUESTS);
+}
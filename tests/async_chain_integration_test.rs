@@ -0,0 +1,119 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::{env, fs};
+
+use blockchain::{AsyncChain, BlockchainError, STATE_VERSION};
+
+use crate::common::setup;
+
+#[tokio::test]
+async fn test_mining_does_not_block_the_runtime() {
+    let chain = AsyncChain::new(setup());
+
+    let (hash, height) = tokio::join!(chain.generate_new_block(), async {
+        tokio::task::yield_now().await;
+        1
+    });
+
+    assert_eq!(height, 1);
+    assert_eq!(hash, chain.get_last_hash().await);
+    assert_eq!(chain.read(|chain| chain.chain.len()).await, 2);
+}
+
+#[tokio::test]
+async fn test_transactions() {
+    let chain = AsyncChain::new(setup());
+    let from = chain.create_wallet("a@mail.com").await;
+    let to = chain.create_wallet("b@mail.com").await;
+
+    assert!(chain.add_transaction(&from, &to, 10.0).await.is_err());
+
+    let sender = from.clone();
+    chain
+        .write(move |chain| chain.wallets.get_mut(&sender).unwrap().balance += 10.0)
+        .await;
+    chain.add_transaction(&from, &to, 5.0).await.unwrap();
+
+    assert_eq!(chain.get_wallet_balance(&to).await, Some(5.0));
+    assert_eq!(
+        chain.shared().lock().unwrap().get_wallet_balance(&to),
+        Some(5.0)
+    );
+}
+
+#[tokio::test]
+async fn test_validate_chain() {
+    let chain = AsyncChain::new(setup());
+    chain.generate_new_block().await;
+    assert!(chain.validate_chain().await.is_ok());
+
+    chain
+        .write(|chain| chain.chain[1].header.previous_hash = chain.chain[1].hash)
+        .await;
+    assert_eq!(
+        chain.validate_chain().await,
+        Err(BlockchainError::InvalidHeader)
+    );
+}
+
+#[tokio::test]
+async fn test_save_and_load() {
+    let path = env::temp_dir().join(format!("chain-{}.json", uuid::Uuid::new_v4()));
+    let chain = AsyncChain::new(setup());
+    chain.generate_new_block().await;
+    chain.save(&path).await.unwrap();
+
+    let loaded = AsyncChain::load(&path).await.unwrap();
+    assert_eq!(loaded.get_last_hash().await, chain.get_last_hash().await);
+
+    fs::write(&path, b"invalid").unwrap();
+    assert_eq!(
+        AsyncChain::load(&path).await.unwrap_err(),
+        BlockchainError::StorageError
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_load_migrates_older_versions() {
+    let path = env::temp_dir().join(format!("chain-{}.json", uuid::Uuid::new_v4()));
+    let mut chain = setup();
+    chain.generate_new_block();
+    chain.generate_new_block();
+
+    // Chains saved before versioning have no version and no block heights
+    let mut legacy = serde_json::to_value(&chain).unwrap();
+    for block in legacy["chain"].as_array_mut().unwrap() {
+        block.as_object_mut().unwrap().remove("height");
+    }
+    fs::write(&path, legacy.to_string()).unwrap();
+
+    let loaded = AsyncChain::load(&path).await.unwrap();
+    let heights: Vec<_> = loaded
+        .shared()
+        .lock()
+        .unwrap()
+        .chain
+        .iter()
+        .map(|b| b.height)
+        .collect();
+    assert_eq!(heights, vec![0, 1, 2]);
+
+    loaded.save(&path).await.unwrap();
+    let saved: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(saved["version"], STATE_VERSION);
+
+    legacy["version"] = (STATE_VERSION + 1).into();
+    fs::write(&path, legacy.to_string()).unwrap();
+    assert_eq!(
+        AsyncChain::load(&path).await.unwrap_err(),
+        BlockchainError::UnsupportedStateVersion {
+            version: STATE_VERSION + 1
+        }
+    );
+
+    fs::remove_file(&path).unwrap();
+}
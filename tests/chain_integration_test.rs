@@ -1,5 +1,25 @@
 mod common;
 
+use std::{
+    env,
+    io::Cursor,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use blockchain::{
+    migration, Address, AuditAction, Block, BlockAssembler, BlockLimits, BlockchainError, Bloom,
+    Bridge, Chain, ChainCapacity, ChainRegistry, ComponentStatus, DifficultyTarget, EmissionCurve,
+    EmissionSchedule, Environment, FixedRates, GenesisConfig, Hash, HashVersion, InvoiceStatus,
+    KeyScope, KycProvider, KycTier, LightClient, ManualClock, Mempool, Permission, ProposalChange,
+    ProtocolFeature, PublicKeyScheme, RateLimiter, ReplayFormat, SettlementPeriod, Transaction,
+    TransactionHook, TransactionKind, TransactionPriority, TransactionRequest, VanityScheme,
+    Wallet, NATIVE_ASSET,
+};
+use uuid::Uuid;
+
 use crate::common::setup;
 
 #[test]
@@ -12,7 +32,7 @@ fn test_add_transaction() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    let result = chain.add_transaction(from, to, 10.0);
+    let result = chain.add_transaction(&from, &to, 10.0);
 
     assert!(result.is_ok());
     assert_eq!(chain.transactions.len(), 1);
@@ -28,7 +48,7 @@ fn test_add_transaction_invalid_balance() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 5.0;
 
-    let result = chain.add_transaction(from, to, 100.0);
+    let result = chain.add_transaction(&from, &to, 100.0);
 
     assert!(result.is_err());
 }
@@ -42,7 +62,7 @@ fn test_add_transaction_validation_failed() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    let result = chain.add_transaction(from, to, 0.0);
+    let result = chain.add_transaction(&from, &to, 0.0);
 
     assert!(result.is_err());
     assert!(chain.transactions.is_empty());
@@ -82,7 +102,7 @@ fn test_validate_transaction_failed_by_invalid_sender() {
     let _ = chain.create_wallet("s@mail.com");
     let to = chain.create_wallet("r@mail.com");
 
-    let result = chain.validate_transaction("invalid", &to, 1.0);
+    let result = chain.validate_transaction(&Address::random(), &to, 1.0);
 
     assert!(!result);
 }
@@ -96,7 +116,7 @@ fn test_validate_transaction_failed_by_invalid_receiver() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    let result = chain.validate_transaction(&from, "invalid", 1.0);
+    let result = chain.validate_transaction(&from, &Address::random(), 1.0);
 
     assert!(!result);
 }
@@ -104,8 +124,9 @@ fn test_validate_transaction_failed_by_invalid_receiver() {
 #[test]
 fn test_validate_transaction_failed_by_same_addresses() {
     let chain = setup();
+    let address = Address::random();
 
-    let result = chain.validate_transaction("address", "address", 1.0);
+    let result = chain.validate_transaction(&address, &address, 1.0);
 
     assert!(!result);
 }
@@ -125,11 +146,72 @@ fn test_validate_transaction_failed_by_invalid_sender_balance() {
 fn test_validate_transaction_failed_by_root() {
     let chain = setup();
 
-    let result = chain.validate_transaction("Root", "Receiver", 0.01);
+    let result = chain.validate_transaction(&Address::root(), &Address::random(), 0.01);
 
     assert!(!result);
 }
 
+#[test]
+fn test_check_transaction() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    let unknown = Address::random();
+    chain.wallets.get_mut(&from).unwrap().balance += 5.0;
+
+    assert_eq!(chain.check_transaction(&from, &to, 5.0), Ok(()));
+    assert_eq!(
+        chain.check_transaction(&from, &from, 1.0),
+        Err(BlockchainError::InvalidTransaction)
+    );
+    assert!(matches!(
+        chain.check_transaction(&from, &to, f64::NAN),
+        Err(BlockchainError::InvalidAmount { .. })
+    ));
+    assert_eq!(
+        chain.check_transaction(&from, &to, -1.0),
+        Err(BlockchainError::InvalidAmount { amount: -1.0 })
+    );
+    assert_eq!(
+        chain.check_transaction(&from, &unknown, 1.0),
+        Err(BlockchainError::WalletNotFound {
+            address: unknown.clone()
+        })
+    );
+    assert_eq!(
+        chain.check_transaction(&from, &to, 6.0),
+        Err(BlockchainError::InsufficientFunds {
+            address: from.clone(),
+            required: 6.0,
+            available: 5.0,
+        })
+    );
+}
+
+#[test]
+fn test_add_duplicate_transaction() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 5.0;
+
+    // Record the transfer for this second and the next one, so the new transfer collides
+    let mut existing = Transaction::new(from.clone(), to.clone(), chain.fee, 0.1);
+    for _ in 0..2 {
+        chain.transactions.insert(existing.hash, existing.clone());
+        existing.timestamp += 1;
+        existing.hash = existing.compute_hash();
+    }
+
+    let result = chain.add_transaction(&from, &to, 1.0);
+
+    assert!(matches!(
+        result,
+        Err(BlockchainError::DuplicateTransaction { hash }) if chain.transactions.contains_key(&hash)
+    ));
+    assert_eq!(chain.get_wallet_balance(&from), Some(5.0));
+}
+
 #[test]
 fn test_get_transaction() {
     let mut chain = setup();
@@ -139,9 +221,7 @@ fn test_get_transaction() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    chain
-        .add_transaction(from.clone(), to.clone(), 10.0)
-        .unwrap();
+    chain.add_transaction(&from, &to, 10.0).unwrap();
 
     let transaction = chain
         .get_transaction(&chain.transactions.values().next().unwrap().hash)
@@ -155,7 +235,7 @@ fn test_get_transaction() {
 fn test_get_transaction_not_found() {
     let chain = setup();
 
-    let transaction = chain.get_transaction("NonExistentHash");
+    let transaction = chain.get_transaction(&Hash::zero());
 
     assert!(transaction.is_err());
 }
@@ -169,18 +249,37 @@ fn test_get_transactions() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    chain
-        .add_transaction(from.clone(), to.clone(), 10.0)
-        .unwrap();
-    chain
-        .add_transaction(to.clone(), from.clone(), 20.0)
-        .unwrap();
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    chain.add_transaction(&to, &from, 20.0).unwrap();
 
     let transactions = chain.get_transactions(0, 10);
 
     assert_eq!(transactions.len(), 2);
 }
 
+#[test]
+fn test_get_transactions_pages() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+
+    for amount in [1.0, 2.0, 3.0] {
+        chain.add_transaction(&from, &to, amount).unwrap();
+    }
+
+    let first = chain.get_transactions(1, 2);
+    let second = chain.get_transactions(2, 2);
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 1);
+    assert!(first.iter().all(|tx| tx.hash != second[0].hash));
+    assert_eq!(chain.iter_transactions(3, 2).count(), 0);
+
+    let owned = chain.get_transactions_owned(1, 2);
+    assert!(first.iter().all(|tx| owned.contains_key(&tx.hash)));
+}
+
 #[test]
 fn test_get_transactions_not_found() {
     let chain = setup();
@@ -205,7 +304,7 @@ fn test_create_wallet() {
 
     let result = chain.create_wallet("s@mail.com");
 
-    assert_eq!(result.len(), 42);
+    assert_eq!(result.as_str().len(), 42);
 }
 
 #[test]
@@ -222,7 +321,7 @@ fn test_get_wallet_balance() {
 fn test_get_wallet_balance_not_found() {
     let chain = setup();
 
-    let result = chain.get_wallet_balance("address");
+    let result = chain.get_wallet_balance(&Address::random());
 
     assert!(result.is_none());
 }
@@ -237,15 +336,58 @@ fn test_get_wallet_transactions() {
     let sender = chain.wallets.get_mut(&from).unwrap();
     sender.balance += 20.0;
 
-    chain
-        .add_transaction(from.clone(), to.clone(), 10.0)
-        .unwrap();
+    chain.add_transaction(&from, &to, 10.0).unwrap();
 
     let transactions = chain.get_wallet_transactions(&from, 0, 10).unwrap();
 
     assert!(!transactions.is_empty());
 }
 
+#[test]
+fn test_get_wallet_transactions_owned() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    chain.add_transaction(&from, &to, 5.0).unwrap();
+
+    let borrowed = chain.get_wallet_transactions(&to, 2, 1).unwrap();
+    let owned = chain.get_wallet_transactions_owned(&to, 2, 1).unwrap();
+
+    assert_eq!(borrowed.len(), 1);
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0].hash, borrowed[0].hash);
+    assert!(chain
+        .iter_wallet_transactions(&Address::random(), 1, 1)
+        .is_none());
+}
+
+#[test]
+fn test_export_wallet_activity() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    chain.add_transaction(&from, &to, 5.0).unwrap();
+
+    let mut output = Vec::new();
+    assert_eq!(chain.export_wallet_activity(&to, &mut output), Ok(2));
+
+    let hashes: Vec<Hash> = output
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| Transaction::decode_untrusted(line).unwrap().hash)
+        .collect();
+    assert_eq!(hashes, chain.wallets[&to].transaction_hashes);
+    assert!(chain
+        .export_wallet_activity(&Address::random(), Vec::new())
+        .is_err());
+}
+
 #[test]
 fn test_get_new_wallet_transactions() {
     let mut chain = setup();
@@ -261,7 +403,7 @@ fn test_get_new_wallet_transactions() {
 fn test_get_wallet_transactions_not_found() {
     let chain = setup();
 
-    let transactions = chain.get_wallet_transactions("address", 0, 10);
+    let transactions = chain.get_wallet_transactions(&Address::random(), 0, 10);
 
     assert!(transactions.is_none());
 }
@@ -270,7 +412,7 @@ fn test_get_wallet_transactions_not_found() {
 fn test_get_wallet_transactions_empty_page() {
     let chain = setup();
 
-    let transactions = chain.get_wallet_transactions("address", 10, 10);
+    let transactions = chain.get_wallet_transactions(&Address::random(), 10, 10);
 
     assert!(transactions.is_none());
 }
@@ -280,7 +422,7 @@ fn test_get_last_hash() {
     let chain = setup();
     let hash = chain.get_last_hash();
 
-    assert!(!hash.is_empty());
+    assert!(!hash.is_zero());
 }
 
 #[test]
@@ -319,3 +461,2123 @@ fn test_generate_new_block() {
     assert!(result);
     assert_eq!(chain.chain.len(), 2);
 }
+
+#[test]
+fn test_get_merkle_proof() {
+    let chain = setup();
+    let block = &chain.chain[0];
+    let hash = block.transactions.keys().next().unwrap();
+
+    let (height, proof) = chain.get_merkle_proof(hash).unwrap();
+
+    assert_eq!(height, 0);
+    assert!(proof.verify(&block.header.merkle));
+}
+
+#[test]
+fn test_get_merkle_proof_not_found() {
+    let chain = setup();
+
+    let result = chain.get_merkle_proof(&Hash::zero());
+
+    assert_eq!(
+        result.unwrap_err(),
+        BlockchainError::TransactionNotFound { hash: Hash::zero() }
+    );
+}
+
+#[test]
+fn test_get_headers() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    let headers = chain.get_headers(1, 10);
+
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].merkle, chain.chain[1].header.merkle);
+}
+
+#[test]
+fn test_add_header() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = source.clone();
+    chain.chain.truncate(1);
+
+    let result = chain.add_header(source.chain[1].header.clone());
+
+    assert!(result.is_ok());
+    assert!(chain.chain[1].pruned);
+    assert_eq!(chain.get_last_hash(), source.get_last_hash());
+}
+
+#[test]
+fn test_add_header_invalid_difficulty() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = source.clone();
+    chain.chain.truncate(1);
+
+    // A header declaring no difficulty is rejected, even with a valid hash
+    let mut header = source.chain[1].header.clone();
+    header.difficulty = 0.0;
+
+    assert_eq!(
+        chain.add_header(header),
+        Err(BlockchainError::InvalidHeader)
+    );
+    assert_eq!(chain.expected_difficulty(), 1.0);
+}
+
+#[test]
+fn test_add_header_invalid_link() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = setup();
+
+    let result = chain.add_header(source.chain[1].header.clone());
+
+    assert_eq!(result, Err(BlockchainError::InvalidHeader));
+}
+
+#[test]
+fn test_locator() {
+    let mut chain = setup();
+    for _ in 0..19 {
+        chain.generate_new_block();
+    }
+
+    let locator = chain.locator();
+
+    assert_eq!(locator[0], chain.get_last_hash());
+    assert_eq!(locator[9], chain.get_block_hash(10).unwrap());
+    assert_eq!(locator[10], chain.get_block_hash(8).unwrap());
+    assert_eq!(locator.last(), chain.get_block_hash(0).as_ref());
+    assert_eq!(locator.len(), 13);
+}
+
+#[test]
+fn test_find_fork() {
+    let mut source = setup();
+    let mut fork = source.clone();
+
+    source.generate_new_block();
+    source.generate_new_block();
+    fork.generate_new_block();
+
+    assert_eq!(source.find_fork(&fork.locator()), Some(0));
+    assert_eq!(source.find_fork(&source.locator()), Some(2));
+    assert_eq!(source.find_fork(&setup().locator()), None);
+}
+
+#[test]
+fn test_import_block() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = source.clone();
+    chain.chain.truncate(1);
+
+    let result = chain.import_block(source.chain[1].clone());
+
+    assert!(result.is_ok());
+    assert!(!chain.chain[1].pruned);
+    assert_eq!(chain.chain[1].transactions.len(), 1);
+    assert_eq!(chain.get_last_hash(), source.get_last_hash());
+}
+
+#[test]
+fn test_from_genesis() {
+    let config = GenesisConfig {
+        difficulty: 1.0,
+        reward: 100.0,
+        fee: 0.1,
+        address: Address::new(&"G".repeat(42)).unwrap(),
+        timestamp: 1_700_000_000,
+    };
+
+    let mut source = Chain::from_genesis(&config);
+    let mut chain = Chain::from_genesis(&config);
+
+    assert_eq!(Chain::hash(&source.chain[0]), Chain::hash(&chain.chain[0]));
+    assert_eq!(chain.chain[0].header.timestamp, config.timestamp);
+    assert_eq!(chain.address, config.address);
+
+    source.generate_new_block();
+
+    assert!(chain.import_block(source.chain[1].clone()).is_ok());
+    assert_eq!(chain.get_last_hash(), source.get_last_hash());
+}
+
+#[test]
+fn test_import_block_invalid_body() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = source.clone();
+    chain.chain.truncate(1);
+
+    let mut block = source.chain[1].clone();
+    block.transactions.clear();
+
+    let result = chain.import_block(block);
+
+    assert_eq!(result, Err(BlockchainError::InvalidBlockBody));
+    assert_eq!(chain.chain.len(), 1);
+}
+
+#[test]
+fn test_prune_and_restore_bodies() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    let body = chain.chain[0].transactions.clone();
+    let pruned = chain.prune_bodies(1);
+
+    assert_eq!(pruned, 1);
+    assert!(chain.chain[0].pruned);
+    assert!(chain.chain[0].transactions.is_empty());
+    assert!(!chain.chain[1].pruned);
+
+    let result = chain.restore_body(0, body);
+
+    assert!(result.is_ok());
+    assert!(!chain.chain[0].pruned);
+}
+
+#[test]
+fn test_restore_body_invalid() {
+    let mut chain = setup();
+    chain.prune_bodies(0);
+
+    let invalid = chain.restore_body(0, Default::default());
+    let missing = chain.restore_body(5, Default::default());
+
+    assert_eq!(invalid, Err(BlockchainError::InvalidBlockBody));
+    assert_eq!(missing, Err(BlockchainError::BlockNotFound));
+}
+
+#[test]
+fn test_generate_new_block_height_and_hash() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    let block = &chain.chain[1];
+
+    assert_eq!(block.height, 1);
+    assert_eq!(block.hash, Chain::hash(&block.header));
+    assert_eq!(block.header.previous_hash, chain.chain[0].hash);
+    assert_eq!(chain.get_last_hash(), block.hash);
+}
+
+#[test]
+fn test_median_time_past() {
+    let mut chain = setup();
+    chain.generate_new_block();
+    chain.generate_new_block();
+
+    let median = chain.median_time_past().unwrap();
+
+    assert_eq!(median, chain.chain[1].header.timestamp);
+    assert!(chain.chain[2].header.timestamp > chain.chain[1].header.timestamp);
+}
+
+#[test]
+fn test_add_header_invalid_timestamp() {
+    let mut source = setup();
+    source.generate_new_block();
+
+    let mut chain = source.clone();
+    chain.chain.truncate(1);
+
+    let mut header = source.chain[1].header.clone();
+    header.timestamp = chain.chain[0].header.timestamp;
+    blockchain::Block::proof_of_work(&mut header);
+
+    let result = chain.add_header(header);
+
+    assert_eq!(result, Err(BlockchainError::InvalidTimestamp));
+}
+
+#[test]
+fn test_state_root_committed_in_header() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain.generate_new_block();
+
+    let committed = chain.chain[1].header.state_root;
+
+    assert_eq!(committed, chain.state_root());
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+
+    assert_ne!(committed, chain.state_root());
+}
+
+#[test]
+fn test_state_root_empty() {
+    let chain = setup();
+
+    assert!(chain.state_root().is_zero());
+    assert!(chain.chain[0].header.state_root.is_zero());
+}
+
+#[test]
+fn test_prove_balance() {
+    let mut chain = setup();
+    let address = chain.create_wallet("s@mail.com");
+
+    chain.wallets.get_mut(&address).unwrap().balance += 20.0;
+    chain.generate_new_block();
+    chain.wallets.get_mut(&address).unwrap().balance += 5.0;
+
+    let proof = chain.prove_balance(&address, 1).unwrap();
+
+    assert_eq!(proof.account.balance, 20.0);
+    assert!(proof.verify(&chain.chain[1].header.state_root));
+    assert!(!proof.verify(&chain.state_root()));
+}
+
+#[test]
+fn test_state_diff() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    let idle = chain.create_wallet("i@mail.com");
+
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain.generate_new_block();
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    let created = chain.create_wallet("c@mail.com");
+    chain.generate_new_block();
+
+    let trie = chain.state_trie(2).unwrap();
+    let mut expected = vec![from.clone(), to.clone(), created.clone()];
+    expected.sort();
+
+    assert_eq!(trie.root(), chain.chain[2].header.state_root);
+    assert_eq!(trie.get(&to).unwrap().balance, 10.0);
+    assert!(trie
+        .prove(&idle)
+        .verify(&trie.root(), &idle, Some(&trie.get(&idle).unwrap().hash())));
+    assert_eq!(chain.state_diff(1, 2), Ok(expected));
+    assert_eq!(chain.state_diff(2, 2), Ok(vec![]));
+    assert_eq!(chain.state_diff(1, 5), Err(BlockchainError::BlockNotFound));
+}
+
+#[test]
+fn test_prove_balance_not_found() {
+    let mut chain = setup();
+    let address = chain.create_wallet("s@mail.com");
+
+    assert_eq!(
+        chain.prove_balance(&address, 0),
+        Err(BlockchainError::WalletNotFound {
+            address: address.clone()
+        })
+    );
+    assert_eq!(
+        chain.prove_balance(&address, 5),
+        Err(BlockchainError::BlockNotFound)
+    );
+}
+
+#[test]
+fn test_validate_chain() {
+    let mut chain = setup();
+    chain.generate_new_block();
+    chain.generate_new_block();
+    assert!(chain.validate_chain().is_ok());
+
+    chain.prune_bodies(1);
+    assert!(chain.validate_chain().is_ok());
+
+    let mut tampered = chain.clone();
+    tampered
+        .chain
+        .iter_mut()
+        .flat_map(|block| block.transactions.values_mut())
+        .for_each(|tx| tx.amount += 1.0);
+    assert_eq!(
+        tampered.validate_chain(),
+        Err(BlockchainError::InvalidTransaction)
+    );
+
+    let mut tampered = chain.clone();
+    tampered.chain[2].transactions.clear();
+    assert_eq!(
+        tampered.validate_chain(),
+        Err(BlockchainError::InvalidBlockBody)
+    );
+
+    chain.chain.swap(1, 2);
+    assert_eq!(chain.validate_chain(), Err(BlockchainError::InvalidHeader));
+}
+
+#[test]
+fn test_verify_block_hashes() {
+    let mut chain = setup();
+    chain.generate_new_block();
+    let hash = chain.get_last_hash();
+
+    chain.chain[1].hash = Hash::zero();
+    assert_eq!(chain.verify_block_hashes(), Ok(1));
+    assert_eq!(chain.chain[1].hash, hash);
+    assert_eq!(chain.verify_block_hashes(), Ok(0));
+
+    chain.chain[1].header.nonce += 1;
+    assert_eq!(
+        chain.verify_block_hashes(),
+        Err(BlockchainError::InvalidHeader)
+    );
+    assert_eq!(chain.validate_chain(), Err(BlockchainError::InvalidHeader));
+}
+
+#[test]
+fn test_apply_pending() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 2.0;
+
+    let mempool = Mempool::new();
+    let valid = mempool.insert(TransactionRequest::new(&from, &to, 10.0));
+    let invalid = mempool.insert(TransactionRequest::new(&from, &to, 100.0));
+    mempool.insert(TransactionRequest::new(&from, &to, 1.0));
+
+    let outcomes = chain.apply_pending(&mempool, 2);
+
+    assert_eq!(outcomes.len(), 2);
+    assert_eq!(outcomes[0], (valid, Ok(())));
+    assert_eq!(outcomes[1].0, invalid);
+    assert!(outcomes[1].1.is_err());
+    assert_eq!(mempool.len(), 1);
+    assert_eq!(chain.get_wallet_balance(&to), Some(10.0));
+}
+
+#[test]
+fn test_add_transactions() {
+    let mut chain = setup();
+    let a = chain.create_wallet("a@mail.com");
+    let b = chain.create_wallet("b@mail.com");
+    let c = chain.create_wallet("c@mail.com");
+    chain.wallets.get_mut(&a).unwrap().balance += 1.0;
+
+    let results = chain.add_transactions(vec![
+        TransactionRequest::new(&a, &b, 10.0),
+        TransactionRequest::new(&b, &c, 20.0),
+        TransactionRequest::new(&a, &a, 1.0),
+        TransactionRequest::new(&b, &c, 5.0),
+    ]);
+
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    assert_eq!(results[2], Err(BlockchainError::InvalidTransaction));
+    assert!(results[3].is_ok());
+
+    let hash = results[0].as_ref().unwrap();
+    assert_eq!(chain.get_transaction(hash).unwrap().from, a);
+    assert_eq!(chain.get_wallet_balance(&c), Some(25.0));
+}
+
+#[test]
+fn test_capacity_and_memory_usage() {
+    let capacity = ChainCapacity {
+        wallets: 1000,
+        transactions: 5000,
+        blocks: 100,
+    };
+    let mut chain = Chain::with_capacity(1.0, 100.0, 0.1, capacity);
+
+    assert!(chain.wallets.capacity() >= 1000);
+    assert!(chain.transactions.capacity() >= 5000);
+    assert!(chain.chain.capacity() >= 100);
+
+    let reserved = chain.memory_usage();
+    assert_eq!(
+        reserved.total,
+        reserved.blocks + reserved.transactions + reserved.wallets
+    );
+    assert!(reserved.transactions > setup().memory_usage().transactions);
+
+    chain.shrink_to_fit();
+    let shrunk = chain.memory_usage();
+    assert!(shrunk.total < reserved.total);
+
+    chain.create_wallet("a@mail.com");
+    assert!(chain.memory_usage().wallets > shrunk.wallets);
+}
+
+struct LimitHook {
+    limit: f64,
+    applied: Arc<AtomicUsize>,
+}
+
+impl TransactionHook for LimitHook {
+    fn before_validate(
+        &self,
+        _chain: &Chain,
+        request: &TransactionRequest,
+    ) -> Result<(), BlockchainError> {
+        match request.amount > self.limit {
+            true => Err(BlockchainError::Unauthorized),
+            false => Ok(()),
+        }
+    }
+
+    fn after_apply(&self, chain: &Chain, transaction: &Transaction) {
+        assert!(chain.get_transaction(&transaction.hash).is_ok());
+        self.applied.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_transaction_hooks() {
+    let mut chain = setup();
+    let from = chain.create_wallet("a@mail.com");
+    let to = chain.create_wallet("b@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 100.0;
+
+    let applied = Arc::new(AtomicUsize::new(0));
+    chain.register_hook(LimitHook {
+        limit: 10.0,
+        applied: applied.clone(),
+    });
+
+    assert!(chain.add_transaction(&from, &to, 5.0).is_ok());
+    assert_eq!(
+        chain.add_transaction(&from, &to, 50.0),
+        Err(BlockchainError::Unauthorized)
+    );
+    assert_eq!(chain.get_wallet_balance(&to), Some(5.0));
+    assert_eq!(applied.load(Ordering::SeqCst), 1);
+
+    let results = chain.add_transactions(vec![
+        TransactionRequest::new(&from, &to, 1.0),
+        TransactionRequest::new(&from, &to, 20.0),
+    ]);
+    assert!(results[0].is_ok());
+    assert_eq!(results[1], Err(BlockchainError::Unauthorized));
+    assert_eq!(applied.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_admin_keys() {
+    let mut chain = setup();
+    let address = chain.create_wallet("s@mail.com");
+    chain.generate_new_block();
+
+    let economics = chain.issue_admin_key(&[Permission::UpdateParameters]);
+    let operator = chain.issue_admin_key(&[Permission::Freeze, Permission::Rollback]);
+
+    chain.admin(&economics).unwrap().update_fee(0.5).unwrap();
+    assert_eq!(chain.fee, 0.5);
+    assert!(matches!(
+        chain.admin(&economics).unwrap().truncate(1),
+        Err(BlockchainError::Unauthorized)
+    ));
+
+    let mut admin = chain.admin(&operator).unwrap();
+    assert!(!admin.can(Permission::UpdateParameters));
+    assert_eq!(
+        admin.update_reward(100.0),
+        Err(BlockchainError::Unauthorized)
+    );
+    admin.deny_address(&address, None).unwrap();
+    assert_eq!(admin.truncate(1).map(|removed| removed.len()), Ok(1));
+    assert!(chain.compliance.denied.contains_key(&address));
+    assert_eq!(chain.chain.len(), 1);
+
+    let restored: Chain = serde_json::from_str(&serde_json::to_string(&chain).unwrap()).unwrap();
+    assert!(!serde_json::to_string(&restored)
+        .unwrap()
+        .contains(&operator));
+
+    let mut restored = restored;
+    assert!(restored.admin(&operator).is_ok());
+    assert!(restored.revoke_admin_key(&operator));
+    assert!(matches!(
+        restored.admin(&operator),
+        Err(BlockchainError::Unauthenticated)
+    ));
+}
+
+#[test]
+fn test_audit_log() {
+    let mut chain = setup();
+    assert!(chain.audit_log().is_empty());
+
+    chain.update_fee(0.2);
+    chain.update_reward(50.0);
+    chain.generate_new_block();
+    let mined = chain.get_last_hash();
+    chain.truncate(1);
+    chain.truncate(5);
+
+    let log = chain.audit_log();
+    assert_eq!(log.len(), 3);
+    assert!(log.verify().is_ok());
+    assert_eq!(
+        log.entries()[0].action,
+        AuditAction::ParameterChanged {
+            change: ProposalChange::Fee(0.2),
+            previous: 0.1,
+        }
+    );
+    assert_eq!(
+        log.entries()[2].action,
+        AuditAction::Rollback {
+            height: 1,
+            removed: vec![mined],
+        }
+    );
+    assert_eq!(log.head(), Some(log.entries()[2].hash));
+
+    let restored: Chain = serde_json::from_str(&serde_json::to_string(&chain).unwrap()).unwrap();
+    assert_eq!(restored.audit_log().entries(), chain.audit_log().entries());
+}
+
+#[test]
+fn test_health() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    assert_eq!(chain.health().validation, ComponentStatus::Unknown);
+    assert!(chain.revalidate().is_ok());
+
+    let health = chain.health();
+    assert_eq!(health.height, 2);
+    assert!(health.tip_age <= 1);
+    assert_eq!(health.mempool_depth, None);
+    assert_eq!(health.storage, ComponentStatus::Unknown);
+    assert_eq!(health.validation, ComponentStatus::Healthy);
+    assert!(health.is_live());
+    assert!(health.is_ready(60, 0));
+
+    let mempool = Mempool::new();
+    mempool.insert(TransactionRequest::new(
+        &Address::random(),
+        &Address::random(),
+        1.0,
+    ));
+    let health = chain.health().with_mempool(&mempool);
+    assert_eq!(health.mempool_depth, Some(1));
+    assert!(!health.is_ready(60, 0));
+
+    let health = chain
+        .health()
+        .with_storage(Err::<(), _>(BlockchainError::StorageError));
+    assert!(health.is_live());
+    assert!(!health.is_ready(60, 10));
+
+    // The probe reports the cached validation until the chain is validated again
+    chain.chain[1].header.nonce += 1;
+    assert!(chain.health().is_live());
+    assert_eq!(chain.revalidate(), Err(BlockchainError::InvalidHeader));
+
+    let health = chain.health();
+    assert_eq!(
+        health.validation,
+        ComponentStatus::Unhealthy(BlockchainError::InvalidHeader)
+    );
+    assert!(!health.is_live());
+
+    // Removing or importing blocks makes the cached validation stale
+    chain.truncate(1);
+    assert_eq!(chain.health().validation, ComponentStatus::Unknown);
+    assert!(chain.revalidate().is_ok());
+
+    let mut peer = chain.clone();
+    peer.generate_new_block();
+    chain.import_block(peer.chain[1].clone()).unwrap();
+    assert_eq!(chain.health().validation, ComponentStatus::Unknown);
+}
+
+#[test]
+fn test_deterministic_chain() {
+    let simulate = |seed: u64| {
+        let clock = Arc::new(ManualClock::new(1_700_000_000));
+        let environment = Environment::seeded(seed, clock.clone());
+        let mut chain = Chain::with_environment(1.0, 100.0, 0.1, environment);
+
+        let from = chain.create_wallet("s@mail.com");
+        let to = chain.create_wallet("r@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+        clock.advance(60);
+        chain.add_transaction(&from, &to, 1.0).unwrap();
+        chain.update_fee(0.2);
+        chain.generate_new_block();
+
+        assert_eq!(chain.chain[1].header.timestamp, 1_700_000_060);
+        assert!(chain.validate_chain().is_ok());
+
+        serde_json::to_string(&chain).unwrap()
+    };
+
+    assert_eq!(simulate(7), simulate(7));
+    assert_ne!(simulate(7), simulate(8));
+}
+
+#[test]
+fn test_from_bytes_untrusted() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    chain.generate_new_block();
+
+    let bytes = migration::encode(&chain).unwrap();
+    let loaded = Chain::from_bytes_untrusted(&bytes).unwrap();
+
+    assert_eq!(loaded.get_last_hash(), chain.get_last_hash());
+
+    for bytes in [
+        &b""[..],
+        b"{",
+        b"[]",
+        b"{\"chain\": 1}",
+        &bytes[..bytes.len() / 2],
+    ] {
+        assert_eq!(
+            Chain::from_bytes_untrusted(bytes).unwrap_err(),
+            BlockchainError::StorageError
+        );
+    }
+
+    chain.chain[1].height = 5;
+    let bytes = migration::encode(&chain).unwrap();
+
+    assert_eq!(
+        Chain::from_bytes_untrusted(&bytes).unwrap_err(),
+        BlockchainError::StorageError
+    );
+}
+
+#[test]
+fn test_decode_baseline_ledger() {
+    // Chain saved by the first release, whose hashes dropped the leading zero of each byte
+    let bytes = include_bytes!("fixtures/baseline_chain.json");
+    let legacy: serde_json::Value = serde_json::from_slice(bytes).unwrap();
+    let alice = Address::new("Te5kDweLKgzTJe5qeetLdWLAg7s5M1uiuAuGIuLJdd").unwrap();
+
+    let mut chain = migration::decode(bytes).unwrap();
+
+    assert_eq!(chain.chain.len(), 3);
+    assert_eq!(chain.chain[2].height, 2);
+    assert_eq!(
+        chain.get_wallet_balance(&alice),
+        legacy["wallets"][alice.as_str()]["balance"].as_f64()
+    );
+    assert!(chain.validate_chain().is_ok());
+
+    // The histories point to the rehashed transfers, which were never mined and are queued
+    let history = chain.wallets[&alice].transaction_hashes.clone();
+    assert_eq!(history.len(), 2);
+    assert!(history
+        .iter()
+        .all(|hash| chain.get_transaction(hash).is_ok()));
+    assert_eq!(chain.pending.len(), 2);
+
+    chain.generate_new_block();
+    assert!(chain.pending.is_empty());
+    assert!(chain.validate_chain().is_ok());
+
+    // The upgraded chain loads as a current one
+    let bytes = migration::encode(&chain).unwrap();
+    assert!(Chain::from_bytes_untrusted(&bytes).is_ok());
+}
+
+#[test]
+fn test_block_limits() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    for amount in [1.0, 2.0, 3.0] {
+        chain.add_transaction(&from, &to, amount).unwrap();
+    }
+    chain.set_block_limits(BlockLimits {
+        max_transactions: Some(3),
+        max_size: None,
+    });
+
+    chain.generate_new_block();
+    assert_eq!(chain.chain[1].transactions.len(), 3);
+    assert_eq!(chain.pending.len(), 1);
+
+    chain.generate_new_block();
+    assert_eq!(chain.chain[2].transactions.len(), 2);
+    assert!(chain.pending.is_empty());
+    assert!(chain.validate_chain().is_ok());
+
+    chain.truncate(2);
+    assert_eq!(chain.pending.len(), 1);
+
+    chain.set_block_limits(BlockLimits {
+        max_transactions: Some(2),
+        max_size: None,
+    });
+    assert_eq!(
+        chain.validate_chain(),
+        Err(BlockchainError::BlockLimitExceeded)
+    );
+}
+
+#[test]
+fn test_size_accounting() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    assert!(chain.set_fee_per_byte(-1.0).is_err());
+    chain.set_fee_per_byte(0.001).unwrap();
+
+    let hash = chain
+        .add_transactions(vec![TransactionRequest::new(&from, &to, 10.0)])
+        .remove(0)
+        .unwrap();
+    let transaction = chain.get_transaction(&hash).unwrap();
+    assert_eq!(transaction.size, transaction.encoded_size());
+    assert_eq!(transaction.fee, 0.1 + chain.size_fee(transaction.size));
+    assert_eq!(
+        chain.get_wallet_balance(&from),
+        Some(100.0 - 10.0 * 0.1 - chain.size_fee(transaction.size))
+    );
+
+    chain.generate_new_block();
+    let block = &chain.chain[1];
+    assert_eq!(block.size, block.encoded_size());
+    assert!(block.size > block.body_size());
+    assert!(block
+        .transactions
+        .values()
+        .all(|tx| tx.size == tx.encoded_size()));
+    assert!(chain.validate_chain().is_ok());
+
+    chain.chain[1].transactions.get_mut(&hash).unwrap().size += 1;
+    assert!(chain.validate_chain().is_err());
+}
+
+#[test]
+fn test_transaction_priority_age_boost() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+    chain.set_block_limits(BlockLimits {
+        max_transactions: Some(2),
+        max_size: None,
+    });
+    chain.set_transaction_priority(TransactionPriority::AgeBoost { max_wait: 1 });
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    let starved = chain.pending[0].hash;
+    chain.update_fee(0.5);
+    chain.add_transaction(&from, &to, 2.0).unwrap();
+
+    // The higher fee wins while no transaction waited for a block
+    chain.generate_new_block();
+    assert!(!chain.chain[1].transactions.contains_key(&starved));
+
+    chain.add_transaction(&from, &to, 3.0).unwrap();
+
+    // The transaction left out of the previous block is boosted over the higher fee
+    chain.generate_new_block();
+    assert!(chain.chain[2].transactions.contains_key(&starved));
+    assert_eq!(chain.pending.len(), 1);
+}
+
+/// Block assembler holding back the transactions sent by one wallet.
+struct HoldBack(Address);
+
+impl BlockAssembler for HoldBack {
+    fn assemble(&self, chain: &Chain, _height: usize) -> Vec<usize> {
+        (0..chain.pending.len())
+            .filter(|&index| chain.transactions[&chain.pending[index].hash].from != self.0)
+            .collect()
+    }
+}
+
+#[test]
+fn test_block_assembler() {
+    let mut chain = setup();
+    let held = chain.create_wallet("h@mail.com");
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&held).unwrap().balance = 10.0;
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+    chain.set_block_assembler(HoldBack(held.clone()));
+
+    chain.add_transaction(&held, &to, 1.0).unwrap();
+    let kept = chain.pending[0].hash;
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+
+    // The held back transaction stays pending
+    chain.generate_new_block();
+    assert_eq!(chain.chain[1].transactions.len(), 2);
+    assert!(!chain.chain[1].transactions.contains_key(&kept));
+    assert_eq!(chain.pending.len(), 1);
+
+    // Without the assembler, the transaction priority applies again
+    chain.clear_block_assembler();
+    chain.set_transaction_priority(TransactionPriority::Fifo);
+    chain.generate_new_block();
+    assert!(chain.chain[2].transactions.contains_key(&kept));
+    assert!(chain.pending.is_empty());
+}
+
+#[test]
+fn test_difficulty_target() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain = Chain::with_environment(1.0, 100.0, 0.1, Environment::seeded(1, clock.clone()));
+    let target = DifficultyTarget {
+        block_time: 60,
+        interval: 2,
+        max_adjustment: 1.0,
+    };
+
+    assert_eq!(
+        chain.set_difficulty_target(Some(DifficultyTarget {
+            block_time: 0,
+            ..target
+        })),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+    chain.set_difficulty_target(Some(target)).unwrap();
+
+    // Blocks twice as fast as the target raise the difficulty
+    for _ in 0..4 {
+        clock.advance(30);
+        chain.generate_new_block();
+    }
+    assert_eq!(chain.observed_block_time(2), Some(30.0));
+    assert_eq!(chain.difficulty, 2.0);
+    assert_eq!(chain.chain[4].header.difficulty, 2.0);
+
+    // Slow blocks lower it, by at most the maximum adjustment
+    for _ in 0..2 {
+        clock.advance(240);
+        chain.generate_new_block();
+    }
+    assert_eq!(chain.difficulty, 1.0);
+    assert_eq!(chain.observed_block_time(10), None);
+    assert!(chain.validate_chain().is_ok());
+
+    // Light clients follow the retargets
+    let mut client = LightClient::new(1.0).with_difficulty_target(target);
+    assert_eq!(client.sync(&chain), Ok(7));
+    assert!(LightClient::new(1.0).sync(&chain).is_err());
+}
+
+#[test]
+fn test_compliance_lists() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    chain.deny_address(&to, Some("US-OFAC"));
+
+    let denied = || {
+        Err(BlockchainError::AddressDenied {
+            address: to.clone(),
+        })
+    };
+    assert_eq!(chain.check_transaction(&from, &to, 1.0), denied());
+    assert_eq!(chain.add_transaction(&from, &to, 1.0), denied());
+    assert_eq!(
+        chain.audit_log().entries().last().unwrap().action,
+        AuditAction::TransferRefused {
+            from: from.clone(),
+            to: to.clone(),
+            address: to.clone(),
+        }
+    );
+
+    assert!(chain.lift_denial(&to));
+    assert!(!chain.lift_denial(&to));
+    assert!(chain.add_transaction(&from, &to, 1.0).is_ok());
+
+    chain.set_allowlist(Some(vec![from.clone()]));
+    assert_eq!(
+        chain.add_transaction(&from, &to, 2.0),
+        Err(BlockchainError::AddressDenied { address: to })
+    );
+    assert_eq!(chain.audit_log().len(), 5);
+    assert!(chain.audit_log().verify().is_ok());
+}
+
+struct PendingKyc;
+
+impl KycProvider for PendingKyc {
+    fn request_verification(&self, _wallet: &Wallet) -> KycTier {
+        KycTier::Restricted
+    }
+}
+
+#[test]
+fn test_kyc_restricted_wallets() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    chain.set_kyc_provider(PendingKyc, 10.0);
+    let to = chain.create_wallet("r@mail.com");
+    assert_eq!(chain.wallets[&to].kyc, KycTier::Restricted);
+
+    // Restricted wallets receive up to the cap but cannot send
+    assert!(chain.add_transaction(&from, &to, 4.0).is_ok());
+    assert_eq!(
+        chain.add_transaction(&from, &to, 7.0),
+        Err(BlockchainError::KycRequired {
+            address: to.clone(),
+        })
+    );
+    assert_eq!(
+        chain.add_transaction(&to, &from, 1.0),
+        Err(BlockchainError::KycRequired {
+            address: to.clone(),
+        })
+    );
+
+    chain.set_kyc_tier(&to, KycTier::Verified).unwrap();
+    assert!(chain.add_transaction(&to, &from, 1.0).is_ok());
+    assert!(chain.add_transaction(&from, &to, 20.0).is_ok());
+    assert!(chain
+        .set_kyc_tier(&Address::random(), KycTier::Verified)
+        .is_err());
+}
+
+#[test]
+fn test_settled_and_available_balance() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+    chain.generate_new_block();
+
+    chain.add_transaction(&from, &to, 5.0).unwrap();
+
+    // The pending transfer is available but not settled yet
+    assert_eq!(chain.settled_balance(&from), Some(10.0));
+    assert_eq!(chain.settled_balance(&to), Some(0.0));
+    assert_eq!(chain.available_balance(&from), Some(9.5));
+    assert_eq!(chain.available_balance(&to), Some(5.0));
+
+    chain.generate_new_block();
+    assert_eq!(chain.settled_balance(&from), Some(9.5));
+    assert_eq!(chain.settled_balance(&to), Some(5.0));
+    assert_eq!(chain.settled_balance(&Address::random()), None);
+}
+
+#[test]
+fn test_fast_sync_from_snapshot() {
+    let mut source = setup();
+    let from = source.create_wallet("s@mail.com");
+    let to = source.create_wallet("r@mail.com");
+    source.wallets.get_mut(&from).unwrap().balance = 10.0;
+    source.add_transaction(&from, &to, 5.0).unwrap();
+    source.generate_new_block();
+    source.add_transaction(&from, &to, 1.0).unwrap();
+    source.generate_new_block();
+
+    let snapshot = source.snapshot(1).unwrap();
+    let trusted = source.chain[1].hash;
+    assert_eq!(snapshot.height(), Some(1));
+
+    // The snapshot must end with the trusted block and match its state root
+    assert!(matches!(
+        Chain::from_snapshot(snapshot.clone(), &source.chain[2].hash),
+        Err(BlockchainError::InvalidHeader)
+    ));
+    let mut tampered = snapshot.clone();
+    tampered.accounts[0].balance += 1.0;
+    assert!(matches!(
+        Chain::from_snapshot(tampered, &trusted),
+        Err(BlockchainError::InvalidSnapshot)
+    ));
+
+    let mut chain = Chain::from_snapshot(snapshot, &trusted).unwrap();
+    assert_eq!(chain.chain.len(), 2);
+    assert_eq!(chain.get_last_hash(), trusted);
+    assert_eq!(chain.get_wallet_balance(&to), Some(5.0));
+    assert_eq!(chain.settled_balance(&to), Some(5.0));
+
+    // Only the blocks after the checkpoint are synchronized
+    chain.import_block(source.chain[2].clone()).unwrap();
+    assert_eq!(chain.get_last_hash(), source.get_last_hash());
+    assert!(matches!(
+        source.snapshot(9),
+        Err(BlockchainError::BlockNotFound)
+    ));
+}
+
+#[test]
+fn test_convert_with_rate_provider() {
+    let mut chain = setup();
+
+    assert!(matches!(
+        chain.convert(2.0, "USD"),
+        Err(BlockchainError::RateUnavailable { .. })
+    ));
+
+    chain.set_rate_provider(
+        FixedRates::default()
+            .with_rate(NATIVE_ASSET, "USD", 1.5)
+            .with_rate("GOLD", "EUR", 3.0),
+    );
+
+    assert_eq!(chain.convert(2.0, "USD"), Ok(3.0));
+    assert_eq!(chain.convert_asset(2.0, "GOLD", "EUR"), Ok(6.0));
+    assert!(chain.convert(2.0, "EUR").is_err());
+
+    // The provider is shared with cloned chains
+    assert_eq!(chain.clone().convert(1.0, "USD"), Ok(1.5));
+}
+
+#[test]
+fn test_sweep() {
+    let mut chain = Chain::new(1.0, 100.0, 0.3);
+    let hot = chain.create_wallet("h@mail.com");
+    let other = chain.create_wallet("o@mail.com");
+    let empty = chain.create_wallet("e@mail.com");
+    let cold = chain.create_wallet("c@mail.com");
+    chain.wallets.get_mut(&hot).unwrap().balance = 0.7;
+    chain.wallets.get_mut(&other).unwrap().balance = 10.0;
+
+    // The amount is rounded down so the fee never pushes the debit over the balance
+    let amount = chain.sweep_amount(&hot).unwrap();
+    assert!(amount * 0.3 <= 0.7);
+    assert_eq!(chain.sweep_amount(&empty), None);
+
+    let requests = chain.build_sweep(&[hot.clone(), other.clone(), empty, cold.clone()], &cold);
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].amount, amount);
+
+    let results = chain.sweep(&[hot.clone(), other.clone()], &cold);
+    assert!(results.iter().all(Result::is_ok));
+    assert!(chain.get_wallet_balance(&hot).unwrap() < 1e-9);
+    assert!(chain.get_wallet_balance(&other).unwrap() < 1e-9);
+    assert!(chain.get_wallet_balance(&hot).unwrap() >= 0.0);
+}
+
+#[test]
+fn test_invoices() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain = Chain::with_environment(1.0, 100.0, 0.1, Environment::seeded(1, clock.clone()));
+    let merchant = chain.create_wallet("m@mail.com");
+    let customer = chain.create_wallet("c@mail.com");
+    chain.wallets.get_mut(&customer).unwrap().balance = 10.0;
+
+    assert!(chain.create_invoice(&merchant, 0.0, 60).is_err());
+    let paid = chain.create_invoice(&merchant, 5.0, 60).unwrap();
+    let expired = chain.create_invoice(&merchant, 2.0, 60).unwrap();
+
+    // The payment references the invoice and settles it
+    let hash = chain.pay_invoice(&customer, paid).unwrap();
+    let invoice = chain.get_invoice(paid).unwrap();
+    assert_eq!(invoice.status, InvoiceStatus::Paid);
+    assert_eq!(invoice.payment, Some(hash));
+    assert_eq!(chain.transactions[&hash].data, paid.as_bytes().to_vec());
+    assert_eq!(chain.get_wallet_balance(&merchant), Some(5.0));
+    assert_eq!(
+        chain.pay_invoice(&customer, paid),
+        Err(BlockchainError::InvoiceClosed)
+    );
+
+    // Unpaid invoices expire, at the latest when a block is generated
+    clock.advance(61);
+    chain.generate_new_block();
+    assert_eq!(
+        chain.get_invoice(expired).unwrap().status,
+        InvoiceStatus::Expired
+    );
+    assert_eq!(
+        chain.pay_invoice(&customer, expired),
+        Err(BlockchainError::InvoiceClosed)
+    );
+    assert_eq!(
+        chain.get_invoices(&merchant, Some(InvoiceStatus::Paid))[0].id,
+        paid
+    );
+    assert_eq!(chain.get_invoices(&merchant, None).len(), 2);
+    assert!(chain.get_invoice(Uuid::nil()).is_err());
+}
+
+#[test]
+fn test_rate_limits() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain = Chain::with_environment(2.0, 100.0, 0.1, Environment::seeded(1, clock.clone()));
+    chain.enable_faucet(100.0, 0, 5.0);
+    chain.set_rate_limits(Some(RateLimiter::new(2, 60)), Some(RateLimiter::new(1, 60)));
+
+    let first = chain.create_wallet_from("a@mail.com", "10.0.0.1").unwrap();
+    chain.create_wallet_from("b@mail.com", "10.0.0.1").unwrap();
+    assert_eq!(
+        chain.create_wallet_from("c@mail.com", "10.0.0.1"),
+        Err(BlockchainError::RateLimited)
+    );
+    chain.create_wallet_from("c@mail.com", "10.0.0.2").unwrap();
+
+    assert!(chain.faucet_from(&first, 1.0, "10.0.0.1").is_ok());
+    assert_eq!(
+        chain.faucet_from(&first, 1.0, "10.0.0.1"),
+        Err(BlockchainError::RateLimited)
+    );
+
+    clock.advance(60);
+    assert!(chain.create_wallet_from("d@mail.com", "10.0.0.1").is_ok());
+    assert!(chain.faucet_from(&first, 1.0, "10.0.0.1").is_ok());
+    assert_eq!(chain.wallets.len(), 5);
+}
+
+#[test]
+fn test_faucet() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain =
+        Chain::with_environment(2.0, 100.0, 0.01, Environment::seeded(1, clock.clone()));
+    let to = chain.create_wallet("r@mail.com");
+
+    assert_eq!(
+        chain.faucet(&to, 1.0),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+
+    let faucet = chain.enable_faucet(1_000.0, 60, 10.0);
+    assert!(chain.faucet(&to, 5.0).is_ok());
+    assert_eq!(chain.get_wallet_balance(&to), Some(5.0));
+
+    assert_eq!(
+        chain.faucet(&to, 20.0),
+        Err(BlockchainError::InvalidAmount { amount: 20.0 })
+    );
+    assert_eq!(
+        chain.faucet(&to, 5.0),
+        Err(BlockchainError::FaucetCooldown {
+            address: to.clone(),
+            available_at: 1_060,
+        })
+    );
+
+    clock.advance(60);
+    assert!(chain.faucet(&to, 5.0).is_ok());
+    assert_eq!(chain.get_wallet_balance(&to), Some(10.0));
+    assert!(chain.get_wallet_balance(&faucet).unwrap() < 1_000.0);
+}
+
+#[test]
+fn test_airdrop() {
+    let mut chain = setup();
+    let source = chain.create_wallet("s@mail.com");
+    let first = chain.create_wallet("a@mail.com");
+    let second = chain.create_wallet("b@mail.com");
+    chain.wallets.get_mut(&source).unwrap().balance = 1.0;
+    chain.schedule_upgrade(ProtocolFeature::FlatFee, 2).unwrap();
+
+    // Insufficient total funds leave every balance unchanged
+    assert!(matches!(
+        chain.airdrop(&source, &[(first.clone(), 6.0), (second.clone(), 6.0)]),
+        Err(BlockchainError::InsufficientFunds { .. })
+    ));
+    assert_eq!(
+        chain.airdrop(&source, &[(first.clone(), 1.0), (first.clone(), 1.0)]),
+        Err(BlockchainError::InvalidTransaction)
+    );
+    assert_eq!(chain.get_wallet_balance(&first), Some(0.0));
+
+    let hash = chain
+        .airdrop(&source, &[(first.clone(), 3.0), (second.clone(), 4.0)])
+        .unwrap();
+    let transaction = chain.get_transaction(&hash).unwrap();
+
+    assert_eq!(transaction.amount, 7.0);
+    assert_eq!(transaction.outputs.len(), 2);
+    assert!(transaction.involves(&second));
+    assert_eq!(chain.get_wallet_balance(&first), Some(3.0));
+    assert_eq!(chain.get_wallet_balance(&second), Some(4.0));
+    assert_eq!(
+        chain.get_wallet_balance(&source),
+        Some(1.0 - (chain.transfer_debit(3.0) + chain.transfer_debit(4.0)))
+    );
+    assert_eq!(
+        chain.get_wallet_transactions(&second, 0, 10).unwrap().len(),
+        1
+    );
+
+    // Each credit is debited with the flat fee once active
+    chain.generate_new_block();
+    chain.wallets.get_mut(&source).unwrap().balance = 10.0;
+    let hash = chain
+        .airdrop(&source, &[(first.clone(), 1.0), (second.clone(), 2.0)])
+        .unwrap();
+
+    assert_eq!(chain.get_transaction(&hash).unwrap().fee, 2.0 * chain.fee);
+    assert_eq!(
+        chain.get_wallet_balance(&source),
+        Some(10.0 - (chain.transfer_debit(1.0) + chain.transfer_debit(2.0)))
+    );
+    assert_eq!(chain.get_wallet_balance(&first), Some(4.0));
+}
+
+#[test]
+fn test_address_scheme() {
+    let mut chain = setup();
+    let random = chain.create_wallet("r@mail.com");
+
+    chain.set_address_scheme(VanityScheme::new("Shop").unwrap());
+    let vanity = chain.create_wallet("v@mail.com");
+
+    chain.set_address_scheme(PublicKeyScheme);
+    let derived = chain.create_wallet("d@mail.com");
+
+    assert!(vanity.as_str().starts_with("Shop"));
+    assert_eq!(chain.wallets[&derived].address, derived);
+    chain.wallets.get_mut(&vanity).unwrap().balance += 5.0;
+    assert!(chain.add_transaction(&vanity, &random, 1.0).is_ok());
+
+    let loaded: Chain = serde_json::from_str(&serde_json::to_string(&chain).unwrap()).unwrap();
+    assert!(loaded.wallets.contains_key(&vanity));
+}
+
+#[test]
+fn test_burn_native() {
+    let mut chain = setup();
+    let holder = chain.create_wallet("h@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&holder).unwrap().balance = 10.0;
+
+    let hash = chain.burn(&holder, NATIVE_ASSET, 4.0).unwrap();
+    let transaction = chain.get_transaction(&hash).unwrap();
+
+    assert_eq!(transaction.kind, TransactionKind::Burn);
+    assert!(transaction.to.is_burn());
+    assert_eq!(chain.get_wallet_balance(&holder), Some(6.0));
+    assert_eq!(chain.burned(NATIVE_ASSET), 4.0);
+    assert_eq!(chain.circulating_supply(), 6.0);
+
+    // Burned funds cannot be spent
+    assert_eq!(
+        chain.add_transaction(&Address::burn(), &to, 1.0),
+        Err(BlockchainError::Unauthorized)
+    );
+    assert_eq!(
+        chain.burn(&holder, NATIVE_ASSET, f64::NAN),
+        Err(BlockchainError::InvalidTransaction)
+    );
+    assert_eq!(chain.burned(NATIVE_ASSET), 4.0);
+    assert_eq!(
+        chain.burn(&holder, NATIVE_ASSET, 7.0),
+        Err(BlockchainError::InsufficientFunds {
+            address: holder,
+            required: 7.0,
+            available: 6.0,
+        })
+    );
+}
+
+#[test]
+fn test_emission_schedule() {
+    let mut chain = Chain::new(1.0, 100.0, 0.01);
+    let halving = EmissionSchedule {
+        curve: EmissionCurve::Halving { interval: 2 },
+        max_supply: Some(350.0),
+    };
+
+    assert_eq!(
+        chain.set_emission_schedule(EmissionSchedule {
+            curve: EmissionCurve::ExponentialDecay { rate: 1.5 },
+            max_supply: None,
+        }),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+    assert!(chain.set_emission_schedule(halving).is_ok());
+
+    // 100 + 100 + 50 + 50 + 25, capped at 350
+    assert_eq!(chain.projected_supply(3), 300.0);
+    assert_eq!(chain.projected_supply(4), 325.0);
+    assert_eq!(chain.projected_supply(100), 350.0);
+
+    for _ in 0..6 {
+        chain.generate_new_block();
+    }
+
+    let rewards: Vec<f64> = chain.chain[1..]
+        .iter()
+        .flat_map(|block| block.transactions.values())
+        .filter(|tx| tx.kind == TransactionKind::Reward)
+        .map(|tx| tx.amount)
+        .collect();
+    assert_eq!(rewards, vec![100.0, 50.0, 50.0, 25.0, 25.0, 0.0]);
+
+    assert_eq!(
+        chain.set_emission_schedule(halving),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+}
+
+#[test]
+fn test_treasury_fee_routing() {
+    let mut chain = Chain::new(1.0, 100.0, 0.5);
+    let treasury = chain.create_wallet("t@mail.com");
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    assert_eq!(
+        chain.set_treasury(&treasury, 1.5),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+    chain.set_treasury(&treasury, 0.25).unwrap();
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    chain.add_transaction(&from, &to, 2.0).unwrap();
+    chain.generate_new_block();
+
+    let block = &chain.chain[1];
+    assert_eq!(block.fees(), 1.0);
+    assert_eq!(block.treasury_fees(), 0.25);
+    assert_eq!(block.transactions.len(), 4);
+    assert_eq!(chain.get_wallet_balance(&treasury), Some(0.25));
+    assert!(chain.validate_chain().is_ok());
+
+    // A block without fees routes nothing
+    chain.generate_new_block();
+    assert_eq!(chain.chain[2].treasury_fees(), 0.0);
+    assert_eq!(
+        chain.set_treasury(&treasury, 0.5),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+}
+
+#[test]
+fn test_reserved_transfers() {
+    let mut chain = Chain::new(1.0, 100.0, 0.5);
+    let treasury = chain.create_wallet("t@mail.com");
+    let from = chain.create_wallet("s@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+    chain.set_treasury(&treasury, 0.25).unwrap();
+
+    let reserved = Err(BlockchainError::ReservedAddress {
+        address: treasury.to_owned(),
+    });
+    assert_eq!(chain.add_transaction(&from, &treasury, 1.0), reserved);
+    assert_eq!(chain.add_transaction(&treasury, &from, 1.0), reserved);
+
+    chain.address = from.to_owned();
+    let to = chain.create_wallet("r@mail.com");
+    assert_eq!(
+        chain.add_transaction(&from, &to, 1.0),
+        Err(BlockchainError::ReservedAddress {
+            address: from.to_owned(),
+        })
+    );
+
+    chain.set_reserved_transfers(true);
+    assert!(chain.add_transaction(&from, &treasury, 1.0).is_ok());
+}
+
+#[test]
+fn test_hash_version_activation() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    chain.set_chain_id("main").unwrap();
+    chain.activate_hash_version(HashVersion::V2, 2).unwrap();
+    assert_eq!(
+        chain.activate_hash_version(HashVersion::V2, 3),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+
+    let v1 = chain
+        .add_transactions(vec![TransactionRequest::new(&from, &to, 1.0)])
+        .remove(0)
+        .unwrap();
+    chain.generate_new_block();
+    let v2 = chain
+        .add_transactions(vec![TransactionRequest::new(&from, &to, 2.0)])
+        .remove(0)
+        .unwrap();
+    chain.generate_new_block();
+
+    assert_eq!(chain.transactions[&v1].hash_version, HashVersion::V1);
+    assert_eq!(chain.transactions[&v2].hash_version, HashVersion::V2);
+    assert_eq!(chain.transactions[&v2].chain_id, "main");
+    assert_eq!(chain.hash_version(1), HashVersion::V1);
+    assert!(chain.validate_chain().is_ok());
+    assert_eq!(
+        chain.activate_hash_version(HashVersion::V2, 1),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+    assert_eq!(
+        chain.set_chain_id("test"),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+
+    // A transaction of another chain is rejected
+    chain.consensus.chain_id = "test".to_string();
+    assert_eq!(
+        chain.validate_chain(),
+        Err(BlockchainError::InvalidTransaction)
+    );
+}
+
+#[test]
+fn test_protocol_upgrade() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    chain.schedule_upgrade(ProtocolFeature::FlatFee, 2).unwrap();
+    assert_eq!(
+        chain.schedule_upgrade(ProtocolFeature::FlatFee, 3),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+
+    // Transfers before the activation debit the amount times the fee
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    assert_eq!(chain.get_wallet_balance(&from), Some(99.0));
+    chain.generate_new_block();
+
+    // Transfers from the activation debit the amount plus the fee
+    assert!(chain.is_upgrade_active(ProtocolFeature::FlatFee, 2));
+    assert!(!chain.is_upgrade_active(ProtocolFeature::FlatFee, 1));
+    assert_eq!(chain.transfer_debit(10.0), 10.1);
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    assert_eq!(chain.get_wallet_balance(&from), Some(88.9));
+    assert_eq!(chain.sweep_amount(&from), Some(88.80000000000001));
+    chain.generate_new_block();
+
+    assert!(chain.validate_chain().is_ok());
+    assert_eq!(
+        chain.schedule_upgrade(ProtocolFeature::HashV2, 1),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+}
+
+#[test]
+fn test_settlement_report() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    chain.generate_new_block();
+
+    let report = chain
+        .settlement_report(SettlementPeriod::Blocks(1))
+        .unwrap();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[1].first_height, 1);
+    assert_eq!(report[1].transactions, 1);
+    assert_eq!(report[1].fees, 0.1);
+    assert_eq!(report[1].active_wallets(), 3);
+    assert!(report[1].net_flows[&from] < 0.0);
+
+    let daily = chain.settlement_report(SettlementPeriod::Daily).unwrap();
+    assert_eq!(daily.last().unwrap().last_height, 1);
+    assert_eq!(daily.iter().map(|day| day.transactions).sum::<usize>(), 1);
+}
+
+#[test]
+fn test_wallet_keys() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    let key = chain
+        .issue_wallet_key(&from, KeyScope::Send { limit: 15.0 })
+        .unwrap();
+    let reader = chain.issue_wallet_key(&from, KeyScope::ReadOnly).unwrap();
+    assert!(chain
+        .issue_wallet_key(&Address::random(), KeyScope::ReadOnly)
+        .is_err());
+
+    assert!(chain.send_with_key(&key, &to, 10.0).is_ok());
+    assert_eq!(chain.wallet_key(&key).unwrap().spent, 10.0);
+    assert_eq!(
+        chain.send_with_key(&key, &to, 10.0),
+        Err(BlockchainError::Unauthorized)
+    );
+    assert_eq!(
+        chain.send_with_key(&reader, &to, 1.0),
+        Err(BlockchainError::Unauthorized)
+    );
+
+    assert!(chain.revoke_wallet_key(&key));
+    assert_eq!(
+        chain.send_with_key(&key, &to, 1.0),
+        Err(BlockchainError::Unauthenticated)
+    );
+}
+
+#[test]
+fn test_dead_letters() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    assert!(chain.add_transaction(&from, &to, -1.0).is_err());
+    assert!(chain.dead_letters(&from).is_empty());
+
+    assert!(chain.enable_dead_letters(0).is_err());
+    chain.enable_dead_letters(2).unwrap();
+    let results = chain.add_transactions(vec![
+        TransactionRequest::new(&from, &to, -1.0),
+        TransactionRequest::new(&from, &to, 1.0),
+        TransactionRequest::new(&to, &from, 1000.0),
+        TransactionRequest::new(&from, &Address::random(), 1.0),
+    ]);
+    assert_eq!(results.iter().filter(|result| result.is_err()).count(), 3);
+
+    let letters = chain.dead_letters(&from);
+    assert_eq!(letters.len(), 2);
+    assert_eq!(letters[0].amount, 1000.0);
+    assert!(letters[0].reason.contains("Insufficient"));
+    assert_eq!(chain.dead_letters(&to).len(), 1);
+
+    chain.disable_dead_letters();
+    assert!(chain.dead_letters(&from).is_empty());
+}
+
+#[test]
+fn test_lazy_fork() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    let applied = Arc::new(AtomicUsize::new(0));
+    chain.register_hook(LimitHook {
+        limit: 50.0,
+        applied: applied.clone(),
+    });
+
+    let mut fork = chain.lazy_fork();
+    let results = fork.add_transactions(vec![
+        TransactionRequest::new(&from, &to, 10.0),
+        TransactionRequest::new(&to, &from, 4.0),
+    ]);
+    assert!(results.iter().all(Result::is_ok));
+    fork.generate_new_block();
+
+    let changes = fork.balance_changes();
+    assert_eq!(changes[&to], 10.0 - 4.0 * 0.1);
+    assert_eq!(fork.chain.len(), 2);
+
+    // The hooks of the chain do not see the transfers of the fork
+    assert_eq!(applied.load(Ordering::SeqCst), 0);
+
+    assert_eq!(chain.chain.len(), 1);
+    assert!(chain.pending.is_empty());
+    assert_eq!(chain.get_wallet_balance(&from), Some(100.0));
+}
+
+#[test]
+fn test_simulate_transaction() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+    chain.set_fee_per_byte(0.001).unwrap();
+
+    let simulation = chain.simulate_transaction(&from, &to, 10.0);
+    assert!(simulation.is_valid());
+    assert!(simulation.fee > 0.1);
+    assert_eq!(simulation.to_balance, Some(10.0));
+    assert_eq!(
+        simulation.from_balance,
+        Some(100.0 - 10.0 * 0.1 - (simulation.fee - 0.1))
+    );
+    assert!(chain.pending.is_empty());
+    assert_eq!(chain.get_wallet_balance(&to), Some(0.0));
+
+    let simulation = chain.simulate_transaction(&from, &to, 1e6);
+    assert!(matches!(
+        simulation.error,
+        Some(BlockchainError::InsufficientFunds { .. })
+    ));
+    assert_eq!(simulation.fee, 0.1);
+    assert_eq!(simulation.from_balance, Some(100.0));
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    assert_eq!(chain.get_wallet_balance(&to), Some(10.0));
+}
+
+#[test]
+fn test_simulate_transaction_hooks() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    let applied = Arc::new(AtomicUsize::new(0));
+    chain.register_hook(LimitHook {
+        limit: 50.0,
+        applied: applied.clone(),
+    });
+
+    assert!(chain.simulate_transaction(&from, &to, 10.0).is_valid());
+    assert_eq!(applied.load(Ordering::SeqCst), 0);
+    assert_eq!(
+        chain.simulate_transaction(&from, &to, 60.0).error,
+        Some(BlockchainError::Unauthorized)
+    );
+
+    chain.add_transaction(&from, &to, 10.0).unwrap();
+    assert_eq!(applied.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_block_beneficiary() {
+    let mut chain = setup();
+    let miner = chain.create_wallet("m@mail.com");
+    let other = chain.create_wallet("o@mail.com");
+    let reward_to = |block: &Block| {
+        block
+            .transactions
+            .values()
+            .find(|tx| tx.kind == TransactionKind::Reward)
+            .map(|tx| tx.to.to_owned())
+    };
+    assert_eq!(reward_to(&chain.chain[0]), Some(chain.address.to_owned()));
+
+    assert!(chain.generate_new_block_for(&other));
+    assert_eq!(reward_to(&chain.chain[1]), Some(other.to_owned()));
+    assert_eq!(chain.get_wallet_balance(&other), Some(100.0));
+
+    let history = chain.get_wallet_transactions(&other, 1, 10).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].kind, TransactionKind::Reward);
+    assert_eq!(history[0].amount, 100.0);
+
+    assert!(chain.set_beneficiary(Some(Address::root())).is_err());
+    chain.set_beneficiary(Some(miner.to_owned())).unwrap();
+    assert_eq!(chain.beneficiary(), &miner);
+    assert!(chain.generate_new_block());
+    assert_eq!(reward_to(&chain.chain[2]), Some(miner.to_owned()));
+    assert_eq!(chain.get_wallet_balance(&miner), Some(100.0));
+
+    assert!(!chain.generate_new_block_for(&Address::burn()));
+    assert_eq!(chain.chain.len(), 3);
+    assert!(chain.validate_chain().is_ok());
+}
+
+#[test]
+fn test_chain_registry() {
+    let directory = env::temp_dir().join(format!("registry-{}", uuid::Uuid::new_v4()));
+    let mut registry = ChainRegistry::with_storage(&directory);
+
+    let mainnet = registry.create("mainnet", setup()).unwrap();
+    let tenant = registry.create("tenant-1", setup()).unwrap();
+    mainnet.lock().unwrap().generate_new_block();
+    assert_eq!(tenant.lock().unwrap().chain.len(), 1);
+
+    let hash = mainnet.lock().unwrap().get_last_hash();
+    registry.unload("mainnet").unwrap();
+    assert_eq!(registry.names(), vec!["tenant-1"]);
+    assert!(matches!(
+        registry.get("mainnet"),
+        Err(BlockchainError::ChainNotFound { .. })
+    ));
+
+    let mainnet = registry.load("mainnet").unwrap();
+    assert_eq!(mainnet.lock().unwrap().get_last_hash(), hash);
+    assert!(matches!(
+        registry.load("mainnet"),
+        Err(BlockchainError::ChainExists { .. })
+    ));
+
+    registry.save_all().unwrap();
+    registry.delete("mainnet").unwrap();
+    assert_eq!(
+        registry.load("mainnet").err(),
+        Some(BlockchainError::StorageError)
+    );
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_bridge() {
+    let mut registry = ChainRegistry::new();
+    let source = registry.create("mainnet", setup()).unwrap();
+    let destination = registry.create("sidechain", setup()).unwrap();
+    let impostor = source.lock().unwrap().create_wallet("bridge@sidechain");
+    let bridge = Bridge::new(&registry, "mainnet", "sidechain", "WMAIN").unwrap();
+    assert!(Bridge::new(&registry, "mainnet", "mainnet", "WSELF").is_err());
+    assert_ne!(bridge.escrow(), &impostor);
+    assert_eq!(
+        Bridge::new(&registry, "mainnet", "sidechain", "WMAIN")
+            .unwrap()
+            .escrow(),
+        bridge.escrow()
+    );
+
+    let alice = source.lock().unwrap().create_wallet("a@mail.com");
+    let bob = destination.lock().unwrap().create_wallet("b@mail.com");
+    source
+        .lock()
+        .unwrap()
+        .wallets
+        .get_mut(&alice)
+        .unwrap()
+        .balance = 100.0;
+
+    // Lock on the source chain and mint on the destination chain once mined
+    let lock = bridge.lock(&alice, 40.0, &bob).unwrap();
+    assert!(bridge.prove("mainnet", &lock).is_err());
+    source.lock().unwrap().generate_new_block();
+    let proof = bridge.prove("mainnet", &lock).unwrap();
+
+    bridge.mint(&proof).unwrap();
+    assert_eq!(
+        destination
+            .lock()
+            .unwrap()
+            .get_asset_balance(&bob, bridge.asset()),
+        Some(40.0)
+    );
+    assert_eq!(
+        source.lock().unwrap().get_wallet_balance(bridge.escrow()),
+        Some(40.0)
+    );
+    assert!(matches!(
+        bridge.mint(&proof),
+        Err(BlockchainError::ProofAlreadyClaimed { .. })
+    ));
+    assert_eq!(
+        bridge.release(&proof),
+        Err(BlockchainError::InvalidBridgeProof)
+    );
+
+    let mut forged = proof.clone();
+    forged.transaction.amount = 1000.0;
+    forged.transaction.hash = forged.transaction.compute_hash();
+    assert_eq!(
+        bridge.mint(&forged),
+        Err(BlockchainError::InvalidBridgeProof)
+    );
+
+    // Burn on the destination chain and release on the source chain
+    let burn = bridge.burn(&bob, 15.0, &alice).unwrap();
+    destination.lock().unwrap().generate_new_block();
+    let proof = bridge.prove("sidechain", &burn).unwrap();
+
+    bridge.release(&proof).unwrap();
+    assert_eq!(
+        source.lock().unwrap().get_wallet_balance(&alice),
+        Some(100.0 - 40.0 - 0.1 + 15.0)
+    );
+    assert_eq!(
+        destination
+            .lock()
+            .unwrap()
+            .get_token(bridge.asset())
+            .unwrap()
+            .supply,
+        25.0
+    );
+    assert!(bridge.release(&proof).is_err());
+}
+
+#[test]
+fn test_bridge_claims_survive_restart() {
+    let directory = env::temp_dir().join(format!("bridge-{}", uuid::Uuid::new_v4()));
+    let mut registry = ChainRegistry::with_storage(&directory);
+    let source = registry.create("mainnet", setup()).unwrap();
+    let destination = registry.create("sidechain", setup()).unwrap();
+    let bridge = Bridge::new(&registry, "mainnet", "sidechain", "WMAIN").unwrap();
+
+    let alice = source.lock().unwrap().create_wallet("a@mail.com");
+    let bob = destination.lock().unwrap().create_wallet("b@mail.com");
+    source
+        .lock()
+        .unwrap()
+        .wallets
+        .get_mut(&alice)
+        .unwrap()
+        .balance = 100.0;
+
+    let lock = bridge.lock(&alice, 40.0, &bob).unwrap();
+    source.lock().unwrap().generate_new_block();
+    let lock = bridge.prove("mainnet", &lock).unwrap();
+    bridge.mint(&lock).unwrap();
+
+    let burn = bridge.burn(&bob, 15.0, &alice).unwrap();
+    destination.lock().unwrap().generate_new_block();
+    let burn = bridge.prove("sidechain", &burn).unwrap();
+    bridge.release(&burn).unwrap();
+
+    // Reopen the bridge over the reloaded chains and replay both proofs
+    registry.save_all().unwrap();
+    let mut registry = ChainRegistry::with_storage(&directory);
+    let source = registry.load("mainnet").unwrap();
+    registry.load("sidechain").unwrap();
+    let reopened = Bridge::new(&registry, "mainnet", "sidechain", "WMAIN").unwrap();
+
+    assert_eq!(reopened.escrow(), bridge.escrow());
+    assert!(matches!(
+        reopened.mint(&lock),
+        Err(BlockchainError::ProofAlreadyClaimed { .. })
+    ));
+    assert!(matches!(
+        reopened.release(&burn),
+        Err(BlockchainError::ProofAlreadyClaimed { .. })
+    ));
+    assert_eq!(
+        source.lock().unwrap().get_wallet_balance(reopened.escrow()),
+        Some(25.0)
+    );
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_fee_burn() {
+    let mut chain = Chain::new(1.0, 100.0, 0.5);
+    let treasury = chain.create_wallet("t@mail.com");
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+
+    chain.set_treasury(&treasury, 0.25).unwrap();
+    assert_eq!(
+        chain.set_fee_burn(0.8),
+        Err(BlockchainError::InvalidConfiguration)
+    );
+    chain.set_fee_burn(0.5).unwrap();
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    chain.add_transaction(&from, &to, 2.0).unwrap();
+    let supply = chain.circulating_supply();
+    chain.generate_new_block();
+
+    let block = &chain.chain[1];
+    assert_eq!(block.fees(), 1.0);
+    assert_eq!(block.burned_fees(), 0.5);
+    assert_eq!(block.miner_fees(), 0.25);
+    assert_eq!(block.transactions.len(), 5);
+    assert_eq!(chain.burned(NATIVE_ASSET), 0.5);
+    assert_eq!(chain.circulating_supply(), supply + 0.25);
+    assert!(chain.validate_chain().is_ok());
+
+    // The burned share stays out of the pending transactions after a rollback
+    chain.truncate(1);
+    assert!(chain
+        .pending
+        .iter()
+        .all(|queued| { chain.transactions[&queued.hash].kind == TransactionKind::Transfer }));
+    assert_eq!(chain.pending.len(), 2);
+}
+
+#[test]
+fn test_block_bloom_filters() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    let idle = chain.create_wallet("i@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    chain.generate_new_block();
+    chain.generate_new_block();
+
+    assert!(chain.chain[1].may_involve(&to));
+    assert!(!chain.chain[1].may_involve(&idle));
+    assert!(!chain.chain[2].may_involve(&to));
+    assert_eq!(chain.find_address_transactions(&to).len(), 1);
+    assert!(chain.find_address_transactions(&idle).is_empty());
+    assert!(chain.validate_chain().is_ok());
+
+    let mut client = LightClient::new(1.0);
+    client.sync(&chain).unwrap();
+    assert_eq!(client.blocks_involving(&from), vec![1]);
+
+    // A body that does not match the filter is rejected
+    chain.chain[1].header.bloom = Bloom::default();
+    chain.chain[1].header.bloom.insert(&idle);
+    chain.chain[1].mine();
+    assert_eq!(
+        chain.chain[1].validate(),
+        Err(BlockchainError::InvalidBlockBody)
+    );
+}
+
+#[test]
+fn test_block_archive() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain.add_transaction(&from, &to, 5.0).unwrap();
+    chain.generate_new_block();
+    chain.generate_new_block();
+
+    let archive = chain.export_archive(Vec::new()).unwrap();
+    let mut copy = chain.fork_at(0).unwrap();
+
+    assert_eq!(&archive[..4], b"BCRA");
+    assert_eq!(copy.import_archive(Cursor::new(&archive)), Ok(2));
+    assert_eq!(copy.get_last_hash(), chain.get_last_hash());
+    assert_eq!(copy.import_archive(Cursor::new(&archive)), Ok(0));
+
+    let mut corrupted = archive.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        chain
+            .fork_at(1)
+            .unwrap()
+            .import_archive(Cursor::new(&corrupted)),
+        Err(BlockchainError::CorruptArchive { .. })
+    ));
+    assert_eq!(
+        setup().import_archive(Cursor::new(&archive)),
+        Err(BlockchainError::InvalidHeader)
+    );
+}
+
+#[test]
+fn test_replay() {
+    let mut chain = setup();
+    let (alice, bob) = (Address::random(), Address::random());
+    let stream = format!(
+        "from,to,amount\nRoot,{alice},100\n{alice},{bob},10\n{bob},{alice},500\nnot,a,record\n"
+    );
+
+    let report = chain
+        .replay(Cursor::new(stream), ReplayFormat::Csv)
+        .unwrap();
+
+    assert_eq!(report.applied, 2);
+    assert_eq!(report.wallets_created, vec![alice.clone(), bob.clone()]);
+    assert!(!report.is_complete());
+    assert_eq!(report.failures[1].line, 5);
+    assert_eq!(report.failures[1].error, BlockchainError::InvalidAddress);
+    assert!(matches!(
+        report.failures[0].error,
+        BlockchainError::InsufficientFunds { .. }
+    ));
+    assert_eq!(chain.get_wallet_balance(&bob), Some(10.0));
+
+    let stream = format!("{{\"from\":\"{bob}\",\"to\":\"{alice}\",\"amount\":1.0}}\n");
+    let report = chain
+        .replay(Cursor::new(stream), ReplayFormat::Jsonl)
+        .unwrap();
+    assert!(report.is_complete());
+    assert_eq!(chain.get_wallet_balance(&bob), Some(10.0 - chain.fee));
+}
+
+#[test]
+fn test_fork_and_reorganize() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain = Chain::with_environment(1.0, 10.0, 0.01, Environment::seeded(7, clock.clone()));
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    chain.generate_new_block();
+    let mut fork = chain.fork_at(1).unwrap();
+    assert!(chain.fork_at(2).is_err());
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    clock.advance(1);
+    chain.generate_new_block();
+
+    for _ in 0..2 {
+        clock.advance(1);
+        fork.generate_new_block();
+    }
+    assert_ne!(fork.get_last_hash(), chain.get_last_hash());
+
+    // A longer branch of blocks without work is rejected
+    let mut cheap = chain.fork_at(1).unwrap();
+    cheap.update_difficulty(0.0);
+    for _ in 0..3 {
+        clock.advance(1);
+        cheap.generate_new_block();
+    }
+    assert_eq!(
+        chain.reorganize(cheap.chain[2..].to_vec()).err(),
+        Some(BlockchainError::InvalidHeader)
+    );
+
+    // A branch without more work is rejected
+    assert!(matches!(
+        chain.reorganize(fork.chain[2..3].to_vec()),
+        Err(BlockchainError::InvalidHeader)
+    ));
+
+    let removed = chain.reorganize(fork.chain[2..].to_vec()).unwrap();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(chain.get_last_hash(), fork.get_last_hash());
+    assert_eq!(chain.pending.len(), 1);
+    assert!(chain.validate_chain().is_ok());
+}
+
+#[test]
+fn test_reorganize_balances() {
+    let clock = Arc::new(ManualClock::new(1_000));
+    let mut chain = Chain::with_environment(1.0, 10.0, 0.01, Environment::seeded(7, clock.clone()));
+    let miner = chain.create_wallet("m@mail.com");
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    chain.generate_new_block_for(&miner);
+    let mut fork = chain.fork_at(1).unwrap();
+    assert_eq!(chain.get_wallet_balance(&miner), Some(10.0));
+
+    chain.add_transaction(&from, &to, 1.0).unwrap();
+    clock.advance(1);
+    chain.generate_new_block_for(&miner);
+    assert_eq!(chain.get_wallet_balance(&miner), Some(20.0));
+
+    for _ in 0..2 {
+        clock.advance(1);
+        fork.generate_new_block();
+    }
+
+    // An invalid branch leaves the chain and its audit log unchanged
+    let audited = chain.audit_log().len();
+    let mut invalid = fork.chain[2..].to_vec();
+    invalid[1].transactions.clear();
+    assert_eq!(
+        chain.reorganize(invalid).err(),
+        Some(BlockchainError::InvalidBlockBody)
+    );
+    assert_eq!(chain.chain.len(), 3);
+    assert_eq!(chain.get_wallet_balance(&miner), Some(20.0));
+    assert_eq!(chain.audit_log().len(), audited);
+
+    // The reward of the removed block is reverted and the transfer is pending again
+    chain.reorganize(fork.chain[2..].to_vec()).unwrap();
+    assert_eq!(chain.get_wallet_balance(&miner), Some(10.0));
+    assert_eq!(chain.get_wallet_balance(&to), Some(1.0));
+    assert_eq!(chain.pending.len(), 1);
+    assert_eq!(chain.audit_log().len(), audited + 1);
+
+    // Mining the transfer again credits a single reward and leaves the transfer as it was
+    clock.advance(1);
+    chain.generate_new_block_for(&miner);
+    assert_eq!(chain.get_wallet_balance(&miner), Some(20.0));
+    assert_eq!(chain.get_wallet_balance(&to), Some(1.0));
+    assert!(chain.pending.is_empty());
+    assert!(chain.validate_chain().is_ok());
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "contracts")]
+
+mod common;
+
+use blockchain::{
+    Address, BlockchainError, Contract, Hash, TransactionKind, DEFAULT_GAS_LIMIT, NATIVE_ASSET,
+};
+
+use crate::common::setup;
+
+/// Build a WebAssembly module importing the contract store and exporting:
+///
+/// - `increment(key: i64) -> i64`: increment the value stored under `key` and return it.
+/// - `spin()`: loop forever.
+fn module() -> Vec<u8> {
+    let sections: [&[u8]; 7] = [
+        // Magic number and version
+        b"\0asm\x01\0\0\0",
+        // Types: (i64) -> i64, (i64, i64) -> (), () -> ()
+        b"\x01\x0e\x03\x60\x01\x7e\x01\x7e\x60\x02\x7e\x7e\x00\x60\x00\x00",
+        // Imports: env.storage_get and env.storage_set
+        b"\x02\x25\x02\x03env\x0bstorage_get\x00\x00\x03env\x0bstorage_set\x00\x01",
+        // Functions
+        b"\x03\x03\x02\x00\x02",
+        // Exports
+        b"\x07\x14\x02\x09increment\x00\x02\x04spin\x00\x03",
+        // Code
+        b"\x0a\x1b\x02\x11\x00\x20\x00\x20\x00\x10\x00\x42\x01\x7c\x10\x01\x20\x00\x10\x00\x0b",
+        b"\x07\x00\x03\x40\x0c\x00\x0b\x0b",
+    ];
+
+    sections.concat()
+}
+
+#[test]
+fn test_deploy_contract() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    let address = chain.deploy_contract(&owner, module()).unwrap();
+    let contract = chain.get_contract(&address).unwrap();
+
+    assert_eq!(contract.owner, owner);
+    assert!(contract.storage.is_empty());
+    assert_eq!(chain.get_wallet_balance(&owner), Some(0.9));
+
+    let hash = chain.wallets[&owner].transaction_hashes[0];
+    let transaction = chain.get_transaction(&hash).unwrap();
+
+    assert_eq!(transaction.kind, TransactionKind::ContractDeploy);
+    assert_eq!(transaction.asset, NATIVE_ASSET);
+    assert_eq!(transaction.data, Hash::digest(&module()).as_bytes());
+    assert_eq!(address, Contract::derive_address(&owner, &hash));
+}
+
+#[test]
+fn test_deploy_invalid_contract() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    let result = chain.deploy_contract(&owner, b"not wasm".to_vec());
+
+    assert_eq!(result.err(), Some(BlockchainError::InvalidContract));
+    assert!(chain.contracts.is_empty());
+}
+
+#[test]
+fn test_call_contract() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    let address = chain.deploy_contract(&owner, module()).unwrap();
+
+    let first = chain
+        .call_contract(&owner, &address, "increment", &[7], DEFAULT_GAS_LIMIT)
+        .unwrap();
+    let second = chain
+        .call_contract(&owner, &address, "increment", &[7], DEFAULT_GAS_LIMIT)
+        .unwrap();
+
+    assert_eq!(first.result, Some(1));
+    assert_eq!(second.result, Some(2));
+    assert!(first.gas_used > 0);
+
+    let transaction = chain.get_transaction(&first.hash).unwrap();
+    assert_eq!(transaction.kind, TransactionKind::ContractCall);
+    assert_eq!(transaction.asset, NATIVE_ASSET);
+    assert_eq!(transaction.data, b"increment");
+    assert_eq!(
+        chain.get_contract(&address).unwrap().storage.get(&7),
+        Some(&2)
+    );
+}
+
+#[test]
+fn test_call_contract_out_of_gas() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    let address = chain.deploy_contract(&owner, module()).unwrap();
+    let balance = chain.get_wallet_balance(&owner);
+
+    let result = chain.call_contract(&owner, &address, "spin", &[], 1_000);
+
+    assert_eq!(result, Err(BlockchainError::OutOfGas));
+    assert_eq!(chain.get_wallet_balance(&owner), balance);
+}
+
+#[test]
+fn test_call_contract_failure_keeps_storage() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    let address = chain.deploy_contract(&owner, module()).unwrap();
+
+    let unknown = chain.call_contract(&owner, &address, "missing", &[], DEFAULT_GAS_LIMIT);
+    let low_gas = chain.call_contract(&owner, &address, "increment", &[1], 1);
+
+    assert_eq!(unknown, Err(BlockchainError::ContractFailed));
+    assert_eq!(low_gas, Err(BlockchainError::OutOfGas));
+    assert!(chain.get_contract(&address).unwrap().storage.is_empty());
+}
+
+#[test]
+fn test_call_unknown_contract() {
+    let mut chain = setup();
+    let caller = chain.create_wallet("c@mail.com");
+
+    let result = chain.call_contract(&caller, &Address::random(), "increment", &[1], 10);
+
+    assert_eq!(result, Err(BlockchainError::ContractNotFound));
+}
+
+#[test]
+fn test_contract_root_committed_in_header() {
+    let mut chain = setup();
+    let owner = chain.create_wallet("o@mail.com");
+    chain.wallets.get_mut(&owner).unwrap().balance += 1.0;
+
+    assert!(chain.chain[0].header.contract_root.is_zero());
+
+    let address = chain.deploy_contract(&owner, module()).unwrap();
+    chain
+        .call_contract(&owner, &address, "increment", &[1], DEFAULT_GAS_LIMIT)
+        .unwrap();
+    chain.generate_new_block();
+
+    let committed = chain.chain[1].header.contract_root;
+    assert_eq!(committed, chain.contract_root());
+
+    chain
+        .call_contract(&owner, &address, "increment", &[1], DEFAULT_GAS_LIMIT)
+        .unwrap();
+    assert_ne!(committed, chain.contract_root());
+}
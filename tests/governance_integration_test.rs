@@ -0,0 +1,123 @@
+mod common;
+
+use blockchain::{Address, BlockchainError, ProposalChange, ProposalStatus};
+use uuid::Uuid;
+
+use crate::common::setup;
+
+#[test]
+fn test_proposal_passes_at_activation_height() {
+    let mut chain = setup();
+    let proposer = chain.create_wallet("p@mail.com");
+    let voter = chain.create_wallet("v@mail.com");
+    chain.wallets.get_mut(&proposer).unwrap().balance = 10.0;
+    chain.wallets.get_mut(&voter).unwrap().balance = 5.0;
+
+    let id = chain
+        .submit_proposal(&proposer, ProposalChange::Fee(0.5), 2)
+        .unwrap();
+    chain.vote(&proposer, &id, true).unwrap();
+    chain.vote(&voter, &id, false).unwrap();
+
+    // Not yet due at height 1
+    chain.generate_new_block();
+    assert_eq!(chain.fee, 0.1);
+    assert_eq!(
+        chain.get_proposal(&id).unwrap().status,
+        ProposalStatus::Open
+    );
+
+    chain.generate_new_block();
+    assert_eq!(chain.fee, 0.5);
+    assert_eq!(
+        chain.get_proposal(&id).unwrap().status,
+        ProposalStatus::Passed
+    );
+}
+
+#[test]
+fn test_proposal_rejected_by_weighted_votes() {
+    let mut chain = setup();
+    let proposer = chain.create_wallet("p@mail.com");
+    let whale = chain.create_wallet("w@mail.com");
+    chain.wallets.get_mut(&proposer).unwrap().balance = 1.0;
+    chain.wallets.get_mut(&whale).unwrap().balance = 100.0;
+
+    let id = chain
+        .submit_proposal(&proposer, ProposalChange::Reward(500.0), 1)
+        .unwrap();
+    chain.vote(&proposer, &id, true).unwrap();
+    chain.vote(&whale, &id, false).unwrap();
+
+    chain.generate_new_block();
+
+    assert_eq!(chain.reward, 100.0);
+    assert_eq!(
+        chain.get_proposal(&id).unwrap().status,
+        ProposalStatus::Rejected
+    );
+    assert_eq!(
+        chain.vote(&whale, &id, true),
+        Err(BlockchainError::ProposalClosed)
+    );
+}
+
+#[test]
+fn test_proposal_without_quorum_is_rejected() {
+    let mut chain = setup();
+    let proposer = chain.create_wallet("p@mail.com");
+    let absent = chain.create_wallet("a@mail.com");
+    chain.wallets.get_mut(&proposer).unwrap().balance = 1.0;
+    chain.wallets.get_mut(&absent).unwrap().balance = 10.0;
+
+    let id = chain
+        .submit_proposal(&proposer, ProposalChange::Difficulty(2.0), 1)
+        .unwrap();
+    chain.vote(&proposer, &id, true).unwrap();
+
+    chain.generate_new_block();
+
+    assert_eq!(chain.difficulty, 1.0);
+    assert_eq!(
+        chain.get_proposal(&id).unwrap().status,
+        ProposalStatus::Rejected
+    );
+}
+
+#[test]
+fn test_submit_invalid_proposal() {
+    let mut chain = setup();
+    let proposer = chain.create_wallet("p@mail.com");
+    let empty = chain.create_wallet("e@mail.com");
+    let unknown = Address::random();
+    chain.wallets.get_mut(&proposer).unwrap().balance = 1.0;
+
+    assert_eq!(
+        chain.submit_proposal(&proposer, ProposalChange::Fee(-1.0), 5),
+        Err(BlockchainError::InvalidProposal)
+    );
+    assert_eq!(
+        chain.submit_proposal(&proposer, ProposalChange::Fee(0.2), 0),
+        Err(BlockchainError::InvalidProposal)
+    );
+    assert_eq!(
+        chain.submit_proposal(&empty, ProposalChange::Fee(0.2), 5),
+        Err(BlockchainError::Unauthorized)
+    );
+    assert_eq!(
+        chain.submit_proposal(&unknown, ProposalChange::Fee(0.2), 5),
+        Err(BlockchainError::WalletNotFound { address: unknown })
+    );
+}
+
+#[test]
+fn test_vote_unknown_proposal() {
+    let mut chain = setup();
+    let voter = chain.create_wallet("v@mail.com");
+    chain.wallets.get_mut(&voter).unwrap().balance = 1.0;
+
+    assert_eq!(
+        chain.vote(&voter, &Uuid::new_v4(), true),
+        Err(BlockchainError::ProposalNotFound)
+    );
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "graphql")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use blockchain::graphql;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use crate::common::setup;
+
+#[tokio::test]
+async fn test_blocks_with_transactions() {
+    let chain = Arc::new(Mutex::new(setup()));
+    chain.lock().unwrap().generate_new_block();
+    let schema = graphql::schema(chain.clone());
+
+    let response = schema
+        .execute("{ height blocks(limit: 1) { height hash transactions { kind amount } } }")
+        .await;
+    assert!(response.errors.is_empty());
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["height"], 2);
+    assert_eq!(data["blocks"][0]["height"], 1);
+    assert_eq!(
+        data["blocks"][0]["hash"],
+        chain.lock().unwrap().get_last_hash().to_string()
+    );
+    assert_eq!(data["blocks"][0]["transactions"][0]["kind"], "Reward");
+    assert_eq!(data["blocks"][0]["transactions"][0]["amount"], 100.0);
+}
+
+#[tokio::test]
+async fn test_wallet_with_nested_transactions() {
+    let chain = Arc::new(Mutex::new(setup()));
+    let (from, to) = {
+        let mut chain = chain.lock().unwrap();
+        let from = chain.create_wallet("a@mail.com");
+        let to = chain.create_wallet("b@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance += 10.0;
+        chain.add_transaction(&from, &to, 10.0).unwrap();
+
+        (from, to)
+    };
+    let schema = graphql::schema(chain);
+
+    let query = format!(
+        r#"{{ wallet(address: "{}") {{ email transactions {{ amount receiver {{ email balance }} }} }} }}"#,
+        from
+    );
+    let data = schema.execute(query).await.data.into_json().unwrap();
+
+    assert_eq!(data["wallet"]["email"], "a@mail.com");
+    assert_eq!(
+        data["wallet"]["transactions"][0]["receiver"]["email"],
+        "b@mail.com"
+    );
+    assert_eq!(
+        data["wallet"]["transactions"][0]["receiver"]["balance"],
+        json!(10.0)
+    );
+
+    let query = format!(r#"{{ wallet(address: "{}") {{ balance }} }}"#, to);
+    let data = schema.execute(query).await.data.into_json().unwrap();
+    assert_eq!(data["wallet"]["balance"], json!(10.0));
+}
+
+#[tokio::test]
+async fn test_missing_and_invalid() {
+    let schema = graphql::schema(Arc::new(Mutex::new(setup())));
+
+    let data = schema
+        .execute("{ block(height: 5) { hash } }")
+        .await
+        .data
+        .into_json()
+        .unwrap();
+    assert_eq!(data["block"], Value::Null);
+
+    let response = schema
+        .execute(r#"{ transaction(hash: "invalid") { hash } }"#)
+        .await;
+    assert_eq!(response.errors.len(), 1);
+}
+
+#[tokio::test]
+async fn test_router() {
+    let app = graphql::router(Arc::new(Mutex::new(setup())));
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/graphql")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "query": "{ height }" }).to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["data"]["height"], 1);
+}
@@ -0,0 +1,176 @@
+#![cfg(feature = "grpc")]
+
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use blockchain::{
+    grpc::proto::{self, blockchain_client::BlockchainClient},
+    Chain, GrpcService,
+};
+use tokio::{net::TcpListener, time::timeout};
+use tokio_stream::{wrappers::TcpListenerStream, StreamExt};
+use tonic::{
+    transport::{Channel, Server},
+    Code,
+};
+
+use crate::common::setup;
+
+/// Start a gRPC server on a random local port and connect a client to it.
+///
+/// # Returns
+///
+/// The shared chain, the service, and the connected client.
+async fn start() -> (Arc<Mutex<Chain>>, GrpcService, BlockchainClient<Channel>) {
+    let chain = Arc::new(Mutex::new(setup()));
+    let service = GrpcService::new(chain.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    tokio::spawn(
+        Server::builder()
+            .add_service(service.clone().into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let client = BlockchainClient::connect(format!("http://{}", address))
+        .await
+        .unwrap();
+
+    (chain, service, client)
+}
+
+#[tokio::test]
+async fn test_get_block() {
+    let (chain, _, mut client) = start().await;
+
+    let block = client
+        .get_block(proto::GetBlockRequest { height: 0 })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        block.hash,
+        chain.lock().unwrap().get_last_hash().to_string()
+    );
+
+    let status = client
+        .get_block(proto::GetBlockRequest { height: 5 })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn test_send_transaction_and_get_balance() {
+    let (chain, _, mut client) = start().await;
+
+    let from = client
+        .create_wallet(proto::CreateWalletRequest {
+            email: "a@mail.com".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .address;
+    let to = client
+        .create_wallet(proto::CreateWalletRequest {
+            email: "b@mail.com".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .address;
+
+    let request = proto::SendTransactionRequest {
+        from: from.clone(),
+        to: to.clone(),
+        amount: 10.0,
+    };
+
+    let status = client.send_transaction(request.clone()).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    chain
+        .lock()
+        .unwrap()
+        .wallets
+        .get_mut(&from.parse().unwrap())
+        .unwrap()
+        .balance = 10.0;
+
+    let hash = client
+        .send_transaction(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .hash;
+    let transaction = client
+        .get_transaction(proto::GetTransactionRequest { hash: hash.clone() })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(transaction.hash, hash);
+    assert_eq!(transaction.kind, "Transfer");
+
+    let balance = client
+        .get_balance(proto::GetBalanceRequest { address: to })
+        .await
+        .unwrap()
+        .into_inner()
+        .balance;
+    assert_eq!(balance, 10.0);
+}
+
+#[tokio::test]
+async fn test_subscribe_blocks() {
+    let (chain, service, mut client) = start().await;
+
+    let mut blocks = client
+        .subscribe_blocks(proto::SubscribeBlocksRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mined = client
+        .generate_block(proto::GenerateBlockRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+
+    let streamed = timeout(Duration::from_secs(5), blocks.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(streamed, mined);
+    assert_eq!(streamed.height, 1);
+
+    // Blocks added outside the service are streamed once published
+    let block = {
+        let mut chain = chain.lock().unwrap();
+        chain.generate_new_block();
+        chain.chain.last().unwrap().clone()
+    };
+    service.publish_block(&block);
+
+    let streamed = timeout(Duration::from_secs(5), blocks.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(streamed.height, 2);
+
+    let count = client
+        .get_block_count(proto::GetBlockCountRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .count;
+    assert_eq!(count, 3);
+}
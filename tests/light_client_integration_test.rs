@@ -0,0 +1,89 @@
+mod common;
+
+use blockchain::{BlockchainError, Hash, LightClient};
+
+use crate::common::setup;
+
+#[test]
+fn test_sync() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    let mut client = LightClient::new(1.0);
+    let added = client.sync(&chain).unwrap();
+
+    assert_eq!(added, 2);
+    assert_eq!(client.get_last_hash().unwrap(), chain.get_last_hash());
+    assert_eq!(client.sync(&chain).unwrap(), 0);
+}
+
+#[test]
+fn test_add_header_invalid_link() {
+    let mut chain = setup();
+    chain.generate_new_block();
+
+    let mut client = LightClient::new(1.0);
+    client.add_header(chain.chain[0].header.clone()).unwrap();
+
+    let mut header = chain.chain[1].header.clone();
+    header.previous_hash = Hash::zero();
+
+    assert_eq!(
+        client.add_header(header),
+        Err(BlockchainError::InvalidHeader)
+    );
+}
+
+#[test]
+fn test_add_header_invalid_difficulty() {
+    let chain = setup();
+
+    let mut header = chain.chain[0].header.clone();
+    header.difficulty = 0.0;
+
+    assert_eq!(
+        LightClient::new(1.0).add_header(header),
+        Err(BlockchainError::InvalidHeader)
+    );
+    assert_eq!(
+        LightClient::new(2.0).add_header(chain.chain[0].header.clone()),
+        Err(BlockchainError::InvalidHeader)
+    );
+}
+
+#[test]
+fn test_verify_transaction() {
+    let mut chain = setup();
+    chain.update_reward(50.0);
+    chain.generate_new_block();
+
+    let mut client = LightClient::new(1.0);
+    client.sync(&chain).unwrap();
+
+    let transaction = chain.chain[1].transactions.values().next().unwrap();
+    let (height, proof) = chain.get_merkle_proof(&transaction.hash).unwrap();
+
+    assert_eq!(height, 1);
+    assert!(client.verify_transaction(height, transaction, &proof));
+    assert!(!client.verify_transaction(0, transaction, &proof));
+    assert!(!client.verify_transaction(5, transaction, &proof));
+}
+
+#[test]
+fn test_verify_balance() {
+    let mut chain = setup();
+    let address = chain.create_wallet("s@mail.com");
+    chain.generate_new_block();
+
+    let mut client = LightClient::new(1.0);
+    client.sync(&chain).unwrap();
+
+    let proof = chain.prove_balance(&address, 1).unwrap();
+
+    assert!(client.verify_balance(&proof));
+
+    let mut forged = proof.clone();
+    forged.account.balance = 1000.0;
+
+    assert!(!client.verify_balance(&forged));
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "napi")]
+
+use blockchain::napi::NapiChain;
+
+#[test]
+fn test_napi_chain() {
+    let chain = NapiChain::new(1.0, 100.0, 0.1);
+    let from = chain.create_wallet("a@mail.com".to_string());
+    let to = chain.create_wallet("b@mail.com".to_string());
+
+    assert_eq!(chain.height(), 1);
+    assert_eq!(chain.get_wallet(from).unwrap().email, "a@mail.com");
+    assert_eq!(chain.get_transactions(1, 10).len(), 0);
+
+    let hash = chain.generate_new_block();
+    assert_eq!(chain.height(), 2);
+    assert_eq!(chain.get_last_hash(), hash);
+    assert!(chain.validate_chain().is_ok());
+
+    let restored = NapiChain::from_json(chain.to_json().unwrap()).unwrap();
+    assert_eq!(restored.get_last_hash(), hash);
+    assert_eq!(restored.get_wallet(to).unwrap().balance, 0.0);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use blockchain::{Address, BlockchainError, NftMetadata, TransactionKind};
+use uuid::Uuid;
+
+use crate::common::setup;
+
+fn metadata() -> NftMetadata {
+    NftMetadata::from([("name".to_string(), "Art".to_string())])
+}
+
+#[test]
+fn test_mint_nft() {
+    let mut chain = setup();
+    let creator = chain.create_wallet("c@mail.com");
+
+    let id = chain.mint_nft(&creator, metadata()).unwrap();
+    let nft = chain.get_nft(&id).unwrap();
+
+    assert_eq!(nft.owner, creator);
+    assert_eq!(nft.metadata, metadata());
+    assert_eq!(chain.get_nfts(&creator).len(), 1);
+}
+
+#[test]
+fn test_mint_nft_unknown_creator() {
+    let mut chain = setup();
+
+    let creator = Address::random();
+    let result = chain.mint_nft(&creator, metadata());
+
+    assert_eq!(
+        result,
+        Err(BlockchainError::WalletNotFound { address: creator })
+    );
+    assert!(chain.nfts.is_empty());
+}
+
+#[test]
+fn test_transfer_nft() {
+    let mut chain = setup();
+    let creator = chain.create_wallet("c@mail.com");
+    let receiver = chain.create_wallet("r@mail.com");
+    let id = chain.mint_nft(&creator, metadata()).unwrap();
+
+    chain.wallets.get_mut(&creator).unwrap().balance += 1.0;
+
+    let result = chain.transfer_nft(&creator, &receiver, &id);
+
+    assert!(result.is_ok());
+    assert!(chain.get_nfts(&creator).is_empty());
+    assert_eq!(chain.get_nfts(&receiver)[0].id, id);
+
+    let history = chain.get_nft_history(&id).unwrap();
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].kind, TransactionKind::NftMint);
+    assert_eq!(history[1].kind, TransactionKind::NftTransfer);
+    assert_eq!(history[1].to, receiver);
+}
+
+#[test]
+fn test_transfer_nft_not_owner() {
+    let mut chain = setup();
+    let creator = chain.create_wallet("c@mail.com");
+    let receiver = chain.create_wallet("r@mail.com");
+    let id = chain.mint_nft(&creator, metadata()).unwrap();
+
+    let result = chain.transfer_nft(&receiver, &creator, &id);
+
+    assert_eq!(result, Err(BlockchainError::Unauthorized));
+}
+
+#[test]
+fn test_get_nft_not_found() {
+    let chain = setup();
+
+    let result = chain.get_nft(&Uuid::new_v4());
+
+    assert_eq!(result.unwrap_err(), BlockchainError::NftNotFound);
+}
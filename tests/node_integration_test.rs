@@ -0,0 +1,113 @@
+#![cfg(feature = "node")]
+
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use blockchain::{Chain, Node, NodeConfig, NodeEvent, NodeHandle};
+use libp2p::Multiaddr;
+use tokio::time::timeout;
+
+use crate::common::setup;
+
+/// Start a node listening on a random local port.
+///
+/// # Returns
+///
+/// The shared chain, the node handle, and the address the node listens on.
+async fn start(
+    chain: Chain,
+    chain_id: &str,
+    peers: Vec<Multiaddr>,
+) -> (Arc<Mutex<Chain>>, NodeHandle, Multiaddr) {
+    let chain = Arc::new(Mutex::new(chain));
+    let config = NodeConfig {
+        chain_id: chain_id.to_string(),
+        listen_address: "/ip4/127.0.0.1/tcp/0".parse().unwrap(),
+        peers,
+    };
+
+    let (node, mut handle) = Node::new(chain.clone(), config).unwrap();
+    tokio::spawn(node.run());
+
+    let address = loop {
+        if let Some(NodeEvent::Listening(address)) = handle.next_event().await {
+            break address;
+        }
+    };
+
+    (chain, handle, address)
+}
+
+/// Wait for the handshake with a peer to complete.
+///
+/// # Returns
+///
+/// `true` if the peer was accepted, `false` if it was rejected.
+async fn handshake(handle: &mut NodeHandle) -> bool {
+    loop {
+        match handle.next_event().await {
+            Some(NodeEvent::PeerConnected { .. }) => return true,
+            Some(NodeEvent::PeerRejected { .. }) => return false,
+            Some(_) => continue,
+            None => panic!("node stopped"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_nodes_exchange_blocks() {
+    let source = setup();
+
+    let (first, first_handle, address) = start(source.clone(), "test", vec![]).await;
+    let (second, mut second_handle, _) = start(source, "test", vec![address]).await;
+
+    let connected = timeout(Duration::from_secs(10), handshake(&mut second_handle))
+        .await
+        .unwrap();
+    assert!(connected);
+
+    let block = {
+        let mut chain = first.lock().unwrap();
+        chain.generate_new_block();
+        chain.chain.last().unwrap().clone()
+    };
+
+    // Publish until the subscription of the peer has propagated
+    let imported = timeout(Duration::from_secs(20), async {
+        loop {
+            first_handle.publish_block(block.clone()).unwrap();
+
+            let event = timeout(Duration::from_millis(500), second_handle.next_event()).await;
+            if let Ok(Some(NodeEvent::BlockImported { height, .. })) = event {
+                return height;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(imported, 1);
+    assert_eq!(
+        second.lock().unwrap().get_last_hash(),
+        first.lock().unwrap().get_last_hash()
+    );
+
+    first_handle.shutdown().unwrap();
+    second_handle.shutdown().unwrap();
+}
+
+#[tokio::test]
+async fn test_nodes_reject_other_network() {
+    let (_, _first_handle, address) = start(setup(), "main", vec![]).await;
+    let (_, mut second_handle, _) = start(setup(), "test", vec![address]).await;
+
+    let connected = timeout(Duration::from_secs(10), handshake(&mut second_handle))
+        .await
+        .unwrap();
+
+    assert!(!connected);
+}
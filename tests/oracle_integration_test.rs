@@ -0,0 +1,99 @@
+mod common;
+
+use blockchain::{Address, BlockchainError, Oracle, TransactionKind};
+
+use crate::common::setup;
+
+struct PriceOracle {
+    address: Address,
+}
+
+impl Oracle for PriceOracle {
+    fn address(&self) -> &Address {
+        &self.address
+    }
+
+    fn fetch(&self, feed: &str) -> Option<f64> {
+        match feed {
+            "BTC/USD" => Some(65000.0),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_submit_oracle_report() {
+    let mut chain = setup();
+    let oracle = chain.create_wallet("o@mail.com");
+    chain.allow_oracle("BTC/USD", &oracle).unwrap();
+
+    let hash = chain
+        .submit_oracle_report(&oracle, "BTC/USD", 64000.0, &[oracle.to_owned()])
+        .unwrap();
+    let report = chain.get_oracle_value("BTC/USD").unwrap();
+    let transaction = chain.get_transaction(&hash).unwrap();
+
+    assert_eq!(report.value, 64000.0);
+    assert_eq!(report.oracle, oracle);
+    assert_eq!(transaction.kind, TransactionKind::Oracle);
+    assert_eq!(transaction.asset, "BTC/USD");
+}
+
+#[test]
+fn test_submit_oracle_report_not_allowed() {
+    let mut chain = setup();
+    let oracle = chain.create_wallet("o@mail.com");
+    let other = chain.create_wallet("x@mail.com");
+    chain.allow_oracle("BTC/USD", &oracle).unwrap();
+
+    let result = chain.submit_oracle_report(&other, "BTC/USD", 1.0, &[other.to_owned()]);
+    assert_eq!(result, Err(BlockchainError::Unauthorized));
+
+    let result = chain.submit_oracle_report(&oracle, "ETH/USD", 1.0, &[oracle.to_owned()]);
+    assert_eq!(result, Err(BlockchainError::FeedNotFound));
+}
+
+#[test]
+fn test_submit_oracle_report_unsigned() {
+    let mut chain = setup();
+    let oracle = chain.create_wallet("o@mail.com");
+    chain.allow_oracle("BTC/USD", &oracle).unwrap();
+
+    let result = chain.submit_oracle_report(&oracle, "BTC/USD", 1.0, &[]);
+
+    assert_eq!(result, Err(BlockchainError::Unauthorized));
+}
+
+#[test]
+fn test_revoke_oracle() {
+    let mut chain = setup();
+    let oracle = chain.create_wallet("o@mail.com");
+    chain.allow_oracle("BTC/USD", &oracle).unwrap();
+    chain.revoke_oracle("BTC/USD", &oracle).unwrap();
+
+    let result = chain.submit_oracle_report(&oracle, "BTC/USD", 1.0, &[oracle.to_owned()]);
+
+    assert_eq!(result, Err(BlockchainError::Unauthorized));
+    assert_eq!(
+        chain.get_oracle_value("BTC/USD"),
+        Err(BlockchainError::FeedNotFound)
+    );
+}
+
+#[test]
+fn test_pull_oracle() {
+    let mut chain = setup();
+    let address = chain.create_wallet("o@mail.com");
+    chain.allow_oracle("BTC/USD", &address).unwrap();
+    chain.allow_oracle("ETH/USD", &address).unwrap();
+
+    let oracle = PriceOracle { address };
+
+    chain.pull_oracle(&oracle, "BTC/USD").unwrap();
+
+    assert_eq!(chain.get_oracle_value("BTC/USD").unwrap().value, 65000.0);
+    assert_eq!(
+        chain.pull_oracle(&oracle, "ETH/USD"),
+        Err(BlockchainError::InvalidData)
+    );
+}
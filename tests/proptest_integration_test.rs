@@ -0,0 +1,72 @@
+#![cfg(feature = "proptest")]
+
+use blockchain::{arbitrary::Transfer, Address, Chain, Transaction, Wallet};
+use proptest::prelude::*;
+
+/// Sum the native balances of all wallets.
+fn total_balance(chain: &Chain) -> f64 {
+    chain.wallets.values().map(|wallet| wallet.balance).sum()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 64,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn test_transaction_hash(transaction in any::<Transaction>()) {
+        prop_assert_eq!(transaction.hash, transaction.compute_hash());
+
+        let json = serde_json::to_string(&transaction).unwrap();
+        let decoded: Transaction = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded.hash, transaction.hash);
+    }
+
+    #[test]
+    fn test_wallet_roundtrip(wallet in any::<Wallet>()) {
+        let json = serde_json::to_string(&wallet).unwrap();
+        let decoded: Wallet = serde_json::from_str(&json).unwrap();
+
+        prop_assert_eq!(decoded.id, wallet.id);
+        prop_assert_eq!(decoded.address, wallet.address);
+        prop_assert!((decoded.balance - wallet.balance).abs() <= 1e-9 * wallet.balance.max(1.0));
+    }
+
+    #[test]
+    fn test_hash_linkage(chain in any::<Chain>()) {
+        for pair in chain.chain.windows(2) {
+            prop_assert_eq!(pair[1].header.previous_hash, pair[0].hash);
+            prop_assert_eq!(pair[1].height, pair[0].height + 1);
+        }
+
+        prop_assert!(chain.validate_chain().is_ok());
+    }
+
+    #[test]
+    fn test_conservation_of_funds(
+        mut chain in any::<Chain>(),
+        transfers in proptest::collection::vec(any::<Transfer>(), 0..32),
+    ) {
+        // The sender is debited the amount multiplied by the fee, so a fee of 1.0 moves exactly the
+        // amount from the sender to the receiver
+        chain.fee = 1.0;
+        let wallets: Vec<Address> = chain.wallets.keys().cloned().collect();
+        let total = total_balance(&chain);
+
+        for transfer in transfers {
+            let before = chain.wallets.clone();
+
+            if transfer.apply(&mut chain, &wallets).is_err() {
+                // A rejected transfer leaves every balance untouched
+                for (address, wallet) in &before {
+                    prop_assert_eq!(chain.wallets[address].balance, wallet.balance);
+                }
+            }
+
+            prop_assert!(chain.wallets.values().all(|wallet| wallet.balance >= 0.0));
+            prop_assert!((total_balance(&chain) - total).abs() <= 1e-6 * total.max(1.0));
+        }
+    }
+}
@@ -0,0 +1,245 @@
+#![cfg(feature = "rpc")]
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use blockchain::{
+    auth::{Auth, AuthConfig, Role},
+    Chain, KeyScope, RpcResponse, RpcServer, TraceContext,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use crate::common::setup;
+
+fn server() -> (Arc<Mutex<Chain>>, RpcServer) {
+    let chain = Arc::new(Mutex::new(setup()));
+
+    (chain.clone(), RpcServer::new(chain))
+}
+
+fn call(server: &RpcServer, method: &str, params: Value) -> RpcResponse {
+    let request = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+    let response = server.handle(&request.to_string()).unwrap();
+
+    serde_json::from_str(&response).unwrap()
+}
+
+#[test]
+fn test_get_block_by_height() {
+    let (chain, server) = server();
+
+    let response = call(&server, "getBlockByHeight", json!([0]));
+    let hash = chain.lock().unwrap().get_last_hash();
+
+    assert_eq!(response.id, json!(1));
+    assert_eq!(response.result.unwrap()["hash"], json!(hash));
+
+    let response = call(&server, "getBlockByHeight", json!({ "height": 5 }));
+    let error = response.error.unwrap();
+
+    assert_eq!(error.code, -32000);
+    assert_eq!(error.data, Some(json!("BlockNotFound")));
+}
+
+#[test]
+fn test_send_transaction_and_get_balance() {
+    let (chain, server) = server();
+
+    let from = call(&server, "createWallet", json!(["a@mail.com"]))
+        .result
+        .unwrap();
+    let to = call(&server, "createWallet", json!(["b@mail.com"]))
+        .result
+        .unwrap();
+
+    let address = serde_json::from_value(from.clone()).unwrap();
+    chain
+        .lock()
+        .unwrap()
+        .wallets
+        .get_mut(&address)
+        .unwrap()
+        .balance = 10.0;
+
+    let response = call(&server, "sendTransaction", json!([from, to, 10.0]));
+    let hash = serde_json::from_value(response.result.unwrap()).unwrap();
+
+    assert!(chain.lock().unwrap().get_transaction(&hash).is_ok());
+
+    let balance = call(&server, "getBalance", json!({ "address": to }));
+    assert_eq!(balance.result, Some(json!(10.0)));
+}
+
+#[test]
+fn test_send_transaction_rejected() {
+    let (_, server) = server();
+
+    let from = call(&server, "createWallet", json!(["a@mail.com"]))
+        .result
+        .unwrap();
+    let to = call(&server, "createWallet", json!(["b@mail.com"]))
+        .result
+        .unwrap();
+
+    let response = call(&server, "sendTransaction", json!([from, to, 10.0]));
+
+    assert_eq!(
+        response.error.unwrap().data,
+        Some(json!({
+            "InsufficientFunds": { "address": from, "required": 1.0, "available": 0.0 }
+        }))
+    );
+}
+
+#[test]
+fn test_errors() {
+    let (_, server) = server();
+
+    let unknown = call(&server, "getUnknown", json!([]));
+    let invalid = call(&server, "getBalance", json!(["not an address"]));
+    let parse: RpcResponse = serde_json::from_str(&server.handle("{").unwrap()).unwrap();
+
+    assert_eq!(unknown.error.unwrap().code, -32601);
+    assert_eq!(invalid.error.unwrap().code, -32602);
+    assert_eq!(parse.error.unwrap().code, -32700);
+}
+
+#[test]
+fn test_batch_and_notifications() {
+    let (chain, server) = server();
+
+    let notification = json!({ "jsonrpc": "2.0", "method": "generateBlock" });
+    assert!(server.handle(&notification.to_string()).is_none());
+    assert_eq!(chain.lock().unwrap().chain.len(), 2);
+
+    let batch = json!([
+        { "jsonrpc": "2.0", "method": "getBlockCount", "id": 1 },
+        { "jsonrpc": "2.0", "method": "getLastHash", "id": 2 },
+        notification,
+    ]);
+    let responses: Vec<RpcResponse> =
+        serde_json::from_str(&server.handle(&batch.to_string()).unwrap()).unwrap();
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].result, Some(json!(2)));
+}
+
+#[test]
+fn test_admin_methods() {
+    let (chain, server) = server();
+
+    let request =
+        json!({ "jsonrpc": "2.0", "method": "updateFee", "params": { "fee": 0.2 }, "id": 1 });
+    let response: RpcResponse =
+        serde_json::from_str(&server.handle_as(&request.to_string(), Role::User).unwrap()).unwrap();
+    assert!(response.error.is_some());
+    assert_eq!(chain.lock().unwrap().fee, 0.1);
+
+    let response = call(&server, "updateFee", json!({ "fee": 0.2 }));
+    assert_eq!(response.result, Some(json!(0.2)));
+    assert_eq!(chain.lock().unwrap().fee, 0.2);
+
+    let response = call(&server, "updateReward", json!([50.0]));
+    assert_eq!(response.result, Some(json!(50.0)));
+
+    let request = json!({ "jsonrpc": "2.0", "method": "getBlockCount", "id": 1 });
+    assert!(server
+        .handle_as(&request.to_string(), Role::User)
+        .unwrap()
+        .contains("\"result\":1"));
+}
+
+#[test]
+fn test_wallet_key_methods() {
+    let (chain, server) = server();
+    let (from, to, key) = {
+        let mut chain = chain.lock().unwrap();
+        let from = chain.create_wallet("s@mail.com");
+        let to = chain.create_wallet("r@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance = 100.0;
+        let key = chain
+            .issue_wallet_key(&from, KeyScope::Send { limit: 5.0 })
+            .unwrap();
+
+        (from, to, key)
+    };
+    let call = |method: &str, params: Value| -> RpcResponse {
+        let request = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let response = server.handle_with_wallet_key(&request.to_string(), &key);
+
+        serde_json::from_str(&response.unwrap()).unwrap()
+    };
+
+    assert_eq!(call("getBlockCount", json!([])).result, Some(json!(1)));
+    assert!(call("updateFee", json!([0.2])).error.is_some());
+
+    let send = json!({ "from": from, "to": to, "amount": 4.0 });
+    assert!(call("sendTransaction", send.clone()).result.is_some());
+    assert!(call("sendTransaction", send).error.is_some());
+
+    let send = json!({ "from": to, "to": from, "amount": 1.0 });
+    assert!(call("sendTransaction", send).error.is_some());
+}
+
+#[tokio::test]
+async fn test_http_authentication() {
+    let chain = Arc::new(Mutex::new(setup()));
+    let app = RpcServer::new(chain)
+        .with_auth(Auth::new(AuthConfig {
+            keys: HashMap::from([("user".to_string(), Role::User)]),
+            ..AuthConfig::default()
+        }))
+        .router();
+
+    let request = |key: Option<&str>| {
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("content-type", "application/json");
+        if let Some(key) = key {
+            builder = builder.header("authorization", format!("Bearer {}", key));
+        }
+
+        builder
+            .body(Body::from(
+                json!({ "jsonrpc": "2.0", "method": "getBlockCount", "id": 1 }).to_string(),
+            ))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app.oneshot(request(Some("user"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[test]
+fn test_parse_trace_context() {
+    let context =
+        TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+
+    assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(context.parent_id, "00f067aa0ba902b7");
+    assert!(context.sampled);
+
+    for value in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+    ] {
+        assert_eq!(TraceContext::parse(value), None, "{}", value);
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "schemars")]
+
+use blockchain::{BlockchainError, Chain, Transaction};
+use schemars::schema_for;
+
+#[test]
+fn test_chain_schema() {
+    let schema = serde_json::to_value(schema_for!(Chain)).unwrap();
+    let definitions = &schema["definitions"];
+
+    for name in [
+        "Block",
+        "BlockHeader",
+        "Transaction",
+        "Wallet",
+        "Address",
+        "Hash",
+    ] {
+        assert!(
+            definitions.get(name).is_some(),
+            "missing definition: {}",
+            name
+        );
+    }
+    assert_eq!(definitions["Hash"]["pattern"], "^[0-9a-fA-F]{64}$");
+    assert!(schema["properties"].get("subscribers").is_none());
+    assert!(schema["properties"].get("hooks").is_none());
+}
+
+#[test]
+fn test_transaction_schema() {
+    let schema = serde_json::to_value(schema_for!(Transaction)).unwrap();
+    let required = schema["required"].as_array().unwrap();
+
+    assert!(required.contains(&"hash".into()));
+    assert!(!required.contains(&"asset".into()));
+}
+
+#[test]
+fn test_error_schema() {
+    let schema = serde_json::to_string(&schema_for!(BlockchainError)).unwrap();
+
+    assert!(schema.contains("InsufficientFunds"));
+    assert!(schema.contains("WalletNotFound"));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use std::slice;
+
+use blockchain::{Address, BlockchainError, OpCode, Script};
+
+use crate::common::setup;
+
+#[test]
+fn test_spend_condition_multisig() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+    let cosigner = Address::random();
+
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain
+        .set_spend_condition(
+            &from,
+            Some(Script::multisig(2, vec![from.clone(), cosigner.clone()])),
+            slice::from_ref(&from),
+        )
+        .unwrap();
+
+    let unsigned = chain.add_transaction(&from, &to, 10.0);
+    let signed = chain.add_authorized_transaction(&from, &to, 10.0, &[from.clone(), cosigner]);
+
+    assert_eq!(unsigned, Err(BlockchainError::ScriptFailed));
+    assert!(signed.is_ok());
+    assert_eq!(chain.transactions.len(), 1);
+}
+
+#[test]
+fn test_spend_condition_height_lock() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain
+        .set_spend_condition(
+            &from,
+            Some(Script(vec![OpCode::CheckHeightLock(2), OpCode::Verify])),
+            slice::from_ref(&from),
+        )
+        .unwrap();
+
+    assert_eq!(
+        chain.add_transaction(&from, &to, 10.0),
+        Err(BlockchainError::ScriptFailed)
+    );
+
+    chain.generate_new_block();
+
+    assert!(chain.add_transaction(&from, &to, 10.0).is_ok());
+}
+
+#[test]
+fn test_clear_spend_condition() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    let owner = Address::random();
+
+    chain.wallets.get_mut(&from).unwrap().balance += 20.0;
+    chain
+        .set_spend_condition(
+            &from,
+            Some(Script::signature(&owner)),
+            slice::from_ref(&from),
+        )
+        .unwrap();
+    chain.set_spend_condition(&from, None, &[owner]).unwrap();
+
+    assert!(chain.add_transaction(&from, &to, 10.0).is_ok());
+}
+
+#[test]
+fn test_set_spend_condition_unauthorized() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let owner = Address::random();
+
+    chain
+        .set_spend_condition(
+            &from,
+            Some(Script::signature(&owner)),
+            slice::from_ref(&from),
+        )
+        .unwrap();
+
+    let result = chain.set_spend_condition(&from, None, slice::from_ref(&from));
+
+    assert_eq!(result, Err(BlockchainError::ScriptFailed));
+    assert_eq!(
+        chain.wallets.get(&from).unwrap().spend_condition,
+        Some(Script::signature(&owner))
+    );
+}
+
+#[test]
+fn test_set_spend_condition_wallet_not_found() {
+    let mut chain = setup();
+
+    let address = Address::random();
+    let result = chain.set_spend_condition(&address, None, &[]);
+
+    assert_eq!(result, Err(BlockchainError::WalletNotFound { address }));
+}
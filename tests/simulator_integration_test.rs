@@ -0,0 +1,66 @@
+#![cfg(feature = "testing")]
+
+use blockchain::{
+    simulator::{InvariantViolation, TransferDistribution},
+    Simulator, WorkloadConfig,
+};
+
+#[test]
+fn test_run() {
+    let config = WorkloadConfig {
+        steps: 200,
+        block_interval: 50,
+        ..WorkloadConfig::default()
+    };
+    let mut simulator = Simulator::new(1.0, 100.0, 0.1, config);
+    let report = simulator.run();
+
+    assert_eq!(report.steps, 200);
+    assert_eq!(report.blocks_mined, 4);
+    assert_eq!(simulator.chain.chain.len(), 5);
+    assert_eq!(report.wallets_created, simulator.wallets.len());
+    assert_eq!(
+        report.transfers_accepted,
+        simulator.chain.transactions.len()
+    );
+    assert_eq!(report.transfers_accepted + report.transfers_rejected, 200);
+    assert!(report.throughput() > 0.0);
+    assert!(report.is_consistent());
+}
+
+#[test]
+fn test_run_reproducible() {
+    let config = WorkloadConfig {
+        seed: 7,
+        steps: 100,
+        distribution: TransferDistribution::Hotspot {
+            wallets: 2,
+            share: 0.8,
+        },
+        ..WorkloadConfig::default()
+    };
+
+    let mut first = Simulator::new(1.0, 100.0, 0.1, config.clone());
+    let mut second = Simulator::new(1.0, 100.0, 0.1, config);
+    first.run();
+    second.run();
+
+    assert_eq!(first.wallets, second.wallets);
+    assert_eq!(first.chain.get_last_hash(), second.chain.get_last_hash());
+}
+
+#[test]
+fn test_check_invariants() {
+    let mut simulator = Simulator::new(1.0, 100.0, 0.1, WorkloadConfig::default());
+    simulator.run();
+
+    let address = simulator.wallets[0].clone();
+    simulator.chain.wallets.get_mut(&address).unwrap().balance = -1.0;
+
+    assert!(simulator
+        .check_invariants()
+        .contains(&InvariantViolation::InvalidBalance {
+            address,
+            balance: -1.0,
+        }));
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use std::time::Duration;
+
+use blockchain::{Address, ChainEvent, ProposalChange, SubscriptionFilter};
+
+use crate::common::setup;
+
+#[test]
+fn test_subscribe_blocks() {
+    let mut chain = setup();
+    let subscription = chain.subscribe(SubscriptionFilter::Blocks);
+
+    chain.generate_new_block();
+
+    match subscription.next_timeout(Duration::from_secs(1)) {
+        Some(ChainEvent::Block(block)) => {
+            assert_eq!(block.height, 1);
+            assert_eq!(block.hash, chain.get_last_hash());
+        }
+        event => panic!("unexpected event: {:?}", event),
+    }
+    assert!(subscription.try_next().is_none());
+}
+
+#[test]
+fn test_subscribe_transactions() {
+    let mut chain = setup();
+    let from = chain.create_wallet("a@mail.com");
+    let to = chain.create_wallet("b@mail.com");
+    chain.wallets.get_mut(&from).unwrap().balance += 10.0;
+
+    let transactions = chain.subscribe(SubscriptionFilter::Transactions);
+    let blocks = chain.subscribe(SubscriptionFilter::Blocks);
+
+    chain.add_transaction(&from, &to, 5.0).unwrap();
+
+    let events = transactions.drain();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], ChainEvent::Transaction(tx) if tx.to == to));
+    assert!(blocks.try_next().is_none());
+}
+
+#[test]
+fn test_subscribe_address() {
+    let mut chain = setup();
+    let watched = chain.create_wallet("a@mail.com");
+    let other = chain.create_wallet("b@mail.com");
+    let third = chain.create_wallet("c@mail.com");
+    chain.wallets.get_mut(&watched).unwrap().balance += 10.0;
+    chain.wallets.get_mut(&other).unwrap().balance += 10.0;
+
+    let subscription = chain.subscribe(SubscriptionFilter::Address(watched.clone()));
+
+    chain.add_transaction(&other, &watched, 1.0).unwrap();
+    chain.add_transaction(&other, &third, 1.0).unwrap();
+    chain.generate_new_block();
+
+    // The block is delivered too since it includes the transfer to the watched address
+    let events = subscription.drain();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(&events[0], ChainEvent::Transaction(tx) if tx.from == other));
+    assert!(matches!(&events[1], ChainEvent::Block(block) if block.transactions.len() == 3));
+}
+
+#[test]
+fn test_subscribe_lifecycle() {
+    let mut chain = setup();
+    let subscription = chain.subscribe(SubscriptionFilter::Lifecycle);
+
+    let address = chain.create_wallet("a@mail.com");
+    chain.update_fee(0.2);
+    chain.generate_new_block();
+    let mined = chain.get_last_hash();
+    let removed = chain.truncate(1);
+
+    let events = subscription.drain();
+    assert_eq!(events.len(), 3);
+    assert!(matches!(&events[0], ChainEvent::WalletCreated(created) if *created == address));
+    assert!(matches!(
+        &events[1],
+        ChainEvent::ParameterChanged(ProposalChange::Fee(fee)) if *fee == 0.2
+    ));
+    assert!(matches!(
+        &events[2],
+        ChainEvent::Reorg { height: 1, removed: hashes } if *hashes == vec![mined]
+    ));
+    assert_eq!(removed.len(), 1);
+    assert_eq!(chain.chain.len(), 1);
+    assert!(chain.truncate(5).is_empty());
+    assert!(subscription.try_next().is_none());
+}
+
+#[test]
+fn test_watch_address() {
+    let mut chain = setup();
+    let deposit = chain.create_wallet("a@mail.com");
+    let customer = chain.create_wallet("b@mail.com");
+    chain.wallets.get_mut(&customer).unwrap().balance += 10.0;
+
+    assert!(chain.watch_address(&Address::random()).is_err());
+    chain.watch_address(&deposit).unwrap();
+    assert_eq!(
+        chain.watched_addresses().collect::<Vec<_>>(),
+        vec![&deposit]
+    );
+
+    let subscription = chain.subscribe(SubscriptionFilter::Watched);
+
+    chain.add_transaction(&customer, &deposit, 5.0).unwrap();
+    let hash = chain.pending[0].hash;
+    chain.generate_new_block();
+
+    let events = subscription.drain();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(
+        &events[0],
+        ChainEvent::BalanceChanged { address, previous, balance }
+            if *address == deposit && *previous == 0.0 && *balance == 5.0
+    ));
+    assert!(matches!(
+        &events[1],
+        ChainEvent::WatchedBlock { address, height: 1, transactions, .. }
+            if *address == deposit && *transactions == vec![hash]
+    ));
+
+    // Unwatched addresses are no longer reported
+    assert!(chain.unwatch_address(&deposit));
+    chain.add_transaction(&customer, &deposit, 1.0).unwrap();
+    chain.generate_new_block();
+    assert!(subscription.try_next().is_none());
+}
+
+#[test]
+fn test_cloned_chain_has_no_subscribers() {
+    let mut chain = setup();
+    let subscription = chain.subscribe(SubscriptionFilter::All);
+
+    let mut clone = chain.clone();
+    clone.generate_new_block();
+
+    assert!(subscription.try_next().is_none());
+}
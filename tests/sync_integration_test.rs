@@ -0,0 +1,131 @@
+#![cfg(feature = "sync")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use blockchain::{
+    sync::{self, read_message, write_message},
+    BlockLimits, BlockchainError, Chain, SyncMessage,
+};
+use tokio::io::duplex;
+
+use crate::common::setup;
+
+/// Serve a chain on one end of an in-memory stream and synchronize another chain on the other.
+///
+/// # Returns
+///
+/// The result of the synchronization.
+async fn run(source: Chain, target: &Arc<Mutex<Chain>>) -> Result<usize, BlockchainError> {
+    let source = Arc::new(Mutex::new(source));
+    let (mut client, mut server) = duplex(64 * 1024);
+
+    let handle = tokio::spawn(async move { sync::serve(&source, &mut server).await });
+    let result = sync::sync(target, &mut client).await;
+
+    drop(client);
+    handle.await.unwrap().unwrap();
+
+    result
+}
+
+#[tokio::test]
+async fn test_sync() {
+    let mut source = setup();
+    let target = Arc::new(Mutex::new(source.clone()));
+
+    for _ in 0..5 {
+        source.generate_new_block();
+    }
+
+    let added = run(source.clone(), &target).await.unwrap();
+    let target = target.lock().unwrap();
+
+    assert_eq!(added, 5);
+    assert_eq!(target.get_last_hash(), source.get_last_hash());
+    assert!(target.chain.iter().all(|block| !block.pruned));
+    assert_eq!(target.chain[3].transactions.len(), 1);
+}
+
+#[tokio::test]
+async fn test_sync_up_to_date() {
+    let source = setup();
+    let target = Arc::new(Mutex::new(source.clone()));
+
+    assert_eq!(run(source, &target).await, Ok(0));
+}
+
+#[tokio::test]
+async fn test_sync_rejects_competing_branch() {
+    let mut source = setup();
+    let mut local = source.clone();
+
+    source.generate_new_block();
+    source.generate_new_block();
+    local.generate_new_block();
+
+    let hash = local.get_last_hash();
+    let target = Arc::new(Mutex::new(local));
+
+    assert_eq!(
+        run(source, &target).await,
+        Err(BlockchainError::InvalidHeader)
+    );
+    assert_eq!(target.lock().unwrap().get_last_hash(), hash);
+}
+
+#[tokio::test]
+async fn test_sync_checks_blocks_as_imported() {
+    let mut source = setup();
+    let mut local = source.clone();
+    let from = source.create_wallet("s@mail.com");
+    let to = source.create_wallet("r@mail.com");
+    source.wallets.get_mut(&from).unwrap().balance = 10.0;
+
+    source.generate_new_block();
+    source.add_transaction(&from, &to, 1.0).unwrap();
+    source.generate_new_block();
+
+    // Blocks over the local limits are rejected as by `import_block`
+    local.set_block_limits(BlockLimits {
+        max_transactions: Some(1),
+        max_size: None,
+    });
+    let target = Arc::new(Mutex::new(local));
+
+    assert_eq!(
+        run(source, &target).await,
+        Err(BlockchainError::BlockLimitExceeded)
+    );
+    assert_eq!(target.lock().unwrap().chain.len(), 1);
+}
+
+#[tokio::test]
+async fn test_sync_rejects_invalid_body() {
+    let mut source = setup();
+    let target = Arc::new(Mutex::new(source.clone()));
+    source.generate_new_block();
+
+    let (mut client, mut server) = duplex(64 * 1024);
+
+    // Peer answering with valid headers but empty bodies
+    tokio::spawn(async move {
+        while let Ok(Some(message)) = read_message(&mut server).await {
+            let response = match message {
+                SyncMessage::GetHeaders(_) => SyncMessage::Headers(source.get_headers(1, 10)),
+                SyncMessage::GetBodies(hashes) => {
+                    SyncMessage::Bodies(hashes.iter().map(|_| Default::default()).collect())
+                }
+                _ => break,
+            };
+
+            write_message(&mut server, &response).await.unwrap();
+        }
+    });
+
+    let result = sync::sync(&target, &mut client).await;
+
+    assert_eq!(result, Err(BlockchainError::InvalidBlockBody));
+    assert_eq!(target.lock().unwrap().chain.len(), 1);
+}
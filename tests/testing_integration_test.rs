@@ -0,0 +1,49 @@
+#![cfg(feature = "testing")]
+
+use blockchain::{testing, Address, TestChain};
+
+#[test]
+fn test_funded_wallets() {
+    let fixture = TestChain::funded_wallets(3, 10.0);
+
+    assert_eq!(fixture.wallets.len(), 3);
+    for address in &fixture.wallets {
+        assert_eq!(fixture.chain.get_wallet_balance(address), Some(10.0));
+    }
+}
+
+#[test]
+fn test_transfers_and_blocks() {
+    let chain = TestChain::funded_wallets(4, 10.0)
+        .with_transfers(4, 1.0)
+        .with_blocks(2)
+        .into_chain();
+
+    assert_eq!(chain.chain.len(), 3);
+    for wallet in chain.wallets.values() {
+        assert_eq!(wallet.transaction_hashes.len(), 2);
+    }
+    assert!(chain.validate_chain().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "was rejected")]
+fn test_transfers_rejected() {
+    TestChain::funded_wallets(2, 0.0).with_transfers(1, 1.0);
+}
+
+#[test]
+fn test_deterministic_transaction() {
+    let (from, to) = (Address::root(), Address::random());
+    let transaction = testing::transaction(7, &from, &to, 1.0);
+
+    assert_eq!(transaction.timestamp, 7);
+    assert_eq!(
+        transaction.hash,
+        testing::transaction(7, &from, &to, 1.0).hash
+    );
+    assert_ne!(
+        transaction.hash,
+        testing::transaction(8, &from, &to, 1.0).hash
+    );
+}
@@ -0,0 +1,243 @@
+mod common;
+
+use blockchain::{Address, BlockchainError, Token, TokenConfig, TransactionKind, NATIVE_ASSET};
+
+use crate::common::setup;
+
+#[test]
+fn test_register_token() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+
+    let token = Token::new("GOLD", "Gold", &issuer, Some(1000.0)).unwrap();
+    let result = chain.register_token(token);
+
+    assert!(result.is_ok());
+    assert_eq!(chain.get_token("GOLD").unwrap().issuer, issuer);
+}
+
+#[test]
+fn test_register_token_duplicate() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+
+    let token = Token::new("GOLD", "Gold", &issuer, None).unwrap();
+    chain.register_token(token.clone()).unwrap();
+
+    let result = chain.register_token(token);
+
+    assert_eq!(result, Err(BlockchainError::TokenAlreadyExists));
+}
+
+#[test]
+fn test_register_token_unknown_issuer() {
+    let mut chain = setup();
+
+    let issuer = Address::random();
+    let token = Token::new("GOLD", "Gold", &issuer, None).unwrap();
+    let result = chain.register_token(token);
+
+    assert_eq!(
+        result,
+        Err(BlockchainError::WalletNotFound { address: issuer })
+    );
+}
+
+#[test]
+fn test_get_token_not_found() {
+    let chain = setup();
+
+    let result = chain.get_token("GOLD");
+
+    assert_eq!(result.unwrap_err(), BlockchainError::TokenNotFound);
+}
+
+#[test]
+fn test_add_asset_transaction() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    let token = Token::new("GOLD", "Gold", &from, None).unwrap();
+    chain.register_token(token).unwrap();
+
+    let sender = chain.wallets.get_mut(&from).unwrap();
+    sender.balance += 1.0;
+    sender.balances.insert("GOLD".to_string(), 20.0);
+
+    let result = chain.add_asset_transaction(&from, &to, "GOLD", 5.0);
+
+    assert!(result.is_ok());
+    assert_eq!(chain.get_asset_balance(&from, "GOLD"), Some(15.0));
+    assert_eq!(chain.get_asset_balance(&to, "GOLD"), Some(5.0));
+    assert_eq!(chain.get_asset_balance(&from, NATIVE_ASSET), Some(0.9));
+    assert_eq!(chain.transactions.values().next().unwrap().asset, "GOLD");
+}
+
+#[test]
+fn test_add_asset_transaction_insufficient_funds() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    let token = Token::new("GOLD", "Gold", &from, None).unwrap();
+    chain.register_token(token).unwrap();
+
+    let result = chain.add_asset_transaction(&from, &to, "GOLD", 5.0);
+
+    assert_eq!(
+        result,
+        Err(BlockchainError::InsufficientFunds {
+            address: from,
+            required: 5.0,
+            available: 0.0,
+        })
+    );
+    assert!(chain.transactions.is_empty());
+}
+
+#[test]
+fn test_add_asset_transaction_unknown_token() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    let result = chain.add_asset_transaction(&from, &to, "GOLD", 5.0);
+
+    assert_eq!(result, Err(BlockchainError::TokenNotFound));
+}
+
+#[test]
+fn test_add_asset_transaction_invalid_amount() {
+    let mut chain = setup();
+    let from = chain.create_wallet("s@mail.com");
+    let to = chain.create_wallet("r@mail.com");
+
+    let token = Token::new("GOLD", "Gold", &from, None).unwrap();
+    chain.register_token(token).unwrap();
+    chain
+        .wallets
+        .get_mut(&from)
+        .unwrap()
+        .balances
+        .insert("GOLD".to_string(), 20.0);
+
+    let result = chain.add_asset_transaction(&from, &to, "GOLD", f64::NAN);
+
+    assert!(matches!(result, Err(BlockchainError::InvalidAmount { .. })));
+    assert_eq!(
+        chain.add_asset_transaction(&from, &to, "GOLD", f64::INFINITY),
+        Err(BlockchainError::InvalidAmount {
+            amount: f64::INFINITY
+        })
+    );
+    assert_eq!(chain.get_asset_balance(&from, "GOLD"), Some(20.0));
+    assert_eq!(chain.get_asset_balance(&to, "GOLD"), Some(0.0));
+}
+
+fn gold(issuer: &Address, initial_supply: f64, max_supply: Option<f64>) -> TokenConfig {
+    TokenConfig {
+        symbol: "GOLD".to_string(),
+        name: "Gold".to_string(),
+        issuer: issuer.to_owned(),
+        initial_supply,
+        max_supply,
+    }
+}
+
+#[test]
+fn test_issue_token() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+
+    let token = chain
+        .issue_token(gold(&issuer, 100.0, Some(1000.0)))
+        .unwrap();
+
+    assert_eq!(token.supply, 100.0);
+    assert_eq!(chain.get_asset_balance(&issuer, "GOLD"), Some(100.0));
+
+    let transaction = chain.transactions.values().next().unwrap();
+
+    assert_eq!(transaction.kind, TransactionKind::Mint);
+}
+
+#[test]
+fn test_issue_token_above_cap() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+
+    let result = chain.issue_token(gold(&issuer, 100.0, Some(10.0)));
+
+    assert_eq!(result.unwrap_err(), BlockchainError::SupplyCapExceeded);
+    assert!(chain.get_token("GOLD").is_err());
+}
+
+#[test]
+fn test_mint() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+    let holder = chain.create_wallet("h@mail.com");
+    chain.issue_token(gold(&issuer, 0.0, Some(50.0))).unwrap();
+
+    let result = chain.mint(&issuer, "GOLD", &holder, 50.0);
+
+    assert!(result.is_ok());
+    assert_eq!(chain.get_asset_balance(&holder, "GOLD"), Some(50.0));
+    assert_eq!(
+        chain.mint(&issuer, "GOLD", &holder, 1.0),
+        Err(BlockchainError::SupplyCapExceeded)
+    );
+    assert_eq!(
+        chain.mint(&issuer, "GOLD", &holder, f64::NAN),
+        Err(BlockchainError::InvalidTransaction)
+    );
+    assert_eq!(chain.get_token("GOLD").unwrap().supply, 50.0);
+}
+
+#[test]
+fn test_mint_unauthorized() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+    let holder = chain.create_wallet("h@mail.com");
+    chain.issue_token(gold(&issuer, 0.0, None)).unwrap();
+
+    let result = chain.mint(&holder, "GOLD", &holder, 1.0);
+
+    assert_eq!(result, Err(BlockchainError::Unauthorized));
+}
+
+#[test]
+fn test_burn() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+    chain.issue_token(gold(&issuer, 100.0, None)).unwrap();
+
+    let hash = chain.burn(&issuer, "GOLD", 40.0).unwrap();
+
+    assert_eq!(chain.get_token("GOLD").unwrap().supply, 60.0);
+    assert_eq!(chain.get_asset_balance(&issuer, "GOLD"), Some(60.0));
+    assert_eq!(
+        chain.get_transaction(&hash).unwrap().kind,
+        TransactionKind::Burn
+    );
+}
+
+#[test]
+fn test_burn_insufficient_funds() {
+    let mut chain = setup();
+    let issuer = chain.create_wallet("i@mail.com");
+    chain.issue_token(gold(&issuer, 10.0, None)).unwrap();
+
+    let result = chain.burn(&issuer, "GOLD", 40.0);
+
+    assert_eq!(
+        result,
+        Err(BlockchainError::InsufficientFunds {
+            address: issuer,
+            required: 40.0,
+            available: 10.0,
+        })
+    );
+    assert_eq!(chain.get_token("GOLD").unwrap().supply, 10.0);
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use tracing_core::span::Current;
+
+use crate::common::setup;
+
+/// Subscriber recording the names of the created spans and their fields.
+#[derive(Default)]
+struct SpanNames {
+    names: Arc<Mutex<Vec<&'static str>>>,
+    fields: Arc<Mutex<Vec<String>>>,
+    metadata: Mutex<HashMap<u64, &'static Metadata<'static>>>,
+    entered: Mutex<Vec<Id>>,
+    next: AtomicU64,
+}
+
+impl Visit for SpanNames {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let field = format!("{}={:?}", field.name(), value);
+        self.fields.lock().unwrap().push(field);
+    }
+}
+
+impl Subscriber for SpanNames {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.names.lock().unwrap().push(span.metadata().name());
+        span.record(&mut SpanNames {
+            fields: self.fields.clone(),
+            ..SpanNames::default()
+        });
+
+        let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+        self.metadata.lock().unwrap().insert(id, span.metadata());
+
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &Id, values: &Record<'_>) {
+        values.record(&mut SpanNames {
+            fields: self.fields.clone(),
+            ..SpanNames::default()
+        });
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.entered.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.entered.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> Current {
+        match self.entered.lock().unwrap().last() {
+            Some(id) => Current::new(id.clone(), self.metadata.lock().unwrap()[&id.into_u64()]),
+            None => Current::none(),
+        }
+    }
+}
+
+#[test]
+fn test_core_operations_are_instrumented() {
+    let subscriber = SpanNames::default();
+    let names = subscriber.names.clone();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let mut chain = setup();
+        let from = chain.create_wallet("a@mail.com");
+        let to = chain.create_wallet("b@mail.com");
+        chain.wallets.get_mut(&from).unwrap().balance += 10.0;
+
+        chain.add_transaction(&from, &to, 1.0).unwrap();
+        chain.generate_new_block();
+        chain.validate_chain().unwrap();
+    });
+
+    let names = names.lock().unwrap();
+    for name in ["add_transaction", "generate_new_block", "validate_chain"] {
+        assert!(names.contains(&name), "missing span: {}", name);
+    }
+}
+
+#[cfg(feature = "rpc")]
+#[test]
+fn test_rpc_request_continues_trace() {
+    use std::sync::Mutex as StdMutex;
+
+    use axum::{
+        body::Body,
+        http::{Method, Request},
+    };
+    use blockchain::RpcServer;
+    use tower::ServiceExt;
+
+    let subscriber = SpanNames::default();
+    let (names, fields) = (subscriber.names.clone(), subscriber.fields.clone());
+    let chain = Arc::new(StdMutex::new(setup()));
+    let app = RpcServer::new(chain.clone()).router();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            )
+            .body(Body::from(
+                r#"{"jsonrpc":"2.0","method":"generateBlock","id":1}"#,
+            ))
+            .unwrap();
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(app.oneshot(request))
+            .unwrap();
+    });
+
+    let hash = chain.lock().unwrap().get_last_hash();
+    let (names, fields) = (names.lock().unwrap(), fields.lock().unwrap());
+    for name in ["rpc.request", "rpc.call", "generate_new_block"] {
+        assert!(names.contains(&name), "missing span: {}", name);
+    }
+    assert!(fields.contains(&"trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"".to_string()));
+    assert!(fields.contains(&"parent_id=\"00f067aa0ba902b7\"".to_string()));
+    assert!(fields.contains(&format!("block={}", hash)));
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "wasm")]
+
+use blockchain::wasm::WasmChain;
+
+#[test]
+fn test_wasm_chain() {
+    let mut chain = WasmChain::new(1.0, 100.0, 0.1);
+    let from = chain.create_wallet("a@mail.com");
+    let to = chain.create_wallet("b@mail.com");
+
+    assert_eq!(chain.height(), 1);
+    assert_eq!(chain.get_wallet(&from).ok().unwrap().email(), "a@mail.com");
+    assert_eq!(chain.get_transactions(1, 10).len(), 0);
+
+    let hash = chain.generate_new_block();
+    assert_eq!(chain.height(), 2);
+    assert_eq!(chain.get_last_hash(), hash);
+    assert!(chain.validate_chain().is_ok());
+
+    let restored = WasmChain::from_json(&chain.to_json().ok().unwrap())
+        .ok()
+        .unwrap();
+    assert_eq!(restored.get_last_hash(), hash);
+    assert_eq!(restored.get_wallet(&to).ok().unwrap().balance(), 0.0);
+}